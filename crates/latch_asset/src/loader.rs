@@ -0,0 +1,272 @@
+//! Background asset decoding with priorities and cancellation.
+//!
+//! Streaming a large world queues far more loads than the decoder threads
+//! can finish in a frame, and what matters changes as the player moves:
+//! assets in view should decode before distant ones, and assets the player
+//! turned away from should not decode at all. `AssetLoader` keeps one FIFO
+//! queue per `Priority`; idle workers always take the oldest job of the
+//! highest non-empty priority. `cancel` drops a queued job, or discards the
+//! result of one already decoding, and unregisters its handle.
+//!
+//! Workers only decode. Payloads reach the `AssetRegistry` when the owner
+//! calls `poll` (typically once per frame), so the registry itself stays
+//! single-threaded.
+
+use crate::registry::{AssetError, AssetHandle, AssetPayload, AssetRegistry};
+use std::collections::{HashSet, VecDeque};
+use std::error::Error as StdError;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread::{self, JoinHandle};
+
+type DecodeError = Box<dyn StdError + Send + Sync>;
+type DecodeFn = dyn Fn(&Path) -> Result<AssetPayload, DecodeError> + Send + Sync;
+
+/// Decode urgency of a queued load, lowest first.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Priority {
+    /// Prefetch for areas the player may reach later.
+    Low,
+    #[default]
+    Normal,
+    /// Visible or about to be.
+    High,
+}
+
+impl Priority {
+    /// Every priority, lowest first.
+    pub const ALL: [Priority; 3] = [Priority::Low, Priority::Normal, Priority::High];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+struct Job {
+    handle: AssetHandle,
+    path: PathBuf,
+}
+
+#[derive(Default)]
+struct Queue {
+    pending: [VecDeque<Job>; Priority::ALL.len()],
+    /// Handles a worker is decoding right now.
+    in_flight: HashSet<AssetHandle>,
+    /// In-flight handles whose result is dropped on completion.
+    cancelled: HashSet<AssetHandle>,
+    completed: Vec<(AssetHandle, Result<AssetPayload, DecodeError>)>,
+    shutdown: bool,
+}
+
+impl Queue {
+    fn next_job(&mut self) -> Option<Job> {
+        self.pending
+            .iter_mut()
+            .rev()
+            .find_map(|queue| queue.pop_front())
+    }
+
+    fn is_idle(&self) -> bool {
+        self.in_flight.is_empty() && self.pending.iter().all(VecDeque::is_empty)
+    }
+}
+
+struct Shared {
+    queue: Mutex<Queue>,
+    /// Signalled when a job is queued or on shutdown.
+    work: Condvar,
+    /// Signalled when a worker finishes a job.
+    done: Condvar,
+    decode: Box<DecodeFn>,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, Queue> {
+        self.queue.lock().expect("asset loader queue poisoned")
+    }
+}
+
+/// Worker pool decoding asset files off the main thread.
+pub struct AssetLoader {
+    shared: Arc<Shared>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl AssetLoader {
+    /// Start `workers` decoder threads (at least one) that turn a file path
+    /// into a payload with `decode`.
+    pub fn new<E>(
+        workers: usize,
+        decode: impl Fn(&Path) -> Result<AssetPayload, E> + Send + Sync + 'static,
+    ) -> Self
+    where
+        E: Into<DecodeError>,
+    {
+        let shared = Arc::new(Shared {
+            queue: Mutex::new(Queue::default()),
+            work: Condvar::new(),
+            done: Condvar::new(),
+            decode: Box::new(move |path| decode(path).map_err(Into::into)),
+        });
+        let workers = (0..workers.max(1))
+            .map(|index| {
+                let shared = Arc::clone(&shared);
+                thread::Builder::new()
+                    .name(format!("asset-decode-{index}"))
+                    .spawn(move || worker(&shared))
+                    .expect("failed to spawn asset decode thread")
+            })
+            .collect();
+        Self { shared, workers }
+    }
+
+    /// `load_async_with_priority` at `Priority::Normal`.
+    pub fn load_async(
+        &self,
+        registry: &mut AssetRegistry,
+        path: impl Into<PathBuf>,
+    ) -> AssetHandle {
+        self.load_async_with_priority(registry, path, Priority::Normal)
+    }
+
+    /// Register a new asset and queue `path` for decoding.
+    ///
+    /// The payload is inserted by the first `poll` after a worker finished
+    /// it. Jobs of higher priority start first; jobs of equal priority start
+    /// in the order they were queued.
+    pub fn load_async_with_priority(
+        &self,
+        registry: &mut AssetRegistry,
+        path: impl Into<PathBuf>,
+        priority: Priority,
+    ) -> AssetHandle {
+        let handle = registry.register();
+        self.shared.lock().pending[priority.index()].push_back(Job {
+            handle,
+            path: path.into(),
+        });
+        self.shared.work.notify_one();
+        handle
+    }
+
+    /// Stop the load of `handle` and unregister it from `registry`: a queued
+    /// job is removed, a running one finishes but its result is discarded.
+    ///
+    /// Returns `false`, leaving `registry` alone, when the loader has nothing
+    /// pending for `handle` (never queued, already polled, or already
+    /// cancelled). Results that finished but were not polled yet are
+    /// discarded too.
+    pub fn cancel(&self, registry: &mut AssetRegistry, handle: AssetHandle) -> bool {
+        let cancelled = self.cancel_job(handle);
+        if cancelled {
+            // Registered by `load_async_with_priority`; nothing else will
+            // ever fill it.
+            let _ = registry.unregister(handle);
+        }
+        cancelled
+    }
+
+    fn cancel_job(&self, handle: AssetHandle) -> bool {
+        let mut queue = self.shared.lock();
+        for pending in &mut queue.pending {
+            if let Some(index) = pending.iter().position(|job| job.handle == handle) {
+                pending.remove(index);
+                return true;
+            }
+        }
+        if let Some(index) = queue.completed.iter().position(|(h, _)| *h == handle) {
+            drop(queue.completed.remove(index));
+            return true;
+        }
+        queue.in_flight.contains(&handle) && queue.cancelled.insert(handle)
+    }
+
+    /// Jobs of `priority` waiting for a worker.
+    pub fn queue_depth(&self, priority: Priority) -> usize {
+        self.shared.lock().pending[priority.index()].len()
+    }
+
+    /// Jobs currently being decoded.
+    pub fn in_flight(&self) -> usize {
+        let queue = self.shared.lock();
+        queue.in_flight.len() - queue.cancelled.len()
+    }
+
+    /// Whether no job is queued or being decoded.
+    pub fn is_idle(&self) -> bool {
+        self.shared.lock().is_idle()
+    }
+
+    /// Insert every finished payload into `registry`, in completion order.
+    ///
+    /// Returns one entry per finished load: the handle, or `AssetError::Load`
+    /// carrying the decode error. Never blocks.
+    pub fn poll(&self, registry: &mut AssetRegistry) -> Vec<Result<AssetHandle, AssetError>> {
+        let completed = std::mem::take(&mut self.shared.lock().completed);
+        completed
+            .into_iter()
+            .map(|(handle, result)| match result {
+                Ok(payload) => registry.insert_payload(handle, payload).map(|()| handle),
+                Err(source) => Err(AssetError::Load { handle, source }),
+            })
+            .collect()
+    }
+
+    /// Block until every queued load has finished, then `poll`.
+    pub fn finish(&self, registry: &mut AssetRegistry) -> Vec<Result<AssetHandle, AssetError>> {
+        let mut queue = self.shared.lock();
+        while !queue.is_idle() {
+            queue = self
+                .shared
+                .done
+                .wait(queue)
+                .expect("asset loader queue poisoned");
+        }
+        drop(queue);
+        self.poll(registry)
+    }
+}
+
+impl Drop for AssetLoader {
+    /// Queued jobs are dropped; jobs already decoding finish first.
+    fn drop(&mut self) {
+        self.shared.lock().shutdown = true;
+        self.shared.work.notify_all();
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+fn worker(shared: &Shared) {
+    loop {
+        let job = {
+            let mut queue = shared.lock();
+            loop {
+                if queue.shutdown {
+                    return;
+                }
+                if let Some(job) = queue.next_job() {
+                    queue.in_flight.insert(job.handle);
+                    break job;
+                }
+                queue = shared
+                    .work
+                    .wait(queue)
+                    .expect("asset loader queue poisoned");
+            }
+        };
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| (shared.decode)(&job.path)))
+            .unwrap_or_else(|_| Err(format!("decoder panicked on {}", job.path.display()).into()));
+
+        let mut queue = shared.lock();
+        queue.in_flight.remove(&job.handle);
+        if !queue.cancelled.remove(&job.handle) {
+            queue.completed.push((job.handle, result));
+        }
+        drop(queue);
+        shared.done.notify_all();
+    }
+}
@@ -0,0 +1,425 @@
+//! Asset handles, payloads and the dependency graph between them.
+//!
+//! Complex assets reference others: a material uses textures, a scene uses
+//! meshes and materials. The registry records those edges so loads run
+//! children first (`load_with_deps`) and hot reloads reach everything built
+//! on top of a changed file (`invalidate`). Edges that would close a cycle
+//! are rejected when added.
+//!
+//! Payloads are reference counted. Holders either pair `acquire`/`release`
+//! by hand or keep an `AssetRef`, which counts itself on clone and drop.
+//! `unload_unused` frees every payload that no counted asset reaches
+//! through its dependencies, so releasing a level's scene also frees the
+//! meshes and textures only that scene used.
+
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
+use std::error::Error as StdError;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use thiserror::Error;
+
+/// Asset handle (opaque ID)
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct AssetHandle(u64);
+
+impl AssetHandle {
+    pub fn id(self) -> u64 {
+        self.0
+    }
+}
+
+/// Decoded asset data, stored type-erased and read back with `get`.
+pub type AssetPayload = Box<dyn Any + Send + Sync>;
+
+#[derive(Debug, Error)]
+pub enum AssetError {
+    #[error("unknown asset {handle:?}")]
+    UnknownAsset { handle: AssetHandle },
+    #[error("asset {handle:?} released more often than acquired")]
+    NotAcquired { handle: AssetHandle },
+    #[error("dependency cycle: {cycle:?}")]
+    DependencyCycle { cycle: Vec<AssetHandle> },
+    #[error("failed to load asset {handle:?}: {source}")]
+    Load {
+        handle: AssetHandle,
+        #[source]
+        source: Box<dyn StdError + Send + Sync>,
+    },
+}
+
+#[derive(Default)]
+struct AssetEntry {
+    dependencies: Vec<AssetHandle>,
+    dependents: Vec<AssetHandle>,
+    payload: Option<AssetPayload>,
+    /// Shared with every `AssetRef` of the asset.
+    refs: Arc<AtomicUsize>,
+}
+
+/// Counted reference to an asset; see `AssetRegistry::acquire_ref`.
+///
+/// Cloning adds a reference and dropping removes one, so the asset stays
+/// loaded across `unload_unused` for as long as any clone is alive.
+#[derive(Debug)]
+pub struct AssetRef {
+    handle: AssetHandle,
+    refs: Arc<AtomicUsize>,
+}
+
+impl AssetRef {
+    pub fn handle(&self) -> AssetHandle {
+        self.handle
+    }
+}
+
+impl Clone for AssetRef {
+    fn clone(&self) -> Self {
+        self.refs.fetch_add(1, Ordering::Relaxed);
+        Self {
+            handle: self.handle,
+            refs: Arc::clone(&self.refs),
+        }
+    }
+}
+
+impl Drop for AssetRef {
+    fn drop(&mut self) {
+        self.refs.fetch_sub(1, Ordering::Release);
+    }
+}
+
+/// Asset registry: handle allocation, loaded payloads and dependencies.
+pub struct AssetRegistry {
+    next_id: u64,
+    entries: HashMap<AssetHandle, AssetEntry>,
+}
+
+impl AssetRegistry {
+    pub fn new() -> Self {
+        Self {
+            next_id: 1,
+            entries: HashMap::new(),
+        }
+    }
+
+    pub fn register(&mut self) -> AssetHandle {
+        let handle = AssetHandle(self.next_id);
+        self.next_id += 1;
+        self.entries.insert(handle, AssetEntry::default());
+        handle
+    }
+
+    /// Forget `handle`: its payload, and every dependency edge to or from it.
+    ///
+    /// Handles are never reused, so stale copies of `handle` just report
+    /// `UnknownAsset` afterwards.
+    pub fn unregister(&mut self, handle: AssetHandle) -> Result<(), AssetError> {
+        let entry = self
+            .entries
+            .remove(&handle)
+            .ok_or(AssetError::UnknownAsset { handle })?;
+        for child in entry.dependencies {
+            if let Some(child) = self.entries.get_mut(&child) {
+                child.dependents.retain(|&h| h != handle);
+            }
+        }
+        for parent in entry.dependents {
+            if let Some(parent) = self.entries.get_mut(&parent) {
+                parent.dependencies.retain(|&h| h != handle);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn contains(&self, handle: AssetHandle) -> bool {
+        self.entries.contains_key(&handle)
+    }
+
+    /// Record that `parent` is built from `child`, so `child` loads first.
+    ///
+    /// Adding an existing edge is a no-op. Fails with `DependencyCycle` if
+    /// `child` already depends on `parent`, directly or transitively; the
+    /// reported cycle starts and ends at `parent`.
+    pub fn add_dependency(
+        &mut self,
+        parent: AssetHandle,
+        child: AssetHandle,
+    ) -> Result<(), AssetError> {
+        self.entry(child)?;
+        if self.entry(parent)?.dependencies.contains(&child) {
+            return Ok(());
+        }
+        if let Some(mut path) = self.dependency_path(child, parent) {
+            path.insert(0, parent);
+            return Err(AssetError::DependencyCycle { cycle: path });
+        }
+        self.entry_mut(parent)?.dependencies.push(child);
+        self.entry_mut(child)?.dependents.push(parent);
+        Ok(())
+    }
+
+    /// Direct dependencies of `handle`, in the order they were added.
+    pub fn dependencies_of(&self, handle: AssetHandle) -> &[AssetHandle] {
+        self.entries
+            .get(&handle)
+            .map_or(&[], |entry| &entry.dependencies)
+    }
+
+    /// Assets that list `handle` as a direct dependency.
+    pub fn dependents_of(&self, handle: AssetHandle) -> &[AssetHandle] {
+        self.entries
+            .get(&handle)
+            .map_or(&[], |entry| &entry.dependents)
+    }
+
+    pub fn is_loaded(&self, handle: AssetHandle) -> bool {
+        self.entries
+            .get(&handle)
+            .is_some_and(|entry| entry.payload.is_some())
+    }
+
+    /// Store the decoded payload of `handle`, replacing any previous one.
+    pub fn insert<T: Any + Send + Sync>(
+        &mut self,
+        handle: AssetHandle,
+        value: T,
+    ) -> Result<(), AssetError> {
+        self.entry_mut(handle)?.payload = Some(Box::new(value));
+        Ok(())
+    }
+
+    /// `insert` for an already boxed payload (from a loader).
+    pub fn insert_payload(
+        &mut self,
+        handle: AssetHandle,
+        payload: AssetPayload,
+    ) -> Result<(), AssetError> {
+        self.entry_mut(handle)?.payload = Some(payload);
+        Ok(())
+    }
+
+    /// Add a reference to `handle`, returning the new count.
+    pub fn acquire(&self, handle: AssetHandle) -> Result<usize, AssetError> {
+        Ok(self.entry(handle)?.refs.fetch_add(1, Ordering::Relaxed) + 1)
+    }
+
+    /// Remove a reference added by `acquire`, returning the new count. The
+    /// payload stays loaded until the next `unload_unused`.
+    pub fn release(&self, handle: AssetHandle) -> Result<usize, AssetError> {
+        self.entry(handle)?
+            .refs
+            .fetch_update(Ordering::Release, Ordering::Relaxed, |count| {
+                count.checked_sub(1)
+            })
+            .map(|previous| previous - 1)
+            .map_err(|_| AssetError::NotAcquired { handle })
+    }
+
+    /// Add a reference to `handle` that is removed when the returned
+    /// `AssetRef` (and every clone of it) is dropped.
+    pub fn acquire_ref(&self, handle: AssetHandle) -> Result<AssetRef, AssetError> {
+        let refs = Arc::clone(&self.entry(handle)?.refs);
+        refs.fetch_add(1, Ordering::Relaxed);
+        Ok(AssetRef { handle, refs })
+    }
+
+    /// References currently held through `acquire` and live `AssetRef`s.
+    pub fn ref_count(&self, handle: AssetHandle) -> usize {
+        self.entries
+            .get(&handle)
+            .map_or(0, |entry| entry.refs.load(Ordering::Acquire))
+    }
+
+    /// Drop the payload of every loaded asset that is neither referenced
+    /// nor a dependency (direct or transitive) of a referenced asset.
+    ///
+    /// Handles and dependency edges stay registered, so an unloaded asset can
+    /// be loaded again with `load_with_deps`. Assets that were loaded but
+    /// never acquired count as unused. Returns the unloaded handles in
+    /// ascending order.
+    pub fn unload_unused(&mut self) -> Vec<AssetHandle> {
+        let mut live = HashSet::new();
+        let mut stack: Vec<AssetHandle> = self
+            .entries
+            .iter()
+            .filter(|(_, entry)| entry.refs.load(Ordering::Acquire) > 0)
+            .map(|(&handle, _)| handle)
+            .collect();
+        while let Some(handle) = stack.pop() {
+            if live.insert(handle) {
+                stack.extend_from_slice(self.dependencies_of(handle));
+            }
+        }
+
+        let mut unloaded: Vec<AssetHandle> = self
+            .entries
+            .iter_mut()
+            .filter(|(handle, entry)| entry.payload.is_some() && !live.contains(*handle))
+            .map(|(&handle, entry)| {
+                entry.payload = None;
+                handle
+            })
+            .collect();
+        unloaded.sort_unstable();
+        unloaded
+    }
+
+    /// Payload of `handle` if it is loaded and of type `T`.
+    pub fn get<T: Any>(&self, handle: AssetHandle) -> Option<&T> {
+        self.entries
+            .get(&handle)?
+            .payload
+            .as_ref()?
+            .downcast_ref::<T>()
+    }
+
+    /// `root` and everything it depends on, children before parents.
+    ///
+    /// Shared dependencies appear once; siblings keep the order in which
+    /// they were added.
+    pub fn load_order(&self, root: AssetHandle) -> Result<Vec<AssetHandle>, AssetError> {
+        self.entry(root)?;
+        let mut order = Vec::new();
+        let mut done = HashSet::new();
+        let mut visiting = Vec::new();
+        self.visit(root, &mut visiting, &mut done, &mut order)?;
+        Ok(order)
+    }
+
+    /// Load `root` after its dependencies, skipping anything already loaded.
+    ///
+    /// `load` receives the registry with every dependency of the handle
+    /// already loaded, so a material can look up its textures. Stops at the
+    /// first failure; assets loaded before it stay loaded. Returns the
+    /// number of assets loaded.
+    pub fn load_with_deps<E>(
+        &mut self,
+        root: AssetHandle,
+        mut load: impl FnMut(&AssetRegistry, AssetHandle) -> Result<AssetPayload, E>,
+    ) -> Result<usize, AssetError>
+    where
+        E: Into<Box<dyn StdError + Send + Sync>>,
+    {
+        let mut loaded = 0;
+        for handle in self.load_order(root)? {
+            if self.is_loaded(handle) {
+                continue;
+            }
+            let payload = load(self, handle).map_err(|err| AssetError::Load {
+                handle,
+                source: err.into(),
+            })?;
+            self.entry_mut(handle)?.payload = Some(payload);
+            loaded += 1;
+        }
+        Ok(loaded)
+    }
+
+    /// Drop the payload of `handle` and of every asset built on it.
+    ///
+    /// Call when a source file changes. Returns the invalidated handles,
+    /// `handle` first and each dependent after everything it depends on, so
+    /// reloading them in order (or calling `load_with_deps` on the last ones)
+    /// rebuilds materials after their new textures.
+    pub fn invalidate(&mut self, handle: AssetHandle) -> Result<Vec<AssetHandle>, AssetError> {
+        self.entry(handle)?;
+        let mut affected = Vec::new();
+        let mut stack = vec![handle];
+        while let Some(current) = stack.pop() {
+            if affected.contains(&current) {
+                continue;
+            }
+            affected.push(current);
+            stack.extend(self.dependents_of(current).iter().rev());
+        }
+
+        // Dependency order among the affected set, children first.
+        let mut order = Vec::with_capacity(affected.len());
+        for &current in &affected {
+            for dependency in self.load_order(current)? {
+                if affected.contains(&dependency) && !order.contains(&dependency) {
+                    order.push(dependency);
+                }
+            }
+        }
+        for &current in &order {
+            self.entry_mut(current)?.payload = None;
+        }
+        Ok(order)
+    }
+
+    fn visit(
+        &self,
+        handle: AssetHandle,
+        visiting: &mut Vec<AssetHandle>,
+        done: &mut HashSet<AssetHandle>,
+        order: &mut Vec<AssetHandle>,
+    ) -> Result<(), AssetError> {
+        if done.contains(&handle) {
+            return Ok(());
+        }
+        if let Some(start) = visiting.iter().position(|&h| h == handle) {
+            let mut cycle = visiting[start..].to_vec();
+            cycle.push(handle);
+            return Err(AssetError::DependencyCycle { cycle });
+        }
+        visiting.push(handle);
+        for &child in self.dependencies_of(handle) {
+            self.visit(child, visiting, done, order)?;
+        }
+        visiting.pop();
+        done.insert(handle);
+        order.push(handle);
+        Ok(())
+    }
+
+    /// Dependency chain from `from` down to `to`, both included.
+    fn dependency_path(&self, from: AssetHandle, to: AssetHandle) -> Option<Vec<AssetHandle>> {
+        let mut path = Vec::new();
+        let mut seen = HashSet::new();
+        self.find_path(from, to, &mut seen, &mut path)
+            .then_some(path)
+    }
+
+    fn find_path(
+        &self,
+        from: AssetHandle,
+        to: AssetHandle,
+        seen: &mut HashSet<AssetHandle>,
+        path: &mut Vec<AssetHandle>,
+    ) -> bool {
+        if !seen.insert(from) {
+            return false;
+        }
+        path.push(from);
+        if from == to {
+            return true;
+        }
+        for &child in self.dependencies_of(from) {
+            if self.find_path(child, to, seen, path) {
+                return true;
+            }
+        }
+        path.pop();
+        false
+    }
+
+    fn entry(&self, handle: AssetHandle) -> Result<&AssetEntry, AssetError> {
+        self.entries
+            .get(&handle)
+            .ok_or(AssetError::UnknownAsset { handle })
+    }
+
+    fn entry_mut(&mut self, handle: AssetHandle) -> Result<&mut AssetEntry, AssetError> {
+        self.entries
+            .get_mut(&handle)
+            .ok_or(AssetError::UnknownAsset { handle })
+    }
+}
+
+impl Default for AssetRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,97 @@
+use latch_asset::{AssetError, AssetPayload, AssetRegistry};
+use std::convert::Infallible;
+
+fn named(name: &'static str) -> AssetPayload {
+    Box::new(name)
+}
+
+#[test]
+fn load_with_deps_loads_children_first() {
+    let mut registry = AssetRegistry::new();
+    let scene = registry.register();
+    let material = registry.register();
+    let albedo = registry.register();
+    let normal = registry.register();
+    let mesh = registry.register();
+    registry.add_dependency(scene, mesh).unwrap();
+    registry.add_dependency(scene, material).unwrap();
+    registry.add_dependency(material, albedo).unwrap();
+    registry.add_dependency(material, normal).unwrap();
+    registry.add_dependency(mesh, material).unwrap();
+
+    let mut loaded = Vec::new();
+    let count = registry
+        .load_with_deps(scene, |registry, handle| {
+            for &dependency in registry.dependencies_of(handle) {
+                assert!(registry.is_loaded(dependency));
+            }
+            loaded.push(handle);
+            Ok::<_, Infallible>(named("asset"))
+        })
+        .unwrap();
+
+    assert_eq!(loaded, vec![albedo, normal, material, mesh, scene]);
+    assert_eq!(count, 5);
+    assert_eq!(registry.get::<&str>(scene), Some(&"asset"));
+    assert_eq!(registry.dependents_of(material), &[scene, mesh]);
+
+    // Everything is loaded now, so a second call does nothing.
+    let again = registry
+        .load_with_deps(scene, |_, _| Ok::<_, Infallible>(named("again")))
+        .unwrap();
+    assert_eq!(again, 0);
+}
+
+#[test]
+fn cycles_are_rejected() {
+    let mut registry = AssetRegistry::new();
+    let a = registry.register();
+    let b = registry.register();
+    let c = registry.register();
+    registry.add_dependency(a, b).unwrap();
+    registry.add_dependency(b, c).unwrap();
+
+    match registry.add_dependency(c, a) {
+        Err(AssetError::DependencyCycle { cycle }) => assert_eq!(cycle, vec![c, a, b, c]),
+        other => panic!("expected a cycle, got {other:?}"),
+    }
+    assert!(matches!(
+        registry.add_dependency(a, a),
+        Err(AssetError::DependencyCycle { .. })
+    ));
+    assert!(registry.dependencies_of(c).is_empty());
+    assert_eq!(registry.load_order(a).unwrap(), vec![c, b, a]);
+}
+
+#[test]
+fn invalidate_reaches_every_dependent() {
+    let mut registry = AssetRegistry::new();
+    let texture = registry.register();
+    let material = registry.register();
+    let scene = registry.register();
+    let other = registry.register();
+    registry.add_dependency(material, texture).unwrap();
+    registry.add_dependency(scene, material).unwrap();
+    registry.add_dependency(scene, texture).unwrap();
+    for handle in [texture, material, scene, other] {
+        registry.insert(handle, handle.id()).unwrap();
+    }
+
+    let invalidated = registry.invalidate(texture).unwrap();
+    assert_eq!(invalidated, vec![texture, material, scene]);
+    assert!(!registry.is_loaded(scene));
+    assert!(registry.is_loaded(other));
+
+    let err = registry
+        .load_with_deps(scene, |_, handle| {
+            if handle == material {
+                Err("bad material")
+            } else {
+                Ok(Box::new(handle.id()) as AssetPayload)
+            }
+        })
+        .unwrap_err();
+    assert!(matches!(err, AssetError::Load { handle, .. } if handle == material));
+    assert!(registry.is_loaded(texture));
+    assert!(!registry.is_loaded(scene));
+}
@@ -0,0 +1,109 @@
+use latch_asset::{AssetError, AssetLoader, AssetPayload, AssetRegistry, Priority};
+use std::path::Path;
+use std::sync::mpsc;
+use std::sync::Mutex;
+
+/// Single-worker loader whose decoder blocks on "gate" until released, so
+/// jobs can be queued behind it deterministically.
+fn gated_loader() -> (AssetLoader, mpsc::Sender<()>) {
+    let (release, gate) = mpsc::channel::<()>();
+    let gate = Mutex::new(gate);
+    let loader = AssetLoader::new(1, move |path: &Path| {
+        let name = path.to_str().unwrap().to_owned();
+        if name == "gate" {
+            gate.lock().unwrap().recv().unwrap();
+        }
+        if name == "broken" {
+            return Err(format!("cannot decode {name}"));
+        }
+        Ok(Box::new(name) as AssetPayload)
+    });
+    (loader, release)
+}
+
+fn wait_until_running(loader: &AssetLoader) {
+    while loader.in_flight() == 0 {
+        std::thread::yield_now();
+    }
+}
+
+#[test]
+fn high_priority_loads_overtake_earlier_low_priority_ones() {
+    let mut registry = AssetRegistry::new();
+    let (loader, release) = gated_loader();
+    let gate = loader.load_async(&mut registry, "gate");
+    wait_until_running(&loader);
+
+    let far = loader.load_async_with_priority(&mut registry, "far", Priority::Low);
+    let near = loader.load_async_with_priority(&mut registry, "near", Priority::High);
+    let middle = loader.load_async(&mut registry, "middle");
+    assert_eq!(loader.queue_depth(Priority::Low), 1);
+    assert_eq!(loader.queue_depth(Priority::Normal), 1);
+    assert_eq!(loader.queue_depth(Priority::High), 1);
+
+    release.send(()).unwrap();
+    let order: Vec<_> = loader
+        .finish(&mut registry)
+        .into_iter()
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(order, [gate, near, middle, far]);
+    assert_eq!(registry.get::<String>(near).unwrap(), "near");
+    assert!(loader.is_idle());
+}
+
+#[test]
+fn cancelled_and_failed_loads_never_reach_the_registry() {
+    let mut registry = AssetRegistry::new();
+    let (loader, release) = gated_loader();
+    let gate = loader.load_async(&mut registry, "gate");
+    wait_until_running(&loader);
+    let behind = loader.load_async(&mut registry, "behind");
+    let broken = loader.load_async(&mut registry, "broken");
+
+    assert!(loader.cancel(&mut registry, behind));
+    assert!(!loader.cancel(&mut registry, behind));
+    assert_eq!(loader.queue_depth(Priority::Normal), 1);
+    assert!(
+        loader.cancel(&mut registry, gate),
+        "running job is discarded on completion"
+    );
+
+    release.send(()).unwrap();
+    let results = loader.finish(&mut registry);
+    assert_eq!(results.len(), 1);
+    assert!(matches!(results[0], Err(AssetError::Load { handle, .. }) if handle == broken));
+    assert!(!registry.is_loaded(gate));
+    assert!(!registry.is_loaded(behind));
+    assert!(!registry.contains(gate));
+    assert!(!registry.contains(behind));
+    assert!(registry.contains(broken));
+}
+
+#[test]
+fn cancelled_loads_are_unregistered() {
+    let mut registry = AssetRegistry::new();
+    let (loader, release) = gated_loader();
+    let gate = loader.load_async(&mut registry, "gate");
+    wait_until_running(&loader);
+
+    let material = registry.register();
+    let mut cancelled = Vec::new();
+    for _ in 0..100 {
+        let texture = loader.load_async(&mut registry, "texture");
+        registry.add_dependency(material, texture).unwrap();
+        assert!(loader.cancel(&mut registry, texture));
+        cancelled.push(texture);
+    }
+    assert!(cancelled.iter().all(|&handle| !registry.contains(handle)));
+    assert!(registry.dependencies_of(material).is_empty());
+    assert_eq!(loader.queue_depth(Priority::Normal), 0);
+
+    // Nothing pending: the registry is left alone.
+    assert!(!loader.cancel(&mut registry, material));
+    assert!(registry.contains(material));
+
+    release.send(()).unwrap();
+    assert_eq!(loader.finish(&mut registry).len(), 1);
+    assert!(registry.is_loaded(gate));
+}
@@ -0,0 +1,56 @@
+use latch_asset::{AssetError, AssetRegistry};
+
+#[test]
+fn releasing_the_last_reference_frees_the_payload() {
+    let mut registry = AssetRegistry::new();
+    let texture = registry.register();
+    registry.insert(texture, vec![0u8; 64]).unwrap();
+
+    assert_eq!(registry.acquire(texture).unwrap(), 1);
+    assert_eq!(registry.acquire(texture).unwrap(), 2);
+    assert_eq!(registry.release(texture).unwrap(), 1);
+    assert!(registry.unload_unused().is_empty());
+    assert!(registry.is_loaded(texture));
+
+    assert_eq!(registry.release(texture).unwrap(), 0);
+    assert_eq!(registry.unload_unused(), vec![texture]);
+    assert!(!registry.is_loaded(texture));
+    assert!(registry.contains(texture));
+    assert!(matches!(
+        registry.release(texture),
+        Err(AssetError::NotAcquired { .. })
+    ));
+}
+
+#[test]
+fn dependencies_are_released_transitively() {
+    let mut registry = AssetRegistry::new();
+    let scene = registry.register();
+    let material = registry.register();
+    let texture = registry.register();
+    let shared = registry.register();
+    let other_scene = registry.register();
+    registry.add_dependency(scene, material).unwrap();
+    registry.add_dependency(material, texture).unwrap();
+    registry.add_dependency(material, shared).unwrap();
+    registry.add_dependency(other_scene, shared).unwrap();
+    for handle in [scene, material, texture, shared, other_scene] {
+        registry.insert(handle, handle.id()).unwrap();
+    }
+
+    let scene_ref = registry.acquire_ref(scene).unwrap();
+    let copy = scene_ref.clone();
+    registry.acquire(other_scene).unwrap();
+    assert_eq!(registry.ref_count(scene), 2);
+    assert!(registry.unload_unused().is_empty());
+
+    drop(scene_ref);
+    assert!(registry.unload_unused().is_empty());
+    drop(copy);
+    assert_eq!(registry.ref_count(scene), 0);
+
+    // `shared` is still reachable from `other_scene`.
+    assert_eq!(registry.unload_unused(), vec![scene, material, texture]);
+    assert!(registry.is_loaded(shared));
+    assert!(registry.is_loaded(other_scene));
+}
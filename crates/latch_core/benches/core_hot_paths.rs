@@ -0,0 +1,219 @@
+//! Regression guard for the ECS hot paths, reported as elements/second.
+//!
+//! Covers bulk row allocation, spawning from a builder versus a template, a
+//! paged `for_each` integration pass, the spatial hash rebuild and the
+//! instance gather that feeds the renderer.
+//! Run with `cargo bench -p latch_core --bench core_hot_paths`; criterion
+//! keeps the previous run under `target/criterion` and reports changes
+//! against it, so CI can fail on a regression.
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use latch_core::define_component;
+use latch_core::ecs::{
+    plan_archetype, ArchetypeLayout, ArchetypeStorage, Component, EntityBuilder, EntityTemplate,
+    PageBudget, RelationAccelerator, RelationBuffer, RelationType, SpatialHashConfig,
+    SpatialHashGrid, World,
+};
+use latch_core::spawn;
+use std::hint::black_box;
+
+#[derive(Clone, Copy, Debug)]
+struct Position {
+    x: i32,
+    y: i32,
+}
+define_component!(Position, 1, "CoreBench::Position");
+
+#[derive(Clone, Copy, Debug)]
+struct Velocity {
+    x: i32,
+    y: i32,
+}
+define_component!(Velocity, 2, "CoreBench::Velocity");
+
+const ALLOC_ENTITIES: usize = 1_000_000;
+const SPAWN_ENTITIES: i32 = 100_000;
+const ITERATE_ENTITIES: i32 = 1_000_000;
+const GATHER_ENTITIES: i32 = 1_000_000;
+const SPATIAL_SIZES: [i32; 2] = [10_000, 100_000];
+const SPATIAL_CELL: i32 = 64;
+
+fn empty_storage() -> ArchetypeStorage {
+    let layout = ArchetypeLayout::new(vec![Position::id(), Velocity::id()]);
+    let plan = plan_archetype(layout, PageBudget::detect()).expect("plan archetype");
+    ArchetypeStorage::from_plan(plan)
+}
+
+/// Entities laid out on a square grid `SPATIAL_CELL / 2` units apart, so
+/// each one has a handful of neighbours within the query radius.
+fn grid_world(count: i32) -> World {
+    let mut world = World::new();
+    let side = (count as f64).sqrt().ceil() as i32;
+    for i in 0..count {
+        let position = Position {
+            x: (i % side) * SPATIAL_CELL / 2,
+            y: (i / side) * SPATIAL_CELL / 2,
+        };
+        spawn!(world, position, Velocity { x: 1, y: -1 });
+    }
+    world
+}
+
+fn alloc_bulk(c: &mut Criterion) {
+    let mut group = c.benchmark_group("alloc_bulk");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(ALLOC_ENTITIES as u64));
+    group.bench_function("1m", |b| {
+        b.iter_batched(
+            empty_storage,
+            |mut storage| {
+                let spans = storage
+                    .alloc_bulk(ALLOC_ENTITIES, 0..ALLOC_ENTITIES as u32)
+                    .expect("alloc_bulk");
+                black_box(spans);
+                storage
+            },
+            BatchSize::LargeInput,
+        );
+    });
+    group.finish();
+}
+
+fn spawn_prefab(c: &mut Criterion) {
+    let template = EntityTemplate::from_builder(
+        EntityBuilder::new()
+            .with(Position { x: 0, y: 0 })
+            .with(Velocity { x: 1, y: -1 }),
+    )
+    .expect("template");
+
+    let mut group = c.benchmark_group("spawn");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(SPAWN_ENTITIES as u64));
+    group.bench_function("builder_100k", |b| {
+        b.iter_batched(
+            World::new,
+            |mut world| {
+                for i in 0..SPAWN_ENTITIES {
+                    spawn!(world, Position { x: i, y: -i }, Velocity { x: 1, y: -1 });
+                }
+                world
+            },
+            BatchSize::LargeInput,
+        );
+    });
+    group.bench_function("template_100k", |b| {
+        b.iter_batched(
+            World::new,
+            |mut world| {
+                for i in 0..SPAWN_ENTITIES {
+                    world
+                        .spawn_template_with(&template, |instance| {
+                            instance.set(Position { x: i, y: -i })
+                        })
+                        .expect("spawn template");
+                }
+                world
+            },
+            BatchSize::LargeInput,
+        );
+    });
+    group.finish();
+}
+
+fn for_each_integrate(c: &mut Criterion) {
+    let mut world = World::new();
+    for i in 0..ITERATE_ENTITIES {
+        spawn!(world, Position { x: i, y: -i }, Velocity { x: 1, y: -1 });
+    }
+    let filter = [Position::id(), Velocity::id()];
+
+    let mut group = c.benchmark_group("for_each");
+    group.throughput(Throughput::Elements(ITERATE_ENTITIES as u64));
+    group.bench_function("integrate_1m", |b| {
+        b.iter(|| {
+            world.for_each(&filter, |storage| {
+                let (pos_col, vel_col) = storage
+                    .columns_mut_pair(Position::id(), Velocity::id())
+                    .expect("position/velocity columns");
+                for page_idx in 0..pos_col.page_count() {
+                    let range = pos_col.page_range(page_idx);
+                    let (pos_read, pos_write) = pos_col
+                        .slice_rw_typed::<Position>(range.clone())
+                        .expect("position page");
+                    let (vel_read, _) = vel_col
+                        .slice_rw_typed::<Velocity>(range)
+                        .expect("velocity page");
+                    for ((out, pos), vel) in pos_write.iter_mut().zip(pos_read).zip(vel_read) {
+                        out.x = pos.x + vel.x;
+                        out.y = pos.y + vel.y;
+                    }
+                }
+            });
+            world.swap_buffers();
+        });
+    });
+    group.finish();
+}
+
+fn spatial_hash_rebuild(c: &mut Criterion) {
+    let mut group = c.benchmark_group("spatial_hash_rebuild");
+    group.sample_size(20);
+    for count in SPATIAL_SIZES {
+        let world = grid_world(count);
+        let config = SpatialHashConfig::new(
+            Position::id(),
+            SPATIAL_CELL,
+            SPATIAL_CELL / 2,
+            RelationType::new(1),
+        );
+        let mut grid = SpatialHashGrid::new(config);
+        let mut buffer = RelationBuffer::new(4096, 256);
+
+        group.throughput(Throughput::Elements(count as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(count), &count, |b, _| {
+            b.iter(|| {
+                buffer.clear();
+                grid.rebuild(&world, &mut buffer);
+                black_box(buffer.len());
+            });
+        });
+    }
+    group.finish();
+}
+
+fn gather_instances(c: &mut Criterion) {
+    let mut world = World::new();
+    for i in 0..GATHER_ENTITIES {
+        spawn!(world, Position { x: i, y: -i }, Velocity { x: 0, y: 0 });
+    }
+    let archetypes: Vec<_> = world.archetypes_matching(&[Position::id()]).collect();
+    let mut instances: Vec<[f32; 2]> = Vec::with_capacity(GATHER_ENTITIES as usize);
+
+    let mut group = c.benchmark_group("gather_instances");
+    group.throughput(Throughput::Elements(GATHER_ENTITIES as u64));
+    group.bench_function("position_1m", |b| {
+        b.iter(|| {
+            instances.clear();
+            for &archetype in &archetypes {
+                world
+                    .storage(archetype)
+                    .expect("archetype storage")
+                    .gather_instances::<Position, _>(&mut instances, |p| [p.x as f32, p.y as f32])
+                    .expect("gather positions");
+            }
+            black_box(instances.len());
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    alloc_bulk,
+    spawn_prefab,
+    for_each_integrate,
+    spatial_hash_rebuild,
+    gather_instances
+);
+criterion_main!(benches);
@@ -0,0 +1,54 @@
+//! Mass component reset: `World::set_all` vs a per-row `for_each` closure.
+//!
+//! Run with `cargo bench -p latch_core --bench fill_column`.
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use latch_core::define_component;
+use latch_core::ecs::World;
+use latch_core::spawn;
+use std::hint::black_box;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Velocity {
+    x: i32,
+    y: i32,
+}
+define_component!(Velocity, 1, "FillBench::Velocity");
+
+const ENTITIES: i32 = 1_000_000;
+
+fn fill_column(c: &mut Criterion) {
+    let mut world = World::new();
+    for i in 0..ENTITIES {
+        spawn!(world, Velocity { x: i, y: -i });
+    }
+    let zero = Velocity { x: 0, y: 0 };
+    let zero_bytes: [u8; 8] = [0; 8];
+
+    let mut group = c.benchmark_group("fill_column");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(ENTITIES as u64));
+    group.bench_function("per_row_closure", |b| {
+        b.iter(|| {
+            world.for_each(&[Velocity::ID], |storage| {
+                let rows = storage.entity_count();
+                let column = storage.column_mut(Velocity::ID).expect("velocity column");
+                for row in 0..rows {
+                    column
+                        .write_next_at(row, black_box(&zero_bytes))
+                        .expect("row in range");
+                }
+            });
+        });
+    });
+    group.bench_function("set_all", |b| {
+        b.iter(|| {
+            let written = world.set_all(black_box(zero)).expect("set_all");
+            assert_eq!(written, ENTITIES as usize);
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(benches, fill_column);
+criterion_main!(benches);
@@ -0,0 +1,70 @@
+//! Query matching over many archetypes.
+//!
+//! Compares a linear `contains` scan against the `ComponentSignature`
+//! subset test. Run with `cargo bench -p latch_core --bench query_matching`.
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use latch_core::ecs::{ArchetypeLayout, ComponentId, ComponentSignature};
+use std::hint::black_box;
+
+const ARCHETYPES: usize = 2_000;
+const COMPONENTS_PER_ARCHETYPE: usize = 12;
+const COMPONENT_VARIETY: u32 = 256;
+
+/// Deterministic pseudo-random layouts (xorshift) so runs are comparable,
+/// plus a query every layout is likely to be tested against.
+fn layouts_and_query() -> (Vec<ArchetypeLayout>, Vec<ComponentId>) {
+    let mut state = 0x9E37_79B9u32;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        state
+    };
+    let layouts = (0..ARCHETYPES)
+        .map(|_| {
+            let mut components: Vec<ComponentId> = (0..COMPONENTS_PER_ARCHETYPE)
+                .map(|_| next() % COMPONENT_VARIETY)
+                .collect();
+            components.push(0);
+            components.push(1);
+            ArchetypeLayout::new(components)
+        })
+        .collect();
+    let query_ids = vec![0, 1, next() % COMPONENT_VARIETY];
+    (layouts, query_ids)
+}
+
+fn query_matching(c: &mut Criterion) {
+    let (layouts, query_ids) = layouts_and_query();
+    let linear = |query_ids: &[ComponentId]| {
+        layouts
+            .iter()
+            .filter(|layout| {
+                let components = layout.components();
+                query_ids.iter().all(|id| components.contains(id))
+            })
+            .count()
+    };
+    let signature = |query_ids: &[ComponentId]| {
+        let query = ComponentSignature::from_components(query_ids);
+        layouts
+            .iter()
+            .filter(|layout| layout.matches(&query))
+            .count()
+    };
+    assert_eq!(linear(&query_ids), signature(&query_ids));
+
+    let mut group = c.benchmark_group("query_matching");
+    group.throughput(Throughput::Elements(ARCHETYPES as u64));
+    group.bench_function("linear_contains", |b| {
+        b.iter(|| linear(black_box(&query_ids)));
+    });
+    group.bench_function("signature_subset", |b| {
+        b.iter(|| signature(black_box(&query_ids)));
+    });
+    group.finish();
+}
+
+criterion_group!(benches, query_matching);
+criterion_main!(benches);
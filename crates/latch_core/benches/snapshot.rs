@@ -0,0 +1,116 @@
+//! Snapshot size and encode/restore time on the poc2 triangle world.
+//!
+//! Prints the compression ratio of each `SnapshotCompression` mode once,
+//! then times `World::snapshot` and `World::restore` per mode. Run with
+//! `cargo bench -p latch_core --bench snapshot`.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use latch_core::define_component;
+use latch_core::ecs::{SnapshotCompression, World};
+use latch_core::spawn;
+use std::hint::black_box;
+
+// Fields are only ever copied as bytes by the snapshot code.
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug)]
+struct Position {
+    x: i32,
+    y: i32,
+}
+define_component!(Position, 1, "SnapshotBench::Position");
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug)]
+struct Velocity {
+    x: i16,
+    y: i16,
+}
+define_component!(Velocity, 2, "SnapshotBench::Velocity");
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug)]
+struct Color {
+    r: u8,
+    g: u8,
+    b: u8,
+}
+define_component!(Color, 3, "SnapshotBench::Color");
+
+const ENTITIES: i32 = 100_000;
+const MODES: [(&str, SnapshotCompression); 3] = [
+    ("none", SnapshotCompression::None),
+    ("fast", SnapshotCompression::Fast),
+    ("best", SnapshotCompression::Best),
+];
+
+/// Same shape as poc2: scattered positions, small velocities and a palette
+/// of a few colors.
+fn triangle_world() -> World {
+    let mut world = World::new();
+    for i in 0..ENTITIES {
+        let position = Position {
+            x: (i * 7919) % 1_000_000 - 500_000,
+            y: (i * 104_729) % 1_000_000 - 500_000,
+        };
+        let velocity = Velocity {
+            x: (i % 200 - 100) as i16,
+            y: (i % 150 - 75) as i16,
+        };
+        let shade = (i % 4) as u8 * 64;
+        spawn!(
+            world,
+            position,
+            velocity,
+            Color {
+                r: shade,
+                g: 255 - shade,
+                b: 128
+            }
+        );
+    }
+    world
+}
+
+fn snapshot_encode(c: &mut Criterion) {
+    let world = triangle_world();
+    let raw_len = world
+        .snapshot(SnapshotCompression::None)
+        .expect("raw snapshot")
+        .len();
+    for (name, mode) in MODES {
+        let len = world.snapshot(mode).expect("snapshot").len();
+        eprintln!(
+            "snapshot/{name}: {len} bytes ({:.1}% of raw)",
+            len as f64 * 100.0 / raw_len as f64
+        );
+    }
+
+    let mut group = c.benchmark_group("snapshot");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(ENTITIES as u64));
+    for (name, mode) in MODES {
+        group.bench_with_input(BenchmarkId::from_parameter(name), &mode, |b, &mode| {
+            b.iter(|| black_box(world.snapshot(mode).expect("snapshot")));
+        });
+    }
+    group.finish();
+}
+
+fn snapshot_restore(c: &mut Criterion) {
+    let world = triangle_world();
+    let mut target = World::new();
+
+    let mut group = c.benchmark_group("restore");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(ENTITIES as u64));
+    for (name, mode) in MODES {
+        let bytes = world.snapshot(mode).expect("snapshot");
+        group.bench_with_input(BenchmarkId::from_parameter(name), &bytes, |b, bytes| {
+            b.iter(|| target.restore(bytes).expect("restore"));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, snapshot_encode, snapshot_restore);
+criterion_main!(benches);
@@ -2,12 +2,13 @@
 //!
 //! Archetypes group entities that share an identical set of component
 //! types. We compute a stable 64-bit identifier by hashing the sorted
-//! component IDs. This allows cheap equality checks and convenient use
-//! as keys in hash maps.
+//! component IDs with `StableHasher`, so the same components get the same
+//! id on every build and host. This allows cheap equality checks,
+//! convenient use as keys in hash maps, and an iteration order that
+//! replays identically everywhere.
 
-use crate::ecs::ComponentId;
-use std::collections::hash_map::DefaultHasher;
-use std::hash::{Hash, Hasher};
+use crate::ecs::{ComponentId, ComponentSignature};
+use crate::hash::StableHasher;
 
 pub type ArchetypeId = u64;
 
@@ -15,6 +16,7 @@ pub type ArchetypeId = u64;
 pub struct ArchetypeLayout {
     id: ArchetypeId,
     components: Box<[ComponentId]>,
+    signature: ComponentSignature,
 }
 
 impl ArchetypeLayout {
@@ -22,9 +24,11 @@ impl ArchetypeLayout {
         components.sort_unstable();
         components.dedup();
         let id = hash_components(&components);
+        let signature = ComponentSignature::from_components(&components);
         Self {
             id,
             components: components.into_boxed_slice(),
+            signature,
         }
     }
 
@@ -38,14 +42,25 @@ impl ArchetypeLayout {
         &self.components
     }
 
+    #[inline]
+    pub fn signature(&self) -> &ComponentSignature {
+        &self.signature
+    }
+
     #[inline]
     pub fn contains(&self, id: ComponentId) -> bool {
-        self.components.binary_search(&id).is_ok()
+        self.signature.contains(id)
+    }
+
+    /// True when this archetype stores every component in `query`.
+    #[inline]
+    pub fn matches(&self, query: &ComponentSignature) -> bool {
+        self.signature.contains_all(query)
     }
 }
 
 fn hash_components(components: &[ComponentId]) -> ArchetypeId {
-    let mut hasher = DefaultHasher::new();
-    components.iter().for_each(|c| c.hash(&mut hasher));
+    let mut hasher = StableHasher::new();
+    components.iter().for_each(|&c| hasher.write_u32(c));
     hasher.finish()
 }
@@ -1,4 +1,4 @@
-use crate::ecs::{meta_of, ArchetypeLayout, Component, ComponentId};
+use crate::ecs::{meta_of, ArchetypeLayout, Component, ComponentId, ComponentMeta};
 use std::{collections::HashMap, mem, ptr};
 use thiserror::Error;
 
@@ -45,13 +45,43 @@ pub enum EntityBuilderError {
     #[error("component id {component_id} is not registered")]
     ComponentNotRegistered { component_id: ComponentId },
     #[error(
-        "component id {component_id} expects stride {expected} bytes but received {actual} bytes"
+        "component '{name}' (id {component_id}) expects stride {expected} bytes but received {actual} bytes"
     )]
     StrideMismatch {
         component_id: ComponentId,
+        name: Box<str>,
         expected: usize,
         actual: usize,
     },
+    #[error(
+        "component '{name}' resolved to id {component_id}, which is registered as '{registered}' \
+         (size {registered_size}, align {registered_align}) but the Rust type has size {size}, align {align}"
+    )]
+    LayoutMismatch {
+        component_id: ComponentId,
+        name: &'static str,
+        registered: Box<str>,
+        registered_size: usize,
+        registered_align: usize,
+        size: usize,
+        align: usize,
+    },
+    #[error("component '{name}' (id {component_id}) added to the builder twice")]
+    DuplicateComponent {
+        component_id: ComponentId,
+        name: Box<str>,
+    },
+    #[error("component '{name}' is not POD and cannot be zero-initialized")]
+    NotPod { name: &'static str },
+    #[error(
+        "component '{name}' (id {component_id}) is not POD and cannot be copied from a template"
+    )]
+    TemplateNotPod {
+        component_id: ComponentId,
+        name: Box<str>,
+    },
+    #[error("component id {component_id} is not part of the template")]
+    NotInTemplate { component_id: ComponentId },
 }
 
 /// Builder for constructing entity blueprints prior to spawning.
@@ -85,6 +115,32 @@ impl EntityBuilder {
         self
     }
 
+    /// Add a Rust-typed component, validating it against the registry first.
+    ///
+    /// Unlike `with`, mistakes are reported here rather than at spawn: the
+    /// registered metadata behind `T::id()` must describe `T` (same name, size
+    /// and alignment), and each component may only be added once.
+    pub fn try_with<T: Component>(self, value: T) -> Result<Self, EntityBuilderError> {
+        self.check_typed::<T>()?;
+        Ok(self.with(value))
+    }
+
+    /// Add a zero-initialized POD component.
+    pub fn with_default<T: Component>(mut self) -> Result<Self, EntityBuilderError> {
+        if !T::is_pod() {
+            return Err(EntityBuilderError::NotPod { name: T::NAME });
+        }
+        let meta = self.check_typed::<T>()?;
+        self.components
+            .insert(meta.id, vec![0u8; meta.stride].into_boxed_slice());
+        Ok(self)
+    }
+
+    /// Layout of the components added so far.
+    pub fn layout(&self) -> ArchetypeLayout {
+        ArchetypeLayout::new(self.components.keys().copied().collect())
+    }
+
     /// Add a component by raw bytes (scripting, serialization, etc.).
     pub fn with_raw_bytes(
         mut self,
@@ -96,6 +152,7 @@ impl EntityBuilder {
         if bytes.len() != meta.stride {
             return Err(EntityBuilderError::StrideMismatch {
                 component_id,
+                name: meta.name,
                 expected: meta.stride,
                 actual: bytes.len(),
             });
@@ -118,6 +175,7 @@ impl EntityBuilder {
             if data.len() != meta.stride {
                 return Err(EntityBuilderError::StrideMismatch {
                     component_id: *component_id,
+                    name: meta.name,
                     expected: meta.stride,
                     actual: data.len(),
                 });
@@ -135,4 +193,30 @@ impl EntityBuilder {
 
         Ok(EntityBlueprint { layout, components })
     }
+
+    fn check_typed<T: Component>(&self) -> Result<ComponentMeta, EntityBuilderError> {
+        let component_id = T::id();
+        let meta = meta_of(component_id)
+            .ok_or(EntityBuilderError::ComponentNotRegistered { component_id })?;
+        let size = mem::size_of::<T>();
+        let align = mem::align_of::<T>();
+        if &*meta.name != T::NAME || meta.size != size || meta.align != align {
+            return Err(EntityBuilderError::LayoutMismatch {
+                component_id,
+                name: T::NAME,
+                registered: meta.name,
+                registered_size: meta.size,
+                registered_align: meta.align,
+                size,
+                align,
+            });
+        }
+        if self.components.contains_key(&component_id) {
+            return Err(EntityBuilderError::DuplicateComponent {
+                component_id,
+                name: meta.name,
+            });
+        }
+        Ok(meta)
+    }
 }
@@ -0,0 +1,73 @@
+//! Per-cell state checksums and region iteration for cell servers.
+//!
+//! Before applying a delta, a receiver checks that it holds the state the
+//! sender assumes. `World::checksum_cells` condenses every cell into one
+//! `u64` that nodes exchange; a mismatching cell is resynced in full
+//! instead of receiving a delta on top of diverged state.
+//!
+//! An authority server only simulates the cells it owns.
+//! `World::for_each_in_cells` visits just the rows inside those cells,
+//! through a `CellIndex` rebuilt from the position column every tick.
+//!
+//! The world does not know how space is partitioned; `CellPartition` maps
+//! rows to cells (`latch_net::cell::CellGrid` is the engine's grid).
+
+use crate::ecs::{ArchetypeId, ArchetypeStorage, ComponentId};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Assigns entities to spatial cells for `World::checksum_cells`.
+pub trait CellPartition {
+    type Cell: Copy + Eq + Hash;
+
+    /// Components an entity needs to be placed in a cell (typically its
+    /// position). Archetypes without all of them are skipped.
+    fn required_components(&self) -> &[ComponentId];
+
+    /// Push the cell of every row of `storage`, in row order; `None` leaves
+    /// the row out of every checksum. `cells` is empty on entry.
+    fn assign_cells(&self, storage: &ArchetypeStorage, cells: &mut Vec<Option<Self::Cell>>);
+}
+
+/// Rows of every cell, from `World::cell_index`.
+///
+/// Row numbers go stale when rows move or positions change, so build one
+/// index per tick after `flush_despawns` and before systems write
+/// positions, and share it between the systems of that tick.
+pub struct CellIndex<C> {
+    rows: HashMap<C, Vec<(ArchetypeId, usize)>>,
+}
+
+impl<C: Copy + Eq + Hash> CellIndex<C> {
+    pub(crate) fn new() -> Self {
+        Self {
+            rows: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn push(&mut self, cell: C, archetype: ArchetypeId, row: usize) {
+        self.rows.entry(cell).or_default().push((archetype, row));
+    }
+
+    /// Rows in `cell` as `(archetype, row)`, in iteration order.
+    pub fn rows(&self, cell: C) -> &[(ArchetypeId, usize)] {
+        self.rows.get(&cell).map_or(&[], Vec::as_slice)
+    }
+
+    /// Cells holding at least one row, in no particular order.
+    pub fn cells(&self) -> impl Iterator<Item = C> + '_ {
+        self.rows.keys().copied()
+    }
+
+    /// Rows across all of `cells`, sorted by archetype id then row, without
+    /// duplicates.
+    pub(crate) fn rows_in(&self, cells: &[C]) -> Vec<(ArchetypeId, usize)> {
+        let mut rows: Vec<_> = cells
+            .iter()
+            .flat_map(|&cell| self.rows(cell).iter().copied())
+            .collect();
+        rows.sort_unstable();
+        rows.dedup();
+        rows
+    }
+}
@@ -0,0 +1,53 @@
+//! Deferred world mutations recorded off the main thread.
+//!
+//! Workers cannot touch `World` while systems run in parallel, so each one
+//! records spawns and despawns into its own `CommandBuffer`. Spawns target
+//! ids handed out beforehand by `World::reserve_entities`, which lets a
+//! worker store references to entities it has not created yet (a projectile
+//! and its owner link, say) with no id collisions between workers. Applying
+//! the buffers in a fixed order with `World::apply_commands` keeps the
+//! result deterministic.
+
+use crate::ecs::{Entity, EntityBuilder};
+
+pub(crate) enum Command {
+    Spawn {
+        entity: Entity,
+        builder: EntityBuilder,
+    },
+    Despawn(Entity),
+}
+
+/// Ordered list of spawns and despawns applied later by `World::apply_commands`.
+#[derive(Default)]
+pub struct CommandBuffer {
+    commands: Vec<Command>,
+}
+
+impl CommandBuffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Spawn `builder`'s components into `entity`, which must come from
+    /// `World::reserve_entities` and not have been filled yet.
+    pub fn spawn(&mut self, entity: Entity, builder: EntityBuilder) {
+        self.commands.push(Command::Spawn { entity, builder });
+    }
+
+    pub fn despawn(&mut self, entity: Entity) {
+        self.commands.push(Command::Despawn(entity));
+    }
+
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    pub(crate) fn into_commands(self) -> Vec<Command> {
+        self.commands
+    }
+}
@@ -5,10 +5,11 @@
 //! systems (Rust, scripting, tooling) can consistently reason about
 //! component layouts.
 
+use crate::ecs::{ComponentCodec, ComponentCodecError, ComponentRegistrationError};
 use once_cell::sync::OnceCell;
 
 pub use once_cell::sync::OnceCell as __ComponentOnceCell;
-use std::{collections::HashMap, fmt, sync::RwLock};
+use std::{any::TypeId, collections::HashMap, fmt, sync::RwLock};
 
 /// Unique identifier assigned to each registered component.
 pub type ComponentId = u32;
@@ -32,6 +33,55 @@ impl FieldMeta {
     }
 }
 
+/// Minimum page alignment requested for a component's column.
+///
+/// Independent of the component's natural `align`: a `[f32; 4]` column can
+/// ask for 32-byte pages so AVX code may use aligned loads from each page start.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SimdAlign {
+    /// Pages use the component's natural alignment.
+    #[default]
+    Natural,
+    /// 16-byte pages (SSE / NEON).
+    Align16,
+    /// 32-byte pages (AVX).
+    Align32,
+    /// 64-byte pages (AVX-512, cache line).
+    Align64,
+}
+
+impl SimdAlign {
+    #[inline]
+    pub const fn bytes(self) -> usize {
+        match self {
+            SimdAlign::Natural => 1,
+            SimdAlign::Align16 => 16,
+            SimdAlign::Align32 => 32,
+            SimdAlign::Align64 => 64,
+        }
+    }
+}
+
+/// Rust type a component was registered from.
+///
+/// Present for components registered through `Component` (including
+/// `define_component!`); scripts and tools register layouts without one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RustType {
+    pub id: TypeId,
+    pub name: &'static str,
+}
+
+impl RustType {
+    #[inline]
+    pub fn of<T: 'static>() -> Self {
+        Self {
+            id: TypeId::of::<T>(),
+            name: std::any::type_name::<T>(),
+        }
+    }
+}
+
 /// Full runtime metadata for a component.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ComponentMeta {
@@ -42,6 +92,12 @@ pub struct ComponentMeta {
     pub stride: usize,
     pub pod: bool,
     pub fields: Box<[FieldMeta]>,
+    pub simd_align: SimdAlign,
+    /// Custom wire encoding; `None` means the raw `stride` bytes are copied.
+    pub codec: Option<ComponentCodec>,
+    /// Registering Rust type, used to reject typed column access through a
+    /// different component type with the same layout.
+    pub rust_type: Option<RustType>,
 }
 
 impl ComponentMeta {
@@ -55,6 +111,42 @@ impl ComponentMeta {
             pod: self.pod,
         }
     }
+
+    /// Alignment of the column pages backing this component.
+    #[inline]
+    pub fn page_align(&self) -> usize {
+        self.align.max(self.simd_align.bytes())
+    }
+
+    /// Append the wire form of one value (`stride` native bytes) to `out`.
+    #[inline]
+    pub fn encode(&self, value: &[u8], out: &mut Vec<u8>) {
+        debug_assert_eq!(value.len(), self.stride);
+        match self.codec {
+            Some(codec) => (codec.serialize)(value, out),
+            None => out.extend_from_slice(value),
+        }
+    }
+
+    /// Decode one value from the front of `input` into `value` (`stride`
+    /// bytes), returning how many input bytes were consumed.
+    #[inline]
+    pub fn decode(&self, input: &[u8], value: &mut [u8]) -> Result<usize, ComponentCodecError> {
+        debug_assert_eq!(value.len(), self.stride);
+        match self.codec {
+            Some(codec) => (codec.deserialize)(input, value),
+            None => {
+                let raw = input
+                    .get(..self.stride)
+                    .ok_or(ComponentCodecError::Truncated {
+                        needed: self.stride,
+                        available: input.len(),
+                    })?;
+                value.copy_from_slice(raw);
+                Ok(self.stride)
+            }
+        }
+    }
 }
 
 /// Lightweight handle cached by systems once registration succeeds.
@@ -78,6 +170,7 @@ impl ComponentHandle {
 struct Registry {
     by_id: HashMap<ComponentId, ComponentMeta>,
     by_name: HashMap<Box<str>, ComponentId>,
+    by_type: HashMap<TypeId, ComponentId>,
     next_id: ComponentId,
 }
 
@@ -111,6 +204,55 @@ fn validate_layout(
     }
 }
 
+/// Reject layouts the byte-cast column helpers cannot handle soundly.
+fn check_layout(
+    size: usize,
+    align: usize,
+    stride: usize,
+    fields: &[FieldMeta],
+) -> Result<(), ComponentRegistrationError> {
+    let invalid = |reason: String| ComponentRegistrationError::InvalidLayout {
+        size,
+        align,
+        reason,
+    };
+    if !align.is_power_of_two() {
+        return Err(invalid("alignment must be a power of two".into()));
+    }
+    if !size.is_multiple_of(align) {
+        return Err(invalid("size must be a multiple of alignment".into()));
+    }
+    if stride < size || !stride.is_multiple_of(align) {
+        return Err(invalid(format!(
+            "stride {stride} must be at least size and a multiple of alignment"
+        )));
+    }
+
+    for field in fields {
+        if field
+            .offset
+            .checked_add(field.size)
+            .is_none_or(|end| end > size)
+        {
+            return Err(invalid(format!(
+                "field '{}' ({} bytes at offset {}) exceeds the component",
+                field.name, field.size, field.offset
+            )));
+        }
+    }
+    let mut spans: Vec<&FieldMeta> = fields.iter().filter(|field| field.size > 0).collect();
+    spans.sort_by_key(|field| field.offset);
+    for pair in spans.windows(2) {
+        if pair[0].offset + pair[0].size > pair[1].offset {
+            return Err(invalid(format!(
+                "fields '{}' and '{}' overlap",
+                pair[0].name, pair[1].name
+            )));
+        }
+    }
+    Ok(())
+}
+
 fn register_internal(
     name: &str,
     size: usize,
@@ -119,16 +261,8 @@ fn register_internal(
     pod: bool,
     fields: Vec<FieldMeta>,
     explicit_id: Option<ComponentId>,
-) -> ComponentHandle {
-    assert!(
-        align.is_power_of_two(),
-        "component alignment must be power-of-two"
-    );
-    assert!(stride >= size, "stride must be >= size");
-    assert!(
-        stride % align == 0,
-        "stride must be a multiple of alignment"
-    );
+) -> Result<ComponentHandle, ComponentRegistrationError> {
+    check_layout(size, align, stride, &fields)?;
 
     let mut reg = registry_mut();
     if let Some(&id) = reg.by_name.get(name) {
@@ -144,7 +278,7 @@ fn register_internal(
             );
         }
         validate_layout(existing, size, align, stride, pod, &fields);
-        return existing.handle();
+        return Ok(existing.handle());
     }
 
     let id = if let Some(explicit) = explicit_id {
@@ -173,14 +307,22 @@ fn register_internal(
         stride,
         pod,
         fields: fields.into_boxed_slice(),
+        simd_align: SimdAlign::Natural,
+        codec: None,
+        rust_type: None,
     };
 
     reg.by_name.insert(meta.name.clone(), meta.id);
     reg.by_id.insert(meta.id, meta.clone());
-    meta.handle()
+    Ok(meta.handle())
 }
 
 /// Register a Rust-side component layout.
+///
+/// Fails with `InvalidLayout` unless `align` is a power of two, `size` and
+/// `stride` are multiples of it, `stride >= size`, and `fields` lie inside
+/// `size` without overlapping. The same checks apply to every
+/// `register_*` function.
 pub fn register_component(
     name: &str,
     size: usize,
@@ -188,7 +330,7 @@ pub fn register_component(
     stride: usize,
     pod: bool,
     fields: Vec<FieldMeta>,
-) -> ComponentHandle {
+) -> Result<ComponentHandle, ComponentRegistrationError> {
     register_internal(name, size, align, stride, pod, fields, None)
 }
 
@@ -200,10 +342,24 @@ pub fn register_external_component_with_fields(
     stride: usize,
     fields: Vec<FieldMeta>,
     pod: bool,
-) -> ComponentHandle {
+) -> Result<ComponentHandle, ComponentRegistrationError> {
     register_internal(name, size, align, stride, pod, fields, None)
 }
 
+/// Register a component whose snapshot/replication bytes go through `codec`.
+pub fn register_component_with_codec(
+    name: &str,
+    size: usize,
+    align: usize,
+    stride: usize,
+    fields: Vec<FieldMeta>,
+    codec: ComponentCodec,
+) -> Result<ComponentHandle, ComponentRegistrationError> {
+    let handle = register_internal(name, size, align, stride, false, fields, None)?;
+    set_component_codec(handle.id, Some(codec));
+    Ok(handle)
+}
+
 /// Register a Rust component with an explicit, stable component id.
 pub fn register_component_with_id(
     id: ComponentId,
@@ -213,10 +369,143 @@ pub fn register_component_with_id(
     stride: usize,
     pod: bool,
     fields: Vec<FieldMeta>,
-) -> ComponentHandle {
+) -> Result<ComponentHandle, ComponentRegistrationError> {
     register_internal(name, size, align, stride, pod, fields, Some(id))
 }
 
+/// Request SIMD over-alignment for an already registered component.
+///
+/// Only archetypes planned after this call pick up the new alignment, so it
+/// should run as part of registration (see `Component::simd_align`).
+pub fn set_component_simd_align(id: ComponentId, simd_align: SimdAlign) {
+    let mut reg = registry_mut();
+    let meta = reg
+        .by_id
+        .get_mut(&id)
+        .unwrap_or_else(|| panic!("component id {id} not registered"));
+    meta.simd_align = simd_align;
+}
+
+/// Attach (or clear) the serialization hooks of a registered component.
+pub fn set_component_codec(id: ComponentId, codec: Option<ComponentCodec>) {
+    let mut reg = registry_mut();
+    let meta = reg
+        .by_id
+        .get_mut(&id)
+        .unwrap_or_else(|| panic!("component id {id} not registered"));
+    meta.codec = codec;
+}
+
+/// Record the Rust type behind a registered component.
+///
+/// The first type wins: if a second type registers under the same name (and
+/// therefore id), typed column access through it reports a mismatch.
+pub fn set_component_rust_type<T: 'static>(id: ComponentId) {
+    let rust_type = RustType::of::<T>();
+    let mut reg = registry_mut();
+    let meta = reg
+        .by_id
+        .get_mut(&id)
+        .unwrap_or_else(|| panic!("component id {id} not registered"));
+    if meta.rust_type.is_none() {
+        meta.rust_type = Some(rust_type);
+    }
+    reg.by_type.entry(rust_type.id).or_insert(id);
+}
+
+/// Handle to use for a component whose handle was cached in a static.
+///
+/// With `test-util`, `reset_registry` can drop a component after its handle
+/// was cached; `register` then runs again so the registry has it back.
+/// Until the first reset this is just an atomic load.
+#[doc(hidden)]
+#[inline]
+pub fn __current_handle(
+    cached: ComponentHandle,
+    register: fn() -> ComponentHandle,
+) -> ComponentHandle {
+    #[cfg(any(test, feature = "test-util"))]
+    if REGISTRY_RESET.load(std::sync::atomic::Ordering::Acquire) {
+        let registered = REGISTRY
+            .get()
+            .and_then(|lock| lock.read().ok())
+            .is_some_and(|reg| reg.by_id.contains_key(&cached.id));
+        if !registered {
+            return register();
+        }
+    }
+    #[cfg(not(any(test, feature = "test-util")))]
+    let _ = register;
+    cached
+}
+
+/// Set by the first `reset_registry`; cached handles are trusted until then.
+#[cfg(any(test, feature = "test-util"))]
+static REGISTRY_RESET: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+/// Held by a test between `reset_registry` and the end of the test.
+#[cfg(any(test, feature = "test-util"))]
+pub struct RegistryReset {
+    _serial: std::sync::MutexGuard<'static, ()>,
+}
+
+/// Clear every registered component so the calling test starts clean.
+///
+/// Test-only: available to unit tests, and to integration tests through the
+/// `test-util` feature (latch_core's own tests enable it). The returned
+/// guard makes other `reset_registry` callers wait until it drops, so tests
+/// of one binary that all reset never see each other's registrations; tests
+/// that don't call it are not held back and must not run alongside ones
+/// that do.
+///
+/// Never call this while a `World`, `QueryCache` or script still refers to
+/// registered components: their ids would resolve to nothing or to a
+/// different layout. Components defined with `define_component!` register
+/// again on their next use. Automatically assigned ids keep counting up,
+/// so a handle cached before the reset never aliases a component
+/// registered after it.
+#[cfg(any(test, feature = "test-util"))]
+pub fn reset_registry() -> RegistryReset {
+    static SERIAL: std::sync::Mutex<()> = std::sync::Mutex::new(());
+    // A test that panicked while holding the guard leaves nothing behind
+    // that the reset below does not clear.
+    let serial = SERIAL
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    REGISTRY_RESET.store(true, std::sync::atomic::Ordering::Release);
+    let mut reg = registry_mut();
+    reg.by_id.clear();
+    reg.by_name.clear();
+    reg.by_type.clear();
+    RegistryReset { _serial: serial }
+}
+
+/// Component id registered for the Rust type `type_id`, if any.
+pub fn component_of_type(type_id: TypeId) -> Option<ComponentId> {
+    REGISTRY
+        .get()
+        .and_then(|lock| lock.read().ok())
+        .and_then(|reg| reg.by_type.get(&type_id).copied())
+}
+
+/// Handle of the component registered for the Rust type `T`, if any.
+///
+/// Bridges type-keyed generic code to the `ComponentId` scheme: for a
+/// `define_component!` type this is the column of its explicit `ID`.
+/// Registration is lazy, so a type that has not been used yet (or passed to
+/// `Component::ensure_registered`) resolves to `None`.
+pub fn handle_of_type<T: 'static>() -> Option<ComponentHandle> {
+    REGISTRY
+        .get()
+        .and_then(|lock| lock.read().ok())
+        .and_then(|reg| {
+            reg.by_type
+                .get(&TypeId::of::<T>())
+                .and_then(|id| reg.by_id.get(id))
+                .map(ComponentMeta::handle)
+        })
+}
+
 /// Retrieve metadata by id.
 pub fn meta_of(id: ComponentId) -> Option<ComponentMeta> {
     REGISTRY
@@ -259,6 +548,16 @@ pub trait Component: 'static + Send + Sync {
         Vec::new()
     }
 
+    /// Override to over-align this component's column pages for SIMD access.
+    fn simd_align() -> SimdAlign {
+        SimdAlign::Natural
+    }
+
+    /// Override to encode this component with custom hooks instead of raw bytes.
+    fn codec() -> Option<ComponentCodec> {
+        None
+    }
+
     /// Register the component layout and return its handle.
     fn register_layout() -> ComponentHandle
     where
@@ -267,7 +566,7 @@ pub trait Component: 'static + Send + Sync {
         let size = std::mem::size_of::<Self>();
         let align = std::mem::align_of::<Self>();
         let stride = size.next_multiple_of(align);
-        register_component(
+        let handle = register_component(
             Self::NAME,
             size,
             align,
@@ -275,6 +574,13 @@ pub trait Component: 'static + Send + Sync {
             Self::is_pod(),
             Self::fields(),
         )
+        .unwrap_or_else(|err| panic!("component '{}': {err}", Self::NAME));
+        set_component_simd_align(handle.id, Self::simd_align());
+        set_component_rust_type::<Self>(handle.id);
+        if let Some(codec) = Self::codec() {
+            set_component_codec(handle.id, Some(codec));
+        }
+        handle
     }
 
     /// Lazy component handle registration.
@@ -283,7 +589,10 @@ pub trait Component: 'static + Send + Sync {
         Self: Sized,
     {
         static HANDLE: OnceCell<ComponentHandle> = OnceCell::new();
-        *HANDLE.get_or_init(Self::register_layout)
+        __current_handle(
+            *HANDLE.get_or_init(Self::register_layout),
+            Self::register_layout,
+        )
     }
 
     #[inline]
@@ -293,6 +602,15 @@ pub trait Component: 'static + Send + Sync {
     {
         Self::handle().id
     }
+
+    /// Force registration up front (e.g. before scripts look components up by name).
+    #[inline]
+    fn ensure_registered()
+    where
+        Self: Sized,
+    {
+        Self::handle();
+    }
 }
 
 /// Helper macro for trivial POD components.
@@ -317,7 +635,7 @@ macro_rules! define_component {
             fn handle() -> $crate::ecs::ComponentHandle {
                 static HANDLE: $crate::ecs::__ComponentOnceCell<$crate::ecs::ComponentHandle> =
                     $crate::ecs::__ComponentOnceCell::new();
-                *HANDLE.get_or_init(|| {
+                fn register() -> $crate::ecs::ComponentHandle {
                     let size = std::mem::size_of::<$ty>();
                     let align = std::mem::align_of::<$ty>();
                     let stride = size.next_multiple_of(align);
@@ -329,9 +647,19 @@ macro_rules! define_component {
                         stride,
                         <$ty as $crate::ecs::Component>::is_pod(),
                         <$ty as $crate::ecs::Component>::fields(),
+                    )
+                    .unwrap_or_else(|err| panic!("component '{}': {err}", $name));
+                    $crate::ecs::set_component_simd_align(
+                        handle.id,
+                        <$ty as $crate::ecs::Component>::simd_align(),
                     );
+                    $crate::ecs::set_component_rust_type::<$ty>(handle.id);
+                    if let Some(codec) = <$ty as $crate::ecs::Component>::codec() {
+                        $crate::ecs::set_component_codec(handle.id, Some(codec));
+                    }
                     handle
-                })
+                }
+                $crate::ecs::__current_handle(*HANDLE.get_or_init(register), register)
             }
         }
 
@@ -349,8 +677,14 @@ impl fmt::Display for ComponentMeta {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "ComponentMeta {{ id: {}, name: {}, size: {}, align: {}, stride: {}, pod: {} }}",
-            self.id, self.name, self.size, self.align, self.stride, self.pod
+            "ComponentMeta {{ id: {}, name: {}, size: {}, align: {}, stride: {}, pod: {}, simd_align: {} }}",
+            self.id,
+            self.name,
+            self.size,
+            self.align,
+            self.stride,
+            self.pod,
+            self.simd_align.bytes()
         )
     }
 }
@@ -0,0 +1,49 @@
+//! Per-component serialization hooks.
+//!
+//! Snapshot and replication encoders copy component bytes verbatim unless a
+//! component registers a `ComponentCodec`. Codecs let a component translate
+//! its in-memory layout to a stable wire form (fixed endianness, remapped
+//! table indices, versioned layouts) without slowing down POD components.
+
+use thiserror::Error;
+
+/// Append the wire form of one component value (`value` is `stride` bytes in
+/// native layout) to `out`.
+pub type SerializeFn = fn(value: &[u8], out: &mut Vec<u8>);
+
+/// Read one component value from the front of `input` into `value` (`stride`
+/// bytes, native layout), returning the number of input bytes consumed.
+pub type DeserializeFn = fn(input: &[u8], value: &mut [u8]) -> Result<usize, ComponentCodecError>;
+
+#[derive(Debug, Error)]
+pub enum ComponentCodecError {
+    #[error("component data truncated: needed {needed} bytes, {available} available")]
+    Truncated { needed: usize, available: usize },
+    #[error("invalid component data: {reason}")]
+    Invalid { reason: Box<str> },
+}
+
+/// Serialize/deserialize pair attached to a component's metadata.
+#[derive(Clone, Copy, Debug)]
+pub struct ComponentCodec {
+    pub serialize: SerializeFn,
+    pub deserialize: DeserializeFn,
+}
+
+impl ComponentCodec {
+    pub const fn new(serialize: SerializeFn, deserialize: DeserializeFn) -> Self {
+        Self {
+            serialize,
+            deserialize,
+        }
+    }
+}
+
+impl PartialEq for ComponentCodec {
+    fn eq(&self, other: &Self) -> bool {
+        self.serialize as usize == other.serialize as usize
+            && self.deserialize as usize == other.deserialize as usize
+    }
+}
+
+impl Eq for ComponentCodec {}
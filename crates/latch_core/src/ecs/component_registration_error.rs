@@ -0,0 +1,14 @@
+use thiserror::Error;
+
+/// Errors that can occur while registering a component layout.
+#[derive(Debug, Error)]
+pub enum ComponentRegistrationError {
+    /// The layout would let column byte casts produce misaligned or
+    /// out-of-bounds references.
+    #[error("invalid component layout (size {size}, align {align}): {reason}")]
+    InvalidLayout {
+        size: usize,
+        align: usize,
+        reason: String,
+    },
+}
@@ -0,0 +1,90 @@
+//! TypeScript declaration output for registered components.
+//!
+//! Scripts address component bytes through typed arrays, so besides an
+//! interface per component they need the row stride and every field's byte
+//! offset. `emit_ts_defs` derives all of it from `ComponentMeta`, keeping the
+//! `.d.ts` a game ships in lockstep with the Rust layouts.
+
+use crate::ecs::{meta_of, ComponentId, ComponentMeta};
+use std::fmt::Write;
+
+/// Render TypeScript declarations for `ids`, in the given order.
+///
+/// Each component produces an interface (all fields typed `number`, since
+/// `FieldMeta` records sizes but not scalar kinds) plus two `const enum`s:
+/// `<Name>Layout` with `ID`, `SIZE` and `STRIDE`, and `<Name>Offsets` with
+/// one member per field. Unregistered ids are noted in a comment.
+pub fn emit_ts_defs(ids: &[ComponentId]) -> String {
+    let mut out = String::from("// Generated from the latch component registry. Do not edit.\n");
+    for &id in ids {
+        out.push('\n');
+        match meta_of(id) {
+            Some(meta) => write_component(&mut out, &meta),
+            None => {
+                let _ = writeln!(out, "// component id {id} is not registered");
+            }
+        }
+    }
+    out
+}
+
+fn write_component(out: &mut String, meta: &ComponentMeta) {
+    let name = ts_type_name(&meta.name);
+    let _ = writeln!(
+        out,
+        "/** `{}` (id {}): {} bytes, stride {}. */",
+        meta.name, meta.id, meta.size, meta.stride
+    );
+    let _ = writeln!(out, "export interface {name} {{");
+    for field in meta.fields.iter() {
+        let _ = writeln!(
+            out,
+            "  /** offset {}, {} bytes */\n  {}: number;",
+            field.offset,
+            field.size,
+            ts_member_name(&field.name)
+        );
+    }
+    out.push_str("}\n");
+
+    let _ = writeln!(out, "export const enum {name}Layout {{");
+    let _ = writeln!(out, "  ID = {},", meta.id);
+    let _ = writeln!(out, "  SIZE = {},", meta.size);
+    let _ = writeln!(out, "  STRIDE = {},", meta.stride);
+    out.push_str("}\n");
+
+    let _ = writeln!(out, "export const enum {name}Offsets {{");
+    for field in meta.fields.iter() {
+        let _ = writeln!(out, "  {} = {},", ts_member_name(&field.name), field.offset);
+    }
+    out.push_str("}\n");
+}
+
+/// `game::Position` → `game_Position`; anything else outside `[A-Za-z0-9_$]`
+/// becomes `_`, and a leading digit gets a `_` prefix.
+fn ts_type_name(name: &str) -> String {
+    let mut ident: String = name
+        .replace("::", "_")
+        .chars()
+        .map(|c| if is_ident_char(c) { c } else { '_' })
+        .collect();
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, '_');
+    }
+    ident
+}
+
+fn ts_member_name(name: &str) -> String {
+    let valid = name.chars().all(is_ident_char)
+        && !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit());
+    if valid {
+        name.to_string()
+    } else {
+        format!("{name:?}")
+    }
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '$'
+}
@@ -4,7 +4,7 @@
 //! index alongside a generation counter. World storage keeps a map
 //! from `Entity` to `EntityLoc`, allowing quick validation and lookup
 //! of archetype/row information without embedding location data in
-use crate::ecs::ArchetypeId;
+use crate::ecs::{ArchetypeId, World};
 
 /// Dense index type used inside packed archetype storage.
 pub type EntityId = u32;
@@ -42,6 +42,24 @@ impl Entity {
     }
 }
 
+/// How a `World` picks the id of a new entity; see
+/// `WorldBuilder::entity_id_policy`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum EntityIdPolicy {
+    /// Reuse the most recently freed id first, so ids depend on despawn
+    /// history but the slot table stays as small as the peak population.
+    #[default]
+    Reuse,
+    /// Hand out ids in strictly increasing order and never reuse one.
+    ///
+    /// Nodes that spawn the same entities in the same order agree on every
+    /// id, whatever they despawned in between. The price is memory: the
+    /// slot table keeps one entry per entity ever spawned, `shrink_slots`
+    /// releases nothing, and spawning fails with `EntityIndexOverflow`
+    /// after `u32::MAX` spawns.
+    Monotonic,
+}
+
 /// Location of a live entity inside world storage.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct EntityLoc {
@@ -60,3 +78,38 @@ impl EntityLoc {
         }
     }
 }
+
+/// Entity reference that may outlive the entity it points at.
+///
+/// Long-lived gameplay state (targets, owners, camera follow) should hold a
+/// `WeakEntity` rather than an `Entity`: the type states that the entity may
+/// have been despawned, and `upgrade` is the only way back to a usable handle.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct WeakEntity(Entity);
+
+impl WeakEntity {
+    #[inline]
+    pub fn new(entity: Entity) -> Self {
+        Self(entity)
+    }
+
+    /// The entity, if it is still alive with the same generation.
+    #[inline]
+    pub fn upgrade(self, world: &World) -> Option<Entity> {
+        world.validate(self.0).map(|_| self.0)
+    }
+
+    /// The referenced handle without checking liveness (for logging and
+    /// keying maps).
+    #[inline]
+    pub fn entity(self) -> Entity {
+        self.0
+    }
+}
+
+impl From<Entity> for WeakEntity {
+    #[inline]
+    fn from(entity: Entity) -> Self {
+        Self(entity)
+    }
+}
@@ -0,0 +1,130 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+
+/// Double-buffered queue of plain-data events.
+///
+/// Producers `send` into the write buffer during tick N; consumers read the
+/// events via `drain` during tick N+1 after `swap` has promoted them. Events
+/// that are not read within that tick are discarded on the following swap, so
+/// the queue never grows unbounded and delivery order is exactly send order.
+pub struct Events<E> {
+    read: Vec<E>,
+    write: Vec<E>,
+}
+
+impl<E: Copy + Send + Sync + 'static> Events<E> {
+    pub fn new() -> Self {
+        Self {
+            read: Vec::new(),
+            write: Vec::new(),
+        }
+    }
+
+    /// Queue an event for delivery after the next `swap`.
+    #[inline]
+    pub fn send(&mut self, event: E) {
+        self.write.push(event);
+    }
+
+    /// Iterate the events published by the previous tick, in send order.
+    #[inline]
+    pub fn drain(&self) -> impl Iterator<Item = &E> {
+        self.read.iter()
+    }
+
+    /// Number of events readable this tick.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.read.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.read.is_empty()
+    }
+
+    /// Number of events queued for the next tick.
+    #[inline]
+    pub fn pending_len(&self) -> usize {
+        self.write.len()
+    }
+
+    /// Promote queued events to the read buffer and drop last tick's events.
+    ///
+    /// Both buffers keep their capacity, so steady-state ticks do not allocate.
+    pub fn swap(&mut self) {
+        self.read.clear();
+        std::mem::swap(&mut self.read, &mut self.write);
+    }
+
+    /// Discard all readable and queued events.
+    pub fn clear(&mut self) {
+        self.read.clear();
+        self.write.clear();
+    }
+}
+
+impl<E: Copy + Send + Sync + 'static> Default for Events<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+trait EventQueue: Send + Sync {
+    fn swap(&mut self);
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<E: Copy + Send + Sync + 'static> EventQueue for Events<E> {
+    fn swap(&mut self) {
+        Events::swap(self);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Type-keyed collection of event queues owned by the `World`.
+#[derive(Default)]
+pub(crate) struct EventRegistry {
+    queues: HashMap<TypeId, Box<dyn EventQueue>>,
+}
+
+impl EventRegistry {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn register<E: Copy + Send + Sync + 'static>(&mut self) -> &mut Events<E> {
+        self.queues
+            .entry(TypeId::of::<E>())
+            .or_insert_with(|| Box::new(Events::<E>::new()))
+            .as_any_mut()
+            .downcast_mut::<Events<E>>()
+            .expect("event queue registered under mismatched type")
+    }
+
+    pub(crate) fn get<E: Copy + Send + Sync + 'static>(&self) -> Option<&Events<E>> {
+        self.queues
+            .get(&TypeId::of::<E>())
+            .and_then(|queue| queue.as_any().downcast_ref::<Events<E>>())
+    }
+
+    pub(crate) fn get_mut<E: Copy + Send + Sync + 'static>(&mut self) -> Option<&mut Events<E>> {
+        self.queues
+            .get_mut(&TypeId::of::<E>())
+            .and_then(|queue| queue.as_any_mut().downcast_mut::<Events<E>>())
+    }
+
+    pub(crate) fn swap_all(&mut self) {
+        for queue in self.queues.values_mut() {
+            queue.swap();
+        }
+    }
+}
@@ -0,0 +1,76 @@
+//! Why a query does or does not visit an entity.
+//!
+//! `World::explain_match` answers "why isn't my system touching this
+//! entity?": whether the handle still refers to a live entity, and which of
+//! the queried components its archetype has and lacks. Editors show it as
+//! is; tests print it when an assertion fails.
+
+use crate::ecs::{meta_of, ArchetypeId, ComponentId, Entity};
+use std::fmt;
+
+/// State of an entity handle, as far as queries are concerned.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EntityStatus {
+    Alive,
+    /// Despawned but not yet flushed; its row is still visited by
+    /// `for_each` until `flush_despawns`.
+    PendingDespawn,
+    /// Allocated by `reserve_entities` but not spawned yet.
+    Reserved,
+    /// Despawned and flushed, or the slot has been reused since.
+    Stale,
+    /// The index was never allocated by this world.
+    Unknown,
+}
+
+/// Result of `World::explain_match`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MatchExplanation {
+    pub entity: Entity,
+    pub status: EntityStatus,
+    /// Archetype holding the entity's row, if it has one.
+    pub archetype: Option<ArchetypeId>,
+    /// Queried components the archetype has, in query order.
+    pub present: Vec<ComponentId>,
+    /// Queried components the archetype lacks, in query order; every one
+    /// of them when the entity has no row.
+    pub missing: Vec<ComponentId>,
+}
+
+impl MatchExplanation {
+    /// Whether a query over these components visits the entity's row.
+    pub fn matches(&self) -> bool {
+        self.archetype.is_some() && self.missing.is_empty()
+    }
+}
+
+impl fmt::Display for MatchExplanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (index, generation) = (self.entity.index(), self.entity.generation());
+        write!(f, "entity {index}v{generation} ")?;
+        match self.status {
+            EntityStatus::Alive => write!(f, "is alive")?,
+            EntityStatus::PendingDespawn => write!(f, "is despawned (not yet flushed)")?,
+            EntityStatus::Reserved => write!(f, "is reserved but not spawned")?,
+            EntityStatus::Stale => write!(f, "is despawned (stale handle)")?,
+            EntityStatus::Unknown => write!(f, "was never allocated")?,
+        }
+        if let Some(archetype) = self.archetype {
+            write!(f, " in archetype {archetype:#018x}")?;
+        }
+        if self.missing.is_empty() {
+            return write!(f, "; has every queried component");
+        }
+        write!(f, "; missing ")?;
+        for (i, &component_id) in self.missing.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            match meta_of(component_id) {
+                Some(meta) => write!(f, "{} (id {component_id})", meta.name)?,
+                None => write!(f, "unregistered component {component_id}")?,
+            }
+        }
+        Ok(())
+    }
+}
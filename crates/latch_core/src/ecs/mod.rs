@@ -1,50 +1,114 @@
 //! Entity Component System core types.
 //!
-//! This module is being rewritten to follow the updated ECS design
-//! documented in `.github/instructions/ecs.instructions.md`. The goal
-//! is to provide cache-efficient, deterministic storage with rich
-//! runtime metadata for both Rust and scripted components. The current
-//! contents focus on foundational building blocks (components,
-//! entities, archetype layout). Higher-level systems such as storage
-//! and world management will be reintroduced in subsequent iterations.
+//! Archetype storage split into cache-sized pages, double-buffered
+//! columns (systems read the current buffer and write the next;
+//! `World::swap_buffers` publishes) and runtime component metadata shared
+//! with scripts. The design is
+//! documented in `.github/instructions/ecs.instructions.md`.
+//!
+//! # Accessing components
+//!
+//! - Iterate with `World::for_each` (or `try_for_each`) and take slices
+//!   with `columns!` / `columns_mut!` / `try_columns!` inside the callback,
+//!   or use `World::archetypes_matching` plus
+//!   `ArchetypeStorage::column_slice`.
+//! - Archetypes are fixed for an entity's lifetime: spawn it with every
+//!   component through `EntityBuilder`.
+//! - `define_component!` gives each type a stable `ComponentId` usable
+//!   from scripts and across builds. Generic code that only has the type
+//!   finds the same column through `handle_of_type::<T>()`.
 
 mod archetype;
 mod builder;
+mod cell_checksum;
+mod command_buffer;
 mod component;
+mod component_codec;
+mod component_registration_error;
+mod component_ts;
 mod entity;
+mod events;
+mod match_explanation;
 pub mod query;
+mod query_cache;
+mod query_view;
+mod resources;
+mod schedule;
+mod shared_world;
+mod signature;
+mod snapshot;
+mod snapshot_diff;
+mod stable_index;
+mod state_hash;
 pub mod storage;
 mod system_descriptor;
 mod system_handle;
 mod system_registration_error;
 mod system_registry;
+mod template;
+mod topology;
 mod world;
+mod world_builder;
 
 pub use archetype::{ArchetypeId, ArchetypeLayout};
 pub use builder::{ComponentBytes, EntityBlueprint, EntityBuilder, EntityBuilderError};
+pub use cell_checksum::{CellIndex, CellPartition};
+pub use command_buffer::CommandBuffer;
 pub use component::{
-    handle_of_name, meta_of, meta_of_name, register_component, register_component_with_id,
-    register_external_component_with_fields, Component, ComponentHandle, ComponentId,
-    ComponentMeta, FieldMeta, __ComponentOnceCell,
+    component_of_type, handle_of_name, handle_of_type, meta_of, meta_of_name, register_component,
+    register_component_with_codec, register_component_with_id,
+    register_external_component_with_fields, set_component_codec, set_component_rust_type,
+    set_component_simd_align, Component, ComponentHandle, ComponentId, ComponentMeta, FieldMeta,
+    RustType, SimdAlign, __ComponentOnceCell, __current_handle,
 };
-pub use entity::{Entity, EntityId, EntityLoc, Generation};
+#[cfg(any(test, feature = "test-util"))]
+pub use component::{reset_registry, RegistryReset};
+pub use component_codec::{ComponentCodec, ComponentCodecError, DeserializeFn, SerializeFn};
+pub use component_registration_error::ComponentRegistrationError;
+pub use component_ts::emit_ts_defs;
+pub use entity::{Entity, EntityId, EntityIdPolicy, EntityLoc, Generation, WeakEntity};
+pub use events::Events;
+pub use match_explanation::{EntityStatus, MatchExplanation};
 pub use query::{
-    QueryRegistry, RelationAccelerator, RelationBuffer, RelationIter, RelationPayloadRange,
-    RelationRecord, RelationType, SpatialHashConfig, SpatialHashGrid,
+    OverflowPolicy, QueryRegistry, RelationAccelerator, RelationBuffer, RelationIter,
+    RelationPayloadRange, RelationRecord, RelationType, SpatialHashConfig, SpatialHashGrid,
+    TriggerAccelerator, TriggerConfig, TriggerPhase, VisibilityAccelerator, VisibilityConfig,
+};
+pub use query_cache::QueryCache;
+pub use query_view::QueryView;
+pub use schedule::{PhaseTiming, Schedule, ScheduleError, SystemContext, TickPhase, TickTimings};
+pub use shared_world::SharedWorld;
+pub use signature::ComponentSignature;
+pub use snapshot::{
+    SnapshotCompression, SnapshotError, SnapshotHeader, SNAPSHOT_FLAG_DEFLATE, SNAPSHOT_MAGIC,
+    SNAPSHOT_VERSION,
+};
+pub use snapshot_diff::{ChangeRecord, ComponentValue, FieldChange, WorldSnapshot};
+pub use stable_index::StableIndexMove;
+pub use state_hash::{
+    verify_determinism, ArchetypeHash, Divergence, HashDifference, ReplayVerifier, StateHashes,
 };
 pub use storage::{
-    plan_archetype, ArchetypePlan, ArchetypeStorage, ColumnError, PageBudget, PlanError,
+    plan_archetype, sort_instances_by_layer, ArchetypePlan, ArchetypeStorage, ColumnError,
+    CullBounds, CullStats, PageAllocator, PageBudget, PagePool, PlanError, RenderLayer, RowInit,
     StorageError,
 };
 pub use system_descriptor::SystemDescriptor;
 pub use system_handle::SystemHandle;
 pub use system_registration_error::SystemRegistrationError;
 pub(crate) use system_registry::SystemRegistry;
+pub use template::{EntityTemplate, TemplateInstance};
+pub use topology::TopologyChanges;
+pub(crate) use topology::TopologyLog;
 pub use world::{World, WorldError};
+pub use world_builder::WorldBuilder;
 
-/// Spawn an entity into the world using builder-style component construction.
+/// Spawn an entity into the world, yielding `Result<Entity, WorldError>`.
+///
+/// Use this in long-running hosts (servers, tools) where a failed spawn
+/// should be handled rather than abort the process.
 #[macro_export]
-macro_rules! spawn {
+macro_rules! try_spawn {
     ($world:expr $(, $component:expr)+ $(,)?) => {{
         let builder = {
             let mut builder = $crate::ecs::EntityBuilder::new();
@@ -53,8 +117,16 @@ macro_rules! spawn {
             )+
             builder
         };
-        $world
-            .spawn(builder)
-            .expect("failed to spawn entity")
+        $world.spawn(builder)
     }};
 }
+
+/// Spawn an entity into the world using builder-style component construction.
+///
+/// Panics if the spawn fails; see `try_spawn!` for the fallible form.
+#[macro_export]
+macro_rules! spawn {
+    ($world:expr $(, $component:expr)+ $(,)?) => {
+        $crate::try_spawn!($world $(, $component)+).expect("failed to spawn entity")
+    };
+}
@@ -2,10 +2,13 @@
 
 use super::relation::{RelationBuffer, RelationType};
 use crate::ecs::{Entity, World};
+use std::any::Any;
 
 /// Trait representing a structure capable of emitting relations for a specific
 /// semantic category (collisions, triggers, visibility, etc.).
-pub trait RelationAccelerator: Send + Sync {
+///
+/// `Any` lets `QueryRegistry::get_as` hand back the concrete type.
+pub trait RelationAccelerator: Any + Send + Sync {
     /// Type discriminator for the relations produced by this accelerator.
     fn relation_type(&self) -> RelationType;
 
@@ -21,6 +24,7 @@ pub trait RelationAccelerator: Send + Sync {
 
     /// Rebuild internal structures from the current world snapshot and emit relations
     /// directly into the provided buffer. Implementations should prefer streaming
-    /// passes that avoid unnecessary allocations.
+    /// passes that avoid unnecessary allocations; `World::for_each_row` and
+    /// `World::for_each_located_row` walk a component's values without one.
     fn rebuild(&mut self, world: &World, output: &mut RelationBuffer);
 }
@@ -3,19 +3,22 @@
 
 mod accelerator;
 mod relation;
+mod scan;
 mod spatial_hash;
+mod trigger;
+mod visibility;
 
 pub use accelerator::RelationAccelerator;
 pub use relation::{
-    EntityRelationEntry, RelationBuffer, RelationDelta, RelationIter, RelationLocation,
-    RelationPayloadRange, RelationRecord, RelationType,
-};
-pub use spatial_hash::{
-    reset_spatial_hash_metrics, spatial_hash_metrics_snapshot, SpatialHashConfig, SpatialHashGrid,
-    SpatialHashMetricsSnapshot,
+    EntityRelationEntry, OverflowPolicy, RelationBuffer, RelationDelta, RelationIter,
+    RelationLocation, RelationPayloadRange, RelationRecord, RelationType,
 };
+pub use spatial_hash::{SpatialHashConfig, SpatialHashGrid, SpatialHashMetricsSnapshot};
+pub use trigger::{TriggerAccelerator, TriggerConfig, TriggerPhase};
+pub use visibility::{VisibilityAccelerator, VisibilityConfig};
 
 use crate::ecs::World;
+use std::any::Any;
 use std::collections::HashMap;
 
 /// Owns all registered relation accelerators and coordinates rebuild/emit passes.
@@ -41,10 +44,13 @@ impl QueryRegistry {
         self.by_type.insert(ty, self.accelerators.len() - 1);
     }
 
+    /// Run every accelerator, then put the buffer into canonical order so
+    /// consumers see the same relation sequence on every run and replay.
     pub fn rebuild_all(&mut self, world: &World, buffer: &mut RelationBuffer) {
         for accelerator in &mut self.accelerators {
             accelerator.rebuild(world, buffer);
         }
+        buffer.sort_canonical();
     }
 
     pub fn get(&self, relation: RelationType) -> Option<&dyn RelationAccelerator> {
@@ -53,6 +59,22 @@ impl QueryRegistry {
             .and_then(|&idx| self.accelerators.get(idx))
             .map(|boxed| &**boxed as &dyn RelationAccelerator)
     }
+
+    /// The accelerator registered for `relation` as its concrete type, e.g.
+    /// to read a `SpatialHashGrid`'s metrics. `None` if nothing is
+    /// registered for `relation` or it is not an `A`.
+    pub fn get_as<A: RelationAccelerator>(&self, relation: RelationType) -> Option<&A> {
+        let idx = *self.by_type.get(&relation.raw())?;
+        let accelerator: &dyn Any = &*self.accelerators[idx];
+        accelerator.downcast_ref()
+    }
+
+    /// Mutable counterpart of `get_as`.
+    pub fn get_as_mut<A: RelationAccelerator>(&mut self, relation: RelationType) -> Option<&mut A> {
+        let idx = *self.by_type.get(&relation.raw())?;
+        let accelerator: &mut dyn Any = &mut *self.accelerators[idx];
+        accelerator.downcast_mut()
+    }
 }
 
 impl Default for QueryRegistry {
@@ -59,10 +59,14 @@ impl RelationRecord {
 }
 
 /// Optional offset metadata describing the relative delta between two entities.
+///
+/// Positions are `i32`, so the difference of two of them needs 33 bits;
+/// the fields are `i64` so entities near opposite ends of the world never
+/// wrap around.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct RelationDelta {
-    pub dx: i32,
-    pub dy: i32,
+    pub dx: i64,
+    pub dy: i64,
 }
 
 impl RelationDelta {
@@ -98,6 +102,45 @@ impl RelationLocation {
     }
 }
 
+impl EntityRelationEntry {
+    /// Ordering for `OverflowPolicy::KeepNearest`: squared delta length,
+    /// entries without a delta last, ties broken by the other entity and
+    /// the relation type so the result never depends on emission order.
+    fn nearness_key(&self) -> (u128, u64, u16) {
+        let distance_sq = self.delta.map_or(u128::MAX, |delta| {
+            let (dx, dy) = (
+                delta.dx.unsigned_abs() as u128,
+                delta.dy.unsigned_abs() as u128,
+            );
+            dx * dx + dy * dy
+        });
+        (distance_sq, self.other.to_bits(), self.relation_type.raw())
+    }
+}
+
+/// What a `RelationBuffer` does with a relation whose entity already has
+/// `per_entity_cap` entries (see `RelationBuffer::with_per_entity_cap`).
+///
+/// The cap bounds each entity's `relations_for` view; `iter` and `iter_type`
+/// still yield every record. Accelerators emit in an order that depends on
+/// their internal hash maps, so only policies that ignore emission order
+/// keep the per-entity views deterministic:
+///
+/// - `Grow` and `KeepNearest` are deterministic (after `sort_canonical`).
+/// - `DropExcess` keeps whichever relations arrived first and may differ
+///   between runs and machines; use it only where that is acceptable.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Keep every relation; the cap is ignored.
+    #[default]
+    Grow,
+    /// Drop relations arriving after the entity is full.
+    DropExcess,
+    /// Keep the `per_entity_cap` relations with the smallest delta
+    /// magnitude; relations without a delta rank after all others.
+    KeepNearest,
+}
+
 #[derive(Debug)]
 struct EntityRelationBucket {
     entity_id: EntityId,
@@ -115,15 +158,28 @@ impl EntityRelationBucket {
 
 /// Paged arena that stores relation headers and optional payloads without
 /// reallocation each tick.
+///
+/// The initial sizes passed to `new` are page sizes, not limits: pushing
+/// past them allocates additional pages, so relations are never dropped.
+/// Growth can therefore allocate mid-tick (timing varies) while the stored
+/// records and per-entity indices stay identical. Use `high_water_mark` to
+/// size the first page so steady-state ticks never grow.
+///
+/// Per-entity views are unbounded too unless `with_per_entity_cap` selects
+/// an `OverflowPolicy`.
 pub struct RelationBuffer {
     records: PagedPool<RelationRecord>,
     payload_bytes: PagedPool<u8>,
     record_count: usize,
     payload_count: usize,
+    high_water_mark: usize,
     entity_buckets: Vec<EntityRelationBucket>,
     active_buckets: Vec<usize>,
     free_buckets: Vec<usize>,
     bucket_lookup: HashMap<EntityId, usize>,
+    per_entity_cap: usize,
+    overflow_policy: OverflowPolicy,
+    dropped_relations: u64,
 }
 
 impl RelationBuffer {
@@ -135,13 +191,47 @@ impl RelationBuffer {
             ),
             record_count: 0,
             payload_count: 0,
+            high_water_mark: 0,
             entity_buckets: Vec::new(),
             active_buckets: Vec::new(),
             free_buckets: Vec::new(),
             bucket_lookup: HashMap::new(),
+            per_entity_cap: usize::MAX,
+            overflow_policy: OverflowPolicy::Grow,
+            dropped_relations: 0,
         }
     }
 
+    /// Limit each entity's `relations_for` view to `cap` entries, resolving
+    /// overflow with `policy`.
+    pub fn with_per_entity_cap(mut self, cap: usize, policy: OverflowPolicy) -> Self {
+        self.per_entity_cap = cap;
+        self.overflow_policy = policy;
+        self
+    }
+
+    #[inline]
+    pub fn overflow_policy(&self) -> OverflowPolicy {
+        self.overflow_policy
+    }
+
+    #[inline]
+    pub fn per_entity_cap(&self) -> usize {
+        self.per_entity_cap
+    }
+
+    /// Per-entity entries dropped by the overflow policy since creation or
+    /// the last `reset_dropped_relations`. A relation dropped from both of
+    /// its entities counts twice.
+    #[inline]
+    pub fn dropped_relations(&self) -> u64 {
+        self.dropped_relations
+    }
+
+    pub fn reset_dropped_relations(&mut self) {
+        self.dropped_relations = 0;
+    }
+
     pub fn clear(&mut self) {
         self.records.clear();
         self.payload_bytes.clear();
@@ -161,6 +251,29 @@ impl RelationBuffer {
         self.record_count
     }
 
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.record_count == 0
+    }
+
+    /// Records the buffer can hold before allocating another page.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.records.capacity()
+    }
+
+    /// Largest number of records held at once since creation or the last
+    /// `reset_high_water_mark`.
+    #[inline]
+    pub fn high_water_mark(&self) -> usize {
+        self.high_water_mark
+    }
+
+    pub fn reset_high_water_mark(&mut self) {
+        self.high_water_mark = self.record_count;
+    }
+
+    /// Append a relation, growing the record/payload pages when full.
     pub fn push_relation(
         &mut self,
         record: RelationRecord,
@@ -178,7 +291,7 @@ impl RelationBuffer {
                 .payload_bytes
                 .alloc_bulk(payload_bytes.len())
                 .into_iter()
-                .flat_map(|range| range);
+                .flatten();
             for (offset, gidx) in spans.enumerate() {
                 self.payload_bytes.write_at(gidx, payload_bytes[offset]);
             }
@@ -194,6 +307,7 @@ impl RelationBuffer {
         let index = self.records.alloc_one();
         self.records.write_at(index, stored);
         self.record_count += 1;
+        self.high_water_mark = self.high_water_mark.max(self.record_count);
 
         let delta_a = delta;
         let delta_b = delta.map(RelationDelta::flipped);
@@ -216,10 +330,59 @@ impl RelationBuffer {
         );
     }
 
+    /// Reorder records and per-entity entries into a canonical order.
+    ///
+    /// Records are sorted by `(entity_a, entity_b, relation_type)` and each
+    /// entity's entries by `(other, relation_type)`; equal keys keep their
+    /// emission order. Consumers whose results depend on visitation order
+    /// (e.g. accumulated collision corrections) then see the same sequence
+    /// regardless of how an accelerator walked its internal structures.
+    pub fn sort_canonical(&mut self) {
+        let mut records: Vec<RelationRecord> = (0..self.record_count)
+            .filter_map(|gidx| self.records.get(gidx).ok().copied())
+            .collect();
+        records.sort_by_key(|record| {
+            (
+                record.entity_a.to_bits(),
+                record.entity_b.to_bits(),
+                record.relation_type.raw(),
+            )
+        });
+        for (gidx, record) in records.into_iter().enumerate() {
+            if let Ok(slot) = self.records.get_mut(gidx) {
+                *slot = record;
+            }
+        }
+
+        self.active_buckets
+            .sort_by_key(|&idx| self.entity_buckets[idx].entity_id);
+        for &idx in &self.active_buckets {
+            self.entity_buckets[idx]
+                .entries
+                .sort_by_key(|entry| (entry.other.to_bits(), entry.relation_type.raw()));
+        }
+    }
+
     pub fn iter(&self) -> RelationIter<'_> {
         RelationIter {
             buffer: self,
             cursor: 0,
+            relation_type: None,
+        }
+    }
+
+    /// Every record of `relation_type`, in buffer order.
+    ///
+    /// Buffer order is emission order, or the canonical order after
+    /// `sort_canonical`, so it is deterministic either way. Lets one system
+    /// consume, say, all trigger events of the tick when several
+    /// accelerators share a buffer. Walks the records in place without
+    /// allocating.
+    pub fn iter_type(&self, relation_type: RelationType) -> RelationIter<'_> {
+        RelationIter {
+            buffer: self,
+            cursor: 0,
+            relation_type: Some(relation_type),
         }
     }
 
@@ -264,14 +427,33 @@ impl RelationBuffer {
         delta: Option<RelationDelta>,
         other_location: Option<RelationLocation>,
     ) {
-        let bucket = self.bucket_mut(entity_id);
-        bucket.entries.push(EntityRelationEntry {
+        let (cap, policy) = (self.per_entity_cap, self.overflow_policy);
+        let entry = EntityRelationEntry {
             other,
             relation_type,
             payload,
             delta,
             other_location,
-        });
+        };
+        let bucket = self.bucket_mut(entity_id);
+        if bucket.entries.len() < cap || policy == OverflowPolicy::Grow {
+            bucket.entries.push(entry);
+            return;
+        }
+        if policy == OverflowPolicy::KeepNearest {
+            let key = entry.nearness_key();
+            let farthest = bucket
+                .entries
+                .iter()
+                .enumerate()
+                .max_by_key(|(_, kept)| kept.nearness_key())
+                .filter(|(_, kept)| kept.nearness_key() > key)
+                .map(|(idx, _)| idx);
+            if let Some(idx) = farthest {
+                bucket.entries[idx] = entry;
+            }
+        }
+        self.dropped_relations += 1;
     }
 
     fn bucket_mut(&mut self, entity_id: EntityId) -> &mut EntityRelationBucket {
@@ -302,20 +484,25 @@ impl RelationBuffer {
 pub struct RelationIter<'a> {
     buffer: &'a RelationBuffer,
     cursor: usize,
+    /// Only yield records of this type when set.
+    relation_type: Option<RelationType>,
 }
 
 impl<'a> Iterator for RelationIter<'a> {
     type Item = RelationRecord;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.cursor >= self.buffer.record_count {
-            return None;
-        }
-        let gidx = self.cursor;
-        self.cursor += 1;
-        match self.buffer.records.get(gidx) {
-            Ok(record) => Some(*record),
-            Err(_) => None,
+        while self.cursor < self.buffer.record_count {
+            let gidx = self.cursor;
+            self.cursor += 1;
+            let record = *self.buffer.records.get(gidx).ok()?;
+            if self
+                .relation_type
+                .is_none_or(|relation_type| relation_type == record.relation_type)
+            {
+                return Some(record);
+            }
         }
+        None
     }
 }
@@ -0,0 +1,11 @@
+//! Byte helpers shared by accelerators reading rows via
+//! `World::for_each_located_row`.
+
+/// Read the native-endian `i32` at `index` (in `i32` units), if present.
+#[inline]
+pub(super) fn read_i32(bytes: &[u8], index: usize) -> Option<i32> {
+    let start = index * 4;
+    bytes
+        .get(start..start + 4)
+        .map(|raw| i32::from_ne_bytes(raw.try_into().unwrap()))
+}
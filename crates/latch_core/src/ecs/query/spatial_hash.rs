@@ -1,13 +1,13 @@
 //! Spatial hash accelerator that emits broad-phase relation pairs in a single pass.
 
+use super::scan::read_i32;
 use super::{
     RelationAccelerator, RelationBuffer, RelationDelta, RelationLocation, RelationRecord,
     RelationType,
 };
 use crate::ecs::{ComponentId, Entity, World};
 use std::collections::{hash_map::Entry, HashMap};
-use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::LazyLock;
+#[cfg(feature = "metrics")]
 use std::time::Instant;
 
 #[derive(Clone, Copy, Debug)]
@@ -45,16 +45,19 @@ impl CellCoord {
         Self { x, y }
     }
 
+    /// The eight surrounding cells; coordinates wrap at the edge of the
+    /// `i32` range, where the distance check rejects the far side.
     fn neighbors(&self) -> [CellCoord; 8] {
+        let (x, y) = (self.x, self.y);
         [
-            CellCoord::new(self.x + 1, self.y - 1),
-            CellCoord::new(self.x + 1, self.y),
-            CellCoord::new(self.x + 1, self.y + 1),
-            CellCoord::new(self.x, self.y + 1),
-            CellCoord::new(self.x, self.y - 1),
-            CellCoord::new(self.x - 1, self.y - 1),
-            CellCoord::new(self.x - 1, self.y),
-            CellCoord::new(self.x - 1, self.y + 1),
+            CellCoord::new(x.wrapping_add(1), y.wrapping_sub(1)),
+            CellCoord::new(x.wrapping_add(1), y),
+            CellCoord::new(x.wrapping_add(1), y.wrapping_add(1)),
+            CellCoord::new(x, y.wrapping_add(1)),
+            CellCoord::new(x, y.wrapping_sub(1)),
+            CellCoord::new(x.wrapping_sub(1), y.wrapping_sub(1)),
+            CellCoord::new(x.wrapping_sub(1), y),
+            CellCoord::new(x.wrapping_sub(1), y.wrapping_add(1)),
         ]
     }
 }
@@ -72,104 +75,55 @@ pub struct SpatialHashGrid {
     config: SpatialHashConfig,
     buckets: HashMap<CellCoord, Vec<GridEntry>>,
     bucket_pool: Vec<Vec<GridEntry>>,
+    metrics: SpatialHashMetrics,
 }
 
+/// Counters accumulated by one grid between `reset_metrics` calls.
+///
+/// Without the `metrics` feature every update compiles away and the
+/// snapshot stays zeroed.
 #[derive(Default)]
-struct StageMetric {
-    nanos: AtomicU64,
-    calls: AtomicU64,
+struct SpatialHashMetrics {
+    counters: SpatialHashMetricsSnapshot,
 }
 
-impl StageMetric {
-    fn record(&self, duration_ns: u64) {
-        self.nanos.fetch_add(duration_ns, Ordering::Relaxed);
-        self.calls.fetch_add(1, Ordering::Relaxed);
-    }
-
-    fn snapshot(&self) -> (u64, u64) {
-        (
-            self.nanos.load(Ordering::Relaxed),
-            self.calls.load(Ordering::Relaxed),
-        )
-    }
-
-    fn reset(&self) {
-        self.nanos.store(0, Ordering::Relaxed);
-        self.calls.store(0, Ordering::Relaxed);
+impl SpatialHashMetrics {
+    #[inline(always)]
+    fn update(&mut self, f: impl FnOnce(&mut SpatialHashMetricsSnapshot)) {
+        #[cfg(feature = "metrics")]
+        f(&mut self.counters);
+        #[cfg(not(feature = "metrics"))]
+        let _ = f;
     }
 }
 
-struct SpatialHashMetrics {
-    total: StageMetric,
-    recycle: StageMetric,
-    emit: StageMetric,
-    entities: AtomicU64,
-    relations: AtomicU64,
-    bucket_lookups: AtomicU64,
-    bucket_hits: AtomicU64,
-    bucket_reuses: AtomicU64,
-    bucket_allocs: AtomicU64,
+/// Start of a timed stage; zero-sized without the `metrics` feature.
+#[derive(Clone, Copy)]
+struct StageTimer {
+    #[cfg(feature = "metrics")]
+    start: Instant,
 }
 
-impl SpatialHashMetrics {
-    const fn new() -> Self {
+impl StageTimer {
+    #[inline(always)]
+    fn start() -> Self {
         Self {
-            total: StageMetric {
-                nanos: AtomicU64::new(0),
-                calls: AtomicU64::new(0),
-            },
-            recycle: StageMetric {
-                nanos: AtomicU64::new(0),
-                calls: AtomicU64::new(0),
-            },
-            emit: StageMetric {
-                nanos: AtomicU64::new(0),
-                calls: AtomicU64::new(0),
-            },
-            entities: AtomicU64::new(0),
-            relations: AtomicU64::new(0),
-            bucket_lookups: AtomicU64::new(0),
-            bucket_hits: AtomicU64::new(0),
-            bucket_reuses: AtomicU64::new(0),
-            bucket_allocs: AtomicU64::new(0),
-        }
-    }
-
-    fn snapshot(&self) -> SpatialHashMetricsSnapshot {
-        let (total_ns, total_calls) = self.total.snapshot();
-        let (recycle_ns, recycle_calls) = self.recycle.snapshot();
-        let (emit_ns, emit_calls) = self.emit.snapshot();
-        SpatialHashMetricsSnapshot {
-            total_ns,
-            total_calls,
-            recycle_ns,
-            recycle_calls,
-            emit_ns,
-            emit_calls,
-            entities: self.entities.load(Ordering::Relaxed),
-            relations: self.relations.load(Ordering::Relaxed),
-            bucket_lookups: self.bucket_lookups.load(Ordering::Relaxed),
-            bucket_hits: self.bucket_hits.load(Ordering::Relaxed),
-            bucket_reuses: self.bucket_reuses.load(Ordering::Relaxed),
-            bucket_allocs: self.bucket_allocs.load(Ordering::Relaxed),
+            #[cfg(feature = "metrics")]
+            start: Instant::now(),
         }
     }
 
-    fn reset(&self) {
-        self.total.reset();
-        self.recycle.reset();
-        self.emit.reset();
-        self.entities.store(0, Ordering::Relaxed);
-        self.relations.store(0, Ordering::Relaxed);
-        self.bucket_lookups.store(0, Ordering::Relaxed);
-        self.bucket_hits.store(0, Ordering::Relaxed);
-        self.bucket_reuses.store(0, Ordering::Relaxed);
-        self.bucket_allocs.store(0, Ordering::Relaxed);
+    #[inline(always)]
+    fn elapsed_ns(self) -> u64 {
+        #[cfg(feature = "metrics")]
+        return self.start.elapsed().as_nanos() as u64;
+        #[cfg(not(feature = "metrics"))]
+        0
     }
 }
 
-static SPATIAL_HASH_METRICS: LazyLock<SpatialHashMetrics> = LazyLock::new(SpatialHashMetrics::new);
-
+/// Totals since the last `SpatialHashGrid::reset_metrics`; `*_ns` and
+/// `*_calls` pairs give average stage times.
 #[derive(Clone, Copy, Debug, Default)]
 pub struct SpatialHashMetricsSnapshot {
     pub total_ns: u64,
@@ -186,23 +140,25 @@ pub struct SpatialHashMetricsSnapshot {
     pub bucket_allocs: u64,
 }
 
-pub fn spatial_hash_metrics_snapshot() -> SpatialHashMetricsSnapshot {
-    SPATIAL_HASH_METRICS.snapshot()
-}
-
-pub fn reset_spatial_hash_metrics() {
-    SPATIAL_HASH_METRICS.reset();
-}
-
 impl SpatialHashGrid {
     pub fn new(config: SpatialHashConfig) -> Self {
         Self {
             config,
             buckets: HashMap::new(),
             bucket_pool: Vec::new(),
+            metrics: SpatialHashMetrics::default(),
         }
     }
 
+    /// Metrics gathered by this grid since the last `reset_metrics`.
+    pub fn metrics_snapshot(&self) -> SpatialHashMetricsSnapshot {
+        self.metrics.counters
+    }
+
+    pub fn reset_metrics(&mut self) {
+        self.metrics = SpatialHashMetrics::default();
+    }
+
     fn recycle_buckets(&mut self) {
         for (_, mut bucket) in self.buckets.drain() {
             bucket.clear();
@@ -216,14 +172,10 @@ impl SpatialHashGrid {
             Entry::Vacant(vacant) => {
                 let mut bucket = if let Some(mut reused) = self.bucket_pool.pop() {
                     reused.clear();
-                    SPATIAL_HASH_METRICS
-                        .bucket_reuses
-                        .fetch_add(1, Ordering::Relaxed);
+                    self.metrics.update(|m| m.bucket_reuses += 1);
                     reused
                 } else {
-                    SPATIAL_HASH_METRICS
-                        .bucket_allocs
-                        .fetch_add(1, Ordering::Relaxed);
+                    self.metrics.update(|m| m.bucket_allocs += 1);
                     Vec::with_capacity(32)
                 };
                 bucket.clear();
@@ -245,14 +197,15 @@ impl SpatialHashGrid {
         radius_sq: i64,
         buffer: &mut RelationBuffer,
         relation: RelationType,
+        metrics: &mut SpatialHashMetrics,
     ) {
-        let start = Instant::now();
+        let timer = StageTimer::start();
         let mut emitted = 0u64;
         for other in bucket {
             if Self::overlap(entry, other, radius_sq) {
                 let delta = RelationDelta {
-                    dx: entry.x - other.x,
-                    dy: entry.y - other.y,
+                    dx: entry.x as i64 - other.x as i64,
+                    dy: entry.y as i64 - other.y as i64,
                 };
                 buffer.push_relation(
                     RelationRecord::new(other.entity, entry.entity, relation, None),
@@ -264,39 +217,41 @@ impl SpatialHashGrid {
                 emitted += 1;
             }
         }
-        if emitted > 0 {
-            SPATIAL_HASH_METRICS
-                .relations
-                .fetch_add(emitted, Ordering::Relaxed);
-        }
-        SPATIAL_HASH_METRICS
-            .emit
-            .record(start.elapsed().as_nanos() as u64);
+        metrics.update(|m| {
+            m.relations += emitted;
+            m.emit_ns += timer.elapsed_ns();
+            m.emit_calls += 1;
+        });
     }
 
     fn process_entry(&mut self, entry: GridEntry, radius_sq: i64, buffer: &mut RelationBuffer) {
-        SPATIAL_HASH_METRICS
-            .entities
-            .fetch_add(1, Ordering::Relaxed);
+        let metrics = &mut self.metrics;
+        metrics.update(|m| m.entities += 1);
         {
-            SPATIAL_HASH_METRICS
-                .bucket_lookups
-                .fetch_add(1, Ordering::Relaxed);
+            metrics.update(|m| m.bucket_lookups += 1);
             if let Some(bucket) = self.buckets.get(&entry.coord) {
-                SPATIAL_HASH_METRICS
-                    .bucket_hits
-                    .fetch_add(1, Ordering::Relaxed);
-                Self::emit_against(&entry, bucket, radius_sq, buffer, self.config.relation);
+                metrics.update(|m| m.bucket_hits += 1);
+                Self::emit_against(
+                    &entry,
+                    bucket,
+                    radius_sq,
+                    buffer,
+                    self.config.relation,
+                    metrics,
+                );
             }
             for neighbor in entry.coord.neighbors() {
-                SPATIAL_HASH_METRICS
-                    .bucket_lookups
-                    .fetch_add(1, Ordering::Relaxed);
+                metrics.update(|m| m.bucket_lookups += 1);
                 if let Some(bucket) = self.buckets.get(&neighbor) {
-                    SPATIAL_HASH_METRICS
-                        .bucket_hits
-                        .fetch_add(1, Ordering::Relaxed);
-                    Self::emit_against(&entry, bucket, radius_sq, buffer, self.config.relation);
+                    metrics.update(|m| m.bucket_hits += 1);
+                    Self::emit_against(
+                        &entry,
+                        bucket,
+                        radius_sq,
+                        buffer,
+                        self.config.relation,
+                        metrics,
+                    );
                 }
             }
         }
@@ -306,9 +261,10 @@ impl SpatialHashGrid {
 
     #[inline]
     fn overlap(a: &GridEntry, b: &GridEntry, radius_sq: i64) -> bool {
-        let dx = (a.x - b.x) as i64;
-        let dy = (a.y - b.y) as i64;
-        dx * dx + dy * dy <= radius_sq
+        let dx = a.x as i64 - b.x as i64;
+        let dy = a.y as i64 - b.y as i64;
+        // Far-apart neighbours can exceed `i64`; saturating keeps them out.
+        dx.saturating_mul(dx).saturating_add(dy.saturating_mul(dy)) <= radius_sq
     }
 }
 
@@ -318,64 +274,32 @@ impl RelationAccelerator for SpatialHashGrid {
     }
 
     fn rebuild(&mut self, world: &World, buffer: &mut RelationBuffer) {
-        let total_start = Instant::now();
-        let recycle_start = Instant::now();
+        let total = StageTimer::start();
+        let recycle = StageTimer::start();
         self.recycle_buckets();
-        SPATIAL_HASH_METRICS
-            .recycle
-            .record(recycle_start.elapsed().as_nanos() as u64);
+        self.metrics.update(|m| {
+            m.recycle_ns += recycle.elapsed_ns();
+            m.recycle_calls += 1;
+        });
 
         let radius_sq = (self.config.radius as i64) * (self.config.radius as i64);
-        let archetypes = world.archetypes_with(self.config.component_id);
-        for &arch in archetypes {
-            let storage = match world.storage(arch) {
-                Some(storage) => storage,
-                None => continue,
+        world.for_each_located_row(self.config.component_id, |entity, location, bytes| {
+            let (Some(x), Some(y)) = (read_i32(bytes, 0), read_i32(bytes, 1)) else {
+                return;
             };
-            let column = match storage.column(self.config.component_id) {
-                Ok(col) => col,
-                Err(_) => continue,
+            let entry = GridEntry {
+                entity,
+                coord: self.pos_to_cell(x, y),
+                x,
+                y,
+                location,
             };
-            let stride = column.stride();
-            for page_idx in 0..column.page_count() {
-                let range = column.page_range(page_idx);
-                if range.is_empty() {
-                    continue;
-                }
-                let entity_ids = match storage.entity_ids_slice(range.clone()) {
-                    Ok(ids) => ids,
-                    Err(_) => continue,
-                };
-                let bytes = match column.slice_read(range.clone()) {
-                    Ok(slice) => slice,
-                    Err(_) => continue,
-                };
-                for (row, &entity_id) in entity_ids.iter().enumerate() {
-                    let base = row * stride;
-                    if base + 8 > bytes.len() {
-                        break;
-                    }
-                    let x = i32::from_ne_bytes(bytes[base..base + 4].try_into().unwrap());
-                    let y = i32::from_ne_bytes(bytes[base + 4..base + 8].try_into().unwrap());
-                    let entity = match world.resolve_entity(entity_id) {
-                        Some(entity) => entity,
-                        None => continue,
-                    };
-                    let coord = self.pos_to_cell(x, y);
-                    let entry = GridEntry {
-                        entity,
-                        coord,
-                        x,
-                        y,
-                        location: RelationLocation::new(arch, range.start + row),
-                    };
-                    self.process_entry(entry, radius_sq, buffer);
-                }
-            }
-        }
+            self.process_entry(entry, radius_sq, buffer);
+        });
 
-        SPATIAL_HASH_METRICS
-            .total
-            .record(total_start.elapsed().as_nanos() as u64);
+        self.metrics.update(|m| {
+            m.total_ns += total.elapsed_ns();
+            m.total_calls += 1;
+        });
     }
 }
@@ -0,0 +1,235 @@
+//! Trigger-volume accelerator emitting enter/stay/exit relations.
+
+use super::scan::read_i32;
+use super::{RelationAccelerator, RelationBuffer, RelationLocation, RelationRecord, RelationType};
+use crate::ecs::{ComponentId, Entity, World};
+
+/// Transition reported in the one-byte payload of every trigger relation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum TriggerPhase {
+    /// Occupant overlaps the volume this tick but did not last tick.
+    Enter = 0,
+    /// Occupant overlapped the volume on both ticks.
+    Stay = 1,
+    /// Occupant overlapped last tick but no longer does (or is gone).
+    Exit = 2,
+}
+
+impl TriggerPhase {
+    #[inline]
+    pub fn as_payload(self) -> [u8; 1] {
+        [self as u8]
+    }
+
+    pub fn from_payload(payload: &[u8]) -> Option<Self> {
+        match payload {
+            [0] => Some(TriggerPhase::Enter),
+            [1] => Some(TriggerPhase::Stay),
+            [2] => Some(TriggerPhase::Exit),
+            _ => None,
+        }
+    }
+}
+
+/// Which components describe trigger volumes and the entities they detect.
+///
+/// The volume component must start with four `i32`s `(min_x, min_y, max_x,
+/// max_y)` in world units; the occupant component must start with an `i32`
+/// `(x, y)` position, like `SpatialHashConfig`'s.
+#[derive(Clone, Copy, Debug)]
+pub struct TriggerConfig {
+    pub volume_component: ComponentId,
+    pub occupant_component: ComponentId,
+    /// Occupant radius; 0 treats occupants as points.
+    pub occupant_radius: i32,
+    pub relation: RelationType,
+}
+
+impl TriggerConfig {
+    pub fn new(
+        volume_component: ComponentId,
+        occupant_component: ComponentId,
+        occupant_radius: i32,
+        relation: RelationType,
+    ) -> Self {
+        Self {
+            volume_component,
+            occupant_component,
+            occupant_radius: occupant_radius.max(0),
+            relation,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct TriggerVolume {
+    entity: Entity,
+    location: RelationLocation,
+    min: [i32; 2],
+    max: [i32; 2],
+}
+
+impl TriggerVolume {
+    fn overlaps_circle(&self, x: i32, y: i32, radius: i32) -> bool {
+        // Distances span up to 2^32, so widen before subtracting; each
+        // square fits in u64 and the sum saturates.
+        let dx = (x as i64).clamp(self.min[0] as i64, self.max[0] as i64) - x as i64;
+        let dy = (y as i64).clamp(self.min[1] as i64, self.max[1] as i64) - y as i64;
+        let (dx, dy) = (dx.unsigned_abs(), dy.unsigned_abs());
+        let radius = radius as u64;
+        (dx * dx).saturating_add(dy * dy) <= radius * radius
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Occupancy {
+    trigger: Entity,
+    occupant: Entity,
+    trigger_location: RelationLocation,
+    occupant_location: RelationLocation,
+}
+
+impl Occupancy {
+    #[inline]
+    fn key(&self) -> (u64, u64) {
+        (self.trigger.to_bits(), self.occupant.to_bits())
+    }
+}
+
+/// Emits a relation `(trigger, occupant)` per overlapping pair, tagged with
+/// a `TriggerPhase` payload. Membership from the previous rebuild is kept so
+/// pairs that stop overlapping produce one `Exit` relation.
+///
+/// Volumes are tested linearly against each occupant, which suits the usual
+/// handful of doors and damage zones; dense trigger fields should be
+/// partitioned first.
+pub struct TriggerAccelerator {
+    config: TriggerConfig,
+    volumes: Vec<TriggerVolume>,
+    /// Pairs overlapping at the last rebuild, sorted by `Occupancy::key`.
+    inside: Vec<(u64, u64)>,
+    current: Vec<Occupancy>,
+}
+
+impl TriggerAccelerator {
+    pub fn new(config: TriggerConfig) -> Self {
+        Self {
+            config,
+            volumes: Vec::new(),
+            inside: Vec::new(),
+            current: Vec::new(),
+        }
+    }
+
+    pub fn config(&self) -> &TriggerConfig {
+        &self.config
+    }
+
+    /// Number of `(trigger, occupant)` pairs overlapping at the last rebuild.
+    pub fn occupied_pairs(&self) -> usize {
+        self.inside.len()
+    }
+
+    fn collect_volumes(&mut self, world: &World) {
+        self.volumes.clear();
+        let volumes = &mut self.volumes;
+        world.for_each_located_row(self.config.volume_component, |entity, location, bytes| {
+            let (Some(x0), Some(y0), Some(x1), Some(y1)) = (
+                read_i32(bytes, 0),
+                read_i32(bytes, 1),
+                read_i32(bytes, 2),
+                read_i32(bytes, 3),
+            ) else {
+                return;
+            };
+            volumes.push(TriggerVolume {
+                entity,
+                location,
+                min: [x0.min(x1), y0.min(y1)],
+                max: [x0.max(x1), y0.max(y1)],
+            });
+        });
+    }
+
+    fn collect_occupancy(&mut self, world: &World) {
+        self.current.clear();
+        let volumes = &self.volumes;
+        let current = &mut self.current;
+        let radius = self.config.occupant_radius;
+        world.for_each_located_row(self.config.occupant_component, |entity, location, bytes| {
+            let (Some(x), Some(y)) = (read_i32(bytes, 0), read_i32(bytes, 1)) else {
+                return;
+            };
+            for volume in volumes {
+                if volume.entity != entity && volume.overlaps_circle(x, y, radius) {
+                    current.push(Occupancy {
+                        trigger: volume.entity,
+                        occupant: entity,
+                        trigger_location: volume.location,
+                        occupant_location: location,
+                    });
+                }
+            }
+        });
+        self.current.sort_unstable_by_key(Occupancy::key);
+    }
+}
+
+impl RelationAccelerator for TriggerAccelerator {
+    fn relation_type(&self) -> RelationType {
+        self.config.relation
+    }
+
+    /// Forget `entity`'s memberships so its removal does not emit `Exit`.
+    fn unregister(&mut self, entity: Entity) {
+        let bits = entity.to_bits();
+        self.inside
+            .retain(|&(trigger, occupant)| trigger != bits && occupant != bits);
+    }
+
+    fn rebuild(&mut self, world: &World, output: &mut RelationBuffer) {
+        self.collect_volumes(world);
+        self.collect_occupancy(world);
+
+        let relation = self.config.relation;
+        for pair in &self.current {
+            let phase = if self.inside.binary_search(&pair.key()).is_ok() {
+                TriggerPhase::Stay
+            } else {
+                TriggerPhase::Enter
+            };
+            output.push_relation(
+                RelationRecord::new(pair.trigger, pair.occupant, relation, None),
+                &phase.as_payload(),
+                None,
+                Some(pair.trigger_location),
+                Some(pair.occupant_location),
+            );
+        }
+
+        let current = &self.current;
+        for &(trigger, occupant) in &self.inside {
+            if current
+                .binary_search_by_key(&(trigger, occupant), Occupancy::key)
+                .is_err()
+            {
+                output.push_relation(
+                    RelationRecord::new(
+                        Entity::from_bits(trigger),
+                        Entity::from_bits(occupant),
+                        relation,
+                        None,
+                    ),
+                    &TriggerPhase::Exit.as_payload(),
+                    None,
+                    None,
+                    None,
+                );
+            }
+        }
+
+        self.inside.clear();
+        self.inside.extend(self.current.iter().map(Occupancy::key));
+    }
+}
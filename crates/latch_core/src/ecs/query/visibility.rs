@@ -0,0 +1,292 @@
+//! Line-of-sight accelerator: observers see targets inside a view radius
+//! (and optional cone) unless a blocker circle crosses the sight line.
+
+use super::scan::read_i32;
+use super::{
+    RelationAccelerator, RelationBuffer, RelationDelta, RelationLocation, RelationRecord,
+    RelationType,
+};
+use crate::ecs::{ComponentId, Entity, World};
+use std::collections::HashMap;
+
+/// Component ids and view parameters for `VisibilityAccelerator`.
+///
+/// Observer, target and blocker components must start with an `i32` `(x, y)`
+/// position. When a cone is configured, the observer component must follow
+/// the position with an `i32` facing vector `(fx, fy)` (any length).
+#[derive(Clone, Copy, Debug)]
+pub struct VisibilityConfig {
+    pub observer_component: ComponentId,
+    pub target_component: ComponentId,
+    pub blocker_component: ComponentId,
+    pub view_radius: i32,
+    pub blocker_radius: i32,
+    /// Cosine of the cone half-angle; `None` sees all around.
+    pub cone_cos: Option<f64>,
+    /// Grid cell size used to bucket targets and blockers.
+    pub cell_size: i32,
+    pub relation: RelationType,
+}
+
+impl VisibilityConfig {
+    pub fn new(
+        observer_component: ComponentId,
+        target_component: ComponentId,
+        blocker_component: ComponentId,
+        view_radius: i32,
+        relation: RelationType,
+    ) -> Self {
+        let view_radius = view_radius.max(1);
+        Self {
+            observer_component,
+            target_component,
+            blocker_component,
+            view_radius,
+            blocker_radius: 0,
+            cone_cos: None,
+            cell_size: view_radius,
+            relation,
+        }
+    }
+
+    /// Treat blockers as circles of `radius` rather than points.
+    pub fn with_blocker_radius(mut self, radius: i32) -> Self {
+        self.blocker_radius = radius.max(0);
+        self
+    }
+
+    /// Restrict sight to a cone of `half_angle` radians around the facing.
+    ///
+    /// The cosine is computed once here so every rebuild compares against
+    /// the same constant.
+    pub fn with_cone(mut self, half_angle: f64) -> Self {
+        self.cone_cos = Some(half_angle.cos());
+        self
+    }
+
+    pub fn with_cell_size(mut self, cell_size: i32) -> Self {
+        self.cell_size = cell_size.max(1);
+        self
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Point {
+    entity: Entity,
+    location: RelationLocation,
+    x: i32,
+    y: i32,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Observer {
+    point: Point,
+    facing: [i32; 2],
+}
+
+/// Uniform grid of points, rebuilt each tick with recycled buckets.
+#[derive(Default)]
+struct PointGrid {
+    cells: HashMap<(i32, i32), Vec<Point>>,
+    pool: Vec<Vec<Point>>,
+}
+
+impl PointGrid {
+    fn clear(&mut self) {
+        for (_, mut bucket) in self.cells.drain() {
+            bucket.clear();
+            self.pool.push(bucket);
+        }
+    }
+
+    fn insert(&mut self, cell: (i32, i32), point: Point) {
+        let pool = &mut self.pool;
+        self.cells
+            .entry(cell)
+            .or_insert_with(|| pool.pop().unwrap_or_default())
+            .push(point);
+    }
+
+    /// Points in cells overlapping `[min, max]` (cell coordinates, inclusive).
+    fn query(&self, min: (i32, i32), max: (i32, i32)) -> impl Iterator<Item = &Point> {
+        (min.1..=max.1)
+            .flat_map(move |cy| (min.0..=max.0).map(move |cx| (cx, cy)))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+    }
+}
+
+/// Emits `(observer, target)` relations for every target an observer can
+/// see, with the offset to the target as the relation delta.
+///
+/// Sight lines are tested against blockers in the grid cells covering the
+/// segment's bounding box, so each pair costs only the nearby blockers.
+pub struct VisibilityAccelerator {
+    config: VisibilityConfig,
+    observers: Vec<Observer>,
+    targets: PointGrid,
+    blockers: PointGrid,
+}
+
+impl VisibilityAccelerator {
+    pub fn new(config: VisibilityConfig) -> Self {
+        Self {
+            config,
+            observers: Vec::new(),
+            targets: PointGrid::default(),
+            blockers: PointGrid::default(),
+        }
+    }
+
+    pub fn config(&self) -> &VisibilityConfig {
+        &self.config
+    }
+
+    fn cell_of(&self, x: i32, y: i32) -> (i32, i32) {
+        (
+            x.div_euclid(self.config.cell_size),
+            y.div_euclid(self.config.cell_size),
+        )
+    }
+
+    fn collect(&mut self, world: &World) {
+        self.observers.clear();
+        self.targets.clear();
+        self.blockers.clear();
+
+        let want_facing = self.config.cone_cos.is_some();
+        let observers = &mut self.observers;
+        world.for_each_located_row(self.config.observer_component, |entity, location, bytes| {
+            let (Some(x), Some(y)) = (read_i32(bytes, 0), read_i32(bytes, 1)) else {
+                return;
+            };
+            let facing = if want_facing {
+                match (read_i32(bytes, 2), read_i32(bytes, 3)) {
+                    (Some(fx), Some(fy)) => [fx, fy],
+                    _ => return,
+                }
+            } else {
+                [0, 0]
+            };
+            observers.push(Observer {
+                point: Point {
+                    entity,
+                    location,
+                    x,
+                    y,
+                },
+                facing,
+            });
+        });
+
+        let mut points = Vec::new();
+        for (component, is_target) in [
+            (self.config.target_component, true),
+            (self.config.blocker_component, false),
+        ] {
+            points.clear();
+            world.for_each_located_row(component, |entity, location, bytes| {
+                if let (Some(x), Some(y)) = (read_i32(bytes, 0), read_i32(bytes, 1)) {
+                    points.push(Point {
+                        entity,
+                        location,
+                        x,
+                        y,
+                    });
+                }
+            });
+            for &point in &points {
+                let cell = self.cell_of(point.x, point.y);
+                if is_target {
+                    self.targets.insert(cell, point);
+                } else {
+                    self.blockers.insert(cell, point);
+                }
+            }
+        }
+    }
+
+    fn in_cone(&self, observer: &Observer, dx: i64, dy: i64) -> bool {
+        let Some(cone_cos) = self.config.cone_cos else {
+            return true;
+        };
+        let (fx, fy) = (observer.facing[0] as f64, observer.facing[1] as f64);
+        let facing_len = (fx * fx + fy * fy).sqrt();
+        let dist = ((dx * dx + dy * dy) as f64).sqrt();
+        if facing_len == 0.0 || dist == 0.0 {
+            return true;
+        }
+        (dx as f64 * fx + dy as f64 * fy) >= cone_cos * facing_len * dist
+    }
+
+    fn occluded(&self, from: &Point, to: &Point) -> bool {
+        let r = self.config.blocker_radius;
+        let min = self.cell_of(
+            from.x.min(to.x).saturating_sub(r),
+            from.y.min(to.y).saturating_sub(r),
+        );
+        let max = self.cell_of(
+            from.x.max(to.x).saturating_add(r),
+            from.y.max(to.y).saturating_add(r),
+        );
+        let radius_sq = (r as i64) * (r as i64);
+        self.blockers.query(min, max).any(|blocker| {
+            blocker.entity != from.entity
+                && blocker.entity != to.entity
+                && segment_point_dist_sq(from, to, blocker) <= radius_sq as f64
+        })
+    }
+}
+
+/// Squared distance from `p` to the segment `a`–`b`.
+fn segment_point_dist_sq(a: &Point, b: &Point, p: &Point) -> f64 {
+    let (abx, aby) = (b.x as i64 - a.x as i64, b.y as i64 - a.y as i64);
+    let (apx, apy) = (p.x as i64 - a.x as i64, p.y as i64 - a.y as i64);
+    let len_sq = abx * abx + aby * aby;
+    let t = if len_sq == 0 {
+        0.0
+    } else {
+        ((apx * abx + apy * aby) as f64 / len_sq as f64).clamp(0.0, 1.0)
+    };
+    let cx = apx as f64 - t * abx as f64;
+    let cy = apy as f64 - t * aby as f64;
+    cx * cx + cy * cy
+}
+
+impl RelationAccelerator for VisibilityAccelerator {
+    fn relation_type(&self) -> RelationType {
+        self.config.relation
+    }
+
+    fn rebuild(&mut self, world: &World, output: &mut RelationBuffer) {
+        self.collect(world);
+
+        let radius = self.config.view_radius;
+        let radius_sq = (radius as i64) * (radius as i64);
+        for observer in &self.observers {
+            let eye = &observer.point;
+            let min = self.cell_of(eye.x.saturating_sub(radius), eye.y.saturating_sub(radius));
+            let max = self.cell_of(eye.x.saturating_add(radius), eye.y.saturating_add(radius));
+            for target in self.targets.query(min, max) {
+                if target.entity == eye.entity {
+                    continue;
+                }
+                let dx = target.x as i64 - eye.x as i64;
+                let dy = target.y as i64 - eye.y as i64;
+                if dx.saturating_mul(dx).saturating_add(dy.saturating_mul(dy)) > radius_sq
+                    || !self.in_cone(observer, dx, dy)
+                    || self.occluded(eye, target)
+                {
+                    continue;
+                }
+                output.push_relation(
+                    RelationRecord::new(eye.entity, target.entity, self.config.relation, None),
+                    &[],
+                    Some(RelationDelta { dx, dy }),
+                    Some(eye.location),
+                    Some(target.location),
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,63 @@
+//! Cached archetype matching for repeated queries.
+//!
+//! Matching a component set against every archetype is cheap but not free,
+//! and most frames create no new archetypes. `QueryCache` remembers the
+//! matching `ArchetypeId`s per component set and only recomputes them when
+//! `World::archetype_generation` has moved since the entry was built.
+
+use crate::ecs::{ArchetypeId, ComponentId, ComponentSignature, World};
+use std::collections::HashMap;
+
+struct CachedQuery {
+    generation: u64,
+    archetypes: Vec<ArchetypeId>,
+}
+
+#[derive(Default)]
+pub struct QueryCache {
+    entries: HashMap<ComponentSignature, CachedQuery>,
+}
+
+impl QueryCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Archetypes storing every component in `component_ids`, sorted by id.
+    ///
+    /// An entry stays valid until the world's archetype generation changes,
+    /// which happens whenever archetypes are created or pruned.
+    pub fn matching(&mut self, world: &World, component_ids: &[ComponentId]) -> &[ArchetypeId] {
+        let signature = ComponentSignature::from_components(component_ids);
+        let generation = world.archetype_generation();
+        let entry = self
+            .entries
+            .entry(signature)
+            .or_insert_with(|| CachedQuery {
+                generation: u64::MAX,
+                archetypes: Vec::new(),
+            });
+        if entry.generation != generation {
+            entry.archetypes.clear();
+            entry
+                .archetypes
+                .extend(world.archetypes_matching(component_ids));
+            entry.archetypes.sort_unstable();
+            entry.generation = generation;
+        }
+        &entry.archetypes
+    }
+
+    /// Drop all cached entries.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
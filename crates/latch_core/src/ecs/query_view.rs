@@ -0,0 +1,162 @@
+//! Materialized query results for tools and debug output.
+//!
+//! `World::for_each` streams matching archetypes into a callback without
+//! allocating and is what systems should use. A `QueryView` instead keeps
+//! the matched archetypes and their page ranges so the same result can be
+//! walked several times, sorted or handed to UI code.
+//!
+//! Cost: building a view allocates one entry per matching archetype;
+//! `iter`/`iter2` allocate one entry per page and resolve every row's
+//! generation through the entity slot table (a random access per row);
+//! `collect` additionally copies every value into a fresh `Vec`. That is
+//! fine for editor panels, inspectors and debug dumps, but a per-tick
+//! system over a large world should stay on `for_each`.
+
+use crate::ecs::{
+    ArchetypeId, ArchetypeStorage, Component, ComponentId, ComponentSignature, Entity, EntityId,
+    StorageError, World,
+};
+use std::ops::Range;
+
+/// Archetypes matching a component set, borrowed from a `World`.
+///
+/// Reads see the current buffer. Rows despawned but not yet flushed are
+/// skipped by every iterator.
+pub struct QueryView<'w> {
+    world: &'w World,
+    component_ids: Vec<ComponentId>,
+    archetypes: Vec<(ArchetypeId, &'w ArchetypeStorage)>,
+}
+
+impl<'w> QueryView<'w> {
+    pub(crate) fn new(world: &'w World, component_ids: &[ComponentId]) -> Self {
+        let query = ComponentSignature::from_components(component_ids);
+        let archetypes = world
+            .archetype_ids()
+            .iter()
+            .filter_map(|&id| world.storage(id).map(|storage| (id, storage)))
+            .filter(|(_, storage)| !storage.is_empty() && storage.plan().layout.matches(&query))
+            .collect();
+        Self {
+            world,
+            component_ids: component_ids.to_vec(),
+            archetypes,
+        }
+    }
+
+    /// Components the view was built for.
+    pub fn component_ids(&self) -> &[ComponentId] {
+        &self.component_ids
+    }
+
+    /// Matching non-empty archetypes in `World` iteration order.
+    pub fn archetypes(&self) -> impl Iterator<Item = (ArchetypeId, &'w ArchetypeStorage)> + '_ {
+        self.archetypes.iter().copied()
+    }
+
+    /// Rows across all matching archetypes, including rows despawned but
+    /// not yet flushed.
+    pub fn row_count(&self) -> usize {
+        self.archetypes
+            .iter()
+            .map(|(_, storage)| storage.entity_count())
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.archetypes.is_empty()
+    }
+
+    /// Every page of every matching archetype with its row range.
+    pub fn pages(&self) -> impl Iterator<Item = (&'w ArchetypeStorage, Range<usize>)> + '_ {
+        self.archetypes.iter().flat_map(|&(_, storage)| {
+            let rows_per_page = storage.rows_per_page();
+            let len = storage.entity_count();
+            (0..len)
+                .step_by(rows_per_page)
+                .map(move |start| (storage, start..(start + rows_per_page).min(len)))
+        })
+    }
+
+    /// Live entities in the view, in storage order.
+    pub fn entities(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.pages().flat_map(|(storage, range)| {
+            let ids = storage
+                .entity_ids_slice(range)
+                .expect("page range within entity_count");
+            ids.iter().filter_map(|&id| self.resolve(id))
+        })
+    }
+
+    /// Live entities with their current value of `T`.
+    ///
+    /// Fails with `ColumnMissing` when `T` is not part of the view.
+    pub fn iter<T: Component>(
+        &self,
+    ) -> Result<impl Iterator<Item = (Entity, &'w T)> + '_, StorageError> {
+        self.require(T::id())?;
+        let mut pages = Vec::new();
+        for (storage, range) in self.pages() {
+            let ids = storage.entity_ids_slice(range.clone())?;
+            let values = storage.column(T::id())?.slice_read_typed::<T>(range)?;
+            pages.push((ids, values));
+        }
+        Ok(pages.into_iter().flat_map(|(ids, values)| {
+            ids.iter()
+                .zip(values)
+                .filter_map(|(&id, value)| Some((self.resolve(id)?, value)))
+        }))
+    }
+
+    /// Live entities with their current values of `A` and `B`.
+    pub fn iter2<A: Component, B: Component>(
+        &self,
+    ) -> Result<impl Iterator<Item = (Entity, &'w A, &'w B)> + '_, StorageError> {
+        self.require(A::id())?;
+        self.require(B::id())?;
+        let mut pages = Vec::new();
+        for (storage, range) in self.pages() {
+            let ids = storage.entity_ids_slice(range.clone())?;
+            let a = storage
+                .column(A::id())?
+                .slice_read_typed::<A>(range.clone())?;
+            let b = storage.column(B::id())?.slice_read_typed::<B>(range)?;
+            pages.push((ids, a, b));
+        }
+        Ok(pages.into_iter().flat_map(|(ids, a, b)| {
+            ids.iter()
+                .zip(a.iter().zip(b))
+                .filter_map(|(&id, (a, b))| Some((self.resolve(id)?, a, b)))
+        }))
+    }
+
+    /// `iter` copied into a `Vec`.
+    pub fn collect<T: Component + Copy>(&self) -> Result<Vec<(Entity, T)>, StorageError> {
+        Ok(self
+            .iter::<T>()?
+            .map(|(entity, &value)| (entity, value))
+            .collect())
+    }
+
+    /// `iter2` copied into a `Vec`.
+    pub fn collect2<A: Component + Copy, B: Component + Copy>(
+        &self,
+    ) -> Result<Vec<(Entity, A, B)>, StorageError> {
+        Ok(self
+            .iter2::<A, B>()?
+            .map(|(entity, &a, &b)| (entity, a, b))
+            .collect())
+    }
+
+    fn require(&self, component_id: ComponentId) -> Result<(), StorageError> {
+        if self.component_ids.contains(&component_id) {
+            Ok(())
+        } else {
+            Err(StorageError::ColumnMissing { component_id })
+        }
+    }
+
+    fn resolve(&self, entity_id: EntityId) -> Option<Entity> {
+        self.world.resolve_entity(entity_id)
+    }
+}
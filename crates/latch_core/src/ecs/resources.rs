@@ -0,0 +1,44 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+
+/// Type-keyed singletons owned by the `World` (configuration, RNG state).
+///
+/// Resources are world-global data that systems read each tick instead of
+/// hardcoding constants, so they can be tuned at runtime and persisted with
+/// the rest of the simulation state.
+#[derive(Default)]
+pub(crate) struct Resources {
+    values: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+}
+
+impl Resources {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn insert<R: Send + Sync + 'static>(&mut self, value: R) -> Option<R> {
+        self.values
+            .insert(TypeId::of::<R>(), Box::new(value))
+            .and_then(|old| old.downcast::<R>().ok())
+            .map(|old| *old)
+    }
+
+    pub(crate) fn get<R: Send + Sync + 'static>(&self) -> Option<&R> {
+        self.values
+            .get(&TypeId::of::<R>())
+            .and_then(|value| value.downcast_ref::<R>())
+    }
+
+    pub(crate) fn get_mut<R: Send + Sync + 'static>(&mut self) -> Option<&mut R> {
+        self.values
+            .get_mut(&TypeId::of::<R>())
+            .and_then(|value| value.downcast_mut::<R>())
+    }
+
+    pub(crate) fn remove<R: Send + Sync + 'static>(&mut self) -> Option<R> {
+        self.values
+            .remove(&TypeId::of::<R>())
+            .and_then(|value| value.downcast::<R>().ok())
+            .map(|value| *value)
+    }
+}
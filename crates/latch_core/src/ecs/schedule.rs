@@ -0,0 +1,275 @@
+//! Fixed-order tick orchestration.
+//!
+//! A tick is more than running systems: writes land in the next buffer and
+//! only become visible after `swap_buffers`, despawned rows linger until
+//! `flush_despawns`, and relation accelerators index rows, so they must be
+//! rebuilt after any flush that moved rows. `Schedule` owns the systems and
+//! accelerators and `World::tick` runs them in one documented order:
+//!
+//! 1. `Stage(UPDATE)`: every due system reads current, writes next.
+//! 2. `SwapBuffers`: publish this tick's writes (and events) and reset
+//!    `World::spawned_since_last_tick`.
+//! 3. `FlushDespawns`: remove despawned rows, moving others into the holes.
+//! 4. `RebuildQueries`: index the published, compacted state, so next
+//!    tick's systems see relations that match the buffer they read.
+//!
+//! Games that need relations between two groups of systems can add stages
+//! and reorder phases with `set_phases`, which rejects orders that would
+//! leave a stage's writes unpublished. Worlds whose entity shapes churn can
+//! add `PruneArchetypes` after `FlushDespawns` to drop emptied archetypes
+//! every few ticks.
+
+use crate::ecs::{
+    ComponentId, QueryRegistry, RelationAccelerator, RelationBuffer, SystemDescriptor,
+    SystemHandle, SystemRegistrationError, World, WorldError,
+};
+use std::{
+    num::NonZeroU64,
+    time::{Duration, Instant},
+};
+use thiserror::Error;
+
+/// One step of a tick.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TickPhase {
+    /// Run the due systems of a stage, in the order they were added.
+    Stage(usize),
+    SwapBuffers,
+    FlushDespawns,
+    RebuildQueries,
+    /// `World::prune_empty_archetypes` on ticks that are a multiple of
+    /// `interval`; a no-op on the others.
+    PruneArchetypes {
+        interval: NonZeroU64,
+    },
+}
+
+#[derive(Debug, Error)]
+pub enum ScheduleError {
+    #[error(transparent)]
+    Registration(#[from] SystemRegistrationError),
+    #[error("stage {stage} does not exist")]
+    UnknownStage { stage: usize },
+    #[error("stage {stage} runs after the last buffer swap, so its writes are never published")]
+    UnpublishedStage { stage: usize },
+}
+
+/// What a system sees besides the world.
+pub struct SystemContext<'a> {
+    /// Tick being simulated (0 on the first `World::tick`).
+    pub tick: u64,
+    /// Union of the system's read and write components, for `World::for_each`.
+    pub components: &'a [ComponentId],
+    /// Relations from the last `RebuildQueries` phase.
+    pub relations: &'a RelationBuffer,
+}
+
+type SystemFn = Box<dyn FnMut(&mut World, &SystemContext<'_>) + Send>;
+
+struct ScheduledSystem {
+    handle: SystemHandle,
+    components: Vec<ComponentId>,
+    run: SystemFn,
+}
+
+/// Wall time spent in one phase.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PhaseTiming {
+    pub phase: TickPhase,
+    pub duration: Duration,
+}
+
+/// Per-phase timings of one `World::tick`, in execution order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TickTimings {
+    pub tick: u64,
+    pub phases: Vec<PhaseTiming>,
+}
+
+impl TickTimings {
+    /// Total time spent in `phase` (summed if it ran more than once).
+    pub fn phase(&self, phase: TickPhase) -> Duration {
+        self.phases
+            .iter()
+            .filter(|timing| timing.phase == phase)
+            .map(|timing| timing.duration)
+            .sum()
+    }
+
+    pub fn total(&self) -> Duration {
+        self.phases.iter().map(|timing| timing.duration).sum()
+    }
+}
+
+/// Systems, relation accelerators and the phase order of a tick.
+pub struct Schedule {
+    stages: Vec<Vec<ScheduledSystem>>,
+    phases: Vec<TickPhase>,
+    queries: QueryRegistry,
+    relations: RelationBuffer,
+    tick: u64,
+}
+
+impl Schedule {
+    /// The stage created by `new`, run by the default phase order.
+    pub const UPDATE: usize = 0;
+
+    pub const DEFAULT_PHASES: [TickPhase; 4] = [
+        TickPhase::Stage(Self::UPDATE),
+        TickPhase::SwapBuffers,
+        TickPhase::FlushDespawns,
+        TickPhase::RebuildQueries,
+    ];
+
+    pub fn new() -> Self {
+        Self::with_relation_buffer(RelationBuffer::new(2048, 256))
+    }
+
+    /// Like `new`, with a relation buffer sized for the game's workload.
+    pub fn with_relation_buffer(relations: RelationBuffer) -> Self {
+        Self {
+            stages: vec![Vec::new()],
+            phases: Self::DEFAULT_PHASES.to_vec(),
+            queries: QueryRegistry::new(),
+            relations,
+            tick: 0,
+        }
+    }
+
+    /// Add an empty stage, returning its index for `add_system_to_stage`
+    /// and `TickPhase::Stage`. New stages do not run until `set_phases`
+    /// includes them.
+    pub fn add_stage(&mut self) -> usize {
+        self.stages.push(Vec::new());
+        self.stages.len() - 1
+    }
+
+    /// Register `descriptor` with `world` and run `system` in the `UPDATE`
+    /// stage on every tick its descriptor is due.
+    pub fn add_system(
+        &mut self,
+        world: &mut World,
+        descriptor: SystemDescriptor,
+        system: impl FnMut(&mut World, &SystemContext<'_>) + Send + 'static,
+    ) -> Result<SystemHandle, ScheduleError> {
+        self.add_system_to_stage(Self::UPDATE, world, descriptor, system)
+    }
+
+    pub fn add_system_to_stage(
+        &mut self,
+        stage: usize,
+        world: &mut World,
+        descriptor: SystemDescriptor,
+        system: impl FnMut(&mut World, &SystemContext<'_>) + Send + 'static,
+    ) -> Result<SystemHandle, ScheduleError> {
+        if stage >= self.stages.len() {
+            return Err(ScheduleError::UnknownStage { stage });
+        }
+        let components = descriptor.all_components().to_vec();
+        let handle = world.register_system(descriptor)?;
+        self.stages[stage].push(ScheduledSystem {
+            handle,
+            components,
+            run: Box::new(system),
+        });
+        Ok(handle)
+    }
+
+    pub fn register_accelerator(
+        &mut self,
+        accelerator: Box<dyn RelationAccelerator + Send + Sync>,
+    ) {
+        self.queries.register(accelerator);
+    }
+
+    /// Replace the phase order.
+    ///
+    /// Every stage must exist and be followed by a `SwapBuffers`. Nothing else
+    /// is enforced, but note that a `FlushDespawns` after the last
+    /// `RebuildQueries` leaves relations pointing at rows that may have moved.
+    pub fn set_phases(&mut self, phases: Vec<TickPhase>) -> Result<(), ScheduleError> {
+        let last_swap = phases
+            .iter()
+            .rposition(|phase| *phase == TickPhase::SwapBuffers);
+        for (index, phase) in phases.iter().enumerate() {
+            if let TickPhase::Stage(stage) = *phase {
+                if stage >= self.stages.len() {
+                    return Err(ScheduleError::UnknownStage { stage });
+                }
+                if last_swap.is_none_or(|swap| swap < index) {
+                    return Err(ScheduleError::UnpublishedStage { stage });
+                }
+            }
+        }
+        self.phases = phases;
+        Ok(())
+    }
+
+    pub fn phases(&self) -> &[TickPhase] {
+        &self.phases
+    }
+
+    /// Number of ticks run so far; the next tick's `SystemContext::tick`.
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    pub fn relations(&self) -> &RelationBuffer {
+        &self.relations
+    }
+
+    pub fn queries(&self) -> &QueryRegistry {
+        &self.queries
+    }
+
+    pub(crate) fn run(&mut self, world: &mut World) -> Result<TickTimings, WorldError> {
+        let tick = self.tick;
+        // Everything logged during the tick carries its number.
+        let _span = tracing::info_span!("tick", tick).entered();
+        let mut timings = TickTimings {
+            tick,
+            phases: Vec::with_capacity(self.phases.len()),
+        };
+        for &phase in &self.phases {
+            let start = Instant::now();
+            match phase {
+                TickPhase::Stage(stage) => {
+                    for system in &mut self.stages[stage] {
+                        if !world.system_should_run(system.handle, tick).unwrap_or(true) {
+                            continue;
+                        }
+                        let context = SystemContext {
+                            tick,
+                            components: &system.components,
+                            relations: &self.relations,
+                        };
+                        (system.run)(world, &context);
+                    }
+                }
+                TickPhase::SwapBuffers => world.swap_buffers(),
+                TickPhase::FlushDespawns => world.flush_despawns()?,
+                TickPhase::RebuildQueries => {
+                    self.relations.clear();
+                    self.queries.rebuild_all(world, &mut self.relations);
+                }
+                TickPhase::PruneArchetypes { interval } => {
+                    if tick.is_multiple_of(interval.get()) {
+                        world.prune_empty_archetypes();
+                    }
+                }
+            }
+            timings.phases.push(PhaseTiming {
+                phase,
+                duration: start.elapsed(),
+            });
+        }
+        self.tick += 1;
+        Ok(timings)
+    }
+}
+
+impl Default for Schedule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
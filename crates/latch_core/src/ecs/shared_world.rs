@@ -0,0 +1,89 @@
+//! A `World` shared between the simulation thread and readers.
+//!
+//! A dedicated server ticks on one thread while network threads answer
+//! snapshot, checksum and status requests. `SharedWorld` puts the world
+//! behind an `RwLock` with one rule: anything that mutates — `tick`,
+//! spawning, despawning, `restore`, resources — holds the write lock, and
+//! readers hold read locks, so they always observe the world between two
+//! ticks, never halfway through one.
+
+use crate::ecs::{
+    CellPartition, Schedule, SnapshotCompression, SnapshotError, TickTimings, World, WorldError,
+};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// Cloneable handle to a `World` behind a read-write lock.
+///
+/// Readers block the simulation while they hold a lock, so keep read
+/// sections short: take a snapshot or checksum and encode or send it after
+/// the lock is released.
+#[derive(Clone)]
+pub struct SharedWorld {
+    inner: Arc<RwLock<World>>,
+}
+
+impl SharedWorld {
+    pub fn new(world: World) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(world)),
+        }
+    }
+
+    /// Shared access between ticks.
+    ///
+    /// Panics if a writer panicked while holding the lock, since the world
+    /// may be half-updated.
+    pub fn read(&self) -> RwLockReadGuard<'_, World> {
+        self.inner.read().expect("shared world poisoned")
+    }
+
+    /// Exclusive access, required for every structural change (spawn,
+    /// despawn, restore) and for writing components or resources.
+    pub fn write(&self) -> RwLockWriteGuard<'_, World> {
+        self.inner.write().expect("shared world poisoned")
+    }
+
+    /// Run one tick of `schedule` under the write lock.
+    pub fn tick(&self, schedule: &mut Schedule) -> Result<TickTimings, WorldError> {
+        self.write().tick(schedule)
+    }
+
+    /// `World::snapshot` under a read lock.
+    pub fn snapshot(&self, compression: SnapshotCompression) -> Result<Vec<u8>, SnapshotError> {
+        self.read().snapshot(compression)
+    }
+
+    /// `World::state_hash` under a read lock.
+    pub fn state_hash(&self) -> u64 {
+        self.read().state_hash()
+    }
+
+    /// `World::checksum_cells` under a read lock.
+    pub fn checksum_cells<P: CellPartition>(&self, grid: &P) -> HashMap<P::Cell, u64> {
+        self.read().checksum_cells(grid)
+    }
+
+    pub fn live_entity_count(&self) -> usize {
+        self.read().live_entity_count()
+    }
+
+    /// Run `f` with shared access, for reads without a forwarding method.
+    pub fn with_read<R>(&self, f: impl FnOnce(&World) -> R) -> R {
+        f(&self.read())
+    }
+
+    /// Take the world back once no other handle is left.
+    pub fn into_inner(self) -> Result<World, Self> {
+        match Arc::try_unwrap(self.inner) {
+            Ok(lock) => Ok(lock.into_inner().expect("shared world poisoned")),
+            Err(inner) => Err(Self { inner }),
+        }
+    }
+}
+
+impl From<World> for SharedWorld {
+    fn from(world: World) -> Self {
+        Self::new(world)
+    }
+}
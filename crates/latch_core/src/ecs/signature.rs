@@ -0,0 +1,88 @@
+//! Bit-packed component sets.
+//!
+//! Every archetype carries a signature with one bit per `ComponentId` it
+//! stores. Query matching then reduces to a word-wise subset test instead of
+//! a search per requested component. Only non-zero 64-bit words are kept,
+//! tagged with their index, so there is no fixed cap on component variety
+//! and a large id costs one word rather than a bitset reaching up to it.
+
+use crate::ecs::ComponentId;
+
+const WORD_BITS: u32 = u64::BITS;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ComponentSignature {
+    /// `(word index, bits)` in ascending index order; no word is zero.
+    words: Box<[(u32, u64)]>,
+}
+
+impl ComponentSignature {
+    pub fn from_components(components: &[ComponentId]) -> Self {
+        let mut bits: Vec<(u32, u64)> = components
+            .iter()
+            .map(|&id| {
+                let (word, bit) = Self::split(id);
+                (word, 1 << bit)
+            })
+            .collect();
+        bits.sort_unstable_by_key(|&(word, _)| word);
+        let mut words: Vec<(u32, u64)> = Vec::with_capacity(bits.len());
+        for (word, bit) in bits {
+            match words.last_mut() {
+                Some((last, set)) if *last == word => *set |= bit,
+                _ => words.push((word, bit)),
+            }
+        }
+        Self {
+            words: words.into_boxed_slice(),
+        }
+    }
+
+    #[inline]
+    pub fn contains(&self, id: ComponentId) -> bool {
+        let (word, bit) = Self::split(id);
+        self.word(word) & (1 << bit) != 0
+    }
+
+    /// True when every component in `other` is also present in `self`.
+    #[inline]
+    pub fn contains_all(&self, other: &ComponentSignature) -> bool {
+        let mut cursor = 0;
+        other.words.iter().all(|&(word, required)| {
+            while self.words.get(cursor).is_some_and(|&(idx, _)| idx < word) {
+                cursor += 1;
+            }
+            let present = match self.words.get(cursor) {
+                Some(&(idx, bits)) if idx == word => bits,
+                _ => 0,
+            };
+            required & !present == 0
+        })
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Number of components in the set.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.words
+            .iter()
+            .map(|(_, bits)| bits.count_ones() as usize)
+            .sum()
+    }
+
+    #[inline]
+    fn word(&self, word: u32) -> u64 {
+        self.words
+            .binary_search_by_key(&word, |&(idx, _)| idx)
+            .map_or(0, |pos| self.words[pos].1)
+    }
+
+    #[inline]
+    fn split(id: ComponentId) -> (u32, u32) {
+        (id / WORD_BITS, id % WORD_BITS)
+    }
+}
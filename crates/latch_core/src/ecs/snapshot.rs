@@ -0,0 +1,337 @@
+//! Binary world snapshots for rewind, save files and network state sync.
+//!
+//! A snapshot is a small uncompressed header followed by the body:
+//!
+//! - magic `SNAPSHOT_MAGIC`, format version (`u16`), flags (`u16`) and the
+//!   body length before compression (`u64`), all little-endian;
+//! - the body: entity slots (generation and state), the free list in pop
+//!   order, then every archetype in ascending id order (the `World`
+//!   iteration order; `restore` rejects any other) with its component ids,
+//!   row count, entity ids and one block per component of values encoded
+//!   through `ComponentMeta::encode`;
+//! - the simulation state kept in resources: for `PhysicsConfig`,
+//!   `CollisionConfig` and then the `Rng`, a presence byte followed by
+//!   `to_bytes` when the world has that resource.
+//!
+//! With `SNAPSHOT_FLAG_DEFLATE` set the body is a raw deflate stream.
+//! Columns of repetitive data (colors, tags, team ids) shrink to a fraction
+//! of their size, which is what makes snapshots practical on disk and over
+//! the network. In-memory rewind buffers should keep
+//! `SnapshotCompression::None`: it skips the codec entirely and is limited
+//! only by memory bandwidth.
+
+use crate::ecs::{ComponentCodecError, ComponentId, StorageError, WorldError};
+use flate2::{read::DeflateDecoder, write::DeflateEncoder, Compression};
+use std::io::{Read, Write};
+use thiserror::Error;
+
+/// Magic bytes at the start of an encoded snapshot.
+pub const SNAPSHOT_MAGIC: [u8; 4] = *b"LSNP";
+/// Version written by `World::snapshot`.
+///
+/// Version 4 appends the `Rng` resource and version 3 the `PhysicsConfig`
+/// and `CollisionConfig` resources. Version 2 had no config section;
+/// version 1 used `DefaultHasher` archetype ids. None of them is accepted.
+pub const SNAPSHOT_VERSION: u16 = 4;
+/// Header flag: the body is deflate-compressed.
+pub const SNAPSHOT_FLAG_DEFLATE: u16 = 1 << 0;
+
+const HEADER_LEN: usize = 16;
+
+/// How `World::snapshot` stores the body.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SnapshotCompression {
+    /// Raw body; the fast path for in-memory rewind.
+    #[default]
+    None,
+    /// Deflate level 1: most of the size win at a fraction of the time.
+    Fast,
+    /// Deflate level 9, for save files and infrequent full syncs.
+    Best,
+}
+
+impl SnapshotCompression {
+    fn level(self) -> Option<Compression> {
+        match self {
+            Self::None => None,
+            Self::Fast => Some(Compression::fast()),
+            Self::Best => Some(Compression::best()),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum SnapshotError {
+    #[error("not a world snapshot (bad magic)")]
+    BadMagic,
+    #[error("unsupported snapshot version {0}")]
+    UnsupportedVersion(u16),
+    #[error("unknown snapshot flags {0:#06x}")]
+    UnknownFlags(u16),
+    #[error("snapshot data truncated: needed {needed} bytes, {available} available")]
+    Truncated { needed: usize, available: usize },
+    #[error("snapshot body is {actual} bytes, header says {expected}")]
+    LengthMismatch { expected: u64, actual: u64 },
+    #[error("corrupt snapshot: {reason}")]
+    Corrupt { reason: &'static str },
+    #[error("component id {component_id} in snapshot is not registered")]
+    UnknownComponent { component_id: ComponentId },
+    #[error("cannot snapshot with despawns pending; call flush_despawns first")]
+    PendingDespawns,
+    #[error(transparent)]
+    Codec(#[from] ComponentCodecError),
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+    #[error("snapshot compression failed: {0}")]
+    Compression(#[from] std::io::Error),
+    #[error(transparent)]
+    World(#[from] WorldError),
+}
+
+/// State of an entity slot as stored in a snapshot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum SlotState {
+    Free = 0,
+    Alive = 1,
+    Reserved = 2,
+}
+
+impl SlotState {
+    fn from_u8(value: u8) -> Result<Self, SnapshotError> {
+        match value {
+            0 => Ok(Self::Free),
+            1 => Ok(Self::Alive),
+            2 => Ok(Self::Reserved),
+            _ => Err(SnapshotError::Corrupt {
+                reason: "invalid slot state",
+            }),
+        }
+    }
+}
+
+/// Little-endian body writer used by `World::snapshot`.
+pub(crate) struct SnapshotWriter {
+    body: Vec<u8>,
+}
+
+impl SnapshotWriter {
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        Self {
+            body: Vec::with_capacity(capacity),
+        }
+    }
+
+    pub(crate) fn u8(&mut self, value: u8) {
+        self.body.push(value);
+    }
+
+    pub(crate) fn u32(&mut self, value: u32) {
+        self.body.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub(crate) fn u64(&mut self, value: u64) {
+        self.body.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub(crate) fn slot(&mut self, generation: u32, state: SlotState) {
+        self.u32(generation);
+        self.u8(state as u8);
+    }
+
+    /// Config resource: a presence byte, then its encoding.
+    pub(crate) fn config<const N: usize>(&mut self, encoded: Option<[u8; N]>) {
+        match encoded {
+            Some(bytes) => {
+                self.u8(1);
+                self.body.extend_from_slice(&bytes);
+            }
+            None => self.u8(0),
+        }
+    }
+
+    /// Buffer that component values are encoded into.
+    pub(crate) fn body_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.body
+    }
+
+    /// Prepend the header and compress the body if requested.
+    pub(crate) fn finish(self, compression: SnapshotCompression) -> Result<Vec<u8>, SnapshotError> {
+        let body_len = self.body.len() as u64;
+        let mut out = Vec::with_capacity(HEADER_LEN + self.body.len());
+        out.extend_from_slice(&SNAPSHOT_MAGIC);
+        out.extend_from_slice(&SNAPSHOT_VERSION.to_le_bytes());
+        match compression.level() {
+            None => {
+                out.extend_from_slice(&0u16.to_le_bytes());
+                out.extend_from_slice(&body_len.to_le_bytes());
+                out.extend_from_slice(&self.body);
+                Ok(out)
+            }
+            Some(level) => {
+                out.extend_from_slice(&SNAPSHOT_FLAG_DEFLATE.to_le_bytes());
+                out.extend_from_slice(&body_len.to_le_bytes());
+                let mut encoder = DeflateEncoder::new(out, level);
+                encoder.write_all(&self.body)?;
+                Ok(encoder.finish()?)
+            }
+        }
+    }
+}
+
+/// Parsed header of an encoded snapshot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SnapshotHeader {
+    pub version: u16,
+    pub flags: u16,
+    /// Body length before compression.
+    pub body_len: u64,
+}
+
+impl SnapshotHeader {
+    /// Read and validate the header at the front of `bytes`.
+    pub fn parse(bytes: &[u8]) -> Result<Self, SnapshotError> {
+        let mut reader = SnapshotReader { bytes };
+        if reader.take::<4>()? != SNAPSHOT_MAGIC {
+            return Err(SnapshotError::BadMagic);
+        }
+        let version = reader.u16()?;
+        if version != SNAPSHOT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(version));
+        }
+        let flags = reader.u16()?;
+        if flags & !SNAPSHOT_FLAG_DEFLATE != 0 {
+            return Err(SnapshotError::UnknownFlags(flags));
+        }
+        let body_len = reader.u64()?;
+        Ok(Self {
+            version,
+            flags,
+            body_len,
+        })
+    }
+
+    pub fn is_compressed(&self) -> bool {
+        self.flags & SNAPSHOT_FLAG_DEFLATE != 0
+    }
+}
+
+/// Uncompressed body of an encoded snapshot: borrowed when it was stored
+/// raw, inflated otherwise.
+pub(crate) enum SnapshotBody<'a> {
+    Raw(&'a [u8]),
+    Inflated(Vec<u8>),
+}
+
+impl SnapshotBody<'_> {
+    pub(crate) fn open(bytes: &[u8]) -> Result<SnapshotBody<'_>, SnapshotError> {
+        let header = SnapshotHeader::parse(bytes)?;
+        let payload = &bytes[HEADER_LEN..];
+        let (body, actual) = if header.is_compressed() {
+            // The header length is untrusted; cap the up-front allocation,
+            // and stop inflating one byte past it so a hostile stream cannot
+            // expand without bound.
+            let capacity = header.body_len.min(payload.len() as u64 * 64) as usize;
+            let mut body = Vec::with_capacity(capacity);
+            DeflateDecoder::new(payload)
+                .take(header.body_len.saturating_add(1))
+                .read_to_end(&mut body)?;
+            let actual = body.len() as u64;
+            (SnapshotBody::Inflated(body), actual)
+        } else {
+            (SnapshotBody::Raw(payload), payload.len() as u64)
+        };
+        if actual != header.body_len {
+            return Err(SnapshotError::LengthMismatch {
+                expected: header.body_len,
+                actual,
+            });
+        }
+        Ok(body)
+    }
+
+    pub(crate) fn reader(&self) -> SnapshotReader<'_> {
+        let bytes = match self {
+            SnapshotBody::Raw(bytes) => bytes,
+            SnapshotBody::Inflated(body) => body.as_slice(),
+        };
+        SnapshotReader { bytes }
+    }
+}
+
+pub(crate) struct SnapshotReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> SnapshotReader<'a> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], SnapshotError> {
+        if self.bytes.len() < N {
+            return Err(SnapshotError::Truncated {
+                needed: N,
+                available: self.bytes.len(),
+            });
+        }
+        let (head, rest) = self.bytes.split_at(N);
+        self.bytes = rest;
+        Ok(head.try_into().expect("split_at returned N bytes"))
+    }
+
+    pub(crate) fn u8(&mut self) -> Result<u8, SnapshotError> {
+        Ok(self.take::<1>()?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, SnapshotError> {
+        Ok(u16::from_le_bytes(self.take()?))
+    }
+
+    pub(crate) fn u32(&mut self) -> Result<u32, SnapshotError> {
+        Ok(u32::from_le_bytes(self.take()?))
+    }
+
+    pub(crate) fn u64(&mut self) -> Result<u64, SnapshotError> {
+        Ok(u64::from_le_bytes(self.take()?))
+    }
+
+    pub(crate) fn slot(&mut self) -> Result<(u32, SlotState), SnapshotError> {
+        let generation = self.u32()?;
+        let state = SlotState::from_u8(self.u8()?)?;
+        Ok((generation, state))
+    }
+
+    /// Config resource written by `SnapshotWriter::config`.
+    pub(crate) fn config<const N: usize>(&mut self) -> Result<Option<[u8; N]>, SnapshotError> {
+        match self.u8()? {
+            0 => Ok(None),
+            1 => Ok(Some(self.take()?)),
+            _ => Err(SnapshotError::Corrupt {
+                reason: "invalid config marker",
+            }),
+        }
+    }
+
+    /// Element count that must be backed by at least `min_size` bytes each,
+    /// so a corrupt count cannot trigger a huge allocation.
+    pub(crate) fn count(&mut self, min_size: usize) -> Result<usize, SnapshotError> {
+        let count = self.u64()?;
+        let needed = count.saturating_mul(min_size.max(1) as u64);
+        if needed > self.bytes.len() as u64 {
+            return Err(SnapshotError::Truncated {
+                needed: usize::try_from(needed).unwrap_or(usize::MAX),
+                available: self.bytes.len(),
+            });
+        }
+        Ok(count as usize)
+    }
+
+    /// Unread bytes; component values are decoded straight from here.
+    pub(crate) fn remaining(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    pub(crate) fn advance(&mut self, len: usize) {
+        self.bytes = &self.bytes[len..];
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+}
@@ -0,0 +1,344 @@
+//! Decoded snapshots and per-component diffs between them.
+//!
+//! `World::snapshot` produces bytes meant for `restore`. The editor needs to
+//! know *what* differs between two of them, for undo/redo and for showing
+//! what a frame changed. `WorldSnapshot::decode` reads a snapshot into
+//! per-entity component values and `WorldSnapshot::diff` lists the
+//! differences: entities added or removed, and component values that
+//! changed, broken down by the fields recorded in `FieldMeta`.
+
+use crate::ecs::{
+    meta_of,
+    snapshot::{SlotState, SnapshotBody},
+    ArchetypeLayout, ComponentId, Entity, EntityId, SnapshotError,
+};
+use crate::math::Rng;
+use crate::physics::{CollisionConfig, PhysicsConfig};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// One component value in native layout (`stride` bytes).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ComponentValue {
+    pub component_id: ComponentId,
+    pub bytes: Box<[u8]>,
+}
+
+/// One field of a component whose bytes differ.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldChange {
+    pub name: Box<str>,
+    pub offset: usize,
+    pub before: Box<[u8]>,
+    pub after: Box<[u8]>,
+}
+
+/// A difference between two snapshots, from `self` to `other` of
+/// `WorldSnapshot::diff`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChangeRecord {
+    /// Alive only in the later snapshot, with every component value.
+    Added {
+        entity: Entity,
+        components: Vec<ComponentValue>,
+    },
+    /// Alive only in the earlier snapshot, with every component value.
+    Removed {
+        entity: Entity,
+        components: Vec<ComponentValue>,
+    },
+    /// One component value differs. `fields` lists the changed fields;
+    /// it is empty when the component registered no field metadata.
+    Changed {
+        entity: Entity,
+        component_id: ComponentId,
+        before: Box<[u8]>,
+        after: Box<[u8]>,
+        fields: Vec<FieldChange>,
+    },
+}
+
+impl ChangeRecord {
+    pub fn entity(&self) -> Entity {
+        match self {
+            Self::Added { entity, .. }
+            | Self::Removed { entity, .. }
+            | Self::Changed { entity, .. } => *entity,
+        }
+    }
+}
+
+impl fmt::Display for ChangeRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let entity = self.entity();
+        let (index, generation) = (entity.index(), entity.generation());
+        match self {
+            Self::Added { components, .. } | Self::Removed { components, .. } => {
+                let verb = if matches!(self, Self::Added { .. }) {
+                    "added"
+                } else {
+                    "removed"
+                };
+                write!(f, "entity {index}v{generation} {verb} (")?;
+                for (i, value) in components.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write_component_name(f, value.component_id)?;
+                }
+                write!(f, ")")
+            }
+            Self::Changed {
+                component_id,
+                before,
+                after,
+                fields,
+                ..
+            } => {
+                write!(f, "entity {index}v{generation} ")?;
+                write_component_name(f, *component_id)?;
+                if fields.is_empty() {
+                    return write!(f, ": {} -> {}", Bytes(before), Bytes(after));
+                }
+                for (i, field) in fields.iter().enumerate() {
+                    let separator = if i == 0 { ": " } else { ", " };
+                    write!(
+                        f,
+                        "{separator}{} {} -> {}",
+                        field.name,
+                        Bytes(&field.before),
+                        Bytes(&field.after)
+                    )?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+fn write_component_name(f: &mut fmt::Formatter<'_>, component_id: ComponentId) -> fmt::Result {
+    match meta_of(component_id) {
+        Some(meta) => write!(f, "{}", meta.name),
+        None => write!(f, "component {component_id}"),
+    }
+}
+
+/// Field bytes for display. `FieldMeta` records no scalar kinds, so values
+/// of up to 8 bytes print as little-endian integers, anything else as hex.
+struct Bytes<'a>(&'a [u8]);
+
+impl fmt::Display for Bytes<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bytes = self.0;
+        match bytes.len() {
+            1 => write!(f, "{}", bytes[0] as i8),
+            2 => write!(f, "{}", i16::from_le_bytes([bytes[0], bytes[1]])),
+            4 => write!(f, "{}", i32::from_le_bytes(bytes.try_into().unwrap())),
+            8 => write!(f, "{}", i64::from_le_bytes(bytes.try_into().unwrap())),
+            _ => {
+                write!(f, "0x")?;
+                bytes.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct EntityRecord {
+    entity: Entity,
+    /// Sorted by component id.
+    components: Vec<ComponentValue>,
+}
+
+/// Every live entity of an encoded snapshot with its component values.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WorldSnapshot {
+    entities: BTreeMap<EntityId, EntityRecord>,
+}
+
+impl WorldSnapshot {
+    /// Decode the output of `World::snapshot`; components must be
+    /// registered, as for `World::restore`.
+    pub fn decode(bytes: &[u8]) -> Result<Self, SnapshotError> {
+        let body = SnapshotBody::open(bytes)?;
+        let mut reader = body.reader();
+
+        reader.u32()?; // generation floor
+        let slot_count = reader.count(5)?;
+        let mut slots = Vec::with_capacity(slot_count);
+        for _ in 0..slot_count {
+            slots.push(reader.slot()?);
+        }
+        let free_count = reader.count(4)?;
+        for _ in 0..free_count {
+            reader.u32()?;
+        }
+
+        let mut entities = BTreeMap::new();
+        let archetype_count = reader.count(16)?;
+        for _ in 0..archetype_count {
+            let component_count = reader.count(4)?;
+            let mut components = Vec::with_capacity(component_count);
+            for _ in 0..component_count {
+                components.push(reader.u32()?);
+            }
+            let layout = ArchetypeLayout::new(components);
+            let metas = layout
+                .components()
+                .iter()
+                .map(|&component_id| {
+                    meta_of(component_id).ok_or(SnapshotError::UnknownComponent { component_id })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let rows = reader.count(4)?;
+            let mut records = Vec::with_capacity(rows);
+            for _ in 0..rows {
+                let entity_id = reader.u32()?;
+                let Some(&(generation, SlotState::Alive)) = slots.get(entity_id as usize) else {
+                    return Err(SnapshotError::Corrupt {
+                        reason: "row belongs to an entity that is not alive",
+                    });
+                };
+                records.push(EntityRecord {
+                    entity: Entity::new(entity_id, generation),
+                    components: Vec::with_capacity(metas.len()),
+                });
+            }
+            for meta in &metas {
+                for record in &mut records {
+                    let mut value = vec![0u8; meta.stride];
+                    let used = meta.decode(reader.remaining(), &mut value)?;
+                    reader.advance(used);
+                    record.components.push(ComponentValue {
+                        component_id: meta.id,
+                        bytes: value.into_boxed_slice(),
+                    });
+                }
+            }
+            for record in records {
+                if entities.insert(record.entity.index(), record).is_some() {
+                    return Err(SnapshotError::Corrupt {
+                        reason: "entity stored in more than one row",
+                    });
+                }
+            }
+        }
+        // Physics and collision configs, then the RNG state.
+        reader.config::<{ PhysicsConfig::ENCODED_BYTES }>()?;
+        reader.config::<{ CollisionConfig::ENCODED_BYTES }>()?;
+        reader.config::<{ Rng::STATE_BYTES }>()?;
+        if !reader.is_empty() {
+            return Err(SnapshotError::Corrupt {
+                reason: "trailing bytes after the simulation config",
+            });
+        }
+        Ok(Self { entities })
+    }
+
+    /// Number of live entities.
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    /// Value of `component_id` on `entity`, if it was alive and had it.
+    pub fn component(&self, entity: Entity, component_id: ComponentId) -> Option<&[u8]> {
+        let record = self.entities.get(&entity.index())?;
+        if record.entity != entity {
+            return None;
+        }
+        record
+            .components
+            .iter()
+            .find(|value| value.component_id == component_id)
+            .map(|value| &*value.bytes)
+    }
+
+    /// Everything that differs from `self` to `other`, by entity id and
+    /// then component id.
+    ///
+    /// An id alive in both with a different generation or component set
+    /// was despawned and respawned, so it is reported as `Removed` followed
+    /// by `Added`.
+    pub fn diff(&self, other: &WorldSnapshot) -> Vec<ChangeRecord> {
+        let mut changes = Vec::new();
+        let mut before = self.entities.iter().peekable();
+        let mut after = other.entities.iter().peekable();
+        loop {
+            let (old, new) = match (before.peek(), after.peek()) {
+                (None, None) => break,
+                (Some((a, _)), Some((b, _))) if a < b => (before.next(), None),
+                (Some((a, _)), Some((b, _))) if a > b => (None, after.next()),
+                (Some(_), Some(_)) => (before.next(), after.next()),
+                (Some(_), None) => (before.next(), None),
+                (None, Some(_)) => (None, after.next()),
+            };
+            match (old.map(|(_, r)| r), new.map(|(_, r)| r)) {
+                (Some(old), Some(new)) if same_shape(old, new) => {
+                    diff_values(old, new, &mut changes);
+                }
+                (old, new) => {
+                    if let Some(old) = old {
+                        changes.push(ChangeRecord::Removed {
+                            entity: old.entity,
+                            components: old.components.clone(),
+                        });
+                    }
+                    if let Some(new) = new {
+                        changes.push(ChangeRecord::Added {
+                            entity: new.entity,
+                            components: new.components.clone(),
+                        });
+                    }
+                }
+            }
+        }
+        changes
+    }
+}
+
+fn same_shape(old: &EntityRecord, new: &EntityRecord) -> bool {
+    old.entity == new.entity
+        && old
+            .components
+            .iter()
+            .map(|value| value.component_id)
+            .eq(new.components.iter().map(|value| value.component_id))
+}
+
+fn diff_values(old: &EntityRecord, new: &EntityRecord, changes: &mut Vec<ChangeRecord>) {
+    for (before, after) in old.components.iter().zip(&new.components) {
+        if before.bytes == after.bytes {
+            continue;
+        }
+        let fields = meta_of(before.component_id)
+            .map(|meta| {
+                meta.fields
+                    .iter()
+                    .filter_map(|field| {
+                        let range = field.offset..field.offset + field.size;
+                        let (old, new) =
+                            (before.bytes.get(range.clone())?, after.bytes.get(range)?);
+                        (old != new).then(|| FieldChange {
+                            name: field.name.clone(),
+                            offset: field.offset,
+                            before: old.into(),
+                            after: new.into(),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        changes.push(ChangeRecord::Changed {
+            entity: new.entity,
+            component_id: before.component_id,
+            before: before.bytes.clone(),
+            after: after.bytes.clone(),
+            fields,
+        });
+    }
+}
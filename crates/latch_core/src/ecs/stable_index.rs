@@ -0,0 +1,110 @@
+//! Per-entity indices that survive row moves, for GPU-side arrays.
+//!
+//! Rows are swap-removed on despawn, so a GPU instance buffer laid out in
+//! iteration order goes stale whenever anything despawns. A stable index is
+//! handed out when an entity spawns and kept until it despawns, whatever
+//! happens to its row; a renderer writes entity `e` to slot
+//! `World::stable_index_of(e)` and only uploads what changed.
+//!
+//! Freed indices are reused by later spawns, so the buffer never needs more
+//! than `World::stable_index_capacity` slots. After a despawn-heavy phase
+//! `World::compact_stable_indices` moves the highest indices into the holes
+//! and reports every move so the GPU copy can follow.
+
+use crate::ecs::{Entity, EntityId};
+
+/// A stable index reassigned by `World::compact_stable_indices`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StableIndexMove {
+    pub entity: Entity,
+    pub from: u32,
+    pub to: u32,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct StableIndices {
+    /// Index of each entity slot, by `EntityId`.
+    by_entity: Vec<Option<u32>>,
+    /// Entity holding each index; its length is the capacity.
+    owners: Vec<Option<EntityId>>,
+    /// Unowned indices below the capacity, reused last-freed first.
+    free: Vec<u32>,
+}
+
+impl StableIndices {
+    pub(crate) fn get(&self, entity_id: EntityId) -> Option<u32> {
+        self.by_entity.get(entity_id as usize).copied().flatten()
+    }
+
+    pub(crate) fn capacity(&self) -> u32 {
+        self.owners.len() as u32
+    }
+
+    pub(crate) fn free(&self) -> &[u32] {
+        &self.free
+    }
+
+    pub(crate) fn assign(&mut self, entity_id: EntityId) -> u32 {
+        debug_assert!(self.get(entity_id).is_none());
+        let index = match self.free.pop() {
+            Some(index) => index,
+            None => {
+                // Every owner is a distinct `EntityId`, so this fits in u32.
+                let index = self.owners.len() as u32;
+                self.owners.push(None);
+                index
+            }
+        };
+        self.owners[index as usize] = Some(entity_id);
+        let slot = entity_id as usize;
+        if self.by_entity.len() <= slot {
+            self.by_entity.resize(slot + 1, None);
+        }
+        self.by_entity[slot] = Some(index);
+        index
+    }
+
+    pub(crate) fn release(&mut self, entity_id: EntityId) -> Option<u32> {
+        let index = self.by_entity.get_mut(entity_id as usize)?.take()?;
+        self.owners[index as usize] = None;
+        self.free.push(index);
+        Some(index)
+    }
+
+    /// Forget entity slots at or above `len` (none of them may hold an
+    /// index).
+    pub(crate) fn truncate_entities(&mut self, len: usize) {
+        debug_assert!(self.by_entity.iter().skip(len).all(Option::is_none));
+        self.by_entity.truncate(len);
+        self.by_entity.shrink_to_fit();
+    }
+
+    /// Move the highest owned indices into the lowest free ones until no
+    /// holes remain, returning `(entity, from, to)` per move.
+    pub(crate) fn compact(&mut self) -> Vec<(EntityId, u32, u32)> {
+        let mut free = std::mem::take(&mut self.free);
+        free.sort_unstable_by(|a, b| b.cmp(a));
+        let mut moves = Vec::new();
+        loop {
+            while self.owners.last() == Some(&None) {
+                self.owners.pop();
+            }
+            let Some(&to) = free.last() else { break };
+            if to as usize >= self.owners.len() {
+                break;
+            }
+            free.pop();
+            let from = self.owners.len() as u32 - 1;
+            let entity_id = self
+                .owners
+                .pop()
+                .flatten()
+                .expect("trailing free indices were popped");
+            self.owners[to as usize] = Some(entity_id);
+            self.by_entity[entity_id as usize] = Some(to);
+            moves.push((entity_id, from, to));
+        }
+        self.owners.shrink_to_fit();
+        moves
+    }
+}
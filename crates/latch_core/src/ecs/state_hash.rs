@@ -0,0 +1,237 @@
+//! World state hashing and replay divergence detection.
+//!
+//! `World::state_hash` condenses the entity table and the current buffer of
+//! every column into one `u64`; two worlds that replayed the same inputs
+//! must produce the same value every tick. When they do not,
+//! `World::state_hashes` keeps one hash per archetype and column so
+//! `ReplayVerifier` can name what diverged instead of just when.
+//!
+//! Column bytes are hashed as stored. Components with implicit padding can
+//! carry arbitrary padding bytes, so give them explicit padding fields (as
+//! `Pod` types already require) before relying on these hashes.
+
+use crate::ecs::{ArchetypeId, ComponentId, World};
+use crate::hash::StableHasher;
+use std::{collections::BTreeMap, fmt};
+
+/// Hashes of one archetype's rows.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ArchetypeHash {
+    pub archetype: ArchetypeId,
+    /// Hash of the entity id of every row, in row order.
+    pub entity_ids: u64,
+    /// Hash of each column's current buffer, in layout order.
+    pub columns: Vec<(ComponentId, u64)>,
+}
+
+/// Per-part hashes of a world, from `World::state_hashes`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StateHashes {
+    /// Hash of the entity table: slot generations and states, the free list
+    /// and the generation floor.
+    pub entities: u64,
+    /// One entry per archetype in `World` iteration order.
+    pub archetypes: Vec<ArchetypeHash>,
+}
+
+impl StateHashes {
+    /// Single hash of every part; equal to `World::state_hash`.
+    pub fn combined(&self) -> u64 {
+        let mut hasher = StableHasher::new();
+        hasher.write_u64(self.entities);
+        for archetype in &self.archetypes {
+            hasher.write_u64(archetype.archetype);
+            hasher.write_u64(archetype.entity_ids);
+            for &(component_id, hash) in &archetype.columns {
+                hasher.write_u32(component_id);
+                hasher.write_u64(hash);
+            }
+        }
+        hasher.finish()
+    }
+
+    /// Parts of `actual` that differ from `self`, archetypes in ascending id
+    /// order.
+    pub fn differences(&self, actual: &StateHashes) -> Vec<HashDifference> {
+        let mut differences = Vec::new();
+        if self.entities != actual.entities {
+            differences.push(HashDifference::Entities);
+        }
+        let expected: BTreeMap<_, _> = self.archetypes.iter().map(|a| (a.archetype, a)).collect();
+        let found: BTreeMap<_, _> = actual.archetypes.iter().map(|a| (a.archetype, a)).collect();
+        for (&archetype, expected) in &expected {
+            let Some(found) = found.get(&archetype) else {
+                differences.push(HashDifference::MissingArchetype { archetype });
+                continue;
+            };
+            if expected.entity_ids != found.entity_ids {
+                differences.push(HashDifference::EntityIds { archetype });
+            }
+            for (&(component_id, hash), &(_, found_hash)) in
+                expected.columns.iter().zip(&found.columns)
+            {
+                if hash != found_hash {
+                    differences.push(HashDifference::Column {
+                        archetype,
+                        component_id,
+                    });
+                }
+            }
+        }
+        for &archetype in found.keys() {
+            if !expected.contains_key(&archetype) {
+                differences.push(HashDifference::UnexpectedArchetype { archetype });
+            }
+        }
+        differences
+    }
+}
+
+/// One part of the world whose hash differs between two runs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HashDifference {
+    /// Entity generations, slot states or the free list.
+    Entities,
+    /// The archetype existed in the recording but not in the replay.
+    MissingArchetype { archetype: ArchetypeId },
+    /// The archetype exists in the replay but not in the recording.
+    UnexpectedArchetype { archetype: ArchetypeId },
+    /// Same archetype, different entities or row order.
+    EntityIds { archetype: ArchetypeId },
+    /// Same rows, different values in one column.
+    Column {
+        archetype: ArchetypeId,
+        component_id: ComponentId,
+    },
+}
+
+impl fmt::Display for HashDifference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Entities => write!(f, "entity table"),
+            Self::MissingArchetype { archetype } => write!(f, "archetype {archetype} missing"),
+            Self::UnexpectedArchetype { archetype } => {
+                write!(f, "archetype {archetype} unexpected")
+            }
+            Self::EntityIds { archetype } => write!(f, "archetype {archetype} entity ids"),
+            Self::Column {
+                archetype,
+                component_id,
+            } => write!(f, "archetype {archetype} component {component_id}"),
+        }
+    }
+}
+
+/// First tick at which a replay's state differed from the recording.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Divergence {
+    pub tick: u64,
+    pub expected: u64,
+    pub actual: u64,
+    pub differences: Vec<HashDifference>,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "replay diverged at tick {} (expected hash {:#018x}, got {:#018x})",
+            self.tick, self.expected, self.actual
+        )?;
+        for (i, difference) in self.differences.iter().enumerate() {
+            let separator = if i == 0 { ": " } else { ", " };
+            write!(f, "{separator}{difference}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Divergence {}
+
+/// Records per-tick state hashes of one run and checks a replay against
+/// them.
+///
+/// Call `record` after every tick of the original run and `verify` after
+/// the same tick of the replay. Only the first divergence is reported;
+/// everything after it is a consequence.
+#[derive(Debug, Default)]
+pub struct ReplayVerifier {
+    recorded: BTreeMap<u64, StateHashes>,
+    divergence: Option<Divergence>,
+}
+
+impl ReplayVerifier {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store the hashes of `world` after `tick`.
+    pub fn record(&mut self, tick: u64, world: &World) {
+        self.recorded.insert(tick, world.state_hashes());
+    }
+
+    /// Compare `world` after `tick` against the recording.
+    ///
+    /// Ticks that were not recorded are skipped. After the first divergence
+    /// every call returns that divergence again without hashing.
+    pub fn verify(&mut self, tick: u64, world: &World) -> Result<(), Divergence> {
+        if let Some(divergence) = &self.divergence {
+            return Err(divergence.clone());
+        }
+        let Some(expected) = self.recorded.get(&tick) else {
+            return Ok(());
+        };
+        let actual = world.state_hashes();
+        if *expected == actual {
+            return Ok(());
+        }
+        let divergence = Divergence {
+            tick,
+            expected: expected.combined(),
+            actual: actual.combined(),
+            differences: expected.differences(&actual),
+        };
+        self.divergence = Some(divergence.clone());
+        Err(divergence)
+    }
+
+    /// Number of recorded ticks.
+    pub fn recorded_ticks(&self) -> usize {
+        self.recorded.len()
+    }
+
+    pub fn divergence(&self) -> Option<&Divergence> {
+        self.divergence.as_ref()
+    }
+
+    /// Forget the recording and any divergence.
+    pub fn clear(&mut self) {
+        self.recorded.clear();
+        self.divergence = None;
+    }
+}
+
+/// Run a simulation twice from identical worlds and report the first tick
+/// whose state differs.
+///
+/// `setup` builds the starting world and is called once per run; `step`
+/// advances the world by one tick (apply the recorded input, run the
+/// schedule). Suitable for CI: a deterministic simulation returns `Ok`.
+pub fn verify_determinism(
+    ticks: u64,
+    mut setup: impl FnMut() -> World,
+    mut step: impl FnMut(&mut World, u64),
+) -> Result<(), Divergence> {
+    let mut verifier = ReplayVerifier::new();
+    let mut world = setup();
+    for tick in 0..ticks {
+        step(&mut world, tick);
+        verifier.record(tick, &world);
+    }
+    let mut world = setup();
+    for tick in 0..ticks {
+        step(&mut world, tick);
+        verifier.verify(tick, &world)?;
+    }
+    Ok(())
+}
@@ -1,8 +1,14 @@
 use crate::{
-    ecs::{meta_of, ArchetypeLayout, Component, ComponentId, ComponentMeta, EntityId},
+    ecs::{
+        meta_of,
+        storage::{CullBounds, CullStats, PageAllocator, PageTile, RenderLayer, RowInit},
+        ArchetypeId, ArchetypeLayout, Component, ComponentId, ComponentMeta, EntityId,
+    },
+    hash::StableHasher,
     pool::{PagedPool, PoolError},
 };
 use latch_env::memory::Memory;
+use rayon::prelude::*;
 use std::{
     alloc::{alloc, dealloc, handle_alloc_error, Layout},
     collections::HashMap,
@@ -19,6 +25,8 @@ use thiserror::Error;
 pub struct ColumnPlan {
     pub component_id: ComponentId,
     pub meta: ComponentMeta,
+    /// Alignment of every page allocated for this column (>= `meta.align`).
+    pub page_align: usize,
 }
 
 #[derive(Debug, Clone)]
@@ -68,21 +76,39 @@ pub fn plan_archetype(
         bytes_per_row = bytes_per_row
             .checked_add(meta.stride)
             .ok_or(PlanError::BytesPerRowOverflow)?;
-        columns.push(ColumnPlan { component_id, meta });
+        let page_align = meta.page_align();
+        columns.push(ColumnPlan {
+            component_id,
+            meta,
+            page_align,
+        });
     }
 
     let bytes_per_row = NonZeroUsize::new(bytes_per_row).ok_or(PlanError::BytesPerRowZero)?;
     let rows_per_page = compute_rows_per_page(bytes_per_row, budget.l2_bytes);
     let rows_per_page =
         NonZeroUsize::new(rows_per_page).ok_or(PlanError::RowsPerPageZero { bytes_per_row })?;
-    let page_bytes = rows_per_page
+    let overflow = PlanError::PageBytesOverflow {
+        rows_per_page,
+        bytes_per_row,
+    };
+    // Each column page is rounded up to its alignment, so over-aligned
+    // columns cost a little padding per page.
+    let mut page_bytes = rows_per_page
         .get()
-        .checked_mul(bytes_per_row.get())
-        .and_then(NonZeroUsize::new)
-        .ok_or(PlanError::PageBytesOverflow {
-            rows_per_page,
-            bytes_per_row,
-        })?;
+        .checked_mul(std::mem::size_of::<EntityId>())
+        .ok_or(overflow.clone())?;
+    for column in &columns {
+        let column_bytes = rows_per_page
+            .get()
+            .checked_mul(column.meta.stride)
+            .and_then(|bytes| bytes.checked_next_multiple_of(column.page_align))
+            .ok_or(overflow.clone())?;
+        page_bytes = page_bytes
+            .checked_add(column_bytes)
+            .ok_or(overflow.clone())?;
+    }
+    let page_bytes = NonZeroUsize::new(page_bytes).ok_or(overflow)?;
 
     Ok(ArchetypePlan {
         layout,
@@ -100,21 +126,32 @@ struct BytePage {
     stride: usize,
     align: usize,
     alloc_size: usize,
+    /// `None` for the global allocator.
+    allocator: Option<Arc<dyn PageAllocator>>,
 }
 
 impl BytePage {
-    fn with_capacity(rows: usize, stride: usize, align: usize) -> Self {
+    fn with_capacity(
+        rows: usize,
+        stride: usize,
+        align: usize,
+        allocator: Option<Arc<dyn PageAllocator>>,
+    ) -> Self {
         debug_assert!(
             align.is_power_of_two(),
             "page alignment must be power-of-two"
         );
         let total = rows
             .checked_mul(stride)
+            .and_then(|bytes| bytes.checked_next_multiple_of(align))
             .expect("byte page allocation overflow");
         let alloc_size = total.max(align);
         let layout = Layout::from_size_align(alloc_size, align).expect("invalid layout");
-        let ptr = unsafe { alloc(layout) };
-        let ptr = NonNull::new(ptr).unwrap_or_else(|| handle_alloc_error(layout));
+        let ptr = match &allocator {
+            Some(allocator) => allocator.alloc_page(layout),
+            None => NonNull::new(unsafe { alloc(layout) }),
+        };
+        let ptr = ptr.unwrap_or_else(|| handle_alloc_error(layout));
         Self {
             ptr,
             len: 0,
@@ -122,6 +159,7 @@ impl BytePage {
             stride,
             align,
             alloc_size,
+            allocator,
         }
     }
 
@@ -167,6 +205,10 @@ impl BytePage {
         }
     }
 
+    fn zero_rows(&mut self, rows: Range<usize>) {
+        self.slice_bytes_mut(rows.start, rows.len()).fill(0);
+    }
+
     fn row_bytes(&self, row: usize) -> &[u8] {
         self.slice_bytes(row, 1)
     }
@@ -187,11 +229,20 @@ impl BytePage {
     }
 }
 
+// SAFETY: a page exclusively owns its allocation and only writes through
+// `&mut self`, so it has the same thread-safety as a `Box<[u8]>`.
+unsafe impl Send for BytePage {}
+unsafe impl Sync for BytePage {}
+
 impl Drop for BytePage {
     fn drop(&mut self) {
         let layout = Layout::from_size_align(self.alloc_size, self.align).expect("invalid layout");
         unsafe {
-            dealloc(self.ptr.as_ptr(), layout);
+            // SAFETY: the page was allocated with `layout` by the same allocator.
+            match &self.allocator {
+                Some(allocator) => allocator.free_page(self.ptr, layout),
+                None => dealloc(self.ptr.as_ptr(), layout),
+            }
         }
     }
 }
@@ -211,7 +262,7 @@ fn floor_power_of_two(value: usize) -> usize {
     1usize << highest_bit
 }
 
-#[derive(Debug, Error)]
+#[derive(Debug, Clone, Error)]
 pub enum PlanError {
     #[error("component id {component_id} not registered")]
     ComponentNotRegistered { component_id: ComponentId },
@@ -248,13 +299,21 @@ pub enum ColumnError {
     IndexOutOfBounds { index: usize, len: usize },
     #[error("stride mismatch: expected {expected} bytes, got {got} bytes")]
     StrideMismatch { expected: usize, got: usize },
-    #[error("type mismatch for component: expected stride {expected_stride} bytes (align {expected_align}), but got stride {actual_stride} (align {actual_align})")]
-    TypeMismatch {
+    #[error("layout mismatch for component: expected stride {expected_stride} bytes (align {expected_align}), but got stride {actual_stride} (align {actual_align})")]
+    LayoutMismatch {
         expected_stride: usize,
         expected_align: usize,
         actual_stride: usize,
         actual_align: usize,
     },
+    #[error(
+        "component {component_id} is registered as `{expected}` but was accessed as `{actual}`"
+    )]
+    TypeMismatch {
+        component_id: ComponentId,
+        expected: &'static str,
+        actual: &'static str,
+    },
 }
 
 #[derive(Debug, Error)]
@@ -278,18 +337,47 @@ pub struct ComponentColumn {
     rows_per_page: usize,
     stride: usize,
     align: usize,
+    page_align: usize,
     shift: u32,
     mask: usize,
     cur_pages: Vec<BytePage>,
     nxt_pages: Vec<BytePage>,
+    /// Empty `(cur, nxt)` page pairs from `reserve`, taken before allocating.
+    spare_pages: Vec<(BytePage, BytePage)>,
+    allocator: Option<Arc<dyn PageAllocator>>,
     len: usize,
+    /// Set by writes that only touch the next buffer, cleared by
+    /// `swap_buffers`. Debug builds use it to catch reads of the current
+    /// buffer that expect to see those writes.
+    #[cfg(debug_assertions)]
+    written_since_swap: bool,
+    #[cfg(debug_assertions)]
+    stale_read_warned: std::sync::atomic::AtomicBool,
 }
 
 impl ComponentColumn {
     pub fn new(plan: ColumnPlan, rows_per_page: usize) -> Self {
+        Self::with_allocator(plan, rows_per_page, None)
+    }
+
+    /// Column whose pages come from `allocator`.
+    pub fn new_in(
+        plan: ColumnPlan,
+        rows_per_page: usize,
+        allocator: Arc<dyn PageAllocator>,
+    ) -> Self {
+        Self::with_allocator(plan, rows_per_page, Some(allocator))
+    }
+
+    fn with_allocator(
+        plan: ColumnPlan,
+        rows_per_page: usize,
+        allocator: Option<Arc<dyn PageAllocator>>,
+    ) -> Self {
         debug_assert!(rows_per_page.is_power_of_two());
         let stride = plan.meta.stride;
         let align = plan.meta.align;
+        let page_align = plan.page_align.max(align);
         let shift = rows_per_page.trailing_zeros();
         let mask = rows_per_page - 1;
         Self {
@@ -297,11 +385,18 @@ impl ComponentColumn {
             rows_per_page,
             stride,
             align,
+            page_align,
             shift,
             mask,
             cur_pages: Vec::new(),
             nxt_pages: Vec::new(),
+            spare_pages: Vec::new(),
+            allocator,
             len: 0,
+            #[cfg(debug_assertions)]
+            written_since_swap: false,
+            #[cfg(debug_assertions)]
+            stale_read_warned: std::sync::atomic::AtomicBool::new(false),
         }
     }
 
@@ -325,6 +420,15 @@ impl ComponentColumn {
         self.align
     }
 
+    /// Guaranteed alignment of the first row of every page, in both buffers.
+    ///
+    /// Rows inside a page are `stride` bytes apart, so a tile starting at
+    /// `page_range(p).start` can be processed with aligned SIMD loads.
+    #[inline]
+    pub fn page_align(&self) -> usize {
+        self.page_align
+    }
+
     #[inline]
     pub fn len(&self) -> usize {
         self.len
@@ -335,6 +439,24 @@ impl ComponentColumn {
         self.cur_pages.len()
     }
 
+    /// Rows that fit without allocating a page, counting reserved pages.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        (self.cur_pages.len() + self.spare_pages.len()) << self.shift
+    }
+
+    /// Allocate pages up front so `additional` more rows fit without
+    /// allocating. Reserved pages are kept until used or `shrink_to_fit`.
+    pub fn reserve(&mut self, additional: usize) {
+        let rows = self.len.saturating_add(additional);
+        let pages = rows.div_ceil(self.rows_per_page);
+        let missing = pages.saturating_sub(self.cur_pages.len() + self.spare_pages.len());
+        self.spare_pages.reserve(missing);
+        for _ in 0..missing {
+            self.spare_pages.push((self.new_page(), self.new_page()));
+        }
+    }
+
     pub fn page_range(&self, page_idx: usize) -> Range<usize> {
         let page = self
             .cur_pages
@@ -346,6 +468,48 @@ impl ComponentColumn {
         start..end
     }
 
+    /// Current-buffer bytes of one page (`page_range(page_idx).len() * stride`
+    /// bytes), suitable for uploading straight to the GPU when the component
+    /// layout matches the instance format.
+    pub fn page_bytes(&self, page_idx: usize) -> &[u8] {
+        let page = self
+            .cur_pages
+            .get(page_idx)
+            .expect("page index out of bounds");
+        page.slice_bytes(0, page.len())
+    }
+
+    /// Pointer to the start of a page's current buffer and its length in
+    /// bytes (live rows only).
+    ///
+    /// Lets a renderer hand column memory to `write_buffer` or a mapped-buffer
+    /// copy without gathering into an intermediate `Vec`. The pointer is
+    /// aligned to `page_align()`. Prefer `page_bytes` where a borrow suffices.
+    ///
+    /// # Safety invariants for the caller
+    ///
+    /// The pointer is only valid while:
+    /// - the column is not mutated: no writes, `alloc_*`, `free_*` or
+    ///   `swap_buffers` (swapping makes it point at the *next* buffer);
+    /// - the page stays alive: `shrink_to_fit`, despawning the page's last
+    ///   rows or dropping the storage frees it.
+    ///
+    /// Reading it from another thread requires the same guarantees. In
+    /// practice: obtain it, copy, and drop it within one render call while
+    /// holding `&World`.
+    pub fn page_ptr(&self, page_idx: usize) -> (*const u8, usize) {
+        let page = self
+            .cur_pages
+            .get(page_idx)
+            .expect("page index out of bounds");
+        (page.ptr.as_ptr() as *const u8, page.len() * self.stride)
+    }
+
+    /// Start of a page's next buffer, for `PageTile` writes.
+    pub(super) fn next_page_ptr(&self, page_idx: usize) -> *mut u8 {
+        self.nxt_pages[page_idx].ptr.as_ptr()
+    }
+
     pub fn alloc_one(&mut self) -> usize {
         let page_idx = self.ensure_page_with_space();
         let local = self.cur_pages[page_idx].alloc_one();
@@ -372,6 +536,15 @@ impl ComponentColumn {
         spans
     }
 
+    /// Zero `range` in both buffers. `range` must lie within one page, as
+    /// every span returned by `alloc_bulk` does.
+    pub fn zero_range(&mut self, range: Range<usize>) -> Result<(), ColumnError> {
+        let (page_idx, local) = self.localize_range(range)?;
+        self.cur_pages[page_idx].zero_rows(local.clone());
+        self.nxt_pages[page_idx].zero_rows(local);
+        Ok(())
+    }
+
     pub fn write_cur_at(&mut self, gidx: usize, bytes: &[u8]) -> Result<(), ColumnError> {
         self.validate_stride(bytes.len())?;
         let (page_idx, local_idx) = self.global_to_local(gidx)?;
@@ -383,12 +556,17 @@ impl ComponentColumn {
         self.validate_stride(bytes.len())?;
         let (page_idx, local_idx) = self.global_to_local(gidx)?;
         self.nxt_pages[page_idx].write_row(local_idx, bytes);
+        self.mark_next_written();
         Ok(())
     }
 
+    /// Write `bytes` to both buffers. Both agree afterwards, so this does not
+    /// count as an unswapped write.
     pub fn write_both_at(&mut self, gidx: usize, bytes: &[u8]) -> Result<(), ColumnError> {
-        self.write_cur_at(gidx, bytes)?;
-        self.write_next_at(gidx, bytes)?;
+        self.validate_stride(bytes.len())?;
+        let (page_idx, local_idx) = self.global_to_local(gidx)?;
+        self.cur_pages[page_idx].write_row(local_idx, bytes);
+        self.nxt_pages[page_idx].write_row(local_idx, bytes);
         Ok(())
     }
 
@@ -406,36 +584,43 @@ impl ComponentColumn {
 
     pub fn slice_write(&mut self, range: Range<usize>) -> Result<&mut [u8], ColumnError> {
         let (page_idx, local) = self.localize_range(range)?;
+        self.mark_next_written();
         Ok(self.nxt_pages[page_idx].slice_bytes_mut(local.start, local.len()))
     }
 
     pub fn slice_rw(&mut self, range: Range<usize>) -> Result<(&[u8], &mut [u8]), ColumnError> {
         let (page_idx, local) = self.localize_range(range)?;
+        self.mark_next_written();
         let read = self.cur_pages[page_idx].slice_bytes(local.start, local.len());
         let write = self.nxt_pages[page_idx].slice_bytes_mut(local.start, local.len());
         Ok((read, write))
     }
 
-    pub fn slice_read_typed<T>(&self, range: Range<usize>) -> Result<&[T], ColumnError> {
+    pub fn slice_read_typed<T: 'static>(&self, range: Range<usize>) -> Result<&[T], ColumnError> {
         self.validate_typed::<T>()?;
         let (page_idx, local) = self.localize_range(range)?;
         let bytes = self.cur_pages[page_idx].slice_bytes(local.start, local.len());
         Ok(Self::cast_bytes::<T>(bytes, local.len()))
     }
 
-    pub fn slice_write_typed<T>(&mut self, range: Range<usize>) -> Result<&mut [T], ColumnError> {
+    pub fn slice_write_typed<T: 'static>(
+        &mut self,
+        range: Range<usize>,
+    ) -> Result<&mut [T], ColumnError> {
         self.validate_typed::<T>()?;
         let (page_idx, local) = self.localize_range(range)?;
+        self.mark_next_written();
         let bytes = self.nxt_pages[page_idx].slice_bytes_mut(local.start, local.len());
         Ok(Self::cast_bytes_mut::<T>(bytes, local.len()))
     }
 
-    pub fn slice_rw_typed<T>(
+    pub fn slice_rw_typed<T: 'static>(
         &mut self,
         range: Range<usize>,
     ) -> Result<(&[T], &mut [T]), ColumnError> {
         self.validate_typed::<T>()?;
         let (page_idx, local) = self.localize_range(range)?;
+        self.mark_next_written();
         let read = self.cur_pages[page_idx].slice_bytes(local.start, local.len());
         let write = self.nxt_pages[page_idx].slice_bytes_mut(local.start, local.len());
         Ok((
@@ -444,16 +629,102 @@ impl ComponentColumn {
         ))
     }
 
-    pub fn column_slice_read<T>(&self) -> Result<&[T], ColumnError> {
+    /// Write `value` into every row of the next buffer, one page at a time.
+    ///
+    /// The current buffer is untouched; the values become visible to readers
+    /// after the next `swap_buffers`.
+    pub fn fill_next_typed<T: Copy + 'static>(&mut self, value: T) -> Result<(), ColumnError> {
+        self.validate_typed::<T>()?;
+        self.mark_next_written();
+        for page in &mut self.nxt_pages {
+            let rows = page.len();
+            let bytes = page.slice_bytes_mut(0, rows);
+            Self::cast_bytes_mut::<T>(bytes, rows).fill(value);
+        }
+        Ok(())
+    }
+
+    /// Append `map(row)` for every row in `range` of the current buffer to `out`.
+    ///
+    /// `range` may span several pages; `out` is reserved once up front and each
+    /// page tile is appended with a single exact-size `extend`.
+    pub fn copy_typed_into<T: 'static, U>(
+        &self,
+        range: Range<usize>,
+        out: &mut Vec<U>,
+        mut map: impl FnMut(&T) -> U,
+    ) -> Result<(), ColumnError> {
+        self.validate_typed::<T>()?;
+        if range.start > range.end || range.end > self.len {
+            return Err(ColumnError::RangeOutOfBounds {
+                start: range.start,
+                end: range.end,
+                len: self.len,
+            });
+        }
+        out.reserve(range.len());
+        let mut start = range.start;
+        while start < range.end {
+            let tile = self.clamp_to_page(start, range.end - start);
+            let values = self.slice_read_typed::<T>(tile.clone())?;
+            out.extend(values.iter().map(&mut map));
+            start = tile.end;
+        }
+        Ok(())
+    }
+
+    pub fn column_slice_read<T: 'static>(&self) -> Result<&[T], ColumnError> {
         self.slice_read_typed::<T>(0..self.len)
     }
 
-    pub fn column_slice_write<T>(&mut self) -> Result<&mut [T], ColumnError> {
+    pub fn column_slice_write<T: 'static>(&mut self) -> Result<&mut [T], ColumnError> {
         self.slice_write_typed::<T>(0..self.len)
     }
 
     pub fn swap_buffers(&mut self) {
         std::mem::swap(&mut self.cur_pages, &mut self.nxt_pages);
+        #[cfg(debug_assertions)]
+        {
+            self.written_since_swap = false;
+            *self.stale_read_warned.get_mut() = false;
+        }
+    }
+
+    /// Whether the next buffer was written through a next-only write
+    /// (`slice_write*`, `slice_rw*`, `write_next_at`, `fill_next_typed`)
+    /// since the last `swap_buffers`. Always `false` in release builds.
+    #[inline]
+    pub fn has_unswapped_writes(&self) -> bool {
+        #[cfg(debug_assertions)]
+        {
+            self.written_since_swap
+        }
+        #[cfg(not(debug_assertions))]
+        {
+            false
+        }
+    }
+
+    #[inline]
+    pub(super) fn mark_next_written(&mut self) {
+        #[cfg(debug_assertions)]
+        {
+            self.written_since_swap = true;
+        }
+    }
+
+    /// Warn (once per swap) when the current buffer is read in full while
+    /// the next buffer holds writes that have not been published yet.
+    #[cfg(debug_assertions)]
+    fn warn_if_unswapped(&self) {
+        use std::sync::atomic::Ordering;
+        if self.written_since_swap && !self.stale_read_warned.swap(true, Ordering::Relaxed) {
+            tracing::warn!(
+                component = self.plan.meta.name,
+                component_id = self.plan.meta.id,
+                "column read before swap_buffers: writes to the next buffer are not visible yet"
+            );
+        }
     }
 
     pub fn free_one_swap_remove(
@@ -509,6 +780,14 @@ impl ComponentColumn {
         Ok(moves)
     }
 
+    /// Release spare page-table capacity left behind by earlier growth, and
+    /// any pages still held by `reserve`.
+    pub fn shrink_to_fit(&mut self) {
+        self.spare_pages = Vec::new();
+        self.cur_pages.shrink_to_fit();
+        self.nxt_pages.shrink_to_fit();
+    }
+
     #[inline]
     pub fn clamp_to_page(&self, start: usize, nominal_len: usize) -> Range<usize> {
         let page_end = self.end_of_page(start).min(self.len);
@@ -523,33 +802,55 @@ impl ComponentColumn {
             .map(|page| page.is_full())
             .unwrap_or(true)
         {
-            self.cur_pages.push(BytePage::with_capacity(
-                self.rows_per_page,
-                self.stride,
-                self.align,
-            ));
-            self.nxt_pages.push(BytePage::with_capacity(
-                self.rows_per_page,
-                self.stride,
-                self.align,
-            ));
+            let (cur, nxt) = match self.spare_pages.pop() {
+                Some(pair) => pair,
+                None => (self.new_page(), self.new_page()),
+            };
+            self.cur_pages.push(cur);
+            self.nxt_pages.push(nxt);
         }
         self.cur_pages.len() - 1
     }
 
-    fn validate_typed<T>(&self) -> Result<(), ColumnError> {
+    fn new_page(&self) -> BytePage {
+        BytePage::with_capacity(
+            self.rows_per_page,
+            self.stride,
+            self.page_align,
+            self.allocator.clone(),
+        )
+    }
+
+    /// Check that `T` may view this column.
+    ///
+    /// Size and alignment must match. In debug builds `T` must also not be a
+    /// *different* registered component: plain views such as `[i32; 2]` over
+    /// a position column are fine, but reading a `Velocity` column as
+    /// `Position` (same layout, swapped ids) is rejected.
+    pub(super) fn validate_typed<T: 'static>(&self) -> Result<(), ColumnError> {
         let expected_stride = self.stride;
         let expected_align = self.align;
         let actual_stride = mem::size_of::<T>();
         let actual_align = mem::align_of::<T>();
         if actual_stride != expected_stride || actual_align != expected_align {
-            return Err(ColumnError::TypeMismatch {
+            return Err(ColumnError::LayoutMismatch {
                 expected_stride,
                 expected_align,
                 actual_stride,
                 actual_align,
             });
         }
+        #[cfg(debug_assertions)]
+        if let Some(expected) = self.plan.meta.rust_type {
+            let actual = std::any::TypeId::of::<T>();
+            if actual != expected.id && crate::ecs::component_of_type(actual).is_some() {
+                return Err(ColumnError::TypeMismatch {
+                    component_id: self.plan.meta.id,
+                    expected: expected.name,
+                    actual: std::any::type_name::<T>(),
+                });
+            }
+        }
         Ok(())
     }
 
@@ -679,12 +980,24 @@ pub struct ArchetypeStorage {
 
 impl ArchetypeStorage {
     pub fn from_plan(plan: ArchetypePlan) -> Self {
+        Self::with_allocator(plan, None)
+    }
+
+    /// Storage whose column pages come from `allocator`. Entity ids stay on
+    /// the global allocator.
+    pub fn from_plan_in(plan: ArchetypePlan, allocator: Arc<dyn PageAllocator>) -> Self {
+        Self::with_allocator(plan, Some(allocator))
+    }
+
+    fn with_allocator(plan: ArchetypePlan, allocator: Option<Arc<dyn PageAllocator>>) -> Self {
         let rows_per_page = plan.rows_per_page.get();
         let columns: Vec<ComponentColumn> = plan
             .columns
             .iter()
             .cloned()
-            .map(|col_plan| ComponentColumn::new(col_plan, rows_per_page))
+            .map(|col_plan| {
+                ComponentColumn::with_allocator(col_plan, rows_per_page, allocator.clone())
+            })
             .collect();
         let index_by_component = columns
             .iter()
@@ -721,10 +1034,46 @@ impl ArchetypeStorage {
         self.plan.rows_per_page.get()
     }
 
+    /// Pages allocated per column buffer.
+    pub fn page_count(&self) -> usize {
+        self.columns
+            .iter()
+            .map(ComponentColumn::page_count)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Rows that fit without allocating a page; see `reserve`.
+    pub fn capacity(&self) -> usize {
+        self.columns
+            .iter()
+            .map(ComponentColumn::capacity)
+            .chain(std::iter::once(self.entity_ids.capacity()))
+            .min()
+            .unwrap_or(0)
+    }
+
+    /// Allocate pages in every column (and for entity ids) so `additional`
+    /// more rows can be spawned without allocating.
+    pub fn reserve(&mut self, additional: usize) {
+        self.entity_ids.reserve(additional);
+        for column in &mut self.columns {
+            column.reserve(additional);
+        }
+    }
+
+    /// Release spare page-table capacity and reserved pages in every column.
+    pub fn shrink_to_fit(&mut self) {
+        self.entity_ids.shrink_to_fit();
+        for column in &mut self.columns {
+            column.shrink_to_fit();
+        }
+    }
+
     pub fn entity_id_at(&self, gidx: usize) -> Result<EntityId, StorageError> {
         self.entity_ids
             .get(gidx)
-            .map(|id| *id)
+            .copied()
             .map_err(StorageError::EntityPool)
     }
 
@@ -757,6 +1106,98 @@ impl ArchetypeStorage {
         Ok(&mut self.columns[idx])
     }
 
+    /// Run `f` on every page of the archetype in parallel on the rayon pool.
+    ///
+    /// This is the parallel granularity for worlds dominated by one huge
+    /// archetype, where `World::par_for_each_read` has nothing to split.
+    /// Each `PageTile` exposes the columns in `component_ids` for one page:
+    /// reads see the current buffer, writes go to the next. Pages are
+    /// disjoint and a tile only reaches its own rows, so the result does not
+    /// depend on scheduling. Every listed column counts as written for the
+    /// double-buffer guard (`has_unswapped_writes`).
+    pub fn par_pages_mut(
+        &mut self,
+        component_ids: &[ComponentId],
+        f: impl Fn(PageTile<'_>) + Sync,
+    ) -> Result<(), StorageError> {
+        let indices = self.tile_column_indices(component_ids)?;
+        let columns: Vec<&ComponentColumn> =
+            indices.iter().map(|&idx| &self.columns[idx]).collect();
+        self.page_tiles(component_ids, &columns)?
+            .into_par_iter()
+            .for_each(&f);
+        Ok(())
+    }
+
+    /// Sequential counterpart of `par_pages_mut`: `f` sees every page in
+    /// row order on the calling thread, so it may capture mutable state.
+    pub fn pages_mut(
+        &mut self,
+        component_ids: &[ComponentId],
+        mut f: impl FnMut(PageTile<'_>),
+    ) -> Result<(), StorageError> {
+        let indices = self.tile_column_indices(component_ids)?;
+        let columns: Vec<&ComponentColumn> =
+            indices.iter().map(|&idx| &self.columns[idx]).collect();
+        for tile in self.page_tiles(component_ids, &columns)? {
+            f(tile);
+        }
+        Ok(())
+    }
+
+    /// Column indices for a tile request, with every column marked written.
+    /// Empty when there is nothing to visit.
+    fn tile_column_indices(
+        &mut self,
+        component_ids: &[ComponentId],
+    ) -> Result<Vec<usize>, StorageError> {
+        let mut indices = Vec::with_capacity(component_ids.len());
+        for &component_id in component_ids {
+            let idx = self
+                .index_by_component
+                .get(&component_id)
+                .copied()
+                .ok_or(StorageError::ColumnMissing { component_id })?;
+            if indices.contains(&idx) {
+                return Err(StorageError::DuplicateColumnRequest { component_id });
+            }
+            indices.push(idx);
+        }
+        if self.is_empty() {
+            indices.clear();
+        }
+        for &idx in &indices {
+            self.columns[idx].mark_next_written();
+        }
+        Ok(indices)
+    }
+
+    fn page_tiles<'a>(
+        &'a self,
+        component_ids: &'a [ComponentId],
+        columns: &'a [&'a ComponentColumn],
+    ) -> Result<Vec<PageTile<'a>>, StorageError> {
+        let Some(first) = columns.first() else {
+            return Ok(Vec::new());
+        };
+        let mut tiles = Vec::with_capacity(first.page_count());
+        for page_idx in 0..first.page_count() {
+            let range = first.page_range(page_idx);
+            if range.is_empty() {
+                continue;
+            }
+            let entity_ids = self.entity_ids_slice(range.clone())?;
+            tiles.push(PageTile::new(
+                page_idx,
+                range,
+                entity_ids,
+                component_ids,
+                columns,
+            ));
+        }
+        Ok(tiles)
+    }
+
     pub fn columns_mut_pair(
         &mut self,
         a: ComponentId,
@@ -842,6 +1283,8 @@ impl ArchetypeStorage {
     pub fn column_slice<T: Component>(&self) -> Result<&[T], StorageError> {
         let component_id = <T as Component>::id();
         let column = self.column(component_id)?;
+        #[cfg(debug_assertions)]
+        column.warn_if_unswapped();
         column.column_slice_read::<T>().map_err(StorageError::from)
     }
 
@@ -851,6 +1294,206 @@ impl ArchetypeStorage {
         column.column_slice_write::<T>().map_err(StorageError::from)
     }
 
+    /// Set component `T` of every row to `value` in the next buffer.
+    ///
+    /// Much faster than writing rows from a `for_each` closure for resets
+    /// such as zeroing velocities. Call `swap_buffers` before reading the
+    /// result back from the current buffer.
+    pub fn fill_column<T: Component + Copy>(&mut self, value: T) -> Result<(), StorageError> {
+        let column = self.column_mut(<T as Component>::id())?;
+        column.fill_next_typed(value).map_err(StorageError::from)
+    }
+
+    /// Append one instance per row built from component `T` (current buffer).
+    pub fn gather_instances<T: Component, U>(
+        &self,
+        out: &mut Vec<U>,
+        map: impl FnMut(&T) -> U,
+    ) -> Result<(), StorageError> {
+        let column = self.column(<T as Component>::id())?;
+        column
+            .copy_typed_into::<T, U>(0..self.len, out, map)
+            .map_err(StorageError::from)
+    }
+
+    /// Append one instance per row built from components `A` and `B` (current buffer).
+    pub fn gather_instances2<A: Component, B: Component, U>(
+        &self,
+        out: &mut Vec<U>,
+        map: impl FnMut(&A, &B) -> U,
+    ) -> Result<(), StorageError> {
+        let column = self.column(<A as Component>::id())?;
+        let spans: Vec<Range<usize>> = (0..column.page_count())
+            .map(|page_idx| column.page_range(page_idx))
+            .collect();
+        out.reserve(self.len);
+        self.gather2_spans(&spans, out, map)
+    }
+
+    /// Collect runs of rows whose position lies inside `bounds`.
+    ///
+    /// Positions are read from `P` in the current buffer via `position_of`.
+    /// Each page is first rejected or accepted wholesale by its min/max box,
+    /// and only straddling pages are tested row by row. Runs never cross a
+    /// page, so every span can be passed to `slice_read_typed` on any column.
+    pub fn visible_spans<P: Component>(
+        &self,
+        bounds: CullBounds,
+        position_of: impl Fn(&P) -> [i32; 2],
+        spans: &mut Vec<Range<usize>>,
+    ) -> Result<CullStats, StorageError> {
+        let column = self.column(<P as Component>::id())?;
+        let mut stats = CullStats {
+            total: self.len,
+            visible: 0,
+        };
+        for page_idx in 0..column.page_count() {
+            let range = column.page_range(page_idx);
+            let positions = column.slice_read_typed::<P>(range.clone())?;
+            if positions.is_empty() {
+                continue;
+            }
+
+            let mut min = [i32::MAX; 2];
+            let mut max = [i32::MIN; 2];
+            for position in positions {
+                let [x, y] = position_of(position);
+                min = [min[0].min(x), min[1].min(y)];
+                max = [max[0].max(x), max[1].max(y)];
+            }
+            if !bounds.overlaps_box(min, max) {
+                continue;
+            }
+            if bounds.contains_box(min, max) {
+                stats.visible += range.len();
+                spans.push(range);
+                continue;
+            }
+
+            let mut run_start = None;
+            for (offset, position) in positions.iter().enumerate() {
+                let row = range.start + offset;
+                if bounds.contains(position_of(position)) {
+                    stats.visible += 1;
+                    run_start.get_or_insert(row);
+                } else if let Some(start) = run_start.take() {
+                    spans.push(start..row);
+                }
+            }
+            if let Some(start) = run_start {
+                spans.push(start..range.end);
+            }
+        }
+        Ok(stats)
+    }
+
+    /// Like `gather_instances`, but only for rows whose position is inside `bounds`.
+    pub fn gather_instances_culled<P: Component, U>(
+        &self,
+        out: &mut Vec<U>,
+        bounds: CullBounds,
+        position_of: impl Fn(&P) -> [i32; 2],
+        mut map: impl FnMut(&P) -> U,
+    ) -> Result<CullStats, StorageError> {
+        let mut spans = Vec::new();
+        let stats = self.visible_spans::<P>(bounds, position_of, &mut spans)?;
+        let column = self.column(<P as Component>::id())?;
+        out.reserve(stats.visible);
+        for span in spans {
+            column.copy_typed_into::<P, U>(span, out, &mut map)?;
+        }
+        Ok(stats)
+    }
+
+    /// Like `gather_instances2`, culling by the position stored in `A`.
+    pub fn gather_instances2_culled<A: Component, B: Component, U>(
+        &self,
+        out: &mut Vec<U>,
+        bounds: CullBounds,
+        position_of: impl Fn(&A) -> [i32; 2],
+        map: impl FnMut(&A, &B) -> U,
+    ) -> Result<CullStats, StorageError> {
+        let mut spans = Vec::new();
+        let stats = self.visible_spans::<A>(bounds, position_of, &mut spans)?;
+        out.reserve(stats.visible);
+        self.gather2_spans(&spans, out, map)?;
+        Ok(stats)
+    }
+
+    /// Like `gather_instances2`, also appending each row's `RenderLayer` to
+    /// `layers` so the combined output can go through `sort_instances_by_layer`.
+    pub fn gather_instances2_layered<A: Component, B: Component, U>(
+        &self,
+        out: &mut Vec<U>,
+        layers: &mut Vec<RenderLayer>,
+        map: impl FnMut(&A, &B) -> U,
+    ) -> Result<(), StorageError> {
+        let column = self.column(<A as Component>::id())?;
+        let spans: Vec<Range<usize>> = (0..column.page_count())
+            .map(|page_idx| column.page_range(page_idx))
+            .collect();
+        out.reserve(self.len);
+        self.gather2_spans(&spans, out, map)?;
+        self.render_layers_for_spans(&spans, layers)
+    }
+
+    /// Like `gather_instances2_culled`, also appending each visible row's
+    /// `RenderLayer` to `layers`.
+    pub fn gather_instances2_culled_layered<A: Component, B: Component, U>(
+        &self,
+        out: &mut Vec<U>,
+        layers: &mut Vec<RenderLayer>,
+        bounds: CullBounds,
+        position_of: impl Fn(&A) -> [i32; 2],
+        map: impl FnMut(&A, &B) -> U,
+    ) -> Result<CullStats, StorageError> {
+        let mut spans = Vec::new();
+        let stats = self.visible_spans::<A>(bounds, position_of, &mut spans)?;
+        out.reserve(stats.visible);
+        self.gather2_spans(&spans, out, map)?;
+        self.render_layers_for_spans(&spans, layers)?;
+        Ok(stats)
+    }
+
+    /// Layers for `spans`; archetypes without the component use the default.
+    fn render_layers_for_spans(
+        &self,
+        spans: &[Range<usize>],
+        layers: &mut Vec<RenderLayer>,
+    ) -> Result<(), StorageError> {
+        let rows: usize = spans.iter().map(Range::len).sum();
+        layers.reserve(rows);
+        match self.index_by_component.get(&RenderLayer::component_id()) {
+            Some(&idx) => {
+                for span in spans {
+                    self.columns[idx].copy_typed_into::<RenderLayer, RenderLayer>(
+                        span.clone(),
+                        layers,
+                        |layer| *layer,
+                    )?;
+                }
+            }
+            None => layers.resize(layers.len() + rows, RenderLayer::DEFAULT),
+        }
+        Ok(())
+    }
+
+    fn gather2_spans<A: Component, B: Component, U>(
+        &self,
+        spans: &[Range<usize>],
+        out: &mut Vec<U>,
+        mut map: impl FnMut(&A, &B) -> U,
+    ) -> Result<(), StorageError> {
+        let column_a = self.column(<A as Component>::id())?;
+        let column_b = self.column(<B as Component>::id())?;
+        for span in spans {
+            let a = column_a.slice_read_typed::<A>(span.clone())?;
+            let b = column_b.slice_read_typed::<B>(span.clone())?;
+            out.extend(a.iter().zip(b).map(|(a, b)| map(a, b)));
+        }
+        Ok(())
+    }
+
     pub fn alloc_row(&mut self, entity_id: EntityId) -> Result<usize, StorageError> {
         let gidx = self.entity_ids.alloc_one();
         for column in &mut self.columns {
@@ -863,10 +1506,24 @@ impl ArchetypeStorage {
         Ok(gidx)
     }
 
+    /// Allocate `count` zero-initialized rows; see `alloc_bulk_with`.
     pub fn alloc_bulk(
+        &mut self,
+        count: usize,
+        entities: impl Iterator<Item = EntityId>,
+    ) -> Result<Vec<Range<usize>>, StorageError> {
+        self.alloc_bulk_with(count, entities, RowInit::Zeroed)
+    }
+
+    /// Allocate `count` rows for `entities`, returning one span per page.
+    ///
+    /// `RowInit::Uninit` skips clearing column bytes and is only sound when
+    /// the caller writes every column of every returned row.
+    pub fn alloc_bulk_with(
         &mut self,
         count: usize,
         mut entities: impl Iterator<Item = EntityId>,
+        init: RowInit,
     ) -> Result<Vec<Range<usize>>, StorageError> {
         let spans = self.entity_ids.alloc_bulk(count);
         let mut written = 0usize;
@@ -886,6 +1543,11 @@ impl ArchetypeStorage {
         for column in &mut self.columns {
             let column_spans = column.alloc_bulk(count);
             debug_assert_eq!(spans, column_spans, "column bulk allocation mismatch");
+            if init == RowInit::Zeroed {
+                for span in &column_spans {
+                    column.zero_range(span.clone())?;
+                }
+            }
         }
         self.len += count;
         debug_assert_eq!(self.len, self.entity_ids.len_total());
@@ -924,6 +1586,42 @@ impl ArchetypeStorage {
         }
     }
 
+    /// Hash of every row's entity id followed by the current buffer of every
+    /// column, rows in storage order.
+    ///
+    /// Stable across hosts (see `World::state_hash`), so a replication peer
+    /// can compare it against the sender's value before applying a delta.
+    /// Writes to the next buffer are not included.
+    pub fn checksum(&self) -> u64 {
+        let mut hasher = StableHasher::new();
+        let rows_per_page = self.rows_per_page();
+        for start in (0..self.len).step_by(rows_per_page) {
+            let range = start..(start + rows_per_page).min(self.len);
+            let entity_ids = self
+                .entity_ids_slice(range)
+                .expect("page range within entity_count");
+            for &entity_id in entity_ids {
+                hasher.write_u32(entity_id);
+            }
+        }
+        for column in &self.columns {
+            hasher.write_u32(column.plan.component_id);
+            for page_idx in 0..column.page_count() {
+                hasher.write(column.page_bytes(page_idx));
+            }
+        }
+        hasher.finish()
+    }
+
+    /// Components whose next buffer was written since the last swap.
+    /// Always empty in release builds.
+    pub fn unswapped_components(&self) -> impl Iterator<Item = ComponentId> + '_ {
+        self.columns
+            .iter()
+            .filter(|column| column.has_unswapped_writes())
+            .map(|column| column.plan().meta.id)
+    }
+
     pub fn free_one_swap_remove(
         &mut self,
         gidx: usize,
@@ -935,13 +1633,13 @@ impl ArchetypeStorage {
                 len: self.len,
             });
         }
-        let mut moved = None;
-        self.entity_ids
-            .free_one_swap_remove(gidx, |from, to| moved = Some((from, to)))
+        let mut moved = self
+            .entity_ids
+            .free_one_swap_remove_dense(gidx)
             .map_err(StorageError::EntityPool)?;
         for column in &mut self.columns {
             if let Some((from, to)) = column.free_one_swap_remove(gidx)? {
-                debug_assert!(moved.map_or(true, |m| m == (from, to)));
+                debug_assert!(moved.is_none_or(|m| m == (from, to)));
                 moved = Some((from, to));
             }
         }
@@ -963,7 +1661,7 @@ impl ArchetypeStorage {
         }
         let mut moved = Vec::new();
         self.entity_ids
-            .free_bulk_swap_remove(gidxs.clone(), |from, to| moved.push((from, to)))
+            .free_bulk_swap_remove_dense(gidxs.clone(), |from, to| moved.push((from, to)))
             .map_err(StorageError::EntityPool)?;
         for column in &mut self.columns {
             let mut column_moves = column.free_bulk_swap_remove(gidxs.clone())?;
@@ -1004,6 +1702,15 @@ impl ArchetypeStorage {
             .map(|&idx| &mut self.columns[idx] as *mut ComponentColumn)
     }
 
+    /// Current-buffer slice of a single-page column.
+    ///
+    /// # Panics
+    /// If the column spans more than one page or `T` does not match its
+    /// layout. `columns!` no longer uses this; prefer
+    /// `try_column_ptr_to_slice_const`.
+    ///
+    /// # Safety
+    /// As for `try_column_ptr_to_slice_const`.
     pub unsafe fn column_ptr_to_slice_const<'a, T: Component>(
         column_ptr: *const ComponentColumn,
         _buffer_index: usize,
@@ -1016,11 +1723,48 @@ impl ArchetypeStorage {
                 column.page_count()
             );
         }
+        #[cfg(debug_assertions)]
+        column.warn_if_unswapped();
         column
             .column_slice_read::<T>()
             .unwrap_or_else(|err| panic!("failed to borrow column for read: {err}"))
     }
 
+    /// Fallible form of `column_ptr_to_slice_const` used by `try_columns!`.
+    ///
+    /// # Safety
+    /// `column_ptr` must come from `get_column_ptr_const` on a storage that
+    /// outlives `'a` and is not mutated while the slice is alive.
+    pub unsafe fn try_column_ptr_to_slice_const<'a, T: Component>(
+        column_ptr: *const ComponentColumn,
+    ) -> Result<&'a [T], StorageError> {
+        let column = &*column_ptr;
+        #[cfg(debug_assertions)]
+        column.warn_if_unswapped();
+        column.column_slice_read::<T>().map_err(StorageError::from)
+    }
+
+    /// Fallible form of `column_ptr_to_slice` used by `try_columns_mut!`.
+    ///
+    /// # Safety
+    /// `column_ptr` must come from `get_column_ptr` on a storage that
+    /// outlives `'a`, and no other reference to the same column may be live.
+    pub unsafe fn try_column_ptr_to_slice<'a, T: Component>(
+        column_ptr: *mut ComponentColumn,
+    ) -> Result<&'a mut [T], StorageError> {
+        let column = &mut *column_ptr;
+        column.column_slice_write::<T>().map_err(StorageError::from)
+    }
+
+    /// Next-buffer slice of a single-page column.
+    ///
+    /// # Panics
+    /// If the column spans more than one page or `T` does not match its
+    /// layout. `columns_mut!` no longer uses this; prefer
+    /// `try_column_ptr_to_slice`.
+    ///
+    /// # Safety
+    /// As for `try_column_ptr_to_slice`.
     pub unsafe fn column_ptr_to_slice<'a, T: Component>(
         column_ptr: *mut ComponentColumn,
         _buffer_index: usize,
@@ -1038,3 +1782,22 @@ impl ArchetypeStorage {
             .unwrap_or_else(|err| panic!("failed to borrow column for write: {err}"))
     }
 }
+
+/// Panic raised by `columns!` and `columns_mut!` when a component cannot be
+/// borrowed, naming the archetype and component. `try_columns!` and
+/// `try_columns_mut!` return the error instead.
+#[doc(hidden)]
+#[cold]
+#[track_caller]
+pub fn __column_access_failed(
+    macro_name: &str,
+    archetype: ArchetypeId,
+    component_id: ComponentId,
+    err: StorageError,
+) -> ! {
+    let name = meta_of(component_id).map_or_else(|| "<unregistered>".into(), |meta| meta.name);
+    panic!(
+        "{macro_name}: cannot borrow component '{name}' (id {component_id}) in archetype \
+         {archetype:#018x}: {err}"
+    )
+}
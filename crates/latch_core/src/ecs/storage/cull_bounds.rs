@@ -0,0 +1,57 @@
+/// Axis-aligned world-space rectangle used to cull rows during instance gather.
+///
+/// Coordinates are integer game units (the same units stored in position
+/// components), and both `min` and `max` are inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CullBounds {
+    pub min: [i32; 2],
+    pub max: [i32; 2],
+}
+
+impl CullBounds {
+    #[inline]
+    pub const fn new(min: [i32; 2], max: [i32; 2]) -> Self {
+        Self { min, max }
+    }
+
+    /// Grow (or shrink, for negative `margin`) the bounds on every side.
+    ///
+    /// Used to keep partially visible sprites whose anchor lies just outside
+    /// the view.
+    #[inline]
+    pub fn expanded(self, margin: i32) -> Self {
+        Self {
+            min: [
+                self.min[0].saturating_sub(margin),
+                self.min[1].saturating_sub(margin),
+            ],
+            max: [
+                self.max[0].saturating_add(margin),
+                self.max[1].saturating_add(margin),
+            ],
+        }
+    }
+
+    #[inline]
+    pub fn contains(&self, point: [i32; 2]) -> bool {
+        point[0] >= self.min[0]
+            && point[0] <= self.max[0]
+            && point[1] >= self.min[1]
+            && point[1] <= self.max[1]
+    }
+
+    /// True when the rectangle `[min, max]` lies entirely inside these bounds.
+    #[inline]
+    pub fn contains_box(&self, min: [i32; 2], max: [i32; 2]) -> bool {
+        self.contains(min) && self.contains(max)
+    }
+
+    /// True when the rectangle `[min, max]` shares at least one point with these bounds.
+    #[inline]
+    pub fn overlaps_box(&self, min: [i32; 2], max: [i32; 2]) -> bool {
+        min[0] <= self.max[0]
+            && max[0] >= self.min[0]
+            && min[1] <= self.max[1]
+            && max[1] >= self.min[1]
+    }
+}
@@ -0,0 +1,21 @@
+/// Row counts produced by a culled gather, for renderer metrics.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CullStats {
+    /// Rows inspected.
+    pub total: usize,
+    /// Rows that passed the bounds test.
+    pub visible: usize,
+}
+
+impl CullStats {
+    #[inline]
+    pub fn culled(&self) -> usize {
+        self.total - self.visible
+    }
+
+    #[inline]
+    pub fn accumulate(&mut self, other: CullStats) {
+        self.total += other.total;
+        self.visible += other.visible;
+    }
+}
@@ -29,6 +29,9 @@
 //
 // // Write multiple (next buffer)
 // let (pos_out, vel_out) = columns_mut!(storage, Position, Velocity);
+//
+// // Fallible forms for systems that propagate errors
+// let (pos, vel) = try_columns!(storage, Position, Velocity)?;
 // ```
 
 /// Macro to get multiple immutable component slices from a storage.
@@ -36,6 +39,11 @@
 /// Reads from the "current" buffer (stable state from last tick).
 /// Handles any number of components using compile-time validation.
 ///
+/// # Panics
+/// If a component is missing from the archetype or its column spans more
+/// than one page; the message names the archetype and component. Servers
+/// that must not abort should use `try_columns!`.
+///
 /// # Example
 /// ```ignore
 /// let positions = columns!(storage, Position);
@@ -45,21 +53,38 @@
 #[macro_export]
 macro_rules! columns {
     // Single component - just call the method directly
-    ($storage:expr, $T:ty) => {
-        $storage.column_as_slice::<$T>().unwrap()
-    };
+    ($storage:expr, $T:ty) => {{
+        let archetype = $storage.plan().layout.id();
+        match $storage.column_slice::<$T>() {
+            Ok(slice) => slice,
+            Err(err) => $crate::ecs::storage::__column_access_failed(
+                "columns!",
+                archetype,
+                <$T as $crate::ecs::Component>::id(),
+                err,
+            ),
+        }
+    }};
 
     // Multiple components - use the general implementation
     ($storage:expr, $($T:ty),+ $(,)?) => {{
-        // Get the current buffer index (what we read from)
-        let current_buffer = $storage.current_buffer_index();
+        let archetype = $storage.plan().layout.id();
 
         // Get raw pointers to each column
-        let ptrs = [$(unsafe {
-            $storage
-                .get_column_ptr_const(<$T as $crate::ecs::Component>::id())
-                .expect("Component not found in archetype")
-        }),+];
+        let ptrs = [$(
+            match $storage.get_column_ptr_const(<$T as $crate::ecs::Component>::id()) {
+                Some(ptr) => ptr,
+                None => {
+                    let component_id = <$T as $crate::ecs::Component>::id();
+                    $crate::ecs::storage::__column_access_failed(
+                        "columns!",
+                        archetype,
+                        component_id,
+                        $crate::ecs::StorageError::ColumnMissing { component_id },
+                    )
+                }
+            }
+        ),+];
 
         // SAFETY: Each column is independently readable, and the macro ensures
         // component types are distinct at compile time.
@@ -67,9 +92,17 @@ macro_rules! columns {
             let mut idx = 0;
             ($(
                 {
-                    let slice = $crate::ecs::ArchetypeStorage::column_ptr_to_slice_const::<$T>(ptrs[idx], current_buffer);
+                    let ptr = ptrs[idx];
                     idx += 1;
-                    slice
+                    match $crate::ecs::ArchetypeStorage::try_column_ptr_to_slice_const::<$T>(ptr) {
+                        Ok(slice) => slice,
+                        Err(err) => $crate::ecs::storage::__column_access_failed(
+                            "columns!",
+                            archetype,
+                            <$T as $crate::ecs::Component>::id(),
+                            err,
+                        ),
+                    }
                 }
             ),+)
         }
@@ -81,6 +114,12 @@ macro_rules! columns {
 /// Writes to the "next" buffer (the one not currently being read from).
 /// Handles any number of components using compile-time validation.
 ///
+/// # Panics
+/// Under the same conditions as `columns!` (use `try_columns_mut!` to get
+/// the error instead). Naming the same component twice also panics: two
+/// `&mut` slices of one column would alias, a programmer error that
+/// `try_columns_mut!` reports as `DuplicateColumnRequest`.
+///
 /// # Example
 /// ```ignore
 /// let positions = columns_mut!(storage, Position);
@@ -91,43 +130,170 @@ macro_rules! columns {
 #[macro_export]
 macro_rules! columns_mut {
     // Single component - just call the method directly
-    ($storage:expr, $T:ty) => {
-        $storage.column_as_slice_mut::<$T>().unwrap()
-    };
+    ($storage:expr, $T:ty) => {{
+        let archetype = $storage.plan().layout.id();
+        match $storage.column_slice_mut::<$T>() {
+            Ok(slice) => slice,
+            Err(err) => $crate::ecs::storage::__column_access_failed(
+                "columns_mut!",
+                archetype,
+                <$T as $crate::ecs::Component>::id(),
+                err,
+            ),
+        }
+    }};
 
     // Multiple components - use the general implementation
     ($storage:expr, $($T:ty),+ $(,)?) => {{
-        // Collect component IDs and verify uniqueness at compile time
-    let ids = [$(<$T as $crate::ecs::Component>::id()),+];
-
-        // Get the next buffer index (what we write to)
-        let next_buffer = $storage.next_buffer_index();
-
-        // Get raw pointers to each column
-        let ptrs = [$(unsafe {
-            $storage
-                .get_column_ptr(<$T as $crate::ecs::Component>::id())
-                .expect("Component not found in archetype")
-        }),+];
+        let archetype = $storage.plan().layout.id();
 
         // Runtime verification that all IDs are unique
+        let ids = [$(<$T as $crate::ecs::Component>::id()),+];
         for i in 0..ids.len() {
-            for j in (i+1)..ids.len() {
-                assert_ne!(ids[i], ids[j], "Cannot get multiple mutable references to the same component");
+            for j in (i + 1)..ids.len() {
+                if ids[i] == ids[j] {
+                    $crate::ecs::storage::__column_access_failed(
+                        "columns_mut!",
+                        archetype,
+                        ids[i],
+                        $crate::ecs::StorageError::DuplicateColumnRequest { component_id: ids[i] },
+                    );
+                }
             }
         }
 
+        // Get raw pointers to each column
+        let ptrs = [$(
+            match $storage.get_column_ptr(<$T as $crate::ecs::Component>::id()) {
+                Some(ptr) => ptr,
+                None => {
+                    let component_id = <$T as $crate::ecs::Component>::id();
+                    $crate::ecs::storage::__column_access_failed(
+                        "columns_mut!",
+                        archetype,
+                        component_id,
+                        $crate::ecs::StorageError::ColumnMissing { component_id },
+                    )
+                }
+            }
+        ),+];
+
         // SAFETY: We've verified all component IDs are unique, so these point to different columns.
         // Each column is independently mutable.
         unsafe {
             let mut idx = 0;
             ($(
                 {
-                    let slice = $crate::ecs::ArchetypeStorage::column_ptr_to_slice::<$T>(ptrs[idx], next_buffer);
+                    let ptr = ptrs[idx];
                     idx += 1;
-                    slice
+                    match $crate::ecs::ArchetypeStorage::try_column_ptr_to_slice::<$T>(ptr) {
+                        Ok(slice) => slice,
+                        Err(err) => $crate::ecs::storage::__column_access_failed(
+                            "columns_mut!",
+                            archetype,
+                            <$T as $crate::ecs::Component>::id(),
+                            err,
+                        ),
+                    }
                 }
             ),+)
         }
     }};
 }
+
+/// Fallible form of `columns!`, yielding `Result<(...), StorageError>`.
+///
+/// A component missing from the archetype or a column spanning several
+/// pages becomes an error the caller can propagate instead of a panic.
+///
+/// # Example
+/// ```ignore
+/// let (positions, velocities) = try_columns!(storage, Position, Velocity)?;
+/// ```
+#[macro_export]
+macro_rules! try_columns {
+    ($storage:expr, $T:ty) => {
+        $storage.column_slice::<$T>()
+    };
+
+    ($storage:expr, $($T:ty),+ $(,)?) => {
+        'try_columns: {
+            let ids = [$(<$T as $crate::ecs::Component>::id()),+];
+            let ptrs = [$($storage.get_column_ptr_const(<$T as $crate::ecs::Component>::id())),+];
+            for (ptr, &component_id) in ptrs.iter().zip(ids.iter()) {
+                if ptr.is_none() {
+                    break 'try_columns Err($crate::ecs::StorageError::ColumnMissing { component_id });
+                }
+            }
+
+            // SAFETY: Every pointer was checked above and refers to a distinct
+            // column of the borrowed storage; reads do not alias writes.
+            unsafe {
+                let mut idx = 0;
+                Ok(($(
+                    {
+                        let ptr = ptrs[idx].unwrap_unchecked();
+                        idx += 1;
+                        match $crate::ecs::ArchetypeStorage::try_column_ptr_to_slice_const::<$T>(ptr) {
+                            Ok(slice) => slice,
+                            Err(err) => break 'try_columns Err(err),
+                        }
+                    }
+                ),+))
+            }
+        }
+    };
+}
+
+/// Fallible form of `columns_mut!`, yielding `Result<(...), StorageError>`.
+///
+/// Duplicate component types report `StorageError::DuplicateColumnRequest`
+/// rather than asserting.
+///
+/// # Example
+/// ```ignore
+/// let (positions, velocities) = try_columns_mut!(storage, Position, Velocity)?;
+/// ```
+#[macro_export]
+macro_rules! try_columns_mut {
+    ($storage:expr, $T:ty) => {
+        $storage.column_slice_mut::<$T>()
+    };
+
+    ($storage:expr, $($T:ty),+ $(,)?) => {
+        'try_columns_mut: {
+            let ids = [$(<$T as $crate::ecs::Component>::id()),+];
+            for i in 0..ids.len() {
+                for j in (i + 1)..ids.len() {
+                    if ids[i] == ids[j] {
+                        break 'try_columns_mut Err(
+                            $crate::ecs::StorageError::DuplicateColumnRequest { component_id: ids[i] },
+                        );
+                    }
+                }
+            }
+            let ptrs = [$($storage.get_column_ptr(<$T as $crate::ecs::Component>::id())),+];
+            for (ptr, &component_id) in ptrs.iter().zip(ids.iter()) {
+                if ptr.is_none() {
+                    break 'try_columns_mut Err($crate::ecs::StorageError::ColumnMissing { component_id });
+                }
+            }
+
+            // SAFETY: Component ids are unique, so every pointer refers to a
+            // different column and the mutable slices cannot alias.
+            unsafe {
+                let mut idx = 0;
+                Ok(($(
+                    {
+                        let ptr = ptrs[idx].unwrap_unchecked();
+                        idx += 1;
+                        match $crate::ecs::ArchetypeStorage::try_column_ptr_to_slice::<$T>(ptr) {
+                            Ok(slice) => slice,
+                            Err(err) => break 'try_columns_mut Err(err),
+                        }
+                    }
+                ),+))
+            }
+        }
+    };
+}
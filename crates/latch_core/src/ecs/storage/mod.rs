@@ -2,10 +2,26 @@
 
 mod archetype_storage;
 mod column;
+mod cull_bounds;
+mod cull_stats;
 mod macros;
+mod page_allocator;
+mod page_pool;
+mod page_tile;
+mod render_layer;
+mod row_init;
 
+#[doc(hidden)]
+pub use archetype_storage::__column_access_failed;
 pub use archetype_storage::{
     plan_archetype, ArchetypePlan, ArchetypeStorage, ColumnError, PageBudget, PlanError,
     StorageError,
 };
 pub use column::Column;
+pub use cull_bounds::CullBounds;
+pub use cull_stats::CullStats;
+pub use page_allocator::PageAllocator;
+pub use page_pool::PagePool;
+pub use page_tile::PageTile;
+pub use render_layer::{sort_instances_by_layer, RenderLayer};
+pub use row_init::RowInit;
@@ -0,0 +1,31 @@
+//! Custom allocation of column pages.
+//!
+//! Column pages come from the global allocator unless a storage is built
+//! with a `PageAllocator`: arenas or slabs shared between worlds, huge
+//! pages, or memory pinned to one NUMA node. Pages are large and allocated
+//! rarely, so the allocator is a trait object chosen at construction;
+//! storages without one call the global allocator directly.
+
+use std::{alloc::Layout, ptr::NonNull};
+
+/// Source of column page memory for `ArchetypeStorage::from_plan_in` and
+/// `World::set_page_allocator`.
+///
+/// # Safety
+///
+/// `alloc_page` must return memory valid for reads and writes of
+/// `layout.size()` bytes, aligned to `layout.align()`, and not handed out
+/// again until `free_page` receives it. Storages call `free_page` exactly
+/// once per page, with the layout the page was allocated with.
+pub unsafe trait PageAllocator: Send + Sync {
+    /// Allocate one page; `None` aborts through `handle_alloc_error`.
+    fn alloc_page(&self, layout: Layout) -> Option<NonNull<u8>>;
+
+    /// Release a page returned by `alloc_page` with the same `layout`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must come from `alloc_page(layout)` on this allocator and must
+    /// not be used afterwards.
+    unsafe fn free_page(&self, ptr: NonNull<u8>, layout: Layout);
+}
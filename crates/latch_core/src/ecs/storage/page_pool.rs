@@ -0,0 +1,152 @@
+//! Column pages recycled between worlds.
+//!
+//! A server running several shards, or an editor previewing a scene next to
+//! the game, frees pages in one world while another allocates pages of the
+//! same shape. `PagePool` is a `PageAllocator` that keeps freed pages per
+//! layout (page size and alignment, which `rows_per_page` and the column
+//! stride determine) and hands them to the next world asking for that
+//! layout, so peak memory follows the worlds' combined population rather
+//! than the sum of their individual peaks.
+
+use crate::ecs::PageAllocator;
+use std::{
+    alloc::{alloc, dealloc, Layout},
+    collections::HashMap,
+    ptr::NonNull,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex, MutexGuard,
+    },
+};
+
+/// Free page owned by the pool.
+struct CachedPage(NonNull<u8>);
+
+// SAFETY: a cached page is unreachable from any storage, so the pool is its
+// only owner.
+unsafe impl Send for CachedPage {}
+
+#[derive(Default)]
+struct FreePages {
+    by_layout: HashMap<Layout, Vec<CachedPage>>,
+    bytes: usize,
+}
+
+/// Page allocator shared by several worlds; see `World::with_page_pool`.
+///
+/// Thread-safe: worlds on different threads may share one pool. The free
+/// lists sit behind a mutex taken once per page allocation or release,
+/// which happen once per `rows_per_page` spawns, not per entity.
+///
+/// Recycled pages keep whatever bytes their last world left in them, just
+/// as fresh pages from the global allocator are uninitialised.
+pub struct PagePool {
+    free: Mutex<FreePages>,
+    /// Upper bound on bytes held in the free lists.
+    max_cached_bytes: usize,
+    fresh_pages: AtomicU64,
+    reused_pages: AtomicU64,
+}
+
+impl PagePool {
+    /// Pool that keeps every freed page until `trim` or drop.
+    pub fn new() -> Self {
+        Self {
+            free: Mutex::new(FreePages::default()),
+            max_cached_bytes: usize::MAX,
+            fresh_pages: AtomicU64::new(0),
+            reused_pages: AtomicU64::new(0),
+        }
+    }
+
+    /// Return pages to the global allocator once the free lists hold
+    /// `bytes`.
+    pub fn with_max_cached_bytes(mut self, bytes: usize) -> Self {
+        self.max_cached_bytes = bytes;
+        self
+    }
+
+    /// Pages waiting for reuse.
+    pub fn cached_pages(&self) -> usize {
+        self.lock().by_layout.values().map(Vec::len).sum()
+    }
+
+    /// Bytes waiting for reuse.
+    pub fn cached_bytes(&self) -> usize {
+        self.lock().bytes
+    }
+
+    /// Pages taken from the global allocator so far.
+    pub fn fresh_pages(&self) -> u64 {
+        self.fresh_pages.load(Ordering::Relaxed)
+    }
+
+    /// Pages served from the free lists so far.
+    pub fn reused_pages(&self) -> u64 {
+        self.reused_pages.load(Ordering::Relaxed)
+    }
+
+    /// Return every cached page to the global allocator.
+    pub fn trim(&self) {
+        let mut free = self.lock();
+        for (layout, pages) in free.by_layout.drain() {
+            for page in pages {
+                // SAFETY: cached pages were allocated with `layout` by the
+                // global allocator and no storage refers to them.
+                unsafe { dealloc(page.0.as_ptr(), layout) };
+            }
+        }
+        free.bytes = 0;
+    }
+
+    fn lock(&self) -> MutexGuard<'_, FreePages> {
+        self.free.lock().expect("page pool poisoned")
+    }
+}
+
+impl Default for PagePool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// SAFETY: pages come from the global allocator with the requested layout; a
+// freed page is only handed out again after `free_page` returned it, and is
+// only ever returned for the layout it was cached under.
+unsafe impl PageAllocator for PagePool {
+    fn alloc_page(&self, layout: Layout) -> Option<NonNull<u8>> {
+        {
+            let mut free = self.lock();
+            if let Some(page) = free.by_layout.get_mut(&layout).and_then(Vec::pop) {
+                free.bytes -= layout.size();
+                self.reused_pages.fetch_add(1, Ordering::Relaxed);
+                return Some(page.0);
+            }
+        }
+        self.fresh_pages.fetch_add(1, Ordering::Relaxed);
+        // SAFETY: column pages never have a zero size.
+        NonNull::new(unsafe { alloc(layout) })
+    }
+
+    unsafe fn free_page(&self, ptr: NonNull<u8>, layout: Layout) {
+        let mut free = self.lock();
+        if free.bytes.saturating_add(layout.size()) > self.max_cached_bytes {
+            drop(free);
+            // SAFETY: the caller passes a page from `alloc_page(layout)`,
+            // which always comes from the global allocator.
+            unsafe { dealloc(ptr.as_ptr(), layout) };
+            return;
+        }
+        free.bytes += layout.size();
+        free.by_layout
+            .entry(layout)
+            .or_default()
+            .push(CachedPage(ptr));
+    }
+}
+
+impl Drop for PagePool {
+    fn drop(&mut self) {
+        self.trim();
+    }
+}
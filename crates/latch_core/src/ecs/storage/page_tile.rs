@@ -0,0 +1,120 @@
+//! One page of an archetype, processed on its own by a `par_pages_mut` worker
+//! or in turn by `pages_mut`.
+
+use super::archetype_storage::{ComponentColumn, StorageError};
+use crate::ecs::{Component, ComponentId, EntityId};
+use std::{cell::Cell, ops::Range, slice};
+
+/// Rows of one page across the columns requested from
+/// `ArchetypeStorage::par_pages_mut` or `ArchetypeStorage::pages_mut`.
+///
+/// Reads see the current buffer and writes go to the next buffer, as in
+/// `ComponentColumn::slice_rw_typed`. Each column may be borrowed for
+/// writing once per tile; any number of reads are allowed.
+pub struct PageTile<'a> {
+    page_idx: usize,
+    range: Range<usize>,
+    entity_ids: &'a [EntityId],
+    component_ids: &'a [ComponentId],
+    columns: &'a [&'a ComponentColumn],
+    next: Vec<*mut u8>,
+    written: Vec<Cell<bool>>,
+}
+
+// SAFETY: `next` points into this tile's page of the next buffer. Pages are
+// disjoint and `par_pages_mut`/`pages_mut` create one tile per page while
+// holding the storage mutably, so no other thread can reach those rows.
+unsafe impl Send for PageTile<'_> {}
+
+impl<'a> PageTile<'a> {
+    pub(super) fn new(
+        page_idx: usize,
+        range: Range<usize>,
+        entity_ids: &'a [EntityId],
+        component_ids: &'a [ComponentId],
+        columns: &'a [&'a ComponentColumn],
+    ) -> Self {
+        Self {
+            page_idx,
+            range,
+            entity_ids,
+            component_ids,
+            columns,
+            next: columns
+                .iter()
+                .map(|column| column.next_page_ptr(page_idx))
+                .collect(),
+            written: columns.iter().map(|_| Cell::new(false)).collect(),
+        }
+    }
+
+    #[inline]
+    pub fn page_idx(&self) -> usize {
+        self.page_idx
+    }
+
+    /// Global rows covered by this tile.
+    #[inline]
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.range.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.range.is_empty()
+    }
+
+    /// Entity id of every row, in row order.
+    #[inline]
+    pub fn entity_ids(&self) -> &'a [EntityId] {
+        self.entity_ids
+    }
+
+    /// Current-buffer values of `T` for this page.
+    pub fn read<T: Component>(&self) -> Result<&'a [T], StorageError> {
+        let idx = self.column_index(T::id())?;
+        let values = self.columns[idx].slice_read_typed::<T>(self.range())?;
+        Ok(values)
+    }
+
+    /// Next-buffer values of `T` for this page.
+    pub fn write<T: Component>(&self) -> Result<&'a mut [T], StorageError> {
+        let idx = self.column_index(T::id())?;
+        self.columns[idx].validate_typed::<T>()?;
+        if self.written[idx].replace(true) {
+            return Err(StorageError::DuplicateColumnRequest {
+                component_id: T::id(),
+            });
+        }
+        // SAFETY: `validate_typed` checked size and alignment, the page holds
+        // at least `len` rows, and the `written` flag hands this slice out
+        // once, so it is the only reference to these next-buffer rows.
+        Ok(unsafe { slice::from_raw_parts_mut(self.next[idx].cast::<T>(), self.len()) })
+    }
+
+    /// `read` and `write` of the same component, for read-modify-write passes.
+    pub fn rw<T: Component>(&self) -> Result<(&'a [T], &'a mut [T]), StorageError> {
+        Ok((self.read::<T>()?, self.write::<T>()?))
+    }
+
+    /// Next-buffer values of `T` initialised from the current buffer, for
+    /// updating rows in place. Rows left untouched keep their value across
+    /// the next swap.
+    pub fn modify<T: Component + Copy>(&self) -> Result<&'a mut [T], StorageError> {
+        let (read, write) = self.rw::<T>()?;
+        write.copy_from_slice(read);
+        Ok(write)
+    }
+
+    fn column_index(&self, component_id: ComponentId) -> Result<usize, StorageError> {
+        self.component_ids
+            .iter()
+            .position(|&id| id == component_id)
+            .ok_or(StorageError::ColumnMissing { component_id })
+    }
+}
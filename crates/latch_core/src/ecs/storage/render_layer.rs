@@ -0,0 +1,35 @@
+use crate::define_component;
+
+/// Draw-order bucket for an entity; lower layers are drawn first.
+///
+/// Entities without this component are treated as `RenderLayer::DEFAULT`.
+/// Gathered instances are sorted by layer with a stable sort, so entities on
+/// the same layer keep their gather order and frames are reproducible.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct RenderLayer(pub u8);
+define_component!(RenderLayer, "Latch::RenderLayer");
+
+impl RenderLayer {
+    pub const DEFAULT: RenderLayer = RenderLayer(0);
+}
+
+/// Stable-sort `instances` by the matching entry in `layers`.
+///
+/// `layers[i]` is the layer of `instances[i]`, as produced by the layered
+/// gather helpers. Already-ordered input (the common single-layer case) is
+/// left untouched without allocating.
+pub fn sort_instances_by_layer<U: Copy>(instances: &mut [U], layers: &[RenderLayer]) {
+    assert_eq!(
+        instances.len(),
+        layers.len(),
+        "one render layer per instance required"
+    );
+    if layers.windows(2).all(|pair| pair[0] <= pair[1]) {
+        return;
+    }
+    let mut order: Vec<usize> = (0..instances.len()).collect();
+    order.sort_by_key(|&idx| layers[idx]);
+    let sorted: Vec<U> = order.iter().map(|&idx| instances[idx]).collect();
+    instances.copy_from_slice(&sorted);
+}
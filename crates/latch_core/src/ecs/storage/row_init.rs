@@ -0,0 +1,13 @@
+/// How `ArchetypeStorage::alloc_bulk_with` prepares freshly allocated rows.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum RowInit {
+    /// Zero every column (both buffers) of the new rows.
+    ///
+    /// Costs one `memset` of `count * bytes_per_row` per buffer, so a column
+    /// the caller forgets to write reads as zeros instead of stale bytes.
+    #[default]
+    Zeroed,
+    /// Leave row bytes as found. Only for callers that write every column
+    /// of every new row before the next read.
+    Uninit,
+}
@@ -1,4 +1,4 @@
-use crate::ecs::ComponentId;
+use crate::{ecs::ComponentId, hash::stable_hash};
 
 /// Metadata describing how a system interacts with the ECS world.
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -7,6 +7,8 @@ pub struct SystemDescriptor {
     reads: Vec<ComponentId>,
     writes: Vec<ComponentId>,
     components: Vec<ComponentId>,
+    tick_interval: u32,
+    tick_phase: Option<u32>,
 }
 
 impl SystemDescriptor {
@@ -17,9 +19,26 @@ impl SystemDescriptor {
             reads: Vec::new(),
             writes: Vec::new(),
             components: Vec::new(),
+            tick_interval: 1,
+            tick_phase: None,
         }
     }
 
+    /// Run this system only on every `interval`-th tick (0 is treated as 1).
+    ///
+    /// Unless `tick_phase` is set, the phase is derived from the system name,
+    /// so staggered systems spread across ticks the same way on every run.
+    pub fn every_n_ticks(mut self, interval: u32) -> Self {
+        self.tick_interval = interval.max(1);
+        self
+    }
+
+    /// Pin the tick (modulo the interval) on which the system runs.
+    pub fn tick_phase(mut self, phase: u32) -> Self {
+        self.tick_phase = Some(phase);
+        self
+    }
+
     /// Replace the read-only component set for this system.
     pub fn reads<I>(mut self, components: I) -> Self
     where
@@ -72,6 +91,24 @@ impl SystemDescriptor {
         &self.components
     }
 
+    /// Number of ticks between runs; 1 means every tick.
+    pub fn tick_interval(&self) -> u32 {
+        self.tick_interval
+    }
+
+    /// Tick offset within the interval on which this system runs.
+    pub fn phase(&self) -> u32 {
+        match self.tick_phase {
+            Some(phase) => phase % self.tick_interval,
+            None => (stable_hash(self.name.as_bytes()) % u64::from(self.tick_interval)) as u32,
+        }
+    }
+
+    /// Whether the system is due on simulation tick `tick`.
+    pub fn should_run(&self, tick: u64) -> bool {
+        self.tick_interval <= 1 || tick % u64::from(self.tick_interval) == u64::from(self.phase())
+    }
+
     /// Whether the descriptor touches any components at all.
     pub fn is_empty(&self) -> bool {
         self.components.is_empty()
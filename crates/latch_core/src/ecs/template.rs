@@ -0,0 +1,137 @@
+//! Prefabs: entity layouts with default component bytes, spawned many times.
+//!
+//! `spawn` hashes, sorts and validates an `EntityBuilder` for every entity.
+//! An `EntityTemplate` does that once; `World::spawn_template` then only
+//! allocates a row and copies the default bytes, and
+//! `World::spawn_template_with` lets each instance overwrite some of them
+//! (a position, a team) before the row is written.
+
+use crate::ecs::{
+    meta_of, ArchetypeLayout, Component, ComponentId, EntityBlueprint, EntityBuilder,
+    EntityBuilderError,
+};
+use std::{mem, ops::Range, ptr};
+
+/// Layout and default component values shared by every instance.
+///
+/// Instances are bitwise copies of the defaults, so every component must be
+/// POD.
+#[derive(Clone, Debug)]
+pub struct EntityTemplate {
+    layout: ArchetypeLayout,
+    /// Sorted by component id, like the layout; ranges index `defaults`.
+    components: Vec<(ComponentId, Range<usize>)>,
+    defaults: Box<[u8]>,
+}
+
+impl EntityTemplate {
+    /// Capture `blueprint` as a template.
+    pub fn new(blueprint: EntityBlueprint) -> Result<Self, EntityBuilderError> {
+        let mut components = Vec::with_capacity(blueprint.components().len());
+        let mut defaults = Vec::new();
+        for component in blueprint.components() {
+            let component_id = component.component_id();
+            let meta = meta_of(component_id)
+                .ok_or(EntityBuilderError::ComponentNotRegistered { component_id })?;
+            if !meta.pod {
+                return Err(EntityBuilderError::TemplateNotPod {
+                    component_id,
+                    name: meta.name,
+                });
+            }
+            let start = defaults.len();
+            defaults.extend_from_slice(component.bytes());
+            components.push((component_id, start..defaults.len()));
+        }
+        Ok(Self {
+            layout: blueprint.layout().clone(),
+            components,
+            defaults: defaults.into_boxed_slice(),
+        })
+    }
+
+    /// Build `builder` and capture the result.
+    pub fn from_builder(builder: EntityBuilder) -> Result<Self, EntityBuilderError> {
+        Self::new(builder.build()?)
+    }
+
+    #[inline]
+    pub fn layout(&self) -> &ArchetypeLayout {
+        &self.layout
+    }
+
+    /// Default bytes of `component_id`, if the template has it.
+    pub fn component(&self, component_id: ComponentId) -> Option<&[u8]> {
+        self.range_of(component_id)
+            .map(|range| &self.defaults[range])
+    }
+
+    /// Components of an instance whose bytes are `bytes` (a copy of the
+    /// defaults).
+    pub(crate) fn instance_components<'a>(
+        &'a self,
+        bytes: &'a [u8],
+    ) -> impl Iterator<Item = (ComponentId, &'a [u8])> + 'a {
+        self.components
+            .iter()
+            .map(move |(component_id, range)| (*component_id, &bytes[range.clone()]))
+    }
+
+    pub(crate) fn defaults(&self) -> &[u8] {
+        &self.defaults
+    }
+
+    fn range_of(&self, component_id: ComponentId) -> Option<Range<usize>> {
+        self.components
+            .binary_search_by_key(&component_id, |(id, _)| *id)
+            .ok()
+            .map(|index| self.components[index].1.clone())
+    }
+}
+
+/// Component values of one instance being spawned by
+/// `World::spawn_template_with`, starting as the template defaults.
+pub struct TemplateInstance<'a> {
+    template: &'a EntityTemplate,
+    bytes: &'a mut [u8],
+}
+
+impl<'a> TemplateInstance<'a> {
+    pub(crate) fn new(template: &'a EntityTemplate, bytes: &'a mut [u8]) -> Self {
+        Self { template, bytes }
+    }
+
+    /// Replace the value of a component the template has.
+    pub fn set<T: Component + Copy>(&mut self, value: T) -> Result<(), EntityBuilderError> {
+        let component_id = T::id();
+        let range = self
+            .template
+            .range_of(component_id)
+            .ok_or(EntityBuilderError::NotInTemplate { component_id })?;
+        let size = mem::size_of::<T>();
+        if size > range.len() {
+            return Err(EntityBuilderError::StrideMismatch {
+                component_id,
+                name: T::NAME.into(),
+                expected: range.len(),
+                actual: size,
+            });
+        }
+        unsafe {
+            // SAFETY: the range holds at least `size_of::<T>()` bytes and
+            // `value` is alive for the copy.
+            ptr::copy_nonoverlapping(
+                &value as *const T as *const u8,
+                self.bytes[range].as_mut_ptr(),
+                size,
+            );
+        }
+        Ok(())
+    }
+
+    /// Raw bytes of a component the template has (`stride` bytes).
+    pub fn bytes_mut(&mut self, component_id: ComponentId) -> Option<&mut [u8]> {
+        let range = self.template.range_of(component_id)?;
+        Some(&mut self.bytes[range])
+    }
+}
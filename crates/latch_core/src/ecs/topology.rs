@@ -0,0 +1,84 @@
+//! Spawns and despawns recorded for replication.
+//!
+//! Value deltas only describe entities both sides already have; a client
+//! also needs to learn that an entity appeared or went away. With
+//! `World::set_topology_tracking` on, the world appends every spawn and
+//! despawn to a `TopologyChanges` log, which the server takes once per tick
+//! (`World::take_topology_changes`) and hands to the net layer's
+//! `replication::encode_delta`.
+
+use crate::ecs::Entity;
+use std::collections::HashMap;
+
+/// Entities spawned and despawned since the log was last taken.
+///
+/// An entity spawned and despawned within the same window appears in
+/// neither list, since a client that never saw it has nothing to undo.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TopologyChanges {
+    /// In spawn order.
+    pub spawned: Vec<Entity>,
+    /// In despawn order.
+    pub despawned: Vec<Entity>,
+}
+
+impl TopologyChanges {
+    pub fn is_empty(&self) -> bool {
+        self.spawned.is_empty() && self.despawned.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.spawned.clear();
+        self.despawned.clear();
+    }
+}
+
+/// The world's running log behind `TopologyChanges`.
+///
+/// Despawning an entity spawned in the same window tombstones its slot
+/// through `pending` instead of searching and shifting `spawned`; the
+/// tombstones are compacted away when the log is read or taken.
+#[derive(Debug, Default)]
+pub(crate) struct TopologyLog {
+    spawned: Vec<Option<Entity>>,
+    /// Live entries of `spawned`, by entity.
+    pending: HashMap<Entity, usize>,
+    despawned: Vec<Entity>,
+}
+
+impl TopologyLog {
+    pub(crate) fn record_spawn(&mut self, entity: Entity) {
+        self.pending.insert(entity, self.spawned.len());
+        self.spawned.push(Some(entity));
+    }
+
+    pub(crate) fn record_despawn(&mut self, entity: Entity) {
+        match self.pending.remove(&entity) {
+            Some(position) => self.spawned[position] = None,
+            None => self.despawned.push(entity),
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.spawned.clear();
+        self.pending.clear();
+        self.despawned.clear();
+    }
+
+    /// The changes so far, leaving the log untouched.
+    pub(crate) fn changes(&self) -> TopologyChanges {
+        TopologyChanges {
+            spawned: self.spawned.iter().flatten().copied().collect(),
+            despawned: self.despawned.clone(),
+        }
+    }
+
+    /// The changes so far, starting a new log.
+    pub(crate) fn take(&mut self) -> TopologyChanges {
+        self.pending.clear();
+        TopologyChanges {
+            spawned: self.spawned.drain(..).flatten().collect(),
+            despawned: std::mem::take(&mut self.despawned),
+        }
+    }
+}
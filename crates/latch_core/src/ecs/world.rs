@@ -1,12 +1,35 @@
 use crate::ecs::{
-    storage::{plan_archetype, ArchetypeStorage, PageBudget, PlanError, StorageError},
-    ArchetypeId, ArchetypeLayout, Component, ComponentId, Entity, EntityBuilder,
-    EntityBuilderError, EntityId, EntityLoc, Generation, SystemDescriptor, SystemHandle,
-    SystemRegistrationError, SystemRegistry,
+    cell_checksum::{CellIndex, CellPartition},
+    command_buffer::{Command, CommandBuffer},
+    events::{EventRegistry, Events},
+    match_explanation::{EntityStatus, MatchExplanation},
+    meta_of,
+    query::RelationLocation,
+    query_view::QueryView,
+    resources::Resources,
+    snapshot::{SlotState, SnapshotBody, SnapshotWriter},
+    stable_index::{StableIndexMove, StableIndices},
+    state_hash::{ArchetypeHash, StateHashes},
+    storage::{
+        plan_archetype, ArchetypeStorage, PageAllocator, PageBudget, PagePool, PageTile, PlanError,
+        StorageError,
+    },
+    ArchetypeId, ArchetypeLayout, Component, ComponentId, ComponentSignature, Entity,
+    EntityBlueprint, EntityBuilder, EntityBuilderError, EntityId, EntityIdPolicy, EntityLoc,
+    EntityTemplate, Generation, Schedule, SnapshotCompression, SnapshotError, SystemDescriptor,
+    SystemHandle, SystemRegistrationError, SystemRegistry, TemplateInstance, TickTimings,
+    TopologyChanges, TopologyLog,
 };
-use std::{collections::HashMap, convert::TryFrom};
+use crate::hash::StableHasher;
+use crate::math::Rng;
+use crate::physics::{CollisionConfig, PhysicsConfig};
+use rayon::prelude::*;
+use std::{collections::HashMap, convert::TryFrom, hash::Hash, sync::Arc};
 use thiserror::Error;
 
+/// Callback run for each entity `flush_despawns` moves to a new row.
+type RowMovedHook = Box<dyn FnMut(Entity, usize, usize) + Send + Sync>;
+
 struct ArchetypeEntry {
     storage: ArchetypeStorage,
     pending_despawns: Vec<usize>,
@@ -19,6 +42,17 @@ impl ArchetypeEntry {
             pending_despawns: Vec::new(),
         }
     }
+
+    fn live_len(&self) -> usize {
+        self.storage.entity_count() - self.pending_despawns.len()
+    }
+
+    /// Rows despawned but not yet flushed, sorted for `binary_search`.
+    fn sorted_pending_despawns(&self) -> Vec<usize> {
+        let mut rows = self.pending_despawns.clone();
+        rows.sort_unstable();
+        rows
+    }
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -31,13 +65,16 @@ struct SlotLocation {
 struct EntitySlot {
     generation: Generation,
     location: Option<SlotLocation>,
+    /// Handed out by `reserve_entities` and not yet spawned or cancelled.
+    reserved: bool,
 }
 
 impl EntitySlot {
-    fn new() -> Self {
+    fn new(generation: Generation) -> Self {
         Self {
-            generation: 0,
+            generation,
             location: None,
+            reserved: false,
         }
     }
 }
@@ -62,16 +99,47 @@ pub enum WorldError {
     UnknownEntityIndex { entity_id: EntityId },
     #[error("storage for archetype {archetype_id} missing")]
     MissingArchetype { archetype_id: ArchetypeId },
+    #[error("entity {entity:?} is not an unfilled reservation")]
+    NotReserved { entity: Entity },
 }
 
+/// Entity storage, systems, events and resources of one simulation.
+///
+/// Archetypes are always visited in ascending `ArchetypeId` order (by
+/// `for_each`, `try_for_each`, `archetypes_matching`, `archetypes_with`,
+/// flushing and defragmenting), never in hash-map order. Per-entity results
+/// do not depend on that order, but side effects that cross archetypes
+/// (collision pairs, event emission, RNG draws) replay identically across
+/// runs and machines, since `ArchetypeId` is a stable hash of the sorted
+/// component ids.
 pub struct World {
     page_budget: PageBudget,
+    /// Column page source for new archetypes; `None` is the global allocator.
+    page_allocator: Option<Arc<dyn PageAllocator>>,
     storages: HashMap<ArchetypeId, ArchetypeEntry>,
+    /// Every key of `storages`, sorted ascending; the iteration order.
+    archetype_order: Vec<ArchetypeId>,
+    /// Archetypes per component, each list ascending like `archetype_order`
+    /// (never creation order), so restored worlds list them identically.
     component_index: HashMap<ComponentId, Vec<ArchetypeId>>,
     systems: SystemRegistry,
     slots: Vec<EntitySlot>,
+    /// Always empty under `EntityIdPolicy::Monotonic`.
     free_list: Vec<EntityId>,
+    entity_id_policy: EntityIdPolicy,
+    /// First generation for newly created slots; raised by `shrink_slots` so
+    /// handles to truncated slots can never match a recreated one.
+    generation_floor: Generation,
     live_count: usize,
+    stable_indices: StableIndices,
+    /// Entities spawned since the last `swap_buffers`, in spawn order.
+    spawned: Vec<Entity>,
+    /// Spawns and despawns for replication; `None` unless tracking is on.
+    topology: Option<TopologyLog>,
+    row_moved: Option<RowMovedHook>,
+    archetype_generation: u64,
+    events: EventRegistry,
+    resources: Resources,
 }
 
 impl World {
@@ -79,15 +147,37 @@ impl World {
         Self::with_page_budget(PageBudget::detect())
     }
 
+    /// World whose column pages come from, and return to, `pool`.
+    ///
+    /// Worlds sharing a pool reuse each other's freed pages whenever their
+    /// archetypes plan the same page size and alignment, e.g. the same
+    /// components under the same `PageBudget`.
+    pub fn with_page_pool(pool: Arc<PagePool>) -> Self {
+        let mut world = Self::new();
+        world.set_page_allocator(pool);
+        world
+    }
+
     pub fn with_page_budget(page_budget: PageBudget) -> Self {
         Self {
             page_budget,
+            page_allocator: None,
             storages: HashMap::new(),
+            archetype_order: Vec::new(),
             component_index: HashMap::new(),
             systems: SystemRegistry::new(),
             slots: Vec::new(),
             free_list: Vec::new(),
+            entity_id_policy: EntityIdPolicy::Reuse,
+            generation_floor: 0,
             live_count: 0,
+            stable_indices: StableIndices::default(),
+            spawned: Vec::new(),
+            topology: None,
+            row_moved: None,
+            archetype_generation: 0,
+            events: EventRegistry::new(),
+            resources: Resources::new(),
         }
     }
 
@@ -99,41 +189,139 @@ impl World {
         self.page_budget = budget;
     }
 
+    pub fn entity_id_policy(&self) -> EntityIdPolicy {
+        self.entity_id_policy
+    }
+
+    /// Only for `WorldBuilder`, before anything has been spawned.
+    pub(crate) fn set_entity_id_policy(&mut self, policy: EntityIdPolicy) {
+        debug_assert!(self.slots.is_empty());
+        self.entity_id_policy = policy;
+    }
+
+    /// Allocate column pages of archetypes created from now on with
+    /// `allocator`; existing archetypes keep the allocator they were built
+    /// with.
+    pub fn set_page_allocator(&mut self, allocator: Arc<dyn PageAllocator>) {
+        self.page_allocator = Some(allocator);
+    }
+
     pub fn spawn(&mut self, builder: EntityBuilder) -> Result<Entity, WorldError> {
         let blueprint = builder.build()?;
-        let archetype_id = blueprint.layout().id();
         self.ensure_archetype_exists(blueprint.layout())?;
 
         let (entity, entity_id) = self.allocate_entity()?;
+        self.place(entity_id, &blueprint)?;
+        Ok(entity)
+    }
 
-        let row = {
-            let entry = self
-                .storages
-                .get_mut(&archetype_id)
-                .ok_or(WorldError::MissingArchetype { archetype_id })?;
-            let row = entry.storage.alloc_row(entity_id)?;
-            for component in blueprint.components() {
-                entry.storage.write_component(
-                    component.component_id(),
-                    row,
-                    component.bytes(),
-                    None,
-                )?;
-            }
-            row
-        };
+    /// Spawn a copy of `template`'s default values.
+    pub fn spawn_template(&mut self, template: &EntityTemplate) -> Result<Entity, WorldError> {
+        self.ensure_archetype_exists(template.layout())?;
+        let (entity, entity_id) = self.allocate_entity()?;
+        self.place_components(
+            entity_id,
+            template.layout().id(),
+            template.instance_components(template.defaults()),
+        )?;
+        Ok(entity)
+    }
 
-        self.record_location(
+    /// Spawn a copy of `template` after `overrides` replaced some of its
+    /// values. Nothing is spawned if `overrides` fails.
+    pub fn spawn_template_with<F>(
+        &mut self,
+        template: &EntityTemplate,
+        overrides: F,
+    ) -> Result<Entity, WorldError>
+    where
+        F: FnOnce(&mut TemplateInstance<'_>) -> Result<(), EntityBuilderError>,
+    {
+        let mut bytes = template.defaults().to_vec();
+        overrides(&mut TemplateInstance::new(template, &mut bytes))?;
+        self.ensure_archetype_exists(template.layout())?;
+        let (entity, entity_id) = self.allocate_entity()?;
+        self.place_components(
             entity_id,
-            SlotLocation {
-                archetype: archetype_id,
-                row,
-            },
+            template.layout().id(),
+            template.instance_components(&bytes),
         )?;
-        self.live_count += 1;
         Ok(entity)
     }
 
+    /// Allocate `count` entity ids without giving them components.
+    ///
+    /// Reserved entities are not alive (`locate` reports `EntityNotAlive`)
+    /// until `spawn_reserved` or `apply_commands` fills them, but their
+    /// handles are final, so parallel workers can record commands that
+    /// refer to each other's new entities. Ids come from the free list first,
+    /// exactly as `spawn` would take them, so reserving then filling in order
+    /// yields the same ids as spawning directly.
+    pub fn reserve_entities(&mut self, count: usize) -> Result<Vec<Entity>, WorldError> {
+        let mut reserved = Vec::with_capacity(count);
+        for _ in 0..count {
+            match self.allocate_entity() {
+                Ok((entity, entity_id)) => {
+                    self.slots[entity_id as usize].reserved = true;
+                    reserved.push(entity);
+                }
+                Err(err) => {
+                    for entity in reserved {
+                        self.cancel_reservation(entity)?;
+                    }
+                    return Err(err);
+                }
+            }
+        }
+        Ok(reserved)
+    }
+
+    /// Give a reserved entity its components, making it alive.
+    pub fn spawn_reserved(
+        &mut self,
+        entity: Entity,
+        builder: EntityBuilder,
+    ) -> Result<(), WorldError> {
+        self.reserved_slot(entity)?;
+        let blueprint = builder.build()?;
+        self.ensure_archetype_exists(blueprint.layout())?;
+        self.place(entity.index(), &blueprint)?;
+        self.slots[entity.index() as usize].reserved = false;
+        Ok(())
+    }
+
+    /// Return an unfilled reservation to the free list; its handle becomes
+    /// stale.
+    pub fn cancel_reservation(&mut self, entity: Entity) -> Result<(), WorldError> {
+        self.reserved_slot(entity)?.reserved = false;
+        self.finish_despawn(entity.index())
+    }
+
+    /// Apply a recorded `CommandBuffer` in recording order.
+    ///
+    /// Apply buffers from parallel workers in a fixed order (e.g. by worker
+    /// index) for deterministic results. Despawns of entities that are no
+    /// longer alive are skipped, since two workers may both decide to remove
+    /// the same entity. A despawn of a reservation not yet spawned cancels
+    /// it, so the entity ends up gone whichever buffer is applied first; a
+    /// later spawn of it fails with `StaleEntity`. Any other failure stops
+    /// at that command; earlier commands stay applied.
+    pub fn apply_commands(&mut self, buffer: CommandBuffer) -> Result<(), WorldError> {
+        for command in buffer.into_commands() {
+            match command {
+                Command::Spawn { entity, builder } => self.spawn_reserved(entity, builder)?,
+                Command::Despawn(entity) => {
+                    if self.validate(entity).is_some() {
+                        self.despawn(entity)?;
+                    } else if self.reserved_slot(entity).is_ok() {
+                        self.cancel_reservation(entity)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub fn despawn(&mut self, entity: Entity) -> Result<(), WorldError> {
         let index = entity.index() as usize;
         let slot = self
@@ -155,59 +343,167 @@ impl World {
                 })?;
         entry.pending_despawns.push(location.row);
         self.live_count = self.live_count.saturating_sub(1);
+        self.stable_indices.release(entity.index());
+        if let Some(topology) = &mut self.topology {
+            topology.record_despawn(entity);
+        }
         Ok(())
     }
 
     pub fn flush_despawns(&mut self) -> Result<(), WorldError> {
         let archetype_ids: Vec<ArchetypeId> = self
-            .storages
+            .archetype_order
             .iter()
-            .filter(|(_, entry)| !entry.pending_despawns.is_empty())
-            .map(|(id, _)| *id)
+            .copied()
+            .filter(|id| {
+                self.storages
+                    .get(id)
+                    .is_some_and(|entry| !entry.pending_despawns.is_empty())
+            })
             .collect();
 
         for archetype_id in archetype_ids {
-            let mut victims = Vec::new();
-            let mut move_updates = Vec::new();
-            {
-                let entry = self
-                    .storages
-                    .get_mut(&archetype_id)
-                    .ok_or(WorldError::MissingArchetype { archetype_id })?;
-                if entry.pending_despawns.is_empty() {
-                    continue;
-                }
-                entry.pending_despawns.sort_unstable();
-                entry.pending_despawns.dedup();
+            self.compact_archetype(archetype_id)?;
+        }
 
-                for &row in &entry.pending_despawns {
-                    victims.push(entry.storage.entity_id_at(row)?);
-                }
+        Ok(())
+    }
 
-                let mut move_rows = Vec::new();
-                entry.storage.free_bulk_swap_remove(
-                    entry.pending_despawns.clone(),
-                    |from, to| {
-                        move_rows.push((from, to));
-                    },
-                )?;
-                entry.pending_despawns.clear();
-
-                for (_from, to) in move_rows {
-                    let entity_id = entry.storage.entity_id_at(to)?;
-                    move_updates.push((entity_id, to));
-                }
+    /// Repack every archetype so its rows occupy the fewest pages.
+    ///
+    /// Swap-remove keeps live rows dense, so the slack this removes is rows
+    /// despawned but not yet flushed (holes scattered across pages) and page
+    /// bookkeeping left over from earlier growth. Holes are filled through
+    /// the same path as `flush_despawns`, so moved entities are relocated via
+    /// `update_entity_location`. Unlike the automatic trailing-page trim this
+    /// visits every archetype; servers run it during lulls.
+    ///
+    /// Returns the number of rows moved.
+    pub fn defragment(&mut self) -> Result<usize, WorldError> {
+        let mut moved = 0;
+        for archetype_id in self.archetype_order.clone() {
+            moved += self.compact_archetype(archetype_id)?;
+            if let Some(entry) = self.storages.get_mut(&archetype_id) {
+                entry.storage.shrink_to_fit();
             }
+        }
+        Ok(moved)
+    }
+
+    /// Call `hook(entity, old_row, new_row)` for every entity that
+    /// `flush_despawns` (or `defragment`) moves to fill a despawned row.
+    ///
+    /// Column-level `on_move` callbacks fire per column and can report a row
+    /// twice when a batch refills it; this fires once per moved entity, with
+    /// its row before the flush and its final row, both within the entity's
+    /// unchanged archetype. A renderer keeping row-indexed GPU data copies
+    /// `old_row` to `new_row` instead of re-uploading the archetype. The
+    /// hook runs after despawned entities are gone, in ascending `new_row`
+    /// order per archetype, and replaces any earlier one.
+    pub fn on_row_moved(&mut self, hook: impl FnMut(Entity, usize, usize) + Send + Sync + 'static) {
+        self.row_moved = Some(Box::new(hook));
+    }
+
+    /// Remove the hook set by `on_row_moved`.
+    pub fn clear_on_row_moved(&mut self) {
+        self.row_moved = None;
+    }
 
-            for entity_id in victims {
-                self.finish_despawn(entity_id)?;
+    /// Drop every archetype that has no rows left, returning how many.
+    ///
+    /// Archetypes outlive their last entity so respawning the same shape is
+    /// cheap, but a world whose entity shapes churn accumulates storages that
+    /// every `for_each` and accelerator rebuild still has to step over.
+    /// Pruned archetypes disappear from `archetype_ids` and the component
+    /// index; a later spawn with the same components recreates them under
+    /// the same id. Rows despawned but not yet flushed keep their archetype
+    /// alive, so call this after `flush_despawns` (`TickPhase::PruneArchetypes`
+    /// does).
+    pub fn prune_empty_archetypes(&mut self) -> usize {
+        let empty: Vec<ArchetypeId> = self
+            .archetype_order
+            .iter()
+            .copied()
+            .filter(|id| {
+                self.storages
+                    .get(id)
+                    .is_some_and(|entry| entry.storage.is_empty())
+            })
+            .collect();
+        if empty.is_empty() {
+            return 0;
+        }
+
+        for archetype_id in &empty {
+            self.storages.remove(archetype_id);
+        }
+        self.archetype_order
+            .retain(|id| empty.binary_search(id).is_err());
+        self.component_index.retain(|_, archetype_ids| {
+            archetype_ids.retain(|id| empty.binary_search(id).is_err());
+            !archetype_ids.is_empty()
+        });
+        self.archetype_generation += 1;
+        empty.len()
+    }
+
+    /// Swap-remove the archetype's pending despawns, returning rows moved.
+    fn compact_archetype(&mut self, archetype_id: ArchetypeId) -> Result<usize, WorldError> {
+        let mut victims = Vec::new();
+        let mut move_updates = Vec::new();
+        {
+            let entry = self
+                .storages
+                .get_mut(&archetype_id)
+                .ok_or(WorldError::MissingArchetype { archetype_id })?;
+            if entry.pending_despawns.is_empty() {
+                return Ok(0);
             }
-            for (entity_id, row) in move_updates {
-                self.update_entity_location(entity_id, archetype_id, row)?;
+            entry.pending_despawns.sort_unstable();
+            entry.pending_despawns.dedup();
+
+            for &row in &entry.pending_despawns {
+                victims.push(entry.storage.entity_id_at(row)?);
+            }
+
+            let mut move_rows = Vec::new();
+            entry
+                .storage
+                .free_bulk_swap_remove(entry.pending_despawns.clone(), |from, to| {
+                    move_rows.push((from, to));
+                })?;
+            entry.pending_despawns.clear();
+
+            // A row can be filled and later moved again while the batch is
+            // processed, so only the final occupant of each surviving row counts.
+            let len = entry.storage.entity_count();
+            let mut rows: Vec<usize> = move_rows
+                .into_iter()
+                .map(|(_from, to)| to)
+                .filter(|&to| to < len)
+                .collect();
+            rows.sort_unstable();
+            rows.dedup();
+            for row in rows {
+                let entity_id = entry.storage.entity_id_at(row)?;
+                move_updates.push((entity_id, row));
             }
         }
 
-        Ok(())
+        for entity_id in victims {
+            self.finish_despawn(entity_id)?;
+        }
+        let moved = move_updates.len();
+        for (entity_id, row) in move_updates {
+            if let Some(hook) = &mut self.row_moved {
+                let slot = &self.slots[entity_id as usize];
+                if let Some(location) = slot.location {
+                    hook(Entity::new(entity_id, slot.generation), location.row, row);
+                }
+            }
+            self.update_entity_location(entity_id, archetype_id, row)?;
+        }
+        Ok(moved)
     }
 
     pub fn locate(&self, entity: Entity) -> Result<EntityLoc, WorldError> {
@@ -227,6 +523,81 @@ impl World {
         ))
     }
 
+    /// Current location of `entity` if it is alive and its generation
+    /// matches; `None` for despawned, recycled or unknown handles.
+    #[inline]
+    pub fn validate(&self, entity: Entity) -> Option<EntityLoc> {
+        self.locate(entity).ok()
+    }
+
+    /// Whether `entity` is alive and its archetype stores `component_id`;
+    /// `false` for despawned, recycled or unknown handles.
+    pub fn has_component(&self, entity: Entity, component_id: ComponentId) -> bool {
+        self.archetype_layout_of(entity)
+            .is_some_and(|layout| layout.contains(component_id))
+    }
+
+    /// Components of `entity`, ascending by id; `None` for despawned,
+    /// recycled or unknown handles.
+    pub fn component_ids_of(&self, entity: Entity) -> Option<&[ComponentId]> {
+        self.archetype_layout_of(entity)
+            .map(ArchetypeLayout::components)
+    }
+
+    fn archetype_layout_of(&self, entity: Entity) -> Option<&ArchetypeLayout> {
+        let location = self.validate(entity)?;
+        self.storages
+            .get(&location.archetype)
+            .map(|entry| &entry.storage.plan().layout)
+    }
+
+    /// Why a query over `component_ids` does or does not visit `entity`.
+    ///
+    /// Reports whether the handle is alive, pending despawn, reserved or
+    /// stale, and which of the queried components its archetype lacks —
+    /// typically one forgotten in `spawn!`. Meant for editors and test
+    /// failures; it scans pending despawns, so keep it off hot paths.
+    pub fn explain_match(&self, entity: Entity, component_ids: &[ComponentId]) -> MatchExplanation {
+        let (status, archetype) = match self.slots.get(entity.index() as usize) {
+            None => (EntityStatus::Unknown, None),
+            Some(slot) if slot.generation != entity.generation() => (EntityStatus::Stale, None),
+            Some(slot) if slot.reserved => (EntityStatus::Reserved, None),
+            Some(slot) => match slot.location {
+                Some(location) => (EntityStatus::Alive, Some(location.archetype)),
+                // Flushing bumps the generation, so a matching handle
+                // without a location still has a pending row.
+                None => (
+                    EntityStatus::PendingDespawn,
+                    self.pending_archetype_of(entity.index()),
+                ),
+            },
+        };
+        let layout = archetype
+            .and_then(|id| self.storages.get(&id))
+            .map(|entry| &entry.storage.plan().layout);
+        let (present, missing) = component_ids
+            .iter()
+            .partition(|&&component_id| layout.is_some_and(|layout| layout.contains(component_id)));
+        MatchExplanation {
+            entity,
+            status,
+            archetype,
+            present,
+            missing,
+        }
+    }
+
+    fn pending_archetype_of(&self, entity_id: EntityId) -> Option<ArchetypeId> {
+        self.archetype_order.iter().copied().find(|id| {
+            self.storages.get(id).is_some_and(|entry| {
+                entry
+                    .pending_despawns
+                    .iter()
+                    .any(|&row| entry.storage.entity_id_at(row).ok() == Some(entity_id))
+            })
+        })
+    }
+
     pub fn storage(&self, archetype: ArchetypeId) -> Option<&ArchetypeStorage> {
         self.storages.get(&archetype).map(|entry| &entry.storage)
     }
@@ -237,6 +608,7 @@ impl World {
             .map(|entry| &mut entry.storage)
     }
 
+    /// Archetypes storing `component_id`, in ascending id order.
     pub fn archetypes_with(&self, component_id: ComponentId) -> &[ArchetypeId] {
         self.component_index
             .get(&component_id)
@@ -244,6 +616,68 @@ impl World {
             .unwrap_or(&[])
     }
 
+    /// Archetypes storing every component in `component_ids`, in ascending id order.
+    pub fn archetypes_matching<'a>(
+        &'a self,
+        component_ids: &[ComponentId],
+    ) -> impl Iterator<Item = ArchetypeId> + 'a {
+        let query = ComponentSignature::from_components(component_ids);
+        self.archetype_order.iter().copied().filter(move |id| {
+            self.storages
+                .get(id)
+                .is_some_and(|entry| entry.storage.plan().layout.matches(&query))
+        })
+    }
+
+    /// The archetype storing exactly `component_ids` (in any order,
+    /// duplicates ignored) and nothing else, if one exists.
+    ///
+    /// A single hash lookup instead of the superset scan of
+    /// `archetypes_matching`, for code that knows the shape it spawned.
+    pub fn archetype_exact(&self, component_ids: &[ComponentId]) -> Option<ArchetypeId> {
+        let layout = ArchetypeLayout::new(component_ids.to_vec());
+        let entry = self.storages.get(&layout.id())?;
+        (entry.storage.plan().layout.components() == layout.components()).then(|| layout.id())
+    }
+
+    /// Create the archetype storing exactly `component_ids` if needed and
+    /// allocate pages for `additional` more of its rows, returning its id.
+    ///
+    /// Spawning up to `additional` entities of that shape then neither
+    /// plans an archetype nor allocates column pages or entity slots.
+    /// Reserved pages are released by `defragment`, and an archetype still
+    /// empty is dropped by `prune_empty_archetypes` like any other.
+    pub fn reserve(
+        &mut self,
+        component_ids: &[ComponentId],
+        additional: usize,
+    ) -> Result<ArchetypeId, WorldError> {
+        let layout = ArchetypeLayout::new(component_ids.to_vec());
+        self.ensure_archetype_exists(&layout)?;
+        let archetype_id = layout.id();
+        self.storages
+            .get_mut(&archetype_id)
+            .ok_or(WorldError::MissingArchetype { archetype_id })?
+            .storage
+            .reserve(additional);
+        let new_slots = additional.saturating_sub(self.free_list.len());
+        self.slots.reserve(new_slots);
+        self.spawned.reserve(additional);
+        Ok(archetype_id)
+    }
+
+    /// Every archetype id in ascending order, the order all iteration uses.
+    pub fn archetype_ids(&self) -> &[ArchetypeId] {
+        &self.archetype_order
+    }
+
+    /// Counter bumped whenever an archetype is created or pruned.
+    ///
+    /// Cached query results (see `QueryCache`) stay valid while it is unchanged.
+    pub fn archetype_generation(&self) -> u64 {
+        self.archetype_generation
+    }
+
     pub fn register_system(
         &mut self,
         descriptor: SystemDescriptor,
@@ -267,6 +701,15 @@ impl World {
         self.systems.write_components(handle)
     }
 
+    /// Whether `handle` is due on `tick`; `None` if the handle is unknown.
+    ///
+    /// Lets hosts that drive systems by hand honour `SystemDescriptor::every_n_ticks`.
+    pub fn system_should_run(&self, handle: SystemHandle, tick: u64) -> Option<bool> {
+        self.systems
+            .descriptor(handle)
+            .map(|descriptor| descriptor.should_run(tick))
+    }
+
     pub fn systems(&self) -> impl Iterator<Item = (SystemHandle, &SystemDescriptor)> {
         self.systems.iter()
     }
@@ -279,10 +722,702 @@ impl World {
         self.slots.len()
     }
 
+    /// Entity slots the slot table holds before it has to reallocate;
+    /// raised ahead of time by `reserve`.
+    pub fn slots_capacity(&self) -> usize {
+        self.slots.capacity()
+    }
+
+    /// Fraction of allocated entity slots holding a live entity (1.0 when
+    /// no slots are allocated).
+    pub fn slot_utilization(&self) -> f32 {
+        if self.slots.is_empty() {
+            return 1.0;
+        }
+        self.live_count as f32 / self.slots.len() as f32
+    }
+
+    /// Release free entity slots at the end of the slot table.
+    ///
+    /// The table only grows while spawning, so a burst of transient entities
+    /// leaves it at its peak size. This truncates every trailing slot that is
+    /// free (despawned and flushed), frees the memory and rebuilds the free
+    /// list so the lowest ids are reused first, keeping later shrinks
+    /// effective. Slots pending despawn are kept until `flush_despawns`.
+    ///
+    /// Recreated slots start above every truncated generation, so stale
+    /// handles stay stale. Returns the number of slots released, which is
+    /// always 0 under `EntityIdPolicy::Monotonic`.
+    pub fn shrink_slots(&mut self) -> usize {
+        let mut free = vec![false; self.slots.len()];
+        for &entity_id in &self.free_list {
+            free[entity_id as usize] = true;
+        }
+        let keep = free
+            .iter()
+            .rposition(|&is_free| !is_free)
+            .map_or(0, |last| last + 1);
+        let released = self.slots.len() - keep;
+
+        for slot in &self.slots[keep..] {
+            self.generation_floor = self.generation_floor.max(slot.generation);
+        }
+        self.slots.truncate(keep);
+        self.slots.shrink_to_fit();
+
+        self.stable_indices.truncate_entities(keep);
+        self.free_list
+            .retain(|&entity_id| (entity_id as usize) < keep);
+        self.free_list.sort_unstable_by(|a, b| b.cmp(a));
+        self.free_list.shrink_to_fit();
+        released
+    }
+
+    /// Run one tick of `schedule` against this world.
+    ///
+    /// Phases run in `schedule.phases()` order; by default that is the due
+    /// systems, `swap_buffers`, `flush_despawns`, then a relation rebuild
+    /// (see `Schedule`). Returns how long each phase took. Events logged
+    /// during the tick are inside a `tick` span with a `tick` field.
+    pub fn tick(&mut self, schedule: &mut Schedule) -> Result<TickTimings, WorldError> {
+        schedule.run(self)
+    }
+
     pub fn swap_buffers(&mut self) {
         for entry in self.storages.values_mut() {
             entry.storage.swap_buffers();
         }
+        self.events.swap_all();
+        self.spawned.clear();
+    }
+
+    /// Entities spawned (by `spawn`, `spawn_reserved` or `apply_commands`)
+    /// since the last `swap_buffers`, in spawn order.
+    ///
+    /// The list is cleared by every swap, so under `World::tick` a system
+    /// sees entities spawned between ticks and earlier in its own stage.
+    /// Entities despawned since are still listed; check them with
+    /// `validate`.
+    pub fn spawned_since_last_tick(&self) -> &[Entity] {
+        &self.spawned
+    }
+
+    /// Start or stop recording spawns and despawns for replication.
+    ///
+    /// Unlike `spawned_since_last_tick`, the log is not cleared by
+    /// `swap_buffers`: it covers everything since the last
+    /// `take_topology_changes`, so a server takes it once per tick, after
+    /// `tick`, and nothing spawned between ticks is missed. Turning tracking
+    /// off drops the log.
+    pub fn set_topology_tracking(&mut self, enabled: bool) {
+        match (enabled, self.topology.is_some()) {
+            (true, false) => self.topology = Some(TopologyLog::default()),
+            (false, true) => self.topology = None,
+            _ => {}
+        }
+    }
+
+    pub fn is_tracking_topology(&self) -> bool {
+        self.topology.is_some()
+    }
+
+    /// Spawns and despawns recorded since the last take, without taking
+    /// them; `None` while tracking is off.
+    pub fn topology_changes(&self) -> Option<TopologyChanges> {
+        self.topology.as_ref().map(TopologyLog::changes)
+    }
+
+    /// Take the recorded spawns and despawns, starting a new log.
+    pub fn take_topology_changes(&mut self) -> TopologyChanges {
+        self.topology
+            .as_mut()
+            .map(TopologyLog::take)
+            .unwrap_or_default()
+    }
+
+    /// Panic if any column has next-buffer writes that `swap_buffers` has not
+    /// published yet.
+    ///
+    /// Call it where a system is about to read state it expects to be fresh
+    /// (after a manual write/swap sequence, say). Compiles to nothing in
+    /// release builds.
+    #[track_caller]
+    pub fn debug_assert_swapped(&self) {
+        #[cfg(debug_assertions)]
+        {
+            let unswapped: Vec<(ArchetypeId, ComponentId)> = self
+                .archetype_order
+                .iter()
+                .filter_map(|archetype_id| {
+                    self.storages
+                        .get(archetype_id)
+                        .map(|entry| (*archetype_id, &entry.storage))
+                })
+                .flat_map(|(archetype_id, storage)| {
+                    storage
+                        .unswapped_components()
+                        .map(move |component_id| (archetype_id, component_id))
+                })
+                .collect();
+            assert!(
+                unswapped.is_empty(),
+                "writes to the next buffer were never swapped in (archetype, component): {unswapped:?}"
+            );
+        }
+    }
+
+    /// Encode every entity and the current buffer of every column.
+    ///
+    /// Entity ids and generations are kept, including the free list order,
+    /// so a restored world hands out the same ids as the original. Values go
+    /// through each component's codec (see `ComponentMeta::encode`). The
+    /// `PhysicsConfig` and `CollisionConfig` resources are included so
+    /// replays run with the same constants, and the `Rng` resource so they
+    /// draw the same numbers; systems, events and other resources are not
+    /// part of the snapshot. Fails with `PendingDespawns`
+    /// between `despawn` and `flush_despawns`.
+    pub fn snapshot(&self, compression: SnapshotCompression) -> Result<Vec<u8>, SnapshotError> {
+        if self
+            .storages
+            .values()
+            .any(|entry| !entry.pending_despawns.is_empty())
+        {
+            return Err(SnapshotError::PendingDespawns);
+        }
+
+        let mut writer = SnapshotWriter::with_capacity(self.snapshot_size_hint());
+        writer.u32(self.generation_floor);
+        writer.u64(self.slots.len() as u64);
+        for slot in &self.slots {
+            let state = if slot.reserved {
+                SlotState::Reserved
+            } else if slot.location.is_some() {
+                SlotState::Alive
+            } else {
+                SlotState::Free
+            };
+            writer.slot(slot.generation, state);
+        }
+        writer.u64(self.free_list.len() as u64);
+        for &entity_id in &self.free_list {
+            writer.u32(entity_id);
+        }
+
+        writer.u64(self.archetype_order.len() as u64);
+        for &archetype_id in &self.archetype_order {
+            let storage = self
+                .storage(archetype_id)
+                .ok_or(WorldError::MissingArchetype { archetype_id })?;
+            let components = storage.plan().layout.components();
+            writer.u64(components.len() as u64);
+            for &component_id in components {
+                writer.u32(component_id);
+            }
+            let rows = storage.entity_count();
+            writer.u64(rows as u64);
+            for row in 0..rows {
+                writer.u32(storage.entity_id_at(row)?);
+            }
+            for &component_id in components {
+                let column = storage.column(component_id)?;
+                let meta = &column.plan().meta;
+                for page_idx in 0..column.page_count() {
+                    let bytes = column
+                        .slice_read(column.page_range(page_idx))
+                        .map_err(StorageError::from)?;
+                    if meta.codec.is_none() {
+                        writer.body_mut().extend_from_slice(bytes);
+                    } else if meta.stride > 0 {
+                        for value in bytes.chunks_exact(meta.stride) {
+                            meta.encode(value, writer.body_mut());
+                        }
+                    }
+                }
+            }
+        }
+
+        writer.config(
+            self.resource::<PhysicsConfig>()
+                .map(PhysicsConfig::to_bytes),
+        );
+        writer.config(
+            self.resource::<CollisionConfig>()
+                .map(CollisionConfig::to_bytes),
+        );
+        writer.config(self.resource::<Rng>().map(Rng::to_bytes));
+        writer.finish(compression)
+    }
+
+    /// Replace every entity with the contents of a `snapshot`.
+    ///
+    /// The snapshot is decoded into fresh storage first, so on error the
+    /// world is left untouched. Archetypes the world created before are
+    /// dropped with it; `archetype_ids`, `archetypes_with` and iteration
+    /// order afterwards match the snapshotted world exactly. Both buffers
+    /// of every column receive the snapshot values. The `PhysicsConfig`,
+    /// `CollisionConfig` and `Rng` resources are replaced by the snapshot's
+    /// (or removed if it had none); systems, events and other resources are
+    /// kept as they are; relation accelerators must be rebuilt before their
+    /// next query.
+    /// Stable indices are not part of the snapshot: live entities get
+    /// `0..live_entity_count()` in entity id order, so re-upload GPU
+    /// instance buffers after a restore. The world keeps its
+    /// `EntityIdPolicy`; under `Monotonic` the snapshot's free list is
+    /// dropped and new ids continue after its last slot.
+    pub fn restore(&mut self, bytes: &[u8]) -> Result<(), SnapshotError> {
+        let body = SnapshotBody::open(bytes)?;
+        let mut reader = body.reader();
+        let mut restored = World::with_page_budget(self.page_budget);
+        restored.page_allocator = self.page_allocator.clone();
+
+        restored.generation_floor = reader.u32()?;
+        let slot_count = reader.count(5)?;
+        let mut states = Vec::with_capacity(slot_count);
+        restored.slots.reserve_exact(slot_count);
+        for _ in 0..slot_count {
+            let (generation, state) = reader.slot()?;
+            let mut slot = EntitySlot::new(generation);
+            slot.reserved = state == SlotState::Reserved;
+            restored.slots.push(slot);
+            states.push(state);
+        }
+        let free_count = reader.count(4)?;
+        for _ in 0..free_count {
+            let entity_id = reader.u32()?;
+            if states.get(entity_id as usize) != Some(&SlotState::Free) {
+                return Err(SnapshotError::Corrupt {
+                    reason: "free list entry is not a free slot",
+                });
+            }
+            if self.entity_id_policy == EntityIdPolicy::Reuse {
+                restored.free_list.push(entity_id);
+            }
+        }
+
+        let archetype_count = reader.count(16)?;
+        for _ in 0..archetype_count {
+            let component_count = reader.count(4)?;
+            let mut components = Vec::with_capacity(component_count);
+            for _ in 0..component_count {
+                components.push(reader.u32()?);
+            }
+            let layout = ArchetypeLayout::new(components);
+            if layout.components().len() != component_count {
+                return Err(SnapshotError::Corrupt {
+                    reason: "duplicate component in archetype",
+                });
+            }
+            let metas = layout
+                .components()
+                .iter()
+                .map(|&component_id| {
+                    meta_of(component_id).ok_or(SnapshotError::UnknownComponent { component_id })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let archetype_id = layout.id();
+            if restored.archetype_order.last() >= Some(&archetype_id) {
+                return Err(SnapshotError::Corrupt {
+                    reason: "archetypes not in ascending id order",
+                });
+            }
+            restored.ensure_archetype_exists(&layout)?;
+
+            let rows = reader.count(4)?;
+            let mut entity_ids = Vec::with_capacity(rows);
+            for row in 0..rows {
+                let entity_id = reader.u32()?;
+                if states.get(entity_id as usize) != Some(&SlotState::Alive) {
+                    return Err(SnapshotError::Corrupt {
+                        reason: "row belongs to an entity that is not alive",
+                    });
+                }
+                let slot = &mut restored.slots[entity_id as usize];
+                if slot.location.is_some() {
+                    return Err(SnapshotError::Corrupt {
+                        reason: "entity stored in more than one row",
+                    });
+                }
+                slot.location = Some(SlotLocation {
+                    archetype: archetype_id,
+                    row,
+                });
+                entity_ids.push(entity_id);
+            }
+
+            let storage = &mut restored
+                .storages
+                .get_mut(&archetype_id)
+                .ok_or(WorldError::MissingArchetype { archetype_id })?
+                .storage;
+            storage.alloc_bulk(rows, entity_ids.into_iter())?;
+            for meta in &metas {
+                let column = storage.column_mut(meta.id)?;
+                let mut value = vec![0u8; meta.stride];
+                for row in 0..rows {
+                    let used = meta.decode(reader.remaining(), &mut value)?;
+                    reader.advance(used);
+                    column
+                        .write_both_at(row, &value)
+                        .map_err(StorageError::from)?;
+                }
+            }
+            restored.live_count += rows;
+        }
+
+        let alive = states
+            .iter()
+            .filter(|&&state| state == SlotState::Alive)
+            .count();
+        if restored.live_count != alive {
+            return Err(SnapshotError::Corrupt {
+                reason: "alive entity without a row",
+            });
+        }
+        let physics = reader.config()?.map(PhysicsConfig::from_bytes);
+        let collision = reader.config()?.map(CollisionConfig::from_bytes);
+        let rng = reader.config()?.map(Rng::from_bytes);
+        if !reader.is_empty() {
+            return Err(SnapshotError::Corrupt {
+                reason: "trailing bytes after the simulation config",
+            });
+        }
+
+        self.storages = restored.storages;
+        self.archetype_order = restored.archetype_order;
+        self.component_index = restored.component_index;
+        self.slots = restored.slots;
+        self.free_list = restored.free_list;
+        self.generation_floor = restored.generation_floor;
+        self.live_count = restored.live_count;
+        self.stable_indices = StableIndices::default();
+        for (entity_id, slot) in self.slots.iter().enumerate() {
+            if slot.location.is_some() {
+                self.stable_indices.assign(entity_id as EntityId);
+            }
+        }
+        self.restore_config(physics);
+        self.restore_config(collision);
+        self.restore_config(rng);
+        self.spawned.clear();
+        // Peers need a full snapshot after a restore, not the old log.
+        if let Some(topology) = &mut self.topology {
+            topology.clear();
+        }
+        self.archetype_generation += 1;
+        Ok(())
+    }
+
+    /// Hash of the entity table and the current buffer of every column.
+    ///
+    /// Stable across runs and hosts: two worlds fed the same inputs hash
+    /// equal after every tick. Call it after `swap_buffers`, since writes to
+    /// the next buffer are not included. Systems, events and resources are
+    /// not hashed.
+    pub fn state_hash(&self) -> u64 {
+        self.state_hashes().combined()
+    }
+
+    /// `state_hash` broken down per archetype and column, for locating a
+    /// divergence (see `ReplayVerifier`).
+    pub fn state_hashes(&self) -> StateHashes {
+        let mut entities = StableHasher::new();
+        entities.write_u32(self.generation_floor);
+        for slot in &self.slots {
+            let state = if slot.reserved {
+                SlotState::Reserved
+            } else if slot.location.is_some() {
+                SlotState::Alive
+            } else {
+                SlotState::Free
+            };
+            entities.write_u32(slot.generation);
+            entities.write(&[state as u8]);
+        }
+        for &entity_id in &self.free_list {
+            entities.write_u32(entity_id);
+        }
+
+        let archetypes = self
+            .archetype_order
+            .iter()
+            .filter_map(|&archetype_id| self.storage(archetype_id).map(|s| (archetype_id, s)))
+            .map(|(archetype, storage)| {
+                let mut entity_ids = StableHasher::new();
+                for row in 0..storage.entity_count() {
+                    let entity_id = storage.entity_id_at(row).expect("row within entity_count");
+                    entity_ids.write_u32(entity_id);
+                }
+                let columns = storage
+                    .plan()
+                    .layout
+                    .components()
+                    .iter()
+                    .filter_map(|&component_id| {
+                        let column = storage.column(component_id).ok()?;
+                        let mut hasher = StableHasher::new();
+                        for page_idx in 0..column.page_count() {
+                            hasher.write(column.page_bytes(page_idx));
+                        }
+                        Some((component_id, hasher.finish()))
+                    })
+                    .collect();
+                ArchetypeHash {
+                    archetype,
+                    entity_ids: entity_ids.finish(),
+                    columns,
+                }
+            })
+            .collect();
+
+        StateHashes {
+            entities: entities.finish(),
+            archetypes,
+        }
+    }
+
+    /// Checksum of every cell of `grid`, for replication acks.
+    ///
+    /// Each row contributes a stable hash of its archetype, entity id and
+    /// current-buffer component bytes; a cell's checksum is the wrapping sum
+    /// of its rows, so it does not depend on row order or on how rows are
+    /// spread over pages. Cells without entities are absent from the map.
+    /// Rows despawned but not yet flushed are left out, so nodes agreeing on
+    /// the live entities agree on the checksums whenever they flush.
+    pub fn checksum_cells<P: CellPartition>(&self, grid: &P) -> HashMap<P::Cell, u64> {
+        let mut checksums = HashMap::new();
+        let mut cells = Vec::new();
+        for archetype_id in self.archetypes_matching(grid.required_components()) {
+            let Some(entry) = self.storages.get(&archetype_id) else {
+                continue;
+            };
+            let storage = &entry.storage;
+            let pending = entry.sorted_pending_despawns();
+            cells.clear();
+            grid.assign_cells(storage, &mut cells);
+            debug_assert_eq!(cells.len(), storage.entity_count(), "one cell per row");
+            for (row, cell) in cells.iter().enumerate() {
+                let Some(cell) = *cell else {
+                    continue;
+                };
+                if pending.binary_search(&row).is_ok() {
+                    continue;
+                }
+                let mut hasher = StableHasher::new();
+                hasher.write_u64(archetype_id);
+                let entity_id = storage.entity_id_at(row).expect("row within entity_count");
+                hasher.write_u32(entity_id);
+                for column in storage.columns() {
+                    let bytes = column
+                        .slice_read(row..row + 1)
+                        .expect("row within column length");
+                    hasher.write(bytes);
+                }
+                let checksum = checksums.entry(cell).or_insert(0u64);
+                *checksum = checksum.wrapping_add(hasher.finish());
+            }
+        }
+        checksums
+    }
+
+    /// Rows of every cell of `grid`, from the current buffer; rows despawned
+    /// but not yet flushed are left out.
+    pub fn cell_index<P: CellPartition>(&self, grid: &P) -> CellIndex<P::Cell> {
+        let mut index = CellIndex::new();
+        let mut cells = Vec::new();
+        for archetype_id in self.archetypes_matching(grid.required_components()) {
+            let Some(entry) = self.storages.get(&archetype_id) else {
+                continue;
+            };
+            let storage = &entry.storage;
+            let pending = entry.sorted_pending_despawns();
+            cells.clear();
+            grid.assign_cells(storage, &mut cells);
+            debug_assert_eq!(cells.len(), storage.entity_count(), "one cell per row");
+            for (row, cell) in cells.iter().enumerate() {
+                if let Some(cell) = *cell {
+                    if pending.binary_search(&row).is_err() {
+                        index.push(cell, archetype_id, row);
+                    }
+                }
+            }
+        }
+        index
+    }
+
+    /// `for_each` restricted to entities inside `cells` of `grid`.
+    ///
+    /// `f` gets each matching archetype that has rows in those cells,
+    /// together with those rows in ascending order; rows elsewhere must be
+    /// left alone. Entities without the grid's position components are in
+    /// no cell and never visited. Builds a fresh `CellIndex`; systems that
+    /// share one per tick use `for_each_in_indexed_cells`.
+    pub fn for_each_in_cells<P: CellPartition>(
+        &mut self,
+        grid: &P,
+        cells: &[P::Cell],
+        component_ids: &[ComponentId],
+        f: impl FnMut(&mut ArchetypeStorage, &[usize]),
+    ) {
+        let index = self.cell_index(grid);
+        self.for_each_in_indexed_cells(&index, cells, component_ids, f);
+    }
+
+    /// `for_each_in_cells` over an index built earlier this tick.
+    pub fn for_each_in_indexed_cells<C: Copy + Eq + Hash>(
+        &mut self,
+        index: &CellIndex<C>,
+        cells: &[C],
+        component_ids: &[ComponentId],
+        mut f: impl FnMut(&mut ArchetypeStorage, &[usize]),
+    ) {
+        if component_ids.is_empty() {
+            return;
+        }
+        let query = ComponentSignature::from_components(component_ids);
+        let located = index.rows_in(cells);
+        let mut rows = Vec::new();
+        for run in located.chunk_by(|a, b| a.0 == b.0) {
+            let archetype_id = run[0].0;
+            let Some(entry) = self.storages.get_mut(&archetype_id) else {
+                continue;
+            };
+            if !entry.storage.plan().layout.matches(&query) {
+                continue;
+            }
+            let len = entry.storage.entity_count();
+            rows.clear();
+            rows.extend(run.iter().map(|&(_, row)| row).filter(|&row| row < len));
+            if !rows.is_empty() {
+                f(&mut entry.storage, &rows);
+            }
+        }
+    }
+
+    /// Install a config resource from a snapshot, or drop the live one if
+    /// the snapshot had none.
+    fn restore_config<R: Send + Sync + 'static>(&mut self, config: Option<R>) {
+        match config {
+            Some(config) => {
+                self.insert_resource(config);
+            }
+            None => {
+                self.remove_resource::<R>();
+            }
+        }
+    }
+
+    /// Uncompressed snapshot size, assuming raw component encodings.
+    fn snapshot_size_hint(&self) -> usize {
+        let rows: usize = self
+            .storages
+            .values()
+            .map(|entry| {
+                let stride: usize = entry
+                    .storage
+                    .plan()
+                    .columns
+                    .iter()
+                    .map(|column| column.meta.stride)
+                    .sum();
+                entry.storage.entity_count() * (stride + 4)
+            })
+            .sum();
+        12 + self.slots.len() * 5
+            + self.free_list.len() * 4
+            + rows
+            + 3
+            + PhysicsConfig::ENCODED_BYTES
+            + CollisionConfig::ENCODED_BYTES
+            + Rng::STATE_BYTES
+    }
+
+    /// Register an event queue for `E`, returning the existing one if present.
+    ///
+    /// Queues are swapped alongside component buffers in `swap_buffers`.
+    pub fn add_events<E: Copy + Send + Sync + 'static>(&mut self) -> &mut Events<E> {
+        self.events.register::<E>()
+    }
+
+    pub fn events<E: Copy + Send + Sync + 'static>(&self) -> Option<&Events<E>> {
+        self.events.get::<E>()
+    }
+
+    pub fn events_mut<E: Copy + Send + Sync + 'static>(&mut self) -> Option<&mut Events<E>> {
+        self.events.get_mut::<E>()
+    }
+
+    /// Queue `event` for delivery next tick, registering its queue on first use.
+    pub fn send_event<E: Copy + Send + Sync + 'static>(&mut self, event: E) {
+        self.events.register::<E>().send(event);
+    }
+
+    /// Store `value` as the world's `R` resource, returning the previous one.
+    pub fn insert_resource<R: Send + Sync + 'static>(&mut self, value: R) -> Option<R> {
+        self.resources.insert(value)
+    }
+
+    pub fn resource<R: Send + Sync + 'static>(&self) -> Option<&R> {
+        self.resources.get::<R>()
+    }
+
+    pub fn resource_mut<R: Send + Sync + 'static>(&mut self) -> Option<&mut R> {
+        self.resources.get_mut::<R>()
+    }
+
+    pub fn remove_resource<R: Send + Sync + 'static>(&mut self) -> Option<R> {
+        self.resources.remove::<R>()
+    }
+
+    /// Current-buffer bytes of `component_id` for every live entity, by
+    /// archetype in ascending id order, then by row.
+    ///
+    /// This is the read side of a custom `RelationAccelerator::rebuild`:
+    /// each value is `stride` bytes laid out as the component's
+    /// `ComponentMeta` describes. Rows despawned but not yet flushed are
+    /// skipped.
+    pub fn for_each_row(&self, component_id: ComponentId, mut visit: impl FnMut(Entity, &[u8])) {
+        self.for_each_located_row(component_id, |entity, _, bytes| visit(entity, bytes));
+    }
+
+    /// `for_each_row`, also passing where each row lives (for the
+    /// `RelationRecord` locations an accelerator emits).
+    pub fn for_each_located_row(
+        &self,
+        component_id: ComponentId,
+        mut visit: impl FnMut(Entity, RelationLocation, &[u8]),
+    ) {
+        for &archetype in self.archetypes_with(component_id) {
+            let Some(storage) = self.storage(archetype) else {
+                continue;
+            };
+            let Ok(column) = storage.column(component_id) else {
+                continue;
+            };
+            let stride = column.stride();
+            for page_idx in 0..column.page_count() {
+                let range = column.page_range(page_idx);
+                if range.is_empty() {
+                    continue;
+                }
+                let (Ok(entity_ids), Ok(bytes)) = (
+                    storage.entity_ids_slice(range.clone()),
+                    column.slice_read(range.clone()),
+                ) else {
+                    continue;
+                };
+                for (row, &entity_id) in entity_ids.iter().enumerate() {
+                    let Some(entity) = self.resolve_entity(entity_id) else {
+                        continue;
+                    };
+                    let base = row * stride;
+                    visit(
+                        entity,
+                        RelationLocation::new(archetype, range.start + row),
+                        &bytes[base..base + stride],
+                    );
+                }
+            }
+        }
     }
 
     pub fn for_each(
@@ -294,21 +1429,243 @@ impl World {
             return;
         }
 
-        let mut ids = component_ids.to_vec();
-        ids.sort_unstable();
-        ids.dedup();
+        let query = ComponentSignature::from_components(component_ids);
 
-        for entry in self.storages.values_mut() {
+        for archetype_id in &self.archetype_order {
+            let Some(entry) = self.storages.get_mut(archetype_id) else {
+                continue;
+            };
             if entry.storage.is_empty() {
                 continue;
             }
-            let layout_components = entry.storage.plan().layout.components();
-            if ids.iter().all(|id| layout_components.contains(id)) {
+            if entry.storage.plan().layout.matches(&query) {
                 f(&mut entry.storage);
             }
         }
     }
 
+    /// Like `for_each`, but stops at the first archetype whose callback fails
+    /// and returns that error, so systems can use `try_columns!` with `?`.
+    pub fn try_for_each<E>(
+        &mut self,
+        component_ids: &[ComponentId],
+        mut f: impl FnMut(&mut ArchetypeStorage) -> Result<(), E>,
+    ) -> Result<(), E> {
+        if component_ids.is_empty() {
+            return Ok(());
+        }
+
+        let query = ComponentSignature::from_components(component_ids);
+
+        for archetype_id in &self.archetype_order {
+            let Some(entry) = self.storages.get_mut(archetype_id) else {
+                continue;
+            };
+            if entry.storage.is_empty() {
+                continue;
+            }
+            if entry.storage.plan().layout.matches(&query) {
+                f(&mut entry.storage)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Typed `for_each` over every entity with `A`, one row at a time.
+    ///
+    /// `f` gets the entity id and a mutable reference into the next buffer,
+    /// pre-filled with the current value; whatever it leaves there becomes
+    /// visible after `swap_buffers`. Page tiling and column casts stay inside
+    /// the storage. As with `for_each`, rows despawned but not yet flushed
+    /// are visited too.
+    pub fn for_each_rw<A: Component + Copy>(
+        &mut self,
+        mut f: impl FnMut(EntityId, &mut A),
+    ) -> Result<(), WorldError> {
+        self.for_each_tile(&[A::id()], |tile| {
+            let a = tile.modify::<A>()?;
+            for (&id, a) in tile.entity_ids().iter().zip(a) {
+                f(id, a);
+            }
+            Ok(())
+        })
+    }
+
+    /// `for_each_rw` over entities with `A` and `B`. Fails with
+    /// `DuplicateColumnRequest` when `A` and `B` are the same component.
+    pub fn for_each_rw2<A: Component + Copy, B: Component + Copy>(
+        &mut self,
+        mut f: impl FnMut(EntityId, &mut A, &mut B),
+    ) -> Result<(), WorldError> {
+        self.for_each_tile(&[A::id(), B::id()], |tile| {
+            let a = tile.modify::<A>()?;
+            let b = tile.modify::<B>()?;
+            for ((&id, a), b) in tile.entity_ids().iter().zip(a).zip(b) {
+                f(id, a, b);
+            }
+            Ok(())
+        })
+    }
+
+    /// `for_each_rw` over entities with `A`, `B` and `C`.
+    pub fn for_each_rw3<A: Component + Copy, B: Component + Copy, C: Component + Copy>(
+        &mut self,
+        mut f: impl FnMut(EntityId, &mut A, &mut B, &mut C),
+    ) -> Result<(), WorldError> {
+        self.for_each_tile(&[A::id(), B::id(), C::id()], |tile| {
+            let a = tile.modify::<A>()?;
+            let b = tile.modify::<B>()?;
+            let c = tile.modify::<C>()?;
+            for (((&id, a), b), c) in tile.entity_ids().iter().zip(a).zip(b).zip(c) {
+                f(id, a, b, c);
+            }
+            Ok(())
+        })
+    }
+
+    /// `for_each_rw` over entities with `A`, `B`, `C` and `D`.
+    pub fn for_each_rw4<
+        A: Component + Copy,
+        B: Component + Copy,
+        C: Component + Copy,
+        D: Component + Copy,
+    >(
+        &mut self,
+        mut f: impl FnMut(EntityId, &mut A, &mut B, &mut C, &mut D),
+    ) -> Result<(), WorldError> {
+        self.for_each_tile(&[A::id(), B::id(), C::id(), D::id()], |tile| {
+            let a = tile.modify::<A>()?;
+            let b = tile.modify::<B>()?;
+            let c = tile.modify::<C>()?;
+            let d = tile.modify::<D>()?;
+            for ((((&id, a), b), c), d) in tile.entity_ids().iter().zip(a).zip(b).zip(c).zip(d) {
+                f(id, a, b, c, d);
+            }
+            Ok(())
+        })
+    }
+
+    /// Every page of every archetype matching `component_ids`, in iteration
+    /// order; stops at the first error.
+    fn for_each_tile(
+        &mut self,
+        component_ids: &[ComponentId],
+        mut f: impl FnMut(PageTile<'_>) -> Result<(), StorageError>,
+    ) -> Result<(), WorldError> {
+        self.try_for_each(component_ids, |storage| {
+            let mut result = Ok(());
+            storage.pages_mut(component_ids, |tile| {
+                if result.is_ok() {
+                    result = f(tile);
+                }
+            })?;
+            result
+        })?;
+        Ok(())
+    }
+
+    /// Read-only counterpart of `for_each` that visits matching archetypes in
+    /// parallel on the rayon pool.
+    ///
+    /// Storages are only borrowed shared, so any number of read passes (render
+    /// gather, accelerator rebuilds) can run without `&mut World`. Archetypes
+    /// are visited in no particular order; `f` must not depend on it.
+    pub fn par_for_each_read(
+        &self,
+        component_ids: &[ComponentId],
+        f: impl Fn(&ArchetypeStorage) + Send + Sync,
+    ) {
+        if component_ids.is_empty() {
+            return;
+        }
+
+        let query = ComponentSignature::from_components(component_ids);
+        let matching: Vec<&ArchetypeStorage> = self
+            .archetype_order
+            .iter()
+            .filter_map(|id| self.storages.get(id))
+            .map(|entry| &entry.storage)
+            .filter(|storage| !storage.is_empty() && storage.plan().layout.matches(&query))
+            .collect();
+
+        matching.into_par_iter().for_each(f);
+    }
+
+    /// Set component `T` of every entity that has it to `value`.
+    ///
+    /// Writes the next buffer of each matching archetype with
+    /// `ArchetypeStorage::fill_column`; the values are visible after the next
+    /// `swap_buffers`. Returns the number of rows written.
+    pub fn set_all<T: Component + Copy>(&mut self, value: T) -> Result<usize, WorldError> {
+        let Some(archetype_ids) = self.component_index.get(&<T as Component>::id()) else {
+            return Ok(0);
+        };
+        let mut written = 0;
+        for archetype_id in archetype_ids {
+            let entry =
+                self.storages
+                    .get_mut(archetype_id)
+                    .ok_or(WorldError::MissingArchetype {
+                        archetype_id: *archetype_id,
+                    })?;
+            entry.storage.fill_column(value)?;
+            written += entry.storage.entity_count();
+        }
+        Ok(written)
+    }
+
+    /// Update component `T` of every entity that has it, whatever its
+    /// archetype, returning the number of rows visited.
+    ///
+    /// `f` edits the next buffer, pre-filled with the current value, as in
+    /// `for_each_rw`; changes are visible after the next `swap_buffers`.
+    pub fn modify_all<T: Component + Copy>(
+        &mut self,
+        mut f: impl FnMut(&mut T),
+    ) -> Result<usize, WorldError> {
+        self.modify_all_with_entity(|_, value| f(value))
+    }
+
+    /// `modify_all` that also passes each row's entity id.
+    pub fn modify_all_with_entity<T: Component + Copy>(
+        &mut self,
+        mut f: impl FnMut(EntityId, &mut T),
+    ) -> Result<usize, WorldError> {
+        let mut visited = 0;
+        self.for_each_tile(&[T::id()], |tile| {
+            let values = tile.modify::<T>()?;
+            visited += values.len();
+            for (&id, value) in tile.entity_ids().iter().zip(values) {
+                f(id, value);
+            }
+            Ok(())
+        })?;
+        Ok(visited)
+    }
+
+    /// Matching archetypes for `component_ids`, kept for repeated
+    /// iteration. Allocates; see `QueryView` for when to prefer `for_each`.
+    pub fn query_view(&self, component_ids: &[ComponentId]) -> QueryView<'_> {
+        QueryView::new(self, component_ids)
+    }
+
+    /// Every live entity with `T` and its current value, in iteration order.
+    ///
+    /// Allocates the whole result; meant for editor and debug paths, not
+    /// per-tick systems (use `for_each` there).
+    pub fn collect_query<T: Component + Copy>(&self) -> Vec<(Entity, T)> {
+        self.query_view(&[T::id()])
+            .collect::<T>()
+            .expect("query view contains the queried component")
+    }
+
+    /// `collect_query` for entities with both `A` and `B`.
+    pub fn collect_query2<A: Component + Copy, B: Component + Copy>(&self) -> Vec<(Entity, A, B)> {
+        self.query_view(&[A::id(), B::id()])
+            .collect2::<A, B>()
+            .expect("query view contains the queried components")
+    }
+
     pub fn column<T: Component>(&self, archetype: ArchetypeId) -> Option<&[T]> {
         self.storages
             .get(&archetype)
@@ -319,6 +1676,71 @@ impl World {
         self.live_count
     }
 
+    /// Components and live entity count of every archetype, sorted by id.
+    ///
+    /// Rows queued for despawn are not counted, so the totals agree with
+    /// `entity_count` between `despawn` and `flush_despawns`.
+    pub fn archetype_stats(&self) -> Vec<(ArchetypeId, Vec<ComponentId>, usize)> {
+        self.archetype_order
+            .iter()
+            .filter_map(|&id| self.storages.get(&id).map(|entry| (id, entry)))
+            .map(|(id, entry)| {
+                (
+                    id,
+                    entry.storage.plan().layout.components().to_vec(),
+                    entry.live_len(),
+                )
+            })
+            .collect()
+    }
+
+    /// Number of live entities carrying `component_id`, across all archetypes.
+    pub fn component_population(&self, component_id: ComponentId) -> usize {
+        self.archetypes_with(component_id)
+            .iter()
+            .filter_map(|id| self.storages.get(id))
+            .map(ArchetypeEntry::live_len)
+            .sum()
+    }
+
+    /// GPU slot of a live entity, unchanged by row moves.
+    ///
+    /// Assigned on spawn and released on `despawn` (not on the later
+    /// flush), after which a new spawn may reuse it.
+    pub fn stable_index_of(&self, entity: Entity) -> Option<u32> {
+        self.validate(entity)?;
+        self.stable_indices.get(entity.index())
+    }
+
+    /// One past the highest stable index handed out; size GPU arrays
+    /// indexed by `stable_index_of` to at least this.
+    pub fn stable_index_capacity(&self) -> u32 {
+        self.stable_indices.capacity()
+    }
+
+    /// Released stable indices below `stable_index_capacity`; the last
+    /// entry is reused first. GPU slots listed here hold stale data.
+    pub fn free_stable_indices(&self) -> &[u32] {
+        self.stable_indices.free()
+    }
+
+    /// Fill every free stable index with an entity from the top of the
+    /// range, so `stable_index_capacity` drops to the live entity count.
+    ///
+    /// Returns each reassignment; copy GPU slot `from` to `to` for every
+    /// one, in order, before the next draw.
+    pub fn compact_stable_indices(&mut self) -> Vec<StableIndexMove> {
+        self.stable_indices
+            .compact()
+            .into_iter()
+            .map(|(entity_id, from, to)| StableIndexMove {
+                entity: Entity::new(entity_id, self.slots[entity_id as usize].generation),
+                from,
+                to,
+            })
+            .collect()
+    }
+
     pub fn resolve_entity(&self, entity_id: EntityId) -> Option<Entity> {
         let slot = self.slots.get(entity_id as usize)?;
         slot.location.as_ref()?;
@@ -334,25 +1756,101 @@ impl World {
         let plan = plan_archetype(layout.clone(), self.page_budget)?;
         let component_ids: Vec<ComponentId> =
             plan.columns.iter().map(|col| col.component_id).collect();
-        let storage = ArchetypeStorage::from_plan(plan);
+        let storage = match &self.page_allocator {
+            Some(allocator) => ArchetypeStorage::from_plan_in(plan, allocator.clone()),
+            None => ArchetypeStorage::from_plan(plan),
+        };
         self.storages
             .insert(archetype_id, ArchetypeEntry::new(storage));
+        insert_sorted(&mut self.archetype_order, archetype_id);
+        self.archetype_generation += 1;
         for component_id in component_ids {
-            self.component_index
-                .entry(component_id)
-                .or_default()
-                .push(archetype_id);
+            insert_sorted(
+                self.component_index.entry(component_id).or_default(),
+                archetype_id,
+            );
+        }
+        Ok(())
+    }
+
+    /// Write `blueprint` into a new row for `entity_id`; its archetype must
+    /// already exist.
+    fn place(
+        &mut self,
+        entity_id: EntityId,
+        blueprint: &EntityBlueprint,
+    ) -> Result<(), WorldError> {
+        self.place_components(
+            entity_id,
+            blueprint.layout().id(),
+            blueprint
+                .components()
+                .iter()
+                .map(|component| (component.component_id(), component.bytes())),
+        )
+    }
+
+    /// Write one value per component of `archetype_id` into a new row for
+    /// `entity_id`.
+    fn place_components<'a>(
+        &mut self,
+        entity_id: EntityId,
+        archetype_id: ArchetypeId,
+        components: impl IntoIterator<Item = (ComponentId, &'a [u8])>,
+    ) -> Result<(), WorldError> {
+        let row = {
+            let entry = self
+                .storages
+                .get_mut(&archetype_id)
+                .ok_or(WorldError::MissingArchetype { archetype_id })?;
+            let row = entry.storage.alloc_row(entity_id)?;
+            for (component_id, bytes) in components {
+                entry
+                    .storage
+                    .write_component(component_id, row, bytes, None)?;
+            }
+            row
+        };
+
+        self.record_location(
+            entity_id,
+            SlotLocation {
+                archetype: archetype_id,
+                row,
+            },
+        )?;
+        self.live_count += 1;
+        self.stable_indices.assign(entity_id);
+        let generation = self.slots[entity_id as usize].generation;
+        let entity = Entity::new(entity_id, generation);
+        self.spawned.push(entity);
+        if let Some(topology) = &mut self.topology {
+            topology.record_spawn(entity);
         }
         Ok(())
     }
 
+    fn reserved_slot(&mut self, entity: Entity) -> Result<&mut EntitySlot, WorldError> {
+        let slot = self
+            .slots
+            .get_mut(entity.index() as usize)
+            .ok_or(WorldError::UnknownEntity { entity })?;
+        if slot.generation != entity.generation() {
+            return Err(WorldError::StaleEntity { entity });
+        }
+        if !slot.reserved {
+            return Err(WorldError::NotReserved { entity });
+        }
+        Ok(slot)
+    }
+
     fn allocate_entity(&mut self) -> Result<(Entity, EntityId), WorldError> {
         let entity_id = if let Some(id) = self.free_list.pop() {
             id
         } else {
             let index = self.slots.len();
             let id = u32::try_from(index).map_err(|_| WorldError::EntityIndexOverflow { index })?;
-            self.slots.push(EntitySlot::new());
+            self.slots.push(EntitySlot::new(self.generation_floor));
             id
         };
 
@@ -386,7 +1884,9 @@ impl World {
             .ok_or(WorldError::UnknownEntityIndex { entity_id })?;
         debug_assert!(slot.location.is_none());
         slot.generation = slot.generation.wrapping_add(1);
-        self.free_list.push(entity_id);
+        if self.entity_id_policy == EntityIdPolicy::Reuse {
+            self.free_list.push(entity_id);
+        }
         Ok(())
     }
 
@@ -418,3 +1918,10 @@ impl Default for World {
         Self::new()
     }
 }
+
+/// Insert `id` into the ascending `ids`, keeping it sorted and unique.
+fn insert_sorted(ids: &mut Vec<ArchetypeId>, id: ArchetypeId) {
+    if let Err(position) = ids.binary_search(&id) {
+        ids.insert(position, id);
+    }
+}
@@ -0,0 +1,72 @@
+//! Up-front world setup for hosts that know their entity shapes.
+//!
+//! The first spawn of a new shape plans its archetype and every page
+//! boundary allocates; a server that knows it will hold 10 000 projectiles
+//! would rather pay for that while loading than mid-tick. `WorldBuilder`
+//! collects those shapes and `build` reserves them through `World::reserve`.
+
+use crate::ecs::{ComponentId, EntityIdPolicy, PageAllocator, PageBudget, World, WorldError};
+use std::sync::Arc;
+
+/// Builder for a `World` with archetypes planned and pages allocated.
+#[derive(Default)]
+pub struct WorldBuilder {
+    page_budget: Option<PageBudget>,
+    page_allocator: Option<Arc<dyn PageAllocator>>,
+    entity_id_policy: EntityIdPolicy,
+    archetypes: Vec<(Vec<ComponentId>, usize)>,
+}
+
+impl WorldBuilder {
+    /// Create a builder for an empty world with the detected page budget.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Plan pages with `budget` instead of `PageBudget::detect`.
+    pub fn page_budget(mut self, budget: PageBudget) -> Self {
+        self.page_budget = Some(budget);
+        self
+    }
+
+    /// Allocate column pages with `allocator`; see `World::set_page_allocator`.
+    pub fn page_allocator(mut self, allocator: Arc<dyn PageAllocator>) -> Self {
+        self.page_allocator = Some(allocator);
+        self
+    }
+
+    /// Choose how entity ids are allocated; networked and lockstep worlds
+    /// use `EntityIdPolicy::Monotonic` so every node assigns the same ids.
+    pub fn entity_id_policy(mut self, policy: EntityIdPolicy) -> Self {
+        self.entity_id_policy = policy;
+        self
+    }
+
+    /// Create the archetype of exactly `component_ids` and make room for
+    /// `expected_count` of its entities.
+    pub fn prealloc_archetype(
+        mut self,
+        component_ids: &[ComponentId],
+        expected_count: usize,
+    ) -> Self {
+        self.archetypes
+            .push((component_ids.to_vec(), expected_count));
+        self
+    }
+
+    /// Build the world; fails if a component is not registered.
+    pub fn build(self) -> Result<World, WorldError> {
+        let mut world = match self.page_budget {
+            Some(budget) => World::with_page_budget(budget),
+            None => World::new(),
+        };
+        world.set_entity_id_policy(self.entity_id_policy);
+        if let Some(allocator) = self.page_allocator {
+            world.set_page_allocator(allocator);
+        }
+        for (component_ids, expected_count) in &self.archetypes {
+            world.reserve(component_ids, *expected_count)?;
+        }
+        Ok(world)
+    }
+}
@@ -0,0 +1,59 @@
+//! Stable hashing for values that must agree across builds and hosts.
+//!
+//! `std`'s `DefaultHasher` may change algorithm between Rust releases and
+//! `Hash` impls feed it native-endian bytes, so nothing derived from it can
+//! be stored, replayed or compared with a peer.
+//! Archetype ids, state hashes, system phases and node buckets all use
+//! `StableHasher` instead: FNV-1a over little-endian input, fixed by spec.
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// FNV-1a over 64-bit little-endian words, byte-wise for the tail.
+///
+/// Folding whole words keeps it fast enough to run over every column each
+/// tick; inputs shorter than a word hash exactly as byte-wise FNV-1a.
+#[derive(Clone, Copy, Debug)]
+pub struct StableHasher(u64);
+
+impl StableHasher {
+    pub fn new() -> Self {
+        Self(FNV_OFFSET_BASIS)
+    }
+
+    pub fn write(&mut self, bytes: &[u8]) {
+        let mut words = bytes.chunks_exact(8);
+        for word in &mut words {
+            let word = u64::from_le_bytes(word.try_into().expect("chunk of 8 bytes"));
+            self.0 = (self.0 ^ word).wrapping_mul(FNV_PRIME);
+        }
+        for &byte in words.remainder() {
+            self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    pub fn write_u32(&mut self, value: u32) {
+        self.write(&value.to_le_bytes());
+    }
+
+    pub fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    pub fn finish(self) -> u64 {
+        self.0
+    }
+}
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `StableHasher` hash of `bytes`.
+pub fn stable_hash(bytes: &[u8]) -> u64 {
+    let mut hasher = StableHasher::new();
+    hasher.write(bytes);
+    hasher.finish()
+}
@@ -7,10 +7,13 @@
 //! - Memory management
 
 pub mod ecs;
+pub mod hash;
 pub mod math;
 pub mod memory;
+pub mod physics;
 pub mod pool;
 pub mod time;
+pub mod transform;
 
 // Re-export metrics from latch_metrics for convenience
 #[cfg(feature = "metrics")]
@@ -29,4 +32,12 @@ mod tests {
     fn version_is_set() {
         assert!(!VERSION.is_empty());
     }
+
+    #[test]
+    fn unit_tests_start_from_a_clean_registry() {
+        let _registry = ecs::reset_registry();
+        assert!(ecs::meta_of_name("LibTest::Marker").is_none());
+        ecs::register_component("LibTest::Marker", 4, 4, 4, true, Vec::new()).unwrap();
+        assert_eq!(ecs::meta_of_name("LibTest::Marker").unwrap().size, 4);
+    }
 }
@@ -4,30 +4,239 @@
 
 pub use glam::*;
 
-/// Deterministic random number generator (placeholder)
-pub struct DeterministicRng {
-    #[allow(dead_code)] // Will be used for re-seeding in full implementation
-    seed: u64,
+pub mod fixed_trig;
+
+use serde::{Deserialize, Serialize};
+
+/// Seedable PCG32 (XSH-RR) generator for simulation-affecting randomness.
+///
+/// Only integer arithmetic is used to advance the state, so a given seed
+/// yields the same sequence on every platform. The full state is exposed
+/// through `to_bytes`/`from_bytes` (and serde); kept as a `World` resource,
+/// it is stored in world snapshots and restored exactly on replay or rewind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Rng {
     state: u64,
+    inc: u64,
 }
 
-impl DeterministicRng {
+impl Rng {
+    const MULTIPLIER: u64 = 6_364_136_223_846_793_005;
+    const DEFAULT_STREAM: u64 = 0xda3e_39cb_94b9_5bdb;
+
+    /// Serialized size of the generator state in bytes.
+    pub const STATE_BYTES: usize = 16;
+
     pub fn new(seed: u64) -> Self {
-        Self { seed, state: seed }
+        Self::with_stream(seed, Self::DEFAULT_STREAM)
+    }
+
+    /// Create a generator on an explicit stream; different streams with the
+    /// same seed produce independent sequences (e.g. one stream per system).
+    pub fn with_stream(seed: u64, stream: u64) -> Self {
+        let mut rng = Self {
+            state: 0,
+            inc: (stream << 1) | 1,
+        };
+        rng.next_u32();
+        rng.state = rng.state.wrapping_add(seed);
+        rng.next_u32();
+        rng
     }
 
-    /// Simple deterministic pseudo-random (use better algorithm in production)
     pub fn next_u32(&mut self) -> u32 {
-        // LCG constants
-        const A: u64 = 1664525;
-        const C: u64 = 1013904223;
-        const M: u64 = 1u64 << 32;
+        let old = self.state;
+        self.state = old.wrapping_mul(Self::MULTIPLIER).wrapping_add(self.inc);
+        let xorshifted = (((old >> 18) ^ old) >> 27) as u32;
+        let rot = (old >> 59) as u32;
+        xorshifted.rotate_right(rot)
+    }
+
+    /// Uniform integer in the half-open range `[min, max)`.
+    ///
+    /// Returns `min` when the range is empty. Uses rejection sampling so the
+    /// result is unbiased.
+    pub fn next_i32_range(&mut self, min: i32, max: i32) -> i32 {
+        if max <= min {
+            return min;
+        }
+        let span = (max as i64 - min as i64) as u32;
+        let threshold = span.wrapping_neg() % span;
+        loop {
+            let r = self.next_u32();
+            if r >= threshold {
+                return (min as i64 + (r % span) as i64) as i32;
+            }
+        }
+    }
+
+    /// Uniform float in `[0, 1)` built from 24 random bits (exact on all platforms).
+    pub fn next_f32_unit(&mut self) -> f32 {
+        (self.next_u32() >> 8) as f32 * (1.0 / (1u32 << 24) as f32)
+    }
 
-        self.state = (A.wrapping_mul(self.state).wrapping_add(C)) % M;
-        self.state as u32
+    /// Encode the generator state (little-endian) for snapshots.
+    pub fn to_bytes(&self) -> [u8; Self::STATE_BYTES] {
+        let mut bytes = [0u8; Self::STATE_BYTES];
+        bytes[..8].copy_from_slice(&self.state.to_le_bytes());
+        bytes[8..].copy_from_slice(&self.inc.to_le_bytes());
+        bytes
     }
 
-    pub fn next_f32(&mut self) -> f32 {
-        self.next_u32() as f32 / u32::MAX as f32
+    /// Restore a generator previously encoded with `to_bytes`.
+    pub fn from_bytes(bytes: [u8; Self::STATE_BYTES]) -> Self {
+        let mut state = [0u8; 8];
+        let mut inc = [0u8; 8];
+        state.copy_from_slice(&bytes[..8]);
+        inc.copy_from_slice(&bytes[8..]);
+        Self {
+            state: u64::from_le_bytes(state),
+            // The increment must stay odd for the LCG to have full period.
+            inc: u64::from_le_bytes(inc) | 1,
+        }
     }
 }
+
+// ============================================================================
+// Fixed-point geometry
+// ============================================================================
+
+/// Number of fractional bits in Q16 fixed-point values.
+pub const Q16_SHIFT: u32 = 16;
+/// 1.0 in Q16 fixed point.
+pub const Q16_ONE: i32 = 1 << Q16_SHIFT;
+
+/// Convert a configuration scalar (friction, damping) to Q16.
+///
+/// Do this once per tick, outside the hot loop: IEEE multiplication and
+/// rounding are exact and portable, so the result is identical everywhere.
+#[inline]
+pub fn to_q16(value: f32) -> i32 {
+    (value as f64 * Q16_ONE as f64).round() as i32
+}
+
+/// `numerator / denominator` rounded to nearest, ties away from zero.
+#[inline]
+pub fn div_round(numerator: i64, denominator: i64) -> i64 {
+    debug_assert!(denominator != 0);
+    let half = denominator.abs() / 2;
+    if (numerator < 0) != (denominator < 0) {
+        (numerator - half * denominator.signum()) / denominator
+    } else {
+        (numerator + half * denominator.signum()) / denominator
+    }
+}
+
+/// Multiply an integer by a Q16 factor, rounding to nearest.
+#[inline]
+pub fn mul_q16(value: i64, factor_q16: i64) -> i64 {
+    div_round(value * factor_q16, Q16_ONE as i64)
+}
+
+/// Floor of the square root of `n`, using integer arithmetic only.
+pub fn isqrt(n: u64) -> u64 {
+    if n < 2 {
+        return n;
+    }
+    // Start from a power of two above the root and descend with Newton steps.
+    let mut x = 1u64 << (64 - n.leading_zeros()).div_ceil(2);
+    loop {
+        let next = (x + n / x) / 2;
+        if next >= x {
+            return x;
+        }
+        x = next;
+    }
+}
+
+/// Euclidean length of `(dx, dy)`, floored to an integer.
+#[inline]
+pub fn length_i32(dx: i32, dy: i32) -> u64 {
+    let (dx, dy) = (dx.unsigned_abs() as u64, dy.unsigned_abs() as u64);
+    isqrt(dx * dx + dy * dy)
+}
+
+/// Unit vector along `(dx, dy)` in Q16, or `None` for the zero vector.
+pub fn normalize_q16(dx: i32, dy: i32) -> Option<[i32; 2]> {
+    let length = length_i32(dx, dy) as i64;
+    if length == 0 {
+        return None;
+    }
+    Some([
+        div_round(dx as i64 * Q16_ONE as i64, length) as i32,
+        div_round(dy as i64 * Q16_ONE as i64, length) as i32,
+    ])
+}
+
+/// Position and velocity change from resolving one circle-circle contact.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ContactResponse {
+    /// Contact normal (from the neighbour towards this body) in Q16.
+    pub normal: [i32; 2],
+    /// Overlap depth in game units.
+    pub penetration: i32,
+    /// Correction to add to this body's position (half the overlap).
+    pub position: [i32; 2],
+    /// Change to add to this body's velocity.
+    pub velocity: [i32; 2],
+}
+
+/// Resolve overlap between two equal circles entirely in integer math.
+///
+/// `separation` is this body's position minus the neighbour's and
+/// `velocity` this body's velocity. Each body is pushed out by half the
+/// overlap along the normal, approaching normal velocity is removed, and
+/// `friction_q16` (see `to_q16`) of the tangential velocity is removed.
+///
+/// Returns `None` when the circles do not overlap or are (nearly) coincident.
+/// The result depends only on the integer inputs, so replays match bit for
+/// bit on every CPU.
+pub fn resolve_circle_contact(
+    separation: [i32; 2],
+    velocity: [i32; 2],
+    diameter: i32,
+    friction_q16: i32,
+) -> Option<ContactResponse> {
+    let (dx, dy) = (separation[0] as i64, separation[1] as i64);
+    let dist = length_i32(separation[0], separation[1]) as i64;
+    if dist <= 1 {
+        return None;
+    }
+    let penetration = diameter as i64 - dist;
+    if penetration <= 0 {
+        return None;
+    }
+
+    let one = Q16_ONE as i64;
+    let nx = div_round(dx * one, dist);
+    let ny = div_round(dy * one, dist);
+    let position = [
+        div_round(nx * penetration, 2 * one) as i32,
+        div_round(ny * penetration, 2 * one) as i32,
+    ];
+
+    let (mut vx, mut vy) = (velocity[0] as i64, velocity[1] as i64);
+    let normal_speed = div_round(vx * nx + vy * ny, one);
+    if normal_speed < 0 {
+        vx -= mul_q16(normal_speed, nx);
+        vy -= mul_q16(normal_speed, ny);
+    }
+
+    let (tx, ty) = (-ny, nx);
+    let tangent_speed = div_round(vx * tx + vy * ty, one);
+    if tangent_speed != 0 {
+        let impulse = mul_q16(tangent_speed, friction_q16 as i64);
+        vx -= mul_q16(impulse, tx);
+        vy -= mul_q16(impulse, ty);
+    }
+
+    Some(ContactResponse {
+        normal: [nx as i32, ny as i32],
+        penetration: penetration as i32,
+        position,
+        velocity: [
+            (vx - velocity[0] as i64) as i32,
+            (vy - velocity[1] as i64) as i32,
+        ],
+    })
+}
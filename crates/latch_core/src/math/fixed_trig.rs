@@ -0,0 +1,100 @@
+//! Deterministic sine, cosine and rotation in fixed point.
+//!
+//! `f32::sin` may differ in the last bit between platforms and libm
+//! versions, which is harmless when placing entities at startup but breaks
+//! lockstep when gameplay rotates something every tick. Angles here are
+//! `u16` binary angles ("brads", `65536` per turn, wrapping naturally) and
+//! results are Q16, read from a quarter-wave table that is computed at
+//! compile time with integer arithmetic only, so every build of the engine
+//! holds the same values.
+
+use super::{div_round, Q16_ONE};
+
+/// A quarter turn (90°) in brads.
+pub const QUARTER_TURN: u16 = 0x4000;
+/// Half a turn (180°) in brads.
+pub const HALF_TURN: u16 = 0x8000;
+
+/// Table entries per quarter turn; the low brad bits interpolate between
+/// them.
+const TABLE_STEPS: usize = 1024;
+const STEP_BITS: u32 = QUARTER_TURN.trailing_zeros() - TABLE_STEPS.trailing_zeros();
+/// Fractional bits of the table computation.
+const CALC_SHIFT: u32 = 40;
+/// π/2 in Q40.
+const HALF_PI_Q40: i128 = 1_727_108_826_179;
+
+/// `sin` at every table step of the first quadrant, in Q16.
+static SIN_TABLE: [i32; TABLE_STEPS + 1] = sin_table();
+
+const fn sin_table() -> [i32; TABLE_STEPS + 1] {
+    let mut table = [0i32; TABLE_STEPS + 1];
+    let mut step = 0;
+    while step <= TABLE_STEPS {
+        // Taylor series in Q40; 12 terms converge far below Q16 resolution
+        // on [0, π/2].
+        let x = HALF_PI_Q40 * step as i128 / TABLE_STEPS as i128;
+        let x2 = (x * x) >> CALC_SHIFT;
+        let mut term = x;
+        let mut sum = x;
+        let mut n = 1;
+        while n <= 12 {
+            term = -((term * x2) >> CALC_SHIFT) / ((2 * n) * (2 * n + 1));
+            sum += term;
+            n += 1;
+        }
+        let shift = CALC_SHIFT - super::Q16_SHIFT;
+        table[step] = ((sum + (1 << (shift - 1))) >> shift) as i32;
+        step += 1;
+    }
+    table
+}
+
+/// `sin` of a first-quadrant angle (`0..=QUARTER_TURN`).
+fn quadrant_sin(angle: u32) -> i32 {
+    let step = (angle >> STEP_BITS) as usize;
+    let frac = (angle & ((1 << STEP_BITS) - 1)) as i32;
+    let low = SIN_TABLE[step];
+    if frac == 0 {
+        return low;
+    }
+    let high = SIN_TABLE[step + 1];
+    low + (((high - low) * frac + (1 << (STEP_BITS - 1))) >> STEP_BITS)
+}
+
+/// Sine of `angle` (brads) in Q16.
+pub fn sin_q16(angle: u16) -> i32 {
+    let within = (angle % QUARTER_TURN) as u32;
+    let quarter = QUARTER_TURN as u32;
+    match angle / QUARTER_TURN {
+        0 => quadrant_sin(within),
+        1 => quadrant_sin(quarter - within),
+        2 => -quadrant_sin(within),
+        _ => -quadrant_sin(quarter - within),
+    }
+}
+
+/// Cosine of `angle` (brads) in Q16.
+pub fn cos_q16(angle: u16) -> i32 {
+    sin_q16(angle.wrapping_add(QUARTER_TURN))
+}
+
+/// Rotate `v` counter-clockwise by `angle` (brads), rounding to nearest.
+pub fn rotate(v: [i32; 2], angle: u16) -> [i32; 2] {
+    let (sin, cos) = (sin_q16(angle) as i64, cos_q16(angle) as i64);
+    let (x, y) = (v[0] as i64, v[1] as i64);
+    let one = Q16_ONE as i64;
+    [
+        div_round(x * cos - y * sin, one) as i32,
+        div_round(x * sin + y * cos, one) as i32,
+    ]
+}
+
+/// Convert a configuration angle in radians to brads, wrapping.
+///
+/// Like `to_q16`, do this outside the simulation: the conversion itself is
+/// exact IEEE arithmetic, so the result is the same everywhere.
+pub fn to_brads(radians: f32) -> u16 {
+    let turns = radians as f64 / std::f64::consts::TAU;
+    (turns * 65_536.0).round() as i64 as u16
+}
@@ -0,0 +1,198 @@
+//! Per-tick bump allocator
+//!
+//! Systems build short-lived scratch (entity id lists, instance staging)
+//! every tick. `FrameArena` hands those out from a reused backing store and
+//! frees everything at once with `reset`, so steady-state ticks do no heap
+//! allocation at all.
+
+use std::alloc::{self, Layout};
+use std::cell::{Cell, RefCell};
+use std::ptr::NonNull;
+use std::slice;
+
+/// Default size of the first chunk when none is given.
+const DEFAULT_CHUNK_BYTES: usize = 64 * 1024;
+/// Chunk base alignment (one cache line).
+const CHUNK_ALIGN: usize = 64;
+
+struct ArenaChunk {
+    ptr: NonNull<u8>,
+    capacity: usize,
+}
+
+impl ArenaChunk {
+    fn new(capacity: usize) -> Self {
+        let layout = Self::layout(capacity);
+        // SAFETY: capacity is non-zero, so the layout has non-zero size.
+        let ptr = unsafe { alloc::alloc(layout) };
+        let ptr = NonNull::new(ptr).unwrap_or_else(|| alloc::handle_alloc_error(layout));
+        Self { ptr, capacity }
+    }
+
+    fn layout(capacity: usize) -> Layout {
+        Layout::from_size_align(capacity, CHUNK_ALIGN).expect("arena chunk too large")
+    }
+}
+
+impl Drop for ArenaChunk {
+    fn drop(&mut self) {
+        // SAFETY: allocated in `new` with the same layout.
+        unsafe { alloc::dealloc(self.ptr.as_ptr(), Self::layout(self.capacity)) };
+    }
+}
+
+/// Bump allocator for per-tick scratch memory.
+///
+/// Allocation takes `&self`, so an arena can be captured by reference inside
+/// `World::for_each` closures and hand out several live slices at once.
+/// `reset` takes `&mut self`, which the borrow checker uses to guarantee no
+/// slice outlives the frame it was allocated in.
+///
+/// When a frame outgrows the current chunk, another chunk is added (existing
+/// slices never move). On the next `reset` all chunks are merged into one of
+/// the combined size, so after a warm-up frame the arena is a single block
+/// that is reused as-is.
+///
+/// Only `Copy` types are supported: nothing is dropped on reset.
+///
+/// ```
+/// use latch_core::memory::FrameArena;
+///
+/// let mut arena = FrameArena::new();
+/// let ids = arena.alloc_slice::<u32>(4);
+/// ids.copy_from_slice(&[1, 2, 3, 4]);
+/// arena.reset();
+/// ```
+pub struct FrameArena {
+    chunks: RefCell<Vec<ArenaChunk>>,
+    /// Byte offset of the next free byte in the last chunk.
+    cursor: Cell<usize>,
+    /// Bytes handed out since the last reset, including alignment padding.
+    used: Cell<usize>,
+    initial_capacity: usize,
+}
+
+// SAFETY: the arena exclusively owns its chunks; moving it to another thread
+// moves that ownership. It is not `Sync` (interior `Cell`s).
+unsafe impl Send for FrameArena {}
+
+impl FrameArena {
+    /// Create an empty arena; the first chunk is allocated lazily.
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_CHUNK_BYTES)
+    }
+
+    /// Create an arena whose first chunk holds `bytes` bytes.
+    pub fn with_capacity(bytes: usize) -> Self {
+        Self {
+            chunks: RefCell::new(Vec::new()),
+            cursor: Cell::new(0),
+            used: Cell::new(0),
+            initial_capacity: bytes.max(CHUNK_ALIGN),
+        }
+    }
+
+    /// Allocate `len` default-initialized values.
+    #[allow(clippy::mut_from_ref)] // each call returns fresh, disjoint memory
+    pub fn alloc_slice<T: Copy + Default>(&self, len: usize) -> &mut [T] {
+        self.alloc_slice_fill(len, T::default())
+    }
+
+    /// Allocate `len` copies of `value`.
+    #[allow(clippy::mut_from_ref)] // each call returns fresh, disjoint memory
+    pub fn alloc_slice_fill<T: Copy>(&self, len: usize, value: T) -> &mut [T] {
+        let ptr = self.alloc_raw::<T>(len);
+        // SAFETY: `ptr` is valid, aligned and exclusive for `len` values of T;
+        // every element is written before the slice is formed.
+        unsafe {
+            for i in 0..len {
+                ptr.as_ptr().add(i).write(value);
+            }
+            slice::from_raw_parts_mut(ptr.as_ptr(), len)
+        }
+    }
+
+    /// Allocate a copy of `src`.
+    #[allow(clippy::mut_from_ref)] // each call returns fresh, disjoint memory
+    pub fn alloc_slice_copy<T: Copy>(&self, src: &[T]) -> &mut [T] {
+        let ptr = self.alloc_raw::<T>(src.len());
+        // SAFETY: destination is fresh arena memory, so it cannot overlap `src`.
+        unsafe {
+            ptr.as_ptr()
+                .copy_from_nonoverlapping(src.as_ptr(), src.len());
+            slice::from_raw_parts_mut(ptr.as_ptr(), src.len())
+        }
+    }
+
+    /// Release every allocation and keep the backing memory for the next frame.
+    pub fn reset(&mut self) {
+        let chunks = self.chunks.get_mut();
+        if chunks.len() > 1 {
+            let total: usize = chunks.iter().map(|chunk| chunk.capacity).sum();
+            chunks.clear();
+            chunks.push(ArenaChunk::new(total));
+        }
+        self.cursor.set(0);
+        self.used.set(0);
+    }
+
+    /// Bytes handed out since the last reset (including alignment padding).
+    #[inline]
+    pub fn allocated_bytes(&self) -> usize {
+        self.used.get()
+    }
+
+    /// Total bytes of backing memory currently owned.
+    pub fn capacity(&self) -> usize {
+        self.chunks
+            .borrow()
+            .iter()
+            .map(|chunk| chunk.capacity)
+            .sum()
+    }
+
+    fn alloc_raw<T>(&self, len: usize) -> NonNull<T> {
+        let layout = Layout::array::<T>(len).expect("arena allocation too large");
+        if layout.size() == 0 {
+            return NonNull::dangling();
+        }
+
+        let mut chunks = self.chunks.borrow_mut();
+        if let Some(chunk) = chunks.last() {
+            if let Some(ptr) = self.bump(chunk, layout) {
+                return ptr.cast();
+            }
+        }
+
+        let needed = layout.size() + layout.align();
+        let grown = chunks
+            .last()
+            .map_or(self.initial_capacity, |chunk| chunk.capacity * 2);
+        chunks.push(ArenaChunk::new(grown.max(needed).next_power_of_two()));
+        self.cursor.set(0);
+        let chunk = chunks.last().expect("chunk just pushed");
+        self.bump(chunk, layout)
+            .expect("fresh chunk fits the allocation")
+            .cast()
+    }
+
+    fn bump(&self, chunk: &ArenaChunk, layout: Layout) -> Option<NonNull<u8>> {
+        let base = chunk.ptr.as_ptr() as usize;
+        let cursor = self.cursor.get();
+        let start = (base + cursor).checked_next_multiple_of(layout.align())?;
+        let end = start.checked_add(layout.size())?;
+        if end > base + chunk.capacity {
+            return None;
+        }
+        self.cursor.set(end - base);
+        self.used.set(self.used.get() + (end - base - cursor));
+        // SAFETY: `start - base` is within the chunk's allocation.
+        Some(unsafe { NonNull::new_unchecked(chunk.ptr.as_ptr().add(start - base)) })
+    }
+}
+
+impl Default for FrameArena {
+    fn default() -> Self {
+        Self::new()
+    }
+}
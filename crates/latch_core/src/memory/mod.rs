@@ -2,6 +2,10 @@
 //!
 //! Arena allocators, tracking, and budgets
 
+mod frame_arena;
+
+pub use frame_arena::FrameArena;
+
 /// Per-frame allocation tracker (placeholder)
 pub struct AllocationTracker {
     frame_allocations: usize,
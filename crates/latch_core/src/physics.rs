@@ -0,0 +1,146 @@
+//! Simulation parameters shared by movement and collision systems
+//!
+//! Stored as `World` resources so they can be tuned at runtime (e.g. from
+//! editor sliders) and persisted alongside the world for exact replays.
+//! `PhysicsConfig` describes the world; `CollisionConfig` the contact
+//! solver's cost/stability trade-off.
+
+use serde::{Deserialize, Serialize};
+
+/// Gravity, damping, friction and world bounds, in integer game units.
+///
+/// Systems read this from `World::resource::<PhysicsConfig>()` each tick
+/// rather than compiling the values in. `World::snapshot` stores it with
+/// `to_bytes` and `World::restore` reinstates it, so a replay runs with the
+/// constants that were active when it was recorded.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PhysicsConfig {
+    /// Vertical acceleration in units per tick² (negative pulls down).
+    pub gravity: i32,
+    /// Velocity multiplier applied after collision response each tick.
+    pub linear_damping: f32,
+    /// Fraction of relative tangential velocity removed on contact.
+    pub tangent_friction: f32,
+    /// Lower-left corner of the simulation area (x walls, floor).
+    pub bounds_min: [i32; 2],
+    /// Upper-right corner of the simulation area.
+    pub bounds_max: [i32; 2],
+}
+
+impl PhysicsConfig {
+    /// Serialized size in bytes.
+    pub const ENCODED_BYTES: usize = 28;
+
+    /// Encode the configuration (little-endian) for snapshots.
+    pub fn to_bytes(&self) -> [u8; Self::ENCODED_BYTES] {
+        let words = [
+            self.gravity as u32,
+            self.linear_damping.to_bits(),
+            self.tangent_friction.to_bits(),
+            self.bounds_min[0] as u32,
+            self.bounds_min[1] as u32,
+            self.bounds_max[0] as u32,
+            self.bounds_max[1] as u32,
+        ];
+        let mut bytes = [0u8; Self::ENCODED_BYTES];
+        for (chunk, word) in bytes.chunks_exact_mut(4).zip(words) {
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Restore a configuration previously encoded with `to_bytes`.
+    pub fn from_bytes(bytes: [u8; Self::ENCODED_BYTES]) -> Self {
+        let mut words = [0u32; 7];
+        for (word, chunk) in words.iter_mut().zip(bytes.chunks_exact(4)) {
+            *word = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        }
+        Self {
+            gravity: words[0] as i32,
+            linear_damping: f32::from_bits(words[1]),
+            tangent_friction: f32::from_bits(words[2]),
+            bounds_min: [words[3] as i32, words[4] as i32],
+            bounds_max: [words[5] as i32, words[6] as i32],
+        }
+    }
+}
+
+impl Default for PhysicsConfig {
+    /// Values of the falling-sand demo: 1 m = 100 000 units, a 4 m wide
+    /// area with the floor 1 m below the origin and no ceiling.
+    fn default() -> Self {
+        Self {
+            gravity: -50,
+            linear_damping: 0.96,
+            tangent_friction: 0.2,
+            bounds_min: [-200_000, -100_000],
+            bounds_max: [200_000, i32::MAX],
+        }
+    }
+}
+
+/// Contact solver parameters.
+///
+/// The collision system resolves every contact `iterations` times per tick;
+/// more passes settle stacks more stably at proportionally higher cost.
+/// Read from `World::resource::<CollisionConfig>()` each tick and stored in
+/// world snapshots (and therefore save files) like `PhysicsConfig`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CollisionConfig {
+    /// Solver passes per tick; 0 is treated as 1.
+    pub iterations: u32,
+    /// Fraction of each contact's overlap pushed out per pass. Below 1.0
+    /// spreads the correction over several passes, reducing jitter.
+    pub correction: f32,
+    /// Fraction of each contact's velocity response (approach speed and
+    /// friction) applied per pass. Below 1.0 gives softer contacts.
+    pub contact_damping: f32,
+}
+
+impl CollisionConfig {
+    /// Serialized size in bytes.
+    pub const ENCODED_BYTES: usize = 12;
+
+    /// `iterations`, at least 1.
+    pub fn passes(&self) -> u32 {
+        self.iterations.max(1)
+    }
+
+    /// Encode the configuration (little-endian) for snapshots.
+    pub fn to_bytes(&self) -> [u8; Self::ENCODED_BYTES] {
+        let words = [
+            self.iterations,
+            self.correction.to_bits(),
+            self.contact_damping.to_bits(),
+        ];
+        let mut bytes = [0u8; Self::ENCODED_BYTES];
+        for (chunk, word) in bytes.chunks_exact_mut(4).zip(words) {
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Restore a configuration previously encoded with `to_bytes`.
+    pub fn from_bytes(bytes: [u8; Self::ENCODED_BYTES]) -> Self {
+        let mut words = [0u32; 3];
+        for (word, chunk) in words.iter_mut().zip(bytes.chunks_exact(4)) {
+            *word = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        }
+        Self {
+            iterations: words[0],
+            correction: f32::from_bits(words[1]),
+            contact_damping: f32::from_bits(words[2]),
+        }
+    }
+}
+
+impl Default for CollisionConfig {
+    /// Ten full-strength passes, as the falling-sand demo has always run.
+    fn default() -> Self {
+        Self {
+            iterations: 10,
+            correction: 1.0,
+            contact_damping: 1.0,
+        }
+    }
+}
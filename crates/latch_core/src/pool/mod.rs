@@ -1,4 +1,5 @@
 pub mod page;
+#[allow(clippy::module_inception)] // keeps the public `pool::pool` path stable
 pub mod pool;
 
 pub use pool::{PagedPool, PoolError};
@@ -38,6 +38,11 @@ impl<T> Page<T> {
         self.len
     }
 
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
     #[inline]
     pub fn is_full(&self) -> bool {
         self.len == self.capacity()
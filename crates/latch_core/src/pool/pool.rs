@@ -28,6 +28,8 @@ pub struct PagedPool<T> {
     shift: u32,
     mask: usize,
     pages: Vec<Page<T>>,
+    /// Empty pages from `reserve`, taken before allocating new ones.
+    spare: Vec<Page<T>>,
 }
 
 impl<T> PagedPool<T> {
@@ -38,6 +40,7 @@ impl<T> PagedPool<T> {
             shift: rows_per_page.trailing_zeros(),
             mask: rows_per_page - 1,
             pages: Vec::new(),
+            spare: Vec::new(),
         }
     }
 
@@ -117,6 +120,30 @@ impl<T> PagedPool<T> {
         self.len_total() == 0
     }
 
+    /// Rows backed by currently allocated pages, including reserved ones.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        (self.pages.len() + self.spare.len()) * self.rows_per_page
+    }
+
+    /// Allocate pages up front so `additional` more rows fit without
+    /// allocating.
+    pub fn reserve(&mut self, additional: usize) {
+        let rows = self.len_total().saturating_add(additional);
+        let pages = rows.div_ceil(self.rows_per_page);
+        let missing = pages.saturating_sub(self.pages.len() + self.spare.len());
+        self.spare.reserve(missing);
+        for _ in 0..missing {
+            self.spare.push(Page::with_capacity(self.rows_per_page));
+        }
+    }
+
+    /// Drop reserved pages and spare page-table capacity.
+    pub fn shrink_to_fit(&mut self) {
+        self.spare = Vec::new();
+        self.pages.shrink_to_fit();
+    }
+
     fn ensure_page_with_space(&mut self) -> usize {
         if let Some((idx, _)) = self
             .pages
@@ -128,7 +155,11 @@ impl<T> PagedPool<T> {
             idx
         } else {
             let idx = self.pages.len();
-            self.pages.push(Page::with_capacity(self.rows_per_page));
+            let page = self
+                .spare
+                .pop()
+                .unwrap_or_else(|| Page::with_capacity(self.rows_per_page));
+            self.pages.push(page);
             idx
         }
     }
@@ -275,9 +306,65 @@ impl<T> PagedPool<T> {
             })?;
         }
 
-        while self.pages.last().map_or(false, |p| p.len() == 0) {
+        while self.pages.last().is_some_and(|p| p.is_empty()) {
+            self.pages.pop();
+        }
+        Ok(())
+    }
+
+    /// Swap-remove across the whole pool: the globally last row fills
+    /// `gidx`, so every page except the last stays full. This matches how
+    /// archetype columns remove rows, unlike `free_one_swap_remove`, which
+    /// only fills holes from within the same page.
+    pub fn free_one_swap_remove_dense(
+        &mut self,
+        gidx: usize,
+    ) -> Result<Option<(usize, usize)>, PoolError>
+    where
+        T: Copy,
+    {
+        let len = self.len_total();
+        if gidx >= len {
+            return Err(PoolError::IndexOutOfBounds { index: gidx, len });
+        }
+        let last = len - 1;
+        let moved = if gidx != last {
+            let value = *self.get(last)?;
+            *self.get_mut(gidx)? = value;
+            Some((last, gidx))
+        } else {
+            None
+        };
+
+        let page_idx = self.page_of(last);
+        let local = self.local_of(last);
+        self.pages
+            .get_mut(page_idx)
+            .ok_or(PoolError::PageMissing { page: page_idx })?
+            .free_one(local)?;
+        while self.pages.last().is_some_and(|p| p.is_empty()) {
             self.pages.pop();
         }
+        Ok(moved)
+    }
+
+    /// Bulk form of `free_one_swap_remove_dense`; reports each `(from, to)`
+    /// move in ascending order of `from`'s removal.
+    pub fn free_bulk_swap_remove_dense(
+        &mut self,
+        mut gidxs: Vec<usize>,
+        mut fix_index: impl FnMut(usize, usize),
+    ) -> Result<(), PoolError>
+    where
+        T: Copy,
+    {
+        gidxs.sort_unstable();
+        gidxs.dedup();
+        for &gidx in gidxs.iter().rev() {
+            if let Some((from, to)) = self.free_one_swap_remove_dense(gidx)? {
+                fix_index(from, to);
+            }
+        }
         Ok(())
     }
 }
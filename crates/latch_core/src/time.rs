@@ -1,17 +1,24 @@
 //! Deterministic time system
 //!
-//! Fixed 60Hz tick rate with interpolation for rendering
+//! Fixed-rate ticks (60Hz by default, configurable per `SimulationTime`)
+//! with interpolation for rendering
 //! Supports input recording/replay for determinism validation
 
 use std::time::{Duration, Instant};
+use thiserror::Error;
 
-/// Fixed simulation tick rate (60 Hz = 16.666ms per tick)
+/// Default simulation tick rate (60 Hz = 16.666ms per tick)
 pub const TICK_RATE_HZ: u32 = 60;
 pub const TICK_DURATION_SECS: f32 = 1.0 / 60.0; // 0.01666...
 pub const TICK_DURATION: Duration = Duration::from_micros(16_666); // ~16.666ms
 
 /// Simulation time tracker with fixed timestep
+///
+/// The tick rate is fixed for the lifetime of a tracker but need not be
+/// 60Hz: servers can simulate at 20-30Hz while clients render at the
+/// display rate and interpolate with `interpolation_alpha`.
 pub struct SimulationTime {
+    tick_duration: Duration,
     tick_count: u64,
     accumulated_time: Duration,
     last_update: Instant,
@@ -19,8 +26,32 @@ pub struct SimulationTime {
 }
 
 impl SimulationTime {
+    /// Tracker ticking at the default `TICK_RATE_HZ`.
     pub fn new() -> Self {
+        Self::with_tick_duration(TICK_DURATION)
+    }
+
+    /// Tracker ticking `hz` times per simulated second.
+    ///
+    /// # Panics
+    /// If `hz` is not finite and positive.
+    pub fn with_tick_hz(hz: f32) -> Self {
+        assert!(
+            hz.is_finite() && hz > 0.0,
+            "tick rate must be positive, got {hz}"
+        );
+        Self::with_tick_duration(Duration::from_secs_f64(1.0 / f64::from(hz)))
+    }
+
+    /// Tracker with an explicit tick length, e.g. the `tick_duration` stored
+    /// in a recording so a replay reproduces its timing.
+    ///
+    /// # Panics
+    /// If `tick_duration` is zero.
+    pub fn with_tick_duration(tick_duration: Duration) -> Self {
+        assert!(!tick_duration.is_zero(), "tick duration must be non-zero");
         Self {
+            tick_duration,
             tick_count: 0,
             accumulated_time: Duration::ZERO,
             last_update: Instant::now(),
@@ -28,6 +59,16 @@ impl SimulationTime {
         }
     }
 
+    /// Length of one simulation tick.
+    pub fn tick_duration(&self) -> Duration {
+        self.tick_duration
+    }
+
+    /// Ticks per simulated second.
+    pub fn tick_hz(&self) -> f32 {
+        (1.0 / self.tick_duration.as_secs_f64()) as f32
+    }
+
     /// Get current tick number
     pub fn tick_count(&self) -> u64 {
         self.tick_count
@@ -40,7 +81,7 @@ impl SimulationTime {
 
     /// Get delta time for this tick (always fixed)
     pub fn delta_time(&self) -> f32 {
-        TICK_DURATION_SECS
+        self.tick_duration.as_secs_f32()
     }
 
     /// Update with elapsed wall-clock time
@@ -53,11 +94,11 @@ impl SimulationTime {
         self.lag += elapsed;
 
         let mut ticks = 0;
-        while self.lag >= TICK_DURATION && ticks < 4 {
+        while self.lag >= self.tick_duration && ticks < 4 {
             // Max 4 ticks per frame to avoid spiral of death
-            self.lag -= TICK_DURATION;
+            self.lag -= self.tick_duration;
             self.tick_count += 1;
-            self.accumulated_time += TICK_DURATION;
+            self.accumulated_time += self.tick_duration;
             ticks += 1;
         }
 
@@ -66,7 +107,7 @@ impl SimulationTime {
 
     /// Get interpolation alpha for smooth rendering between ticks
     pub fn interpolation_alpha(&self) -> f32 {
-        self.lag.as_secs_f32() / TICK_DURATION_SECS
+        self.lag.as_secs_f32() / self.tick_duration.as_secs_f32()
     }
 
     /// Reset time (for replay)
@@ -88,13 +129,122 @@ impl Default for SimulationTime {
 // Input Recording for Determinism
 // ============================================================================
 
+/// Index of a game action (jump, fire, ...) resolved by the input mapping.
+///
+/// Replays store action indices rather than physical keys or buttons, so
+/// rebinding controls does not change what a recording does. The game fixes
+/// the index of each named action, and `InputMap::action_state` in
+/// `latch_services` resolves the held inputs against that list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ActionId(pub u8);
+
+impl ActionId {
+    /// Number of distinct actions an `ActionState` can hold.
+    pub const MAX: usize = 64;
+}
+
+/// Resolved action states for one tick.
+///
+/// `pointer` is in normalized device coordinates (-1..1, +y up) so a
+/// recording replays identically at any window size.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ActionState {
+    /// Bit `n` is set while action `n` is held.
+    pub pressed: u64,
+    pub pointer: [f32; 2],
+}
+
+impl ActionState {
+    #[inline]
+    pub fn is_pressed(&self, action: ActionId) -> bool {
+        debug_assert!((action.0 as usize) < ActionId::MAX);
+        self.pressed & (1 << action.0) != 0
+    }
+
+    #[inline]
+    pub fn set_pressed(&mut self, action: ActionId, pressed: bool) {
+        debug_assert!((action.0 as usize) < ActionId::MAX);
+        if pressed {
+            self.pressed |= 1 << action.0;
+        } else {
+            self.pressed &= !(1 << action.0);
+        }
+    }
+}
+
 /// Input event for a single tick
+///
+/// `mouse_x`/`mouse_y` are in normalized device coordinates, never pixels.
+/// When the application maps raw input to actions, it should also fill
+/// `actions`; replay logic prefers those over the raw mouse fields.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct TickInput {
     pub tick: u64,
     pub mouse_x: f32,
     pub mouse_y: f32,
     pub mouse_pressed: bool,
+    /// Mapped action states, `None` for recordings made before action
+    /// mapping (replay format version 1).
+    pub actions: Option<ActionState>,
+    /// Tick length of the simulation that recorded this input
+    /// (`SimulationTime::tick_duration`). Recordings older than replay
+    /// format version 3 were made at `TICK_DURATION`.
+    pub tick_duration: Duration,
+}
+
+/// Magic bytes at the start of an encoded replay.
+pub const REPLAY_MAGIC: [u8; 4] = *b"LRPL";
+/// Replay format with raw mouse state only.
+pub const REPLAY_VERSION_RAW: u16 = 1;
+/// Replay format with optional mapped action states per tick.
+pub const REPLAY_VERSION_ACTIONS: u16 = 2;
+/// Replay format recording each tick's duration (`u64` nanoseconds).
+pub const REPLAY_VERSION_TICK_DURATION: u16 = 3;
+/// Version written by `InputRecorder::encode`.
+pub const REPLAY_VERSION: u16 = REPLAY_VERSION_TICK_DURATION;
+
+#[derive(Debug, Error)]
+pub enum ReplayDecodeError {
+    #[error("not a replay file (bad magic)")]
+    BadMagic,
+    #[error("unsupported replay version {0}")]
+    UnsupportedVersion(u16),
+    #[error("replay data truncated: needed {needed} bytes, {available} available")]
+    Truncated { needed: usize, available: usize },
+}
+
+struct ReplayReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> ReplayReader<'a> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], ReplayDecodeError> {
+        if self.bytes.len() < N {
+            return Err(ReplayDecodeError::Truncated {
+                needed: N,
+                available: self.bytes.len(),
+            });
+        }
+        let (head, rest) = self.bytes.split_at(N);
+        self.bytes = rest;
+        Ok(head.try_into().expect("split_at returned N bytes"))
+    }
+
+    fn u8(&mut self) -> Result<u8, ReplayDecodeError> {
+        Ok(self.take::<1>()?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, ReplayDecodeError> {
+        Ok(u16::from_le_bytes(self.take()?))
+    }
+
+    fn u64(&mut self) -> Result<u64, ReplayDecodeError> {
+        Ok(u64::from_le_bytes(self.take()?))
+    }
+
+    fn f32(&mut self) -> Result<f32, ReplayDecodeError> {
+        Ok(f32::from_le_bytes(self.take()?))
+    }
 }
 
 /// Input recorder for replay validation
@@ -154,11 +304,54 @@ impl InputRecorder {
         self.recording
     }
 
+    /// Tick length the recording was made at, from its first input; build
+    /// the replay's `SimulationTime` with it (`with_tick_duration`).
+    pub fn tick_duration(&self) -> Option<Duration> {
+        self.inputs.first().map(|input| input.tick_duration)
+    }
+
     /// Get number of recorded inputs
     pub fn input_count(&self) -> usize {
         self.inputs.len()
     }
 
+    /// Number of recorded inputs; same as `input_count`.
+    pub fn len(&self) -> usize {
+        self.inputs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inputs.is_empty()
+    }
+
+    /// Recorded input for `tick`, if that tick has one.
+    ///
+    /// Inputs are stored in recording order, which is ascending tick order,
+    /// so this is a binary search.
+    pub fn input_at(&self, tick: u64) -> Option<&TickInput> {
+        self.inputs
+            .binary_search_by_key(&tick, |input| input.tick)
+            .ok()
+            .map(|index| &self.inputs[index])
+    }
+
+    /// Move playback to `tick`: the next `playback(tick)` returns that
+    /// tick's input and playback continues in order from there.
+    ///
+    /// Seeking to a tick without input positions playback at the next
+    /// recorded tick; seeking past the end finishes playback. Restore the
+    /// matching world state before resuming (a snapshot or rewind buffer
+    /// entry for `tick`), otherwise the replay diverges.
+    pub fn seek(&mut self, tick: u64) {
+        self.playback_index = self.inputs.partition_point(|input| input.tick < tick);
+    }
+
+    /// Tick of the input the next `playback` call returns, `None` once
+    /// playback has finished.
+    pub fn playback_tick(&self) -> Option<u64> {
+        self.inputs.get(self.playback_index).map(|input| input.tick)
+    }
+
     /// Export recorded inputs (for saving to file)
     pub fn export(&self) -> &[TickInput] {
         &self.inputs
@@ -169,6 +362,80 @@ impl InputRecorder {
         self.inputs = inputs;
         self.playback_index = 0;
     }
+
+    /// Encode the recorded inputs (little-endian, `REPLAY_VERSION`).
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(14 + self.inputs.len() * 42);
+        out.extend_from_slice(&REPLAY_MAGIC);
+        out.extend_from_slice(&REPLAY_VERSION.to_le_bytes());
+        out.extend_from_slice(&(self.inputs.len() as u64).to_le_bytes());
+        for input in &self.inputs {
+            out.extend_from_slice(&input.tick.to_le_bytes());
+            out.extend_from_slice(&input.mouse_x.to_le_bytes());
+            out.extend_from_slice(&input.mouse_y.to_le_bytes());
+            out.push(input.mouse_pressed as u8);
+            // Saturates only past ~584 years per tick.
+            let tick_nanos = u64::try_from(input.tick_duration.as_nanos()).unwrap_or(u64::MAX);
+            out.extend_from_slice(&tick_nanos.to_le_bytes());
+            match input.actions {
+                Some(actions) => {
+                    out.push(1);
+                    out.extend_from_slice(&actions.pressed.to_le_bytes());
+                    out.extend_from_slice(&actions.pointer[0].to_le_bytes());
+                    out.extend_from_slice(&actions.pointer[1].to_le_bytes());
+                }
+                None => out.push(0),
+            }
+        }
+        out
+    }
+
+    /// Decode inputs written by `encode`, including older format versions.
+    ///
+    /// Version 1 recordings load with `actions: None`; versions 1 and 2 load
+    /// with `tick_duration: TICK_DURATION`.
+    pub fn decode(bytes: &[u8]) -> Result<Vec<TickInput>, ReplayDecodeError> {
+        let mut reader = ReplayReader { bytes };
+        if reader.take::<4>()? != REPLAY_MAGIC {
+            return Err(ReplayDecodeError::BadMagic);
+        }
+        let version = reader.u16()?;
+        if !(REPLAY_VERSION_RAW..=REPLAY_VERSION).contains(&version) {
+            return Err(ReplayDecodeError::UnsupportedVersion(version));
+        }
+
+        let count = reader.u64()? as usize;
+        // Each record is at least 17 bytes; don't trust `count` for the allocation.
+        let mut inputs = Vec::with_capacity(count.min(reader.bytes.len() / 17));
+        for _ in 0..count {
+            let tick = reader.u64()?;
+            let mouse_x = reader.f32()?;
+            let mouse_y = reader.f32()?;
+            let mouse_pressed = reader.u8()? != 0;
+            let tick_duration = if version >= REPLAY_VERSION_TICK_DURATION {
+                Duration::from_nanos(reader.u64()?)
+            } else {
+                TICK_DURATION
+            };
+            let actions = if version >= REPLAY_VERSION_ACTIONS && reader.u8()? != 0 {
+                Some(ActionState {
+                    pressed: reader.u64()?,
+                    pointer: [reader.f32()?, reader.f32()?],
+                })
+            } else {
+                None
+            };
+            inputs.push(TickInput {
+                tick,
+                mouse_x,
+                mouse_y,
+                mouse_pressed,
+                actions,
+                tick_duration,
+            });
+        }
+        Ok(inputs)
+    }
 }
 
 impl Default for InputRecorder {
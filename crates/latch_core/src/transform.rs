@@ -0,0 +1,153 @@
+//! Float transform component for rendering and non-deterministic gameplay
+//!
+//! Simulation state that must replay bit-for-bit stays in integer game
+//! units (see `math`'s Q16 helpers and `PhysicsConfig`). `Transform` is the
+//! glam-based counterpart for everything else: cameras, UI, particles,
+//! editor gizmos and the model matrices handed to the renderer. The
+//! `fixed_to_world`/`world_to_fixed` pair bridges the two, converting
+//! integer positions to glam once per frame for presentation.
+
+use crate::ecs::{ComponentId, World, WorldError};
+use glam::{Mat4, Quat, Vec2, Vec3};
+
+/// Translation, rotation and non-uniform scale of an entity.
+///
+/// `#[repr(C)]`, `Copy` and free of pointers, so its column is plain bytes
+/// like any other POD component and can be snapshotted or uploaded as is.
+/// `rotation` comes first and the tail padding is an explicit zeroed field,
+/// so every byte of the column is defined and state checksums agree across
+/// peers (implicit padding would carry whatever was on the stack).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub struct Transform {
+    pub rotation: Quat,
+    pub translation: Vec3,
+    pub scale: Vec3,
+    _padding: [u32; 2],
+}
+crate::define_component!(Transform, 1024, "latch::Transform");
+
+impl Transform {
+    pub const IDENTITY: Self = Self {
+        rotation: Quat::IDENTITY,
+        translation: Vec3::ZERO,
+        scale: Vec3::ONE,
+        _padding: [0; 2],
+    };
+
+    pub fn new(translation: Vec3, rotation: Quat, scale: Vec3) -> Self {
+        Self {
+            rotation,
+            translation,
+            scale,
+            ..Self::IDENTITY
+        }
+    }
+
+    pub fn from_translation(translation: Vec3) -> Self {
+        Self {
+            translation,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Transform in the z = 0 plane, as used by 2D scenes.
+    pub fn from_xy(x: f32, y: f32) -> Self {
+        Self::from_translation(Vec3::new(x, y, 0.0))
+    }
+
+    /// Place an entity at an integer game-unit position.
+    pub fn from_fixed(position: [i32; 2], units_per_world: i32) -> Self {
+        Self::from_xy(
+            fixed_to_world(position[0], units_per_world),
+            fixed_to_world(position[1], units_per_world),
+        )
+    }
+
+    pub fn with_rotation(mut self, rotation: Quat) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Rotate about +z by `angle` radians (counter-clockwise in 2D).
+    pub fn with_rotation_z(self, angle: f32) -> Self {
+        self.with_rotation(Quat::from_rotation_z(angle))
+    }
+
+    pub fn with_scale(mut self, scale: Vec3) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// x/y of the translation.
+    pub fn xy(&self) -> Vec2 {
+        Vec2::new(self.translation.x, self.translation.y)
+    }
+
+    /// x/y of the translation in integer game units (rounded to nearest).
+    pub fn fixed_position(&self, units_per_world: i32) -> [i32; 2] {
+        [
+            world_to_fixed(self.translation.x, units_per_world),
+            world_to_fixed(self.translation.y, units_per_world),
+        ]
+    }
+
+    /// Local-to-world matrix: scale, then rotate, then translate.
+    pub fn model_matrix(&self) -> Mat4 {
+        Mat4::from_scale_rotation_translation(self.scale, self.rotation, self.translation)
+    }
+
+    /// `model_matrix` as column arrays, ready for a uniform or instance buffer.
+    pub fn model_cols(&self) -> [[f32; 4]; 4] {
+        self.model_matrix().to_cols_array_2d()
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+/// Convert an integer game-unit coordinate to world space.
+///
+/// Exact while `|value|` stays below 2^24; beyond that the result is the
+/// nearest representable `f32`. Use for presentation only: never feed the
+/// result back into the deterministic simulation.
+#[inline]
+pub fn fixed_to_world(value: i32, units_per_world: i32) -> f32 {
+    debug_assert!(units_per_world > 0, "units_per_world must be positive");
+    (f64::from(value) / f64::from(units_per_world)) as f32
+}
+
+/// Convert a world-space coordinate to integer game units, rounding to
+/// nearest and saturating at the `i32` range.
+///
+/// For authoring (spawn positions from an editor or level file), not for
+/// per-tick simulation.
+#[inline]
+pub fn world_to_fixed(value: f32, units_per_world: i32) -> i32 {
+    debug_assert!(units_per_world > 0, "units_per_world must be positive");
+    (f64::from(value) * f64::from(units_per_world)).round() as i32
+}
+
+/// Collect the model matrix of every entity with a `Transform`.
+///
+/// Archetypes are visited in `World` iteration order and rows in storage
+/// order, matching `ArchetypeStorage::gather_instances`, so indices line up
+/// with other per-instance buffers gathered the same way. `out` is cleared
+/// first; returns the number of matrices written.
+pub fn gather_model_matrices(
+    world: &World,
+    out: &mut Vec<[[f32; 4]; 4]>,
+) -> Result<usize, WorldError> {
+    out.clear();
+    let component_ids: [ComponentId; 1] = [Transform::ID];
+    for archetype_id in world.archetypes_matching(&component_ids) {
+        let storage = world
+            .storage(archetype_id)
+            .ok_or(WorldError::MissingArchetype { archetype_id })?;
+        storage.gather_instances::<Transform, _>(out, Transform::model_cols)?;
+    }
+    Ok(out.len())
+}
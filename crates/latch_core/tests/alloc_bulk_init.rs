@@ -0,0 +1,95 @@
+use latch_core::define_component;
+use latch_core::ecs::{Component, RowInit, World};
+use latch_core::spawn;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Pos(u32);
+define_component!(Pos, 9040, "AllocBulkInitTest::Pos");
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Vel(u32);
+define_component!(Vel, 9041, "AllocBulkInitTest::Vel");
+
+#[test]
+fn unwritten_bulk_columns_read_as_zero() {
+    let mut world = World::new();
+    let first = spawn!(world, Pos(u32::MAX), Vel(u32::MAX));
+    let archetype = world.locate(first).unwrap().archetype;
+    // Recycle the row so its bytes are non-zero before reuse.
+    world.despawn(first).unwrap();
+    world.flush_despawns().unwrap();
+
+    let reserved = world.reserve_entities(64).unwrap();
+    let storage = world.storage_mut(archetype).unwrap();
+    let spans = storage
+        .alloc_bulk(64, reserved.iter().map(|entity| entity.index()))
+        .unwrap();
+    for span in &spans {
+        for row in span.clone() {
+            storage
+                .write_component(Pos::id(), row, &(row as u32).to_ne_bytes(), None)
+                .unwrap();
+        }
+    }
+
+    let vel = storage.column_mut(Vel::id()).unwrap();
+    for span in spans {
+        let (read, write) = vel.slice_rw_typed::<Vel>(span).unwrap();
+        assert!(read.iter().chain(write.iter()).all(|v| *v == Vel(0)));
+    }
+}
+
+#[test]
+fn uninit_rows_keep_the_callers_writes() {
+    let mut world = World::new();
+    let first = spawn!(world, Pos(1), Vel(2));
+    let archetype = world.locate(first).unwrap().archetype;
+
+    let reserved = world.reserve_entities(3).unwrap();
+    let storage = world.storage_mut(archetype).unwrap();
+    let spans = storage
+        .alloc_bulk_with(
+            3,
+            reserved.iter().map(|entity| entity.index()),
+            RowInit::Uninit,
+        )
+        .unwrap();
+    assert_eq!(spans.iter().map(|s| s.len()).sum::<usize>(), 3);
+    assert_eq!(storage.entity_count(), 4);
+
+    // The caller fills every column of every row, in both buffers.
+    for span in &spans {
+        for row in span.clone() {
+            let pos = (row as u32 + 10).to_ne_bytes();
+            let vel = (row as u32 + 20).to_ne_bytes();
+            storage
+                .write_component(Pos::id(), row, &pos, Some(&pos))
+                .unwrap();
+            storage
+                .write_component(Vel::id(), row, &vel, Some(&vel))
+                .unwrap();
+        }
+    }
+
+    let rows: Vec<usize> = spans.iter().cloned().flatten().collect();
+    let ids: Vec<u32> = rows
+        .iter()
+        .map(|&row| storage.entity_id_at(row).unwrap())
+        .collect();
+    let reserved_ids: Vec<u32> = reserved.iter().map(|entity| entity.index()).collect();
+    assert_eq!(ids, reserved_ids);
+
+    for span in spans {
+        let pos = storage.column_mut(Pos::id()).unwrap();
+        let (read, write) = pos.slice_rw_typed::<Pos>(span.clone()).unwrap();
+        let expected: Vec<Pos> = span.clone().map(|row| Pos(row as u32 + 10)).collect();
+        assert_eq!(read, expected.as_slice());
+        assert_eq!(&*write, expected.as_slice());
+
+        let vel = storage.column_mut(Vel::id()).unwrap();
+        let (read, write) = vel.slice_rw_typed::<Vel>(span.clone()).unwrap();
+        let expected: Vec<Vel> = span.map(|row| Vel(row as u32 + 20)).collect();
+        assert_eq!(read, expected.as_slice());
+        assert_eq!(&*write, expected.as_slice());
+    }
+}
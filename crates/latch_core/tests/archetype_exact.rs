@@ -0,0 +1,34 @@
+use latch_core::define_component;
+use latch_core::ecs::World;
+use latch_core::spawn;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Pos(i32);
+define_component!(Pos, 9275, "ArchetypeExactTest::Pos");
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Vel(i32);
+define_component!(Vel, 9276, "ArchetypeExactTest::Vel");
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Tint(u8);
+define_component!(Tint, 9277, "ArchetypeExactTest::Tint");
+
+#[test]
+fn exact_lookup_ignores_supersets() {
+    let mut world = World::new();
+    let moving = spawn!(world, Pos(0), Vel(1));
+    let tinted = spawn!(world, Pos(0), Vel(1), Tint(2));
+
+    let exact = world.archetype_exact(&[Vel::ID, Pos::ID]).unwrap();
+    assert_eq!(exact, world.locate(moving).unwrap().archetype);
+    assert_eq!(
+        world.archetype_exact(&[Pos::ID, Vel::ID, Tint::ID, Pos::ID]),
+        Some(world.locate(tinted).unwrap().archetype)
+    );
+    assert_eq!(world.archetypes_matching(&[Pos::ID, Vel::ID]).count(), 2);
+
+    assert_eq!(world.archetype_exact(&[Pos::ID]), None);
+    assert_eq!(world.archetype_exact(&[Pos::ID, Tint::ID]), None);
+    assert_eq!(world.archetype_exact(&[]), None);
+}
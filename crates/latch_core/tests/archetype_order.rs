@@ -0,0 +1,85 @@
+use latch_core::define_component;
+use latch_core::ecs::{ArchetypeId, ArchetypeLayout, SnapshotCompression, World};
+use latch_core::spawn;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Mass(u32);
+define_component!(Mass, 9150, "OrderTest::Mass");
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Charge(i32);
+define_component!(Charge, 9151, "OrderTest::Charge");
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Spin(u8);
+define_component!(Spin, 9152, "OrderTest::Spin");
+
+fn visit_order(world: &mut World) -> Vec<ArchetypeId> {
+    let mut order = Vec::new();
+    world.for_each(&[Mass::ID], |storage| {
+        order.push(storage.plan().layout.id())
+    });
+    order
+}
+
+#[test]
+fn archetypes_are_visited_in_ascending_id_order() {
+    // Create the same archetypes in two different orders.
+    let mut a = World::new();
+    spawn!(a, Mass(1));
+    spawn!(a, Mass(1), Charge(2));
+    spawn!(a, Mass(1), Spin(3));
+    spawn!(a, Mass(1), Charge(2), Spin(3));
+
+    let mut b = World::new();
+    spawn!(b, Mass(1), Charge(2), Spin(3));
+    spawn!(b, Mass(1), Spin(3));
+    spawn!(b, Mass(1));
+    spawn!(b, Mass(1), Charge(2));
+
+    let order_a = visit_order(&mut a);
+    assert_eq!(order_a.len(), 4);
+    assert!(order_a.windows(2).all(|pair| pair[0] < pair[1]));
+    assert_eq!(order_a, visit_order(&mut b));
+
+    assert_eq!(a.archetype_ids(), b.archetype_ids());
+    assert_eq!(a.archetypes_with(Mass::ID), b.archetypes_with(Mass::ID));
+    assert_eq!(
+        a.archetypes_matching(&[Charge::ID]).collect::<Vec<_>>(),
+        b.archetypes_matching(&[Charge::ID]).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn restore_reproduces_archetype_order() {
+    let mut original = World::new();
+    spawn!(original, Mass(1), Charge(2), Spin(3));
+    spawn!(original, Mass(1));
+    spawn!(original, Mass(1), Spin(3));
+    spawn!(original, Mass(1), Charge(2));
+    let bytes = original.snapshot(SnapshotCompression::None).unwrap();
+
+    // The target already holds archetypes created in a different order.
+    let mut restored = World::new();
+    spawn!(restored, Mass(1), Charge(2));
+    spawn!(restored, Spin(3));
+    restored.restore(&bytes).unwrap();
+
+    assert_eq!(restored.archetype_ids(), original.archetype_ids());
+    for component_id in [Mass::ID, Charge::ID, Spin::ID] {
+        assert_eq!(
+            restored.archetypes_with(component_id),
+            original.archetypes_with(component_id)
+        );
+    }
+    assert_eq!(visit_order(&mut restored), visit_order(&mut original));
+    assert_eq!(restored.snapshot(SnapshotCompression::None).unwrap(), bytes);
+}
+
+#[test]
+fn archetype_ids_are_pinned_across_builds_and_hosts() {
+    // FNV-1a over the sorted ids as little-endian u32s.
+    let id = ArchetypeLayout::new(vec![2, 1]).id();
+    assert_eq!(id, 14_538_333_428_393_601_222);
+    assert_eq!(ArchetypeLayout::new(vec![1, 2, 2]).id(), id);
+}
@@ -0,0 +1,56 @@
+use latch_core::define_component;
+use latch_core::ecs::{meta_of, ColumnError, Component, RustType, World};
+use latch_core::spawn;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Position([i32; 2]);
+define_component!(Position, 9200, "ColumnTypeTagTest::Position");
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Velocity([i32; 2]);
+define_component!(Velocity, 9201, "ColumnTypeTagTest::Velocity");
+
+#[test]
+fn registration_records_the_rust_type() {
+    Position::ensure_registered();
+    let meta = meta_of(Position::ID).unwrap();
+    assert_eq!(meta.rust_type, Some(RustType::of::<Position>()));
+}
+
+#[test]
+#[cfg(debug_assertions)]
+fn same_layout_different_component_is_rejected() {
+    let mut world = World::new();
+    let entity = spawn!(world, Position([1, 2]), Velocity([3, 4]));
+    let archetype = world.locate(entity).unwrap().archetype;
+    let storage = world.storage(archetype).unwrap();
+    let velocity = storage.column(Velocity::ID).unwrap();
+
+    let err = velocity.slice_read_typed::<Position>(0..1).unwrap_err();
+    match err {
+        ColumnError::TypeMismatch {
+            component_id,
+            expected,
+            actual,
+        } => {
+            assert_eq!(component_id, Velocity::ID);
+            assert!(expected.ends_with("Velocity"));
+            assert!(actual.ends_with("Position"));
+        }
+        other => panic!("unexpected error: {other}"),
+    }
+
+    // The registered type and plain layout-compatible views still work.
+    assert_eq!(
+        velocity.slice_read_typed::<Velocity>(0..1).unwrap(),
+        &[Velocity([3, 4])]
+    );
+    assert_eq!(
+        velocity.slice_read_typed::<[i32; 2]>(0..1).unwrap(),
+        &[[3, 4]]
+    );
+    assert!(matches!(
+        velocity.slice_read_typed::<u64>(0..1),
+        Err(ColumnError::LayoutMismatch { .. })
+    ));
+}
@@ -0,0 +1,23 @@
+use latch_core::define_component;
+use latch_core::ecs::World;
+use latch_core::{columns, columns_mut, spawn};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Heat(i32);
+define_component!(Heat, 9170, "ColumnsCompatTest::Heat");
+
+#[test]
+fn single_component_columns_macros_use_paged_storage() {
+    let mut world = World::new();
+    spawn!(world, Heat(5));
+
+    world.for_each(&[Heat::ID], |storage| {
+        assert_eq!(columns!(storage, Heat), &[Heat(5)]);
+        columns_mut!(storage, Heat)[0] = Heat(6);
+    });
+    world.swap_buffers();
+
+    world.for_each(&[Heat::ID], |storage| {
+        assert_eq!(columns!(storage, Heat), &[Heat(6)]);
+    });
+}
@@ -0,0 +1,71 @@
+use latch_core::ecs::{meta_of, Component, ComponentCodec, ComponentCodecError, ComponentMeta};
+
+#[derive(Clone, Copy)]
+#[allow(dead_code)]
+struct Health(u32);
+
+fn write_be(value: &[u8], out: &mut Vec<u8>) {
+    let raw = u32::from_ne_bytes(value.try_into().unwrap());
+    out.extend_from_slice(&raw.to_be_bytes());
+}
+
+fn read_be(input: &[u8], value: &mut [u8]) -> Result<usize, ComponentCodecError> {
+    let bytes = input.get(..4).ok_or(ComponentCodecError::Truncated {
+        needed: 4,
+        available: input.len(),
+    })?;
+    let raw = u32::from_be_bytes(bytes.try_into().unwrap());
+    value.copy_from_slice(&raw.to_ne_bytes());
+    Ok(4)
+}
+
+impl Component for Health {
+    const NAME: &'static str = "ComponentCodecTest::Health";
+
+    fn codec() -> Option<ComponentCodec> {
+        Some(ComponentCodec::new(write_be, read_be))
+    }
+}
+
+#[derive(Clone, Copy)]
+#[allow(dead_code)]
+struct Raw(u32);
+latch_core::define_component!(Raw, 9010, "ComponentCodecTest::Raw");
+
+fn meta<T: Component>() -> ComponentMeta {
+    T::ensure_registered();
+    meta_of(T::id()).unwrap()
+}
+
+#[test]
+fn custom_codec_controls_wire_bytes() {
+    let meta = meta::<Health>();
+    let value = 0x0102_0304u32.to_ne_bytes();
+
+    let mut wire = Vec::new();
+    meta.encode(&value, &mut wire);
+    assert_eq!(wire, [1, 2, 3, 4]);
+
+    let mut decoded = [0u8; 4];
+    assert_eq!(meta.decode(&wire, &mut decoded).unwrap(), 4);
+    assert_eq!(decoded, value);
+}
+
+#[test]
+fn components_without_codec_copy_raw_bytes() {
+    let meta = meta::<Raw>();
+    assert!(meta.codec.is_none());
+
+    let value = 7u32.to_ne_bytes();
+    let mut wire = Vec::new();
+    meta.encode(&value, &mut wire);
+    assert_eq!(wire, value);
+
+    let mut decoded = [0u8; 4];
+    match meta.decode(&wire[..2], &mut decoded) {
+        Err(ComponentCodecError::Truncated { needed, available }) => {
+            assert_eq!((needed, available), (4, 2));
+        }
+        other => panic!("expected truncation error, got {other:?}"),
+    }
+}
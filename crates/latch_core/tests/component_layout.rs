@@ -0,0 +1,80 @@
+use latch_core::ecs::{
+    register_component, register_external_component_with_fields, reset_registry,
+    ComponentRegistrationError, FieldMeta,
+};
+
+fn reason(result: Result<impl Sized, ComponentRegistrationError>) -> String {
+    match result {
+        Err(ComponentRegistrationError::InvalidLayout { reason, .. }) => reason,
+        Ok(_) => panic!("layout was accepted"),
+    }
+}
+
+#[test]
+fn malformed_layouts_are_rejected() {
+    let _registry = reset_registry();
+    let fields = || vec![FieldMeta::new("a", 0, 4), FieldMeta::new("b", 4, 4)];
+    assert!(reason(register_component(
+        "LayoutTest::Align",
+        8,
+        3,
+        9,
+        true,
+        fields()
+    ))
+    .contains("power of two"));
+    assert!(reason(register_component(
+        "LayoutTest::Size",
+        6,
+        4,
+        8,
+        true,
+        Vec::new()
+    ))
+    .contains("multiple of alignment"));
+    assert!(reason(register_component(
+        "LayoutTest::Stride",
+        8,
+        4,
+        4,
+        true,
+        fields()
+    ))
+    .contains("stride"));
+
+    let overlap = vec![FieldMeta::new("a", 0, 4), FieldMeta::new("b", 2, 4)];
+    assert!(reason(register_external_component_with_fields(
+        "LayoutTest::Overlap",
+        8,
+        4,
+        8,
+        overlap,
+        true
+    ))
+    .contains("overlap"));
+    let outside = vec![FieldMeta::new("a", 4, 8)];
+    assert!(reason(register_external_component_with_fields(
+        "LayoutTest::Outside",
+        8,
+        4,
+        8,
+        outside,
+        true
+    ))
+    .contains("exceeds"));
+}
+
+#[test]
+fn valid_layouts_register() {
+    let _registry = reset_registry();
+    let handle = register_external_component_with_fields(
+        "LayoutTest::Health",
+        8,
+        4,
+        8,
+        vec![FieldMeta::new("hp", 0, 4), FieldMeta::new("max", 4, 4)],
+        true,
+    )
+    .unwrap();
+    assert_eq!(handle.stride, 8);
+}
@@ -0,0 +1,44 @@
+use latch_core::define_component;
+use latch_core::ecs::World;
+use latch_core::spawn;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Position(i32);
+define_component!(Position, 9293, "ComponentPresenceTest::Position");
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Velocity(i32);
+define_component!(Velocity, 9294, "ComponentPresenceTest::Velocity");
+
+#[test]
+fn presence_follows_the_entity_archetype() {
+    let mut world = World::new();
+    let moving = spawn!(world, Velocity(1), Position(0));
+    let fixed = spawn!(world, Position(5));
+
+    assert!(world.has_component(moving, Velocity::ID));
+    assert!(world.has_component(fixed, Position::ID));
+    assert!(!world.has_component(fixed, Velocity::ID));
+    assert_eq!(
+        world.component_ids_of(moving),
+        Some(&[Position::ID, Velocity::ID][..])
+    );
+    assert_eq!(world.component_ids_of(fixed), Some(&[Position::ID][..]));
+}
+
+#[test]
+fn stale_handles_have_no_components() {
+    let mut world = World::new();
+    let entity = spawn!(world, Position(1));
+    world.despawn(entity).unwrap();
+    assert!(!world.has_component(entity, Position::ID));
+    assert_eq!(world.component_ids_of(entity), None);
+
+    // The recycled slot does not answer for the old handle.
+    world.flush_despawns().unwrap();
+    let reused = spawn!(world, Position(2));
+    assert_eq!(reused.index(), entity.index());
+    assert!(world.has_component(reused, Position::ID));
+    assert!(!world.has_component(entity, Position::ID));
+    assert_eq!(world.component_ids_of(entity), None);
+}
@@ -0,0 +1,47 @@
+use latch_core::define_component;
+use latch_core::ecs::{CullBounds, PageBudget, World};
+use latch_core::spawn;
+use std::num::NonZeroUsize;
+
+#[derive(Clone, Copy)]
+struct Pos {
+    x: i32,
+    y: i32,
+}
+define_component!(Pos, 9201, "CullTest::Pos");
+
+#[test]
+fn culled_gather_keeps_only_visible_rows() {
+    let budget = PageBudget::with_l2_bytes(NonZeroUsize::new(64).unwrap());
+    let mut world = World::with_page_budget(budget);
+    // First eight rows sit inside the view; the rest alternate in and out.
+    let first = spawn!(world, Pos { x: 0, y: 0 });
+    for i in 1..64 {
+        let x = if i < 8 || i % 2 == 0 { i } else { 10_000 };
+        spawn!(world, Pos { x, y: 0 });
+    }
+
+    let archetype = world.locate(first).unwrap().archetype;
+    let storage = world.storage(archetype).unwrap();
+    assert!(storage.column(Pos::ID).unwrap().page_count() > 1);
+
+    let bounds = CullBounds::new([-10, -10], [100, 10]);
+    let mut xs = Vec::new();
+    let stats = storage
+        .gather_instances_culled::<Pos, _>(&mut xs, bounds, |p| [p.x, p.y], |p| p.x)
+        .unwrap();
+
+    let expected: Vec<i32> = (0..64).filter(|&i| i < 8 || i % 2 == 0).collect();
+    assert_eq!(xs, expected);
+    assert_eq!(stats.total, 64);
+    assert_eq!(stats.visible, expected.len());
+    assert_eq!(stats.culled(), 64 - expected.len());
+
+    let mut spans = Vec::new();
+    let far = CullBounds::new([50_000, 50_000], [60_000, 60_000]);
+    let stats = storage
+        .visible_spans::<Pos>(far, |p| [p.x, p.y], &mut spans)
+        .unwrap();
+    assert!(spans.is_empty());
+    assert_eq!(stats.visible, 0);
+}
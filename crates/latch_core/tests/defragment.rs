@@ -0,0 +1,57 @@
+use latch_core::define_component;
+use latch_core::ecs::{Entity, PageBudget, World};
+use latch_core::spawn;
+use std::num::NonZeroUsize;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Serial(u64);
+define_component!(Serial, 9100, "DefragTest::Serial");
+
+#[test]
+fn defragment_fills_holes_and_minimizes_pages() {
+    let mut world =
+        World::with_page_budget(PageBudget::with_l2_bytes(NonZeroUsize::new(256).unwrap()));
+
+    let entities: Vec<Entity> = (0..200).map(|i| spawn!(world, Serial(i))).collect();
+    let archetype = world.locate(entities[0]).unwrap().archetype;
+    let rows_per_page = world.storage(archetype).unwrap().rows_per_page();
+    let pages_before = world.storage(archetype).unwrap().page_count();
+    assert!(pages_before >= 3, "test needs several pages");
+
+    // Punch holes in every page; they stay until compacted.
+    let mut survivors = Vec::new();
+    for (i, &entity) in entities.iter().enumerate() {
+        if i % 3 == 0 {
+            world.despawn(entity).unwrap();
+        } else {
+            survivors.push((entity, i as u64));
+        }
+    }
+    assert_eq!(world.storage(archetype).unwrap().page_count(), pages_before);
+
+    let moved = world.defragment().unwrap();
+    assert!(moved > 0);
+
+    let storage = world.storage(archetype).unwrap();
+    assert_eq!(storage.entity_count(), survivors.len());
+    assert_eq!(
+        storage.page_count(),
+        survivors.len().div_ceil(rows_per_page)
+    );
+
+    for (entity, serial) in survivors {
+        let loc = world.locate(entity).unwrap();
+        let column = world
+            .storage(loc.archetype)
+            .unwrap()
+            .column(Serial::ID)
+            .unwrap();
+        let row = column
+            .slice_read_typed::<Serial>(loc.index..loc.index + 1)
+            .unwrap();
+        assert_eq!(row[0], Serial(serial));
+    }
+
+    // Already packed: nothing left to move.
+    assert_eq!(world.defragment().unwrap(), 0);
+}
@@ -0,0 +1,55 @@
+use latch_core::define_component;
+use latch_core::ecs::{Entity, World};
+use latch_core::spawn;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Label(u32);
+define_component!(Label, 9160, "DespawnFixupTest::Label");
+
+fn label(world: &World, entity: Entity) -> Label {
+    let loc = world.locate(entity).unwrap();
+    world
+        .storage(loc.archetype)
+        .unwrap()
+        .column(Label::ID)
+        .unwrap()
+        .slice_read_typed::<Label>(loc.index..loc.index + 1)
+        .unwrap()[0]
+}
+
+#[test]
+fn despawning_middle_row_relocates_the_swapped_entity() {
+    let mut world = World::new();
+    let first = spawn!(world, Label(0));
+    let middle = spawn!(world, Label(1));
+    let last = spawn!(world, Label(2));
+
+    world.despawn(middle).unwrap();
+    world.flush_despawns().unwrap();
+
+    // `last` was swapped into the hole; its location must follow it.
+    assert_eq!(world.locate(last).unwrap().index, 1);
+    assert_eq!(label(&world, first), Label(0));
+    assert_eq!(label(&world, last), Label(2));
+    assert!(world.locate(middle).is_err());
+
+    let archetype = world.locate(first).unwrap().archetype;
+    let storage = world.storage(archetype).unwrap();
+    assert_eq!(storage.entity_count(), 2);
+    assert_eq!(storage.entity_id_at(1).unwrap(), last.index());
+}
+
+#[test]
+fn despawning_last_row_leaves_survivors_in_place() {
+    let mut world = World::new();
+    let first = spawn!(world, Label(0));
+    let middle = spawn!(world, Label(1));
+    let last = spawn!(world, Label(2));
+
+    world.despawn(last).unwrap();
+    world.flush_despawns().unwrap();
+
+    assert_eq!(world.locate(first).unwrap().index, 0);
+    assert_eq!(world.locate(middle).unwrap().index, 1);
+    assert_eq!(label(&world, middle), Label(1));
+}
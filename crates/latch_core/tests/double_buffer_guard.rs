@@ -0,0 +1,52 @@
+#![cfg(debug_assertions)]
+
+use latch_core::define_component;
+use latch_core::ecs::World;
+use latch_core::spawn;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Health(i32);
+define_component!(Health, 9220, "DoubleBufferGuardTest::Health");
+
+fn write_next(world: &mut World, value: i32) {
+    world.for_each(&[Health::ID], |storage| {
+        let column = storage.column_mut(Health::ID).unwrap();
+        column.fill_next_typed(Health(value)).unwrap();
+    });
+}
+
+#[test]
+fn spawning_is_not_an_unswapped_write() {
+    let mut world = World::new();
+    spawn!(world, Health(10));
+    world.debug_assert_swapped();
+}
+
+#[test]
+fn swap_clears_the_guard() {
+    let mut world = World::new();
+    let entity = spawn!(world, Health(10));
+    let archetype = world.locate(entity).unwrap().archetype;
+
+    write_next(&mut world, 5);
+    let column = world
+        .storage(archetype)
+        .unwrap()
+        .column(Health::ID)
+        .unwrap();
+    assert!(column.has_unswapped_writes());
+
+    world.swap_buffers();
+    world.debug_assert_swapped();
+    let storage = world.storage(archetype).unwrap();
+    assert_eq!(storage.column_slice::<Health>().unwrap(), &[Health(5)]);
+}
+
+#[test]
+#[should_panic(expected = "never swapped")]
+fn read_before_swap_is_caught() {
+    let mut world = World::new();
+    spawn!(world, Health(10));
+    write_next(&mut world, 5);
+    world.debug_assert_swapped();
+}
@@ -0,0 +1,47 @@
+use latch_core::define_component;
+use latch_core::ecs::{Component, EntityBuilder, EntityBuilderError, World};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Health(i32);
+define_component!(Health, 9501, "BuilderTest::Health");
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Armor(u16);
+define_component!(Armor, 9502, "BuilderTest::Armor");
+
+#[test]
+fn validated_builder_reports_offending_component() {
+    let builder = EntityBuilder::new().try_with(Health(10)).unwrap();
+    let err = builder.try_with(Health(5)).err().unwrap();
+    assert!(matches!(
+        err,
+        EntityBuilderError::DuplicateComponent { ref name, .. } if &**name == "BuilderTest::Health"
+    ));
+
+    Armor::ensure_registered();
+    let err = EntityBuilder::new()
+        .with_raw_bytes(Armor::ID, vec![0; 7])
+        .err()
+        .unwrap();
+    assert!(matches!(
+        err,
+        EntityBuilderError::StrideMismatch { ref name, expected: 2, actual: 7, .. }
+            if &**name == "BuilderTest::Armor"
+    ));
+}
+
+#[test]
+fn with_default_zero_initializes() {
+    let builder = EntityBuilder::new()
+        .try_with(Health(3))
+        .unwrap()
+        .with_default::<Armor>()
+        .unwrap();
+    assert_eq!(builder.layout().components(), &[Health::id(), Armor::id()]);
+
+    let mut world = World::new();
+    let entity = world.spawn(builder).unwrap();
+    let archetype = world.locate(entity).unwrap().archetype;
+    assert_eq!(world.column::<Armor>(archetype).unwrap(), &[Armor(0)]);
+    assert_eq!(world.column::<Health>(archetype).unwrap(), &[Health(3)]);
+}
@@ -0,0 +1,59 @@
+use latch_core::define_component;
+use latch_core::ecs::{Entity, EntityIdPolicy, SnapshotCompression, World, WorldBuilder};
+use latch_core::spawn;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Crate(u32);
+define_component!(Crate, 9284, "EntityIdPolicyTest::Crate");
+
+/// Spawn four crates, despawn two of them in `order`, then spawn two more.
+fn respawned_ids(policy: EntityIdPolicy, order: [usize; 2]) -> Vec<u32> {
+    let mut world = WorldBuilder::new()
+        .entity_id_policy(policy)
+        .build()
+        .unwrap();
+    let entities: Vec<Entity> = (0..4).map(|i| spawn!(world, Crate(i))).collect();
+    for index in order {
+        world.despawn(entities[index]).unwrap();
+        world.flush_despawns().unwrap();
+    }
+    (0..2)
+        .map(|i| spawn!(world, Crate(10 + i)).index())
+        .collect()
+}
+
+#[test]
+fn monotonic_ids_ignore_despawn_order() {
+    let a = respawned_ids(EntityIdPolicy::Monotonic, [1, 2]);
+    let b = respawned_ids(EntityIdPolicy::Monotonic, [2, 1]);
+    assert_eq!(a, vec![4, 5]);
+    assert_eq!(a, b);
+
+    // The default reuses freed ids, most recent first.
+    let a = respawned_ids(EntityIdPolicy::Reuse, [1, 2]);
+    let b = respawned_ids(EntityIdPolicy::Reuse, [2, 1]);
+    assert_eq!(a, vec![2, 1]);
+    assert_eq!(b, vec![1, 2]);
+}
+
+#[test]
+fn monotonic_worlds_never_shrink_or_reuse_after_restore() {
+    let mut source = World::new();
+    let entities: Vec<Entity> = (0..3).map(|i| spawn!(source, Crate(i))).collect();
+    source.despawn(entities[0]).unwrap();
+    source.flush_despawns().unwrap();
+    let bytes = source.snapshot(SnapshotCompression::None).unwrap();
+
+    let mut world = WorldBuilder::new()
+        .entity_id_policy(EntityIdPolicy::Monotonic)
+        .build()
+        .unwrap();
+    world.restore(&bytes).unwrap();
+    assert_eq!(world.entity_id_policy(), EntityIdPolicy::Monotonic);
+    assert_eq!(spawn!(world, Crate(7)).index(), 3);
+
+    world.despawn(entities[2]).unwrap();
+    world.flush_despawns().unwrap();
+    assert_eq!(world.shrink_slots(), 0);
+    assert_eq!(spawn!(world, Crate(8)).index(), 4);
+}
@@ -0,0 +1,100 @@
+use latch_core::define_component;
+use latch_core::ecs::{EntityBuilder, EntityBuilderError, EntityTemplate, World, WorldError};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Position {
+    x: i32,
+    y: i32,
+}
+define_component!(Position, 9290, "EntityTemplateTest::Position");
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Health(u32);
+define_component!(Health, 9291, "EntityTemplateTest::Health");
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Unused(u8);
+define_component!(Unused, 9292, "EntityTemplateTest::Unused");
+
+fn template() -> EntityTemplate {
+    EntityTemplate::from_builder(
+        EntityBuilder::new()
+            .with(Position { x: 1, y: 2 })
+            .with(Health(100)),
+    )
+    .unwrap()
+}
+
+#[test]
+fn template_spawns_identical_independent_entities() {
+    let template = template();
+    let mut world = World::new();
+    let entities: Vec<_> = (0..1000)
+        .map(|_| world.spawn_template(&template).unwrap())
+        .collect();
+    assert_eq!(world.live_entity_count(), 1000);
+
+    let archetype = world.locate(entities[0]).unwrap().archetype;
+    assert!(entities
+        .iter()
+        .all(|&entity| world.locate(entity).unwrap().archetype == archetype));
+    assert!(world
+        .column::<Health>(archetype)
+        .unwrap()
+        .iter()
+        .all(|health| *health == Health(100)));
+
+    // Writing one instance leaves the others and the template untouched.
+    let row = world.locate(entities[7]).unwrap().index;
+    world
+        .storage_mut(archetype)
+        .unwrap()
+        .write_component(Health::ID, row, &5u32.to_ne_bytes(), None)
+        .unwrap();
+    let healths = world.column::<Health>(archetype).unwrap();
+    assert_eq!(healths.iter().filter(|h| **h == Health(5)).count(), 1);
+    assert_eq!(
+        template.component(Health::ID).unwrap(),
+        100u32.to_ne_bytes()
+    );
+}
+
+#[test]
+fn overrides_apply_to_one_instance() {
+    let template = template();
+    let mut world = World::new();
+    let plain = world.spawn_template(&template).unwrap();
+    let moved = world
+        .spawn_template_with(&template, |instance| {
+            instance.set(Position { x: -5, y: 9 })?;
+            instance.bytes_mut(Health::ID).unwrap()[0] = 7;
+            Ok(())
+        })
+        .unwrap();
+
+    let archetype = world.locate(plain).unwrap().archetype;
+    let positions = world.column::<Position>(archetype).unwrap();
+    let healths = world.column::<Health>(archetype).unwrap();
+    let (plain_row, moved_row) = (
+        world.locate(plain).unwrap().index,
+        world.locate(moved).unwrap().index,
+    );
+    assert_eq!(positions[plain_row], Position { x: 1, y: 2 });
+    assert_eq!(positions[moved_row], Position { x: -5, y: 9 });
+    assert_eq!(healths[moved_row], Health(7));
+}
+
+#[test]
+fn failed_override_spawns_nothing() {
+    let template = template();
+    let mut world = World::new();
+    let result = world.spawn_template_with(&template, |instance| instance.set(Unused(1)));
+    assert!(matches!(
+        result,
+        Err(WorldError::Builder(EntityBuilderError::NotInTemplate { component_id }))
+            if component_id == Unused::ID
+    ));
+    assert_eq!(world.live_entity_count(), 0);
+    // No entity id was used up by the failed spawn.
+    assert_eq!(world.spawn_template(&template).unwrap().index(), 0);
+}
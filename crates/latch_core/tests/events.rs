@@ -0,0 +1,31 @@
+use latch_core::ecs::World;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct DamageEvent {
+    target: u32,
+    amount: i32,
+}
+
+#[test]
+fn events_are_visible_for_exactly_one_tick() {
+    let mut world = World::new();
+    world.send_event(DamageEvent { target: 1, amount: 5 });
+    world.send_event(DamageEvent { target: 2, amount: 3 });
+
+    let events = world.events::<DamageEvent>().unwrap();
+    assert!(events.is_empty());
+    assert_eq!(events.pending_len(), 2);
+
+    world.swap_buffers();
+    let received: Vec<_> = world.events::<DamageEvent>().unwrap().drain().copied().collect();
+    assert_eq!(
+        received,
+        vec![
+            DamageEvent { target: 1, amount: 5 },
+            DamageEvent { target: 2, amount: 3 },
+        ]
+    );
+
+    world.swap_buffers();
+    assert!(world.events::<DamageEvent>().unwrap().is_empty());
+}
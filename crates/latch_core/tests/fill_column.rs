@@ -0,0 +1,49 @@
+use latch_core::define_component;
+use latch_core::ecs::{PageBudget, World};
+use latch_core::spawn;
+use std::num::NonZeroUsize;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Velocity {
+    x: i32,
+    y: i32,
+}
+define_component!(Velocity, 9140, "FillTest::Velocity");
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Tag(u32);
+define_component!(Tag, 9141, "FillTest::Tag");
+
+#[test]
+fn set_all_writes_next_buffer_of_every_archetype() {
+    let mut world =
+        World::with_page_budget(PageBudget::with_l2_bytes(NonZeroUsize::new(256).unwrap()));
+    let plain: Vec<_> = (0..60)
+        .map(|i| spawn!(world, Velocity { x: i, y: i }))
+        .collect();
+    let tagged: Vec<_> = (0..5)
+        .map(|i| spawn!(world, Velocity { x: i, y: i }, Tag(i as u32)))
+        .collect();
+
+    let written = world.set_all(Velocity { x: 0, y: -1 }).unwrap();
+    assert_eq!(written, 65);
+
+    let read = |world: &World, entity| {
+        let loc = world.locate(entity).unwrap();
+        world
+            .storage(loc.archetype)
+            .unwrap()
+            .column(Velocity::ID)
+            .unwrap()
+            .slice_read_typed::<Velocity>(loc.index..loc.index + 1)
+            .unwrap()[0]
+    };
+
+    // Current buffer is unchanged until the swap.
+    assert_eq!(read(&world, plain[7]), Velocity { x: 7, y: 7 });
+
+    world.swap_buffers();
+    for &entity in plain.iter().chain(&tagged) {
+        assert_eq!(read(&world, entity), Velocity { x: 0, y: -1 });
+    }
+}
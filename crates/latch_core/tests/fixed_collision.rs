@@ -0,0 +1,80 @@
+use latch_core::ecs::query::RelationDelta;
+use latch_core::hash::stable_hash;
+use latch_core::math::{
+    div_round, isqrt, normalize_q16, resolve_circle_contact, to_q16, ContactResponse, Q16_ONE,
+};
+
+#[test]
+fn integer_primitives() {
+    assert_eq!(isqrt(0), 0);
+    assert_eq!(isqrt(15), 3);
+    assert_eq!(isqrt(16), 4);
+    assert_eq!(isqrt(u64::MAX), u32::MAX as u64);
+    assert_eq!(div_round(5, 2), 3);
+    assert_eq!(div_round(-5, 2), -3);
+    assert_eq!(div_round(4, -3), -1);
+    assert_eq!(normalize_q16(3, -4), Some([39_322, -52_429]));
+    assert_eq!(normalize_q16(0, 0), None);
+    assert_eq!(to_q16(0.5), Q16_ONE / 2);
+}
+
+#[test]
+fn head_on_contact_stops_approach_and_splits_overlap() {
+    let contact = resolve_circle_contact([600, 0], [-100, 40], 1000, to_q16(0.5)).unwrap();
+    assert_eq!(
+        contact,
+        ContactResponse {
+            normal: [Q16_ONE, 0],
+            penetration: 400,
+            position: [200, 0],
+            velocity: [100, -20],
+        }
+    );
+    assert!(resolve_circle_contact([1000, 0], [0, 0], 1000, 0).is_none());
+    assert!(resolve_circle_contact([1, 0], [0, 0], 1000, 0).is_none());
+}
+
+/// Golden outputs for a fixed set of neighbour deltas. Everything is integer
+/// math, so these bytes must match on every platform and compiler.
+#[test]
+fn relation_deltas_resolve_to_identical_bytes() {
+    let mut state = 0x2545_F491u32;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        state
+    };
+
+    let friction = to_q16(0.2);
+    let mut bytes = Vec::new();
+    for _ in 0..256 {
+        let delta = RelationDelta {
+            dx: (next() % 2001) as i64 - 1000,
+            dy: (next() % 2001) as i64 - 1000,
+        };
+        let velocity = [(next() % 4001) as i32 - 2000, (next() % 4001) as i32 - 2000];
+        let separation = delta.flipped();
+        if let Some(contact) = resolve_circle_contact(
+            [separation.dx as i32, separation.dy as i32],
+            velocity,
+            2000,
+            friction,
+        ) {
+            for value in contact
+                .normal
+                .into_iter()
+                .chain([contact.penetration])
+                .chain(contact.position)
+                .chain(contact.velocity)
+            {
+                bytes.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+    }
+
+    assert_eq!(
+        (bytes.len(), stable_hash(&bytes)),
+        (7168, 10_698_534_209_952_620_859)
+    );
+}
@@ -0,0 +1,38 @@
+use latch_core::hash::StableHasher;
+use latch_core::math::fixed_trig::{cos_q16, rotate, sin_q16, to_brads, HALF_TURN, QUARTER_TURN};
+use latch_core::math::Q16_ONE;
+
+#[test]
+fn cardinal_angles_are_exact() {
+    assert_eq!(sin_q16(0), 0);
+    assert_eq!(sin_q16(QUARTER_TURN), Q16_ONE);
+    assert_eq!(sin_q16(HALF_TURN), 0);
+    assert_eq!(sin_q16(HALF_TURN + QUARTER_TURN), -Q16_ONE);
+    assert_eq!(cos_q16(0), Q16_ONE);
+    assert_eq!(cos_q16(HALF_TURN), -Q16_ONE);
+    assert_eq!(rotate([1000, 0], QUARTER_TURN), [0, 1000]);
+    assert_eq!(rotate([3, -7], HALF_TURN), [-3, 7]);
+    assert_eq!(to_brads(std::f32::consts::FRAC_PI_2), QUARTER_TURN);
+}
+
+#[test]
+fn table_is_accurate_and_monotonic_across_the_circle() {
+    let mut previous = sin_q16(0);
+    let mut hasher = StableHasher::new();
+    for angle in 1..=u16::MAX {
+        let sin = sin_q16(angle);
+        let exact = (angle as f64 / 65_536.0 * std::f64::consts::TAU).sin() * Q16_ONE as f64;
+        assert!((sin as f64 - exact).abs() <= 1.0, "sin({angle}) = {sin}");
+        // Rising in the first and last quarter, falling in between.
+        if (QUARTER_TURN..HALF_TURN + QUARTER_TURN).contains(&(angle - 1)) {
+            assert!(sin <= previous, "sin not falling at {angle}");
+        } else {
+            assert!(sin >= previous, "sin not rising at {angle}");
+        }
+        assert_eq!(cos_q16(angle), sin_q16(angle.wrapping_add(QUARTER_TURN)));
+        previous = sin;
+        hasher.write_u64(sin as u32 as u64);
+    }
+    // Pinned so any change to the table is deliberate.
+    assert_eq!(hasher.finish(), 0x17ee_c1ba_b747_b967);
+}
@@ -0,0 +1,44 @@
+use latch_core::define_component;
+use latch_core::ecs::World;
+use latch_core::spawn;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Heat(i32);
+define_component!(Heat, 9273, "ForEachRowTest::Heat");
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Tag(u8);
+define_component!(Tag, 9274, "ForEachRowTest::Tag");
+
+#[test]
+fn for_each_row_yields_live_values_across_archetypes_and_pages() {
+    let mut world = World::new();
+    let mut expected = Vec::new();
+    for i in 0..5_000 {
+        let entity = if i % 2 == 0 {
+            spawn!(world, Heat(i))
+        } else {
+            spawn!(world, Heat(i), Tag(1))
+        };
+        expected.push((entity, i));
+    }
+    let (gone, _) = expected.remove(10);
+    world.despawn(gone).unwrap();
+
+    let mut seen = Vec::new();
+    world.for_each_row(Heat::ID, |entity, bytes| {
+        let value = i32::from_ne_bytes(bytes.try_into().unwrap());
+        seen.push((entity, value));
+    });
+    seen.sort_unstable_by_key(|&(entity, _)| entity.index());
+    assert_eq!(seen, expected);
+
+    let mut rows = 0;
+    world.for_each_located_row(Tag::ID, |entity, location, bytes| {
+        let storage = world.storage(location.archetype).unwrap();
+        assert_eq!(storage.entity_id_at(location.row).unwrap(), entity.index());
+        assert_eq!(bytes, &[1]);
+        rows += 1;
+    });
+    assert_eq!(rows, 2_500);
+}
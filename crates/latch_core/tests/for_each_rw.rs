@@ -0,0 +1,119 @@
+use latch_core::define_component;
+use latch_core::ecs::{PageBudget, StorageError, World, WorldError};
+use latch_core::spawn;
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Position([i32; 2]);
+define_component!(Position, 9266, "ForEachRwTest::Position");
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Velocity([i32; 2]);
+define_component!(Velocity, 9267, "ForEachRwTest::Velocity");
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Age(u32);
+define_component!(Age, 9268, "ForEachRwTest::Age");
+
+fn world() -> World {
+    // Small pages so the moving archetype spans several tiles.
+    let mut world = World::with_page_budget(PageBudget::with_l2_bytes(
+        NonZeroUsize::new(4 * 1024).unwrap(),
+    ));
+    for i in 0..2_000 {
+        spawn!(world, Position([i, 0]), Velocity([1, 2]));
+    }
+    for i in 0..10 {
+        spawn!(world, Position([i, 0]), Velocity([0, 1]), Age(i as u32));
+    }
+    world
+}
+
+fn by_index<T: latch_core::ecs::Component + Copy>(world: &World) -> HashMap<u32, T> {
+    world
+        .collect_query::<T>()
+        .into_iter()
+        .map(|(entity, value)| (entity.index(), value))
+        .collect()
+}
+
+#[test]
+fn updates_every_row_through_the_next_buffer() {
+    let mut world = world();
+    let mut visited = 0;
+    world
+        .for_each_rw2::<Position, Velocity>(|_, position, velocity| {
+            position.0[0] += velocity.0[0];
+            position.0[1] += velocity.0[1];
+            visited += 1;
+        })
+        .unwrap();
+    assert_eq!(visited, 2_010);
+    assert_eq!(by_index::<Position>(&world)[&0], Position([0, 0]));
+
+    world.swap_buffers();
+    let positions = by_index::<Position>(&world);
+    assert_eq!(positions[&1_999], Position([2_000, 2]));
+    assert_eq!(positions[&2_000], Position([0, 1]));
+
+    // Columns visited but not changed keep their value across the swap.
+    world.for_each_rw::<Age>(|_, _| {}).unwrap();
+    world.swap_buffers();
+    let velocities = by_index::<Velocity>(&world);
+    assert!((0..2_000).all(|i| velocities[&i] == Velocity([1, 2])));
+    assert_eq!(by_index::<Age>(&world)[&2_003], Age(3));
+}
+
+#[test]
+fn wider_arities_see_the_entity_id() {
+    let mut world = world();
+    world
+        .for_each_rw3::<Age, Position, Velocity>(|id, age, _, _| age.0 = id)
+        .unwrap();
+    world.swap_buffers();
+    let ids: Vec<u32> = world
+        .collect_query::<Age>()
+        .into_iter()
+        .map(|(entity, age)| {
+            assert_eq!(entity.index(), age.0);
+            age.0
+        })
+        .collect();
+    assert_eq!(ids.len(), 10);
+}
+
+#[test]
+fn duplicate_components_are_rejected() {
+    let mut world = world();
+    let result = world.for_each_rw2::<Position, Position>(|_, _, _| {});
+    assert!(matches!(
+        result,
+        Err(WorldError::Storage(
+            StorageError::DuplicateColumnRequest { .. }
+        ))
+    ));
+}
+
+#[test]
+fn modify_all_reaches_every_archetype_with_the_component() {
+    let mut world = world();
+    let visited = world
+        .modify_all::<Velocity>(|velocity| velocity.0[1] -= 1)
+        .unwrap();
+    assert_eq!(visited, 2_010);
+    world.swap_buffers();
+    let velocities = by_index::<Velocity>(&world);
+    assert_eq!(velocities[&0], Velocity([1, 1]));
+    assert_eq!(velocities[&2_005], Velocity([0, 0]));
+
+    let visited = world
+        .modify_all_with_entity::<Age>(|id, age| age.0 = id * 2)
+        .unwrap();
+    assert_eq!(visited, 10);
+    world.swap_buffers();
+    assert!(world
+        .collect_query::<Age>()
+        .into_iter()
+        .all(|(entity, age)| age.0 == entity.index() * 2));
+}
@@ -0,0 +1,61 @@
+use latch_core::memory::FrameArena;
+
+#[test]
+fn reset_reuses_the_same_memory() {
+    let mut arena = FrameArena::with_capacity(1024);
+
+    let first_ptr = {
+        let ids = arena.alloc_slice::<u32>(16);
+        assert!(ids.iter().all(|&id| id == 0));
+        for (i, id) in ids.iter_mut().enumerate() {
+            *id = i as u32;
+        }
+        let positions = arena.alloc_slice_fill(8, [1.5f32, -2.0]);
+        assert_eq!(positions[7], [1.5, -2.0]);
+        // Earlier slices stay valid while later ones are handed out.
+        assert_eq!(ids[15], 15);
+        ids.as_ptr()
+    };
+    assert!(arena.allocated_bytes() >= 16 * 4 + 8 * 8);
+
+    arena.reset();
+    assert_eq!(arena.allocated_bytes(), 0);
+
+    let again = arena.alloc_slice::<u32>(16);
+    assert_eq!(again.as_ptr(), first_ptr);
+    assert!(again.iter().all(|&id| id == 0));
+}
+
+#[test]
+fn growth_is_merged_into_one_chunk_on_reset() {
+    let mut arena = FrameArena::with_capacity(64);
+
+    {
+        let a = arena.alloc_slice_copy(&[7u64; 8]);
+        let b = arena.alloc_slice::<u64>(64);
+        b[63] = 9;
+        assert_eq!(a, &[7u64; 8]);
+        assert_eq!(b[63], 9);
+    }
+    let grown = arena.capacity();
+    assert!(grown >= 64 * 8 + 8 * 8);
+
+    // After the merge the whole previous frame fits in the single chunk,
+    // so the next frame allocates nothing new and reuses the same base.
+    arena.reset();
+    assert_eq!(arena.capacity(), grown);
+    let base = arena.alloc_slice::<u64>(8).as_ptr();
+    let _ = arena.alloc_slice::<u64>(64);
+    assert_eq!(arena.capacity(), grown);
+
+    arena.reset();
+    assert_eq!(arena.alloc_slice::<u64>(8).as_ptr(), base);
+}
+
+#[test]
+fn empty_and_zero_sized_allocations() {
+    let arena = FrameArena::new();
+    assert!(arena.alloc_slice::<u32>(0).is_empty());
+    assert_eq!(arena.alloc_slice::<()>(5).len(), 5);
+    assert_eq!(arena.allocated_bytes(), 0);
+}
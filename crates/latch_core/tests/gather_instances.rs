@@ -0,0 +1,48 @@
+use latch_core::define_component;
+use latch_core::ecs::{PageBudget, World};
+use latch_core::spawn;
+use std::num::NonZeroUsize;
+
+#[derive(Clone, Copy)]
+struct Pos(i32);
+define_component!(Pos, 9101, "GatherTest::Pos");
+
+#[derive(Clone, Copy)]
+struct Tint(u8);
+define_component!(Tint, 9102, "GatherTest::Tint");
+
+#[test]
+fn gathers_rows_across_pages_in_order() {
+    // Tiny budget forces several pages per column.
+    let budget = PageBudget::with_l2_bytes(NonZeroUsize::new(64).unwrap());
+    let mut world = World::with_page_budget(budget);
+    let first = spawn!(world, Pos(0), Tint(0));
+    for i in 1..50 {
+        spawn!(world, Pos(i), Tint(i as u8));
+    }
+
+    let archetype = world.locate(first).unwrap().archetype;
+    let storage = world.storage(archetype).unwrap();
+    assert!(storage.column(Pos::ID).unwrap().page_count() > 1);
+
+    let mut single = Vec::new();
+    storage
+        .gather_instances::<Pos, _>(&mut single, |p| p.0 * 2)
+        .unwrap();
+    assert_eq!(single, (0..50).map(|i| i * 2).collect::<Vec<_>>());
+
+    let mut paired = vec![(-1, 0)];
+    storage
+        .gather_instances2::<Pos, Tint, _>(&mut paired, |p, t| (p.0, t.0))
+        .unwrap();
+    assert_eq!(paired.len(), 51);
+    assert_eq!(paired[50], (49, 49));
+
+    let mut partial = Vec::new();
+    storage
+        .column(Pos::ID)
+        .unwrap()
+        .copy_typed_into::<Pos, i32>(3..20, &mut partial, |p| p.0)
+        .unwrap();
+    assert_eq!(partial, (3..20).collect::<Vec<_>>());
+}
@@ -0,0 +1,21 @@
+use latch_core::define_component;
+use latch_core::ecs::{handle_of_type, Component};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Heading(f32);
+define_component!(Heading, 9301, "HandleOfTypeTest::Heading");
+
+struct NeverRegistered;
+
+#[test]
+fn type_lookup_agrees_with_explicit_id() {
+    Heading::ensure_registered();
+    let handle = handle_of_type::<Heading>().expect("registered by type");
+    assert_eq!(handle.id, Heading::ID);
+    assert_eq!(handle, Heading::handle());
+}
+
+#[test]
+fn unregistered_type_has_no_handle() {
+    assert_eq!(handle_of_type::<NeverRegistered>(), None);
+}
@@ -0,0 +1,53 @@
+use latch_core::define_component;
+use latch_core::ecs::{Component, EntityStatus, World};
+use latch_core::spawn;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Position([i32; 2]);
+define_component!(Position, 9285, "MatchExplanationTest::Position");
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Velocity([i32; 2]);
+define_component!(Velocity, 9286, "MatchExplanationTest::Velocity");
+
+#[test]
+fn explains_missing_components_and_dead_handles() {
+    let mut world = World::new();
+    let query = [Position::id(), Velocity::id()];
+    let moving = spawn!(world, Position([0, 0]), Velocity([1, 0]));
+    let still = spawn!(world, Position([5, 5]));
+
+    let explanation = world.explain_match(moving, &query);
+    assert!(explanation.matches());
+    assert_eq!(explanation.status, EntityStatus::Alive);
+
+    let explanation = world.explain_match(still, &query);
+    assert!(!explanation.matches());
+    assert_eq!(
+        explanation.archetype,
+        Some(world.locate(still).unwrap().archetype)
+    );
+    assert_eq!(explanation.present, vec![Position::id()]);
+    assert_eq!(explanation.missing, vec![Velocity::id()]);
+    assert!(explanation
+        .to_string()
+        .contains("missing MatchExplanationTest::Velocity (id 9286)"));
+
+    // Despawned rows are still visited until the flush.
+    world.despawn(moving).unwrap();
+    let explanation = world.explain_match(moving, &query);
+    assert_eq!(explanation.status, EntityStatus::PendingDespawn);
+    assert!(explanation.matches());
+
+    world.flush_despawns().unwrap();
+    let explanation = world.explain_match(moving, &query);
+    assert_eq!(explanation.status, EntityStatus::Stale);
+    assert_eq!(explanation.missing, query);
+    assert!(!explanation.matches());
+
+    let reserved = world.reserve_entities(1).unwrap()[0];
+    assert_eq!(
+        world.explain_match(reserved, &query).status,
+        EntityStatus::Reserved
+    );
+}
@@ -0,0 +1,88 @@
+use latch_core::define_component;
+use latch_core::ecs::{Component, PageAllocator, PageBudget, World, WorldBuilder};
+use latch_core::spawn;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::num::NonZeroUsize;
+use std::ptr::NonNull;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Mass(u32);
+define_component!(Mass, 9282, "PageAllocatorTest::Mass");
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Charge(i64);
+define_component!(Charge, 9283, "PageAllocatorTest::Charge");
+
+#[derive(Default)]
+struct CountingAllocator {
+    allocs: AtomicUsize,
+    frees: AtomicUsize,
+}
+
+unsafe impl PageAllocator for CountingAllocator {
+    fn alloc_page(&self, layout: Layout) -> Option<NonNull<u8>> {
+        self.allocs.fetch_add(1, Ordering::Relaxed);
+        NonNull::new(unsafe { System.alloc(layout) })
+    }
+
+    unsafe fn free_page(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.frees.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.dealloc(ptr.as_ptr(), layout) };
+    }
+}
+
+impl CountingAllocator {
+    fn live(&self) -> usize {
+        self.allocs.load(Ordering::Relaxed) - self.frees.load(Ordering::Relaxed)
+    }
+}
+
+fn small_pages() -> PageBudget {
+    PageBudget::with_l2_bytes(NonZeroUsize::new(4 * 1024).unwrap())
+}
+
+#[test]
+fn page_allocations_and_frees_balance() {
+    let allocator = Arc::new(CountingAllocator::default());
+    let mut world = World::with_page_budget(small_pages());
+    world.set_page_allocator(allocator.clone());
+
+    let entities: Vec<_> = (0..2_000)
+        .map(|i| spawn!(world, Mass(i), Charge(i as i64)))
+        .collect();
+    let pages = world
+        .archetype_ids()
+        .iter()
+        .map(|&id| world.storage(id).unwrap().page_count())
+        .sum::<usize>();
+    assert!(pages > 1);
+    // Two columns, each with a current and a next page.
+    assert_eq!(allocator.live(), pages * 4);
+
+    for &entity in &entities[1_000..] {
+        world.despawn(entity).unwrap();
+    }
+    world.flush_despawns().unwrap();
+    assert!(allocator.frees.load(Ordering::Relaxed) > 0);
+
+    drop(world);
+    assert!(allocator.allocs.load(Ordering::Relaxed) > 0);
+    assert_eq!(allocator.live(), 0);
+}
+
+#[test]
+fn builder_reservations_use_the_allocator() {
+    let allocator = Arc::new(CountingAllocator::default());
+    let world = WorldBuilder::new()
+        .page_budget(small_pages())
+        .page_allocator(allocator.clone())
+        .prealloc_archetype(&[Mass::id()], 500)
+        .build()
+        .unwrap();
+    assert!(allocator.live() > 0);
+
+    drop(world);
+    assert_eq!(allocator.live(), 0);
+}
@@ -0,0 +1,73 @@
+use latch_core::define_component;
+use latch_core::ecs::{PagePool, World};
+use latch_core::spawn;
+use std::sync::Arc;
+use std::thread;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Shard(u32);
+define_component!(Shard, 9295, "PagePoolTest::Shard");
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Heat(f32);
+define_component!(Heat, 9296, "PagePoolTest::Heat");
+
+fn populate(world: &mut World, count: u32) {
+    for i in 0..count {
+        spawn!(world, Shard(i), Heat(i as f32));
+    }
+}
+
+#[test]
+fn freed_pages_are_reused_by_another_world() {
+    let pool = Arc::new(PagePool::new());
+
+    let mut first = World::with_page_pool(pool.clone());
+    populate(&mut first, 20_000);
+    let fresh = pool.fresh_pages();
+    assert!(fresh > 4);
+    assert_eq!(pool.cached_pages(), 0);
+
+    drop(first);
+    assert_eq!(pool.cached_pages() as u64, fresh);
+
+    let mut second = World::with_page_pool(pool.clone());
+    populate(&mut second, 20_000);
+    assert_eq!(pool.fresh_pages(), fresh);
+    assert_eq!(pool.reused_pages(), fresh);
+    assert_eq!(pool.cached_pages(), 0);
+    assert_eq!(second.entity_count(), 20_000);
+}
+
+#[test]
+fn byte_limit_releases_excess_pages() {
+    let pool = Arc::new(PagePool::new().with_max_cached_bytes(0));
+    let mut world = World::with_page_pool(pool.clone());
+    populate(&mut world, 5_000);
+    drop(world);
+    assert_eq!(pool.cached_pages(), 0);
+    assert_eq!(pool.cached_bytes(), 0);
+}
+
+#[test]
+fn pool_is_shared_across_threads() {
+    let pool = Arc::new(PagePool::new());
+    let shard = {
+        let pool = pool.clone();
+        thread::spawn(move || {
+            let mut world = World::with_page_pool(pool);
+            populate(&mut world, 5_000);
+        })
+    };
+    shard.join().unwrap();
+    let cached = pool.cached_pages();
+    assert!(cached > 0);
+
+    let mut world = World::with_page_pool(pool.clone());
+    populate(&mut world, 5_000);
+    assert_eq!(pool.reused_pages(), cached as u64);
+
+    drop(world);
+    pool.trim();
+    assert_eq!(pool.cached_bytes(), 0);
+}
@@ -0,0 +1,36 @@
+use latch_core::define_component;
+use latch_core::ecs::{PageBudget, World};
+use latch_core::spawn;
+use std::num::NonZeroUsize;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(C)]
+struct Tint([u8; 4]);
+define_component!(Tint, 9120, "PagePtrTest::Tint");
+
+#[test]
+fn page_ptr_covers_live_rows_of_each_page() {
+    let mut world =
+        World::with_page_budget(PageBudget::with_l2_bytes(NonZeroUsize::new(256).unwrap()));
+    let entities: Vec<_> = (0..100u8)
+        .map(|i| spawn!(world, Tint([i, i, i, 255])))
+        .collect();
+
+    let archetype = world.locate(entities[0]).unwrap().archetype;
+    let column = world.storage(archetype).unwrap().column(Tint::ID).unwrap();
+    assert!(column.page_count() > 1, "test needs several pages");
+
+    let mut uploaded = Vec::new();
+    for page in 0..column.page_count() {
+        let (ptr, len) = column.page_ptr(page);
+        assert_eq!(len, column.page_range(page).len() * column.stride());
+        assert_eq!(ptr as usize % column.page_align(), 0);
+        // SAFETY: the world is borrowed immutably for the whole loop.
+        let bytes = unsafe { std::slice::from_raw_parts(ptr, len) };
+        assert_eq!(bytes, column.page_bytes(page));
+        uploaded.extend_from_slice(bytes);
+    }
+
+    let expected: Vec<u8> = (0..100u8).flat_map(|i| [i, i, i, 255]).collect();
+    assert_eq!(uploaded, expected);
+}
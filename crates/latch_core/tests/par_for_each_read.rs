@@ -0,0 +1,50 @@
+use latch_core::define_component;
+use latch_core::ecs::World;
+use latch_core::spawn;
+use std::sync::atomic::{AtomicI64, AtomicUsize, Ordering};
+
+#[derive(Clone, Copy)]
+struct Health(i32);
+define_component!(Health, 9090, "ParReadTest::Health");
+
+#[allow(dead_code)]
+#[derive(Clone, Copy)]
+struct Marker(u8);
+define_component!(Marker, 9091, "ParReadTest::Marker");
+
+#[test]
+fn visits_every_matching_archetype_through_shared_borrow() {
+    let mut world = World::new();
+    for i in 0..100 {
+        if i % 4 == 0 {
+            spawn!(world, Health(i), Marker(0));
+        } else {
+            spawn!(world, Health(i));
+        }
+    }
+    spawn!(world, Marker(1));
+
+    let world = &world;
+    let rows = AtomicUsize::new(0);
+    let total = AtomicI64::new(0);
+    world.par_for_each_read(&[Health::ID], |storage| {
+        let health = storage.column_slice::<Health>().expect("health column");
+        rows.fetch_add(health.len(), Ordering::Relaxed);
+        let sum: i64 = health.iter().map(|h| h.0 as i64).sum();
+        total.fetch_add(sum, Ordering::Relaxed);
+    });
+    assert_eq!(rows.into_inner(), 100);
+    assert_eq!(total.into_inner(), (0..100).sum::<i64>());
+
+    let tagged = AtomicUsize::new(0);
+    world.par_for_each_read(&[Health::ID, Marker::ID], |storage| {
+        tagged.fetch_add(storage.entity_count(), Ordering::Relaxed);
+    });
+    assert_eq!(tagged.into_inner(), 25);
+}
+
+#[test]
+fn world_is_shareable_across_threads() {
+    fn assert_sync<T: Sync>() {}
+    assert_sync::<World>();
+}
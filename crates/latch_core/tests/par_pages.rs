@@ -0,0 +1,88 @@
+use latch_core::define_component;
+use latch_core::ecs::{PageBudget, StorageError, World};
+use latch_core::spawn;
+use std::num::NonZeroUsize;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Position([i32; 2]);
+define_component!(Position, 9250, "ParPagesTest::Position");
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Velocity([i32; 2]);
+define_component!(Velocity, 9251, "ParPagesTest::Velocity");
+
+const ENTITIES: i32 = 10_000;
+
+fn world() -> World {
+    // Small pages so the single archetype spans many tiles.
+    let mut world = World::with_page_budget(PageBudget::with_l2_bytes(
+        NonZeroUsize::new(4 * 1024).unwrap(),
+    ));
+    for i in 0..ENTITIES {
+        spawn!(world, Position([i, -i]), Velocity([1, 2]));
+    }
+    world
+}
+
+#[test]
+fn integrates_every_page_of_one_archetype() {
+    let mut world = world();
+    let archetype = world.archetypes_matching(&[Position::ID]).next().unwrap();
+    let storage = world.storage_mut(archetype).unwrap();
+    assert!(storage.page_count() > 1);
+
+    storage
+        .par_pages_mut(&[Position::ID, Velocity::ID], |tile| {
+            let (read, write) = tile.rw::<Position>().unwrap();
+            let velocity = tile.read::<Velocity>().unwrap();
+            assert_eq!(tile.entity_ids().len(), tile.len());
+            for ((out, position), velocity) in write.iter_mut().zip(read).zip(velocity) {
+                out.0 = [position.0[0] + velocity.0[0], position.0[1] + velocity.0[1]];
+            }
+        })
+        .unwrap();
+    world.swap_buffers();
+
+    let storage = world.storage(archetype).unwrap();
+    let column = storage.column(Position::ID).unwrap();
+    let mut seen = 0;
+    for page_idx in 0..column.page_count() {
+        let range = column.page_range(page_idx);
+        for (row, position) in column
+            .slice_read_typed::<Position>(range.clone())
+            .unwrap()
+            .iter()
+            .enumerate()
+        {
+            let i = (range.start + row) as i32;
+            assert_eq!(*position, Position([i + 1, -i + 2]));
+            seen += 1;
+        }
+    }
+    assert_eq!(seen, ENTITIES);
+}
+
+#[test]
+fn each_column_is_written_once_per_tile() {
+    let mut world = world();
+    let archetype = world.archetypes_matching(&[Position::ID]).next().unwrap();
+    let storage = world.storage_mut(archetype).unwrap();
+    storage
+        .par_pages_mut(&[Position::ID], |tile| {
+            let _first = tile.write::<Position>().unwrap();
+            assert!(matches!(
+                tile.write::<Position>(),
+                Err(StorageError::DuplicateColumnRequest { .. })
+            ));
+            assert!(matches!(
+                tile.read::<Velocity>(),
+                Err(StorageError::ColumnMissing { .. })
+            ));
+        })
+        .unwrap();
+
+    assert!(matches!(
+        storage.par_pages_mut(&[Position::ID, Position::ID], |_| {}),
+        Err(StorageError::DuplicateColumnRequest { .. })
+    ));
+}
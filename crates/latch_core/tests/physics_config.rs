@@ -0,0 +1,101 @@
+use latch_core::ecs::{SnapshotCompression, World};
+use latch_core::physics::{CollisionConfig, PhysicsConfig};
+
+#[test]
+fn physics_config_is_a_tunable_world_resource() {
+    let mut world = World::new();
+    assert!(world.resource::<PhysicsConfig>().is_none());
+
+    assert!(world.insert_resource(PhysicsConfig::default()).is_none());
+    world.resource_mut::<PhysicsConfig>().unwrap().gravity = -80;
+    assert_eq!(world.resource::<PhysicsConfig>().unwrap().gravity, -80);
+
+    let previous = world.insert_resource(PhysicsConfig::default()).unwrap();
+    assert_eq!(previous.gravity, -80);
+    assert_eq!(
+        world.remove_resource::<PhysicsConfig>(),
+        Some(PhysicsConfig::default())
+    );
+    assert!(world.resource::<PhysicsConfig>().is_none());
+}
+
+#[test]
+fn physics_config_round_trips_through_bytes() {
+    let config = PhysicsConfig {
+        gravity: -123,
+        linear_damping: 0.5,
+        tangent_friction: 0.125,
+        bounds_min: [-7, i32::MIN],
+        bounds_max: [9, i32::MAX],
+    };
+    assert_eq!(PhysicsConfig::from_bytes(config.to_bytes()), config);
+    assert_eq!(&config.to_bytes()[..4], &(-123i32).to_le_bytes());
+}
+
+#[test]
+fn restore_brings_back_the_snapshotted_physics_config() {
+    let mut world = World::new();
+    let recorded = PhysicsConfig {
+        gravity: -80,
+        ..PhysicsConfig::default()
+    };
+    world.insert_resource(recorded);
+    let bytes = world.snapshot(SnapshotCompression::None).unwrap();
+
+    world.resource_mut::<PhysicsConfig>().unwrap().gravity = -10;
+    world.restore(&bytes).unwrap();
+    assert_eq!(world.resource::<PhysicsConfig>(), Some(&recorded));
+
+    let mut replay = World::new();
+    replay.restore(&bytes).unwrap();
+    assert_eq!(replay.resource::<PhysicsConfig>(), Some(&recorded));
+
+    // A snapshot without the resource clears it on restore.
+    let bare = World::new().snapshot(SnapshotCompression::None).unwrap();
+    replay.restore(&bare).unwrap();
+    assert!(replay.resource::<PhysicsConfig>().is_none());
+}
+
+#[test]
+fn collision_config_defaults_and_round_trips() {
+    let defaults = CollisionConfig::default();
+    assert_eq!(defaults.iterations, 10);
+    assert_eq!(defaults.passes(), 10);
+    assert_eq!(
+        CollisionConfig {
+            iterations: 0,
+            ..defaults
+        }
+        .passes(),
+        1
+    );
+
+    let config = CollisionConfig {
+        iterations: 3,
+        correction: 0.5,
+        contact_damping: 0.75,
+    };
+    assert_eq!(CollisionConfig::from_bytes(config.to_bytes()), config);
+
+    let mut world = World::new();
+    world.insert_resource(config);
+    world.resource_mut::<CollisionConfig>().unwrap().iterations = 20;
+    assert_eq!(world.resource::<CollisionConfig>().unwrap().passes(), 20);
+}
+
+#[test]
+fn restore_brings_back_the_snapshotted_collision_config() {
+    let mut world = World::new();
+    let recorded = CollisionConfig {
+        iterations: 4,
+        correction: 0.5,
+        contact_damping: 0.25,
+    };
+    world.insert_resource(recorded);
+    let bytes = world.snapshot(SnapshotCompression::Fast).unwrap();
+
+    world.resource_mut::<CollisionConfig>().unwrap().iterations = 30;
+    world.restore(&bytes).unwrap();
+    assert_eq!(world.resource::<CollisionConfig>(), Some(&recorded));
+    assert!(world.resource::<PhysicsConfig>().is_none());
+}
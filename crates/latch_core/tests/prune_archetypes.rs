@@ -0,0 +1,66 @@
+use latch_core::define_component;
+use latch_core::ecs::{QueryCache, Schedule, TickPhase, World};
+use latch_core::spawn;
+use std::num::NonZeroU64;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Shell(u32);
+define_component!(Shell, 9269, "PruneArchetypesTest::Shell");
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Spark(u32);
+define_component!(Spark, 9270, "PruneArchetypesTest::Spark");
+
+#[test]
+fn emptied_archetypes_are_dropped_and_recreated() {
+    let mut world = World::new();
+    let keep = spawn!(world, Shell(1));
+    let spark = spawn!(world, Shell(2), Spark(3));
+    let archetype = world.locate(spark).unwrap().archetype;
+    let mut cache = QueryCache::new();
+    assert_eq!(cache.matching(&world, &[Spark::ID]), [archetype]);
+
+    world.despawn(spark).unwrap();
+    assert_eq!(
+        world.prune_empty_archetypes(),
+        0,
+        "pending despawn keeps it"
+    );
+    world.flush_despawns().unwrap();
+    assert_eq!(world.prune_empty_archetypes(), 1);
+    assert_eq!(
+        world.archetype_ids(),
+        [world.locate(keep).unwrap().archetype]
+    );
+    assert!(world.archetypes_with(Spark::ID).is_empty());
+    assert_eq!(world.archetypes_with(Shell::ID).len(), 1);
+    assert!(world.storage(archetype).is_none());
+    assert!(cache.matching(&world, &[Spark::ID]).is_empty());
+
+    let again = spawn!(world, Shell(4), Spark(5));
+    assert_eq!(world.locate(again).unwrap().archetype, archetype);
+    assert_eq!(world.archetypes_with(Spark::ID), [archetype]);
+    assert_eq!(world.collect_query::<Spark>(), [(again, Spark(5))]);
+}
+
+#[test]
+fn schedule_prunes_on_interval_ticks() {
+    let mut world = World::new();
+    let mut schedule = Schedule::new();
+    let mut phases = Schedule::DEFAULT_PHASES.to_vec();
+    phases.push(TickPhase::PruneArchetypes {
+        interval: NonZeroU64::new(2).unwrap(),
+    });
+    schedule.set_phases(phases).unwrap();
+
+    spawn!(world, Shell(0));
+    let spark = spawn!(world, Spark(0));
+    world.tick(&mut schedule).unwrap();
+    assert_eq!(world.archetype_ids().len(), 2);
+
+    world.despawn(spark).unwrap();
+    world.tick(&mut schedule).unwrap();
+    assert_eq!(world.archetype_ids().len(), 2, "tick 1 is not a prune tick");
+    world.tick(&mut schedule).unwrap();
+    assert_eq!(world.archetype_ids().len(), 1);
+}
@@ -0,0 +1,39 @@
+use latch_core::define_component;
+use latch_core::ecs::{QueryCache, World};
+use latch_core::spawn;
+
+#[allow(dead_code)]
+#[derive(Clone, Copy)]
+struct A(u32);
+define_component!(A, 9301, "QueryCacheTest::A");
+
+#[allow(dead_code)]
+#[derive(Clone, Copy)]
+struct B(u32);
+define_component!(B, 9302, "QueryCacheTest::B");
+
+#[test]
+fn cache_refreshes_only_on_new_archetypes() {
+    let mut world = World::new();
+    let mut cache = QueryCache::new();
+
+    let ab = spawn!(world, A(0), B(0));
+    let ab_archetype = world.locate(ab).unwrap().archetype;
+    assert_eq!(cache.matching(&world, &[A::ID, B::ID]), &[ab_archetype]);
+
+    // Same archetype: generation unchanged, cached result reused.
+    let generation = world.archetype_generation();
+    spawn!(world, A(1), B(1));
+    assert_eq!(world.archetype_generation(), generation);
+    assert_eq!(cache.matching(&world, &[B::ID, A::ID]), &[ab_archetype]);
+    assert_eq!(cache.len(), 1);
+
+    let a_only = spawn!(world, A(2));
+    let a_archetype = world.locate(a_only).unwrap().archetype;
+    assert_ne!(world.archetype_generation(), generation);
+
+    let mut expected = vec![ab_archetype, a_archetype];
+    expected.sort_unstable();
+    assert_eq!(cache.matching(&world, &[A::ID]), expected.as_slice());
+    assert_eq!(cache.matching(&world, &[A::ID, B::ID]), &[ab_archetype]);
+}
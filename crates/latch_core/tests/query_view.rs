@@ -0,0 +1,51 @@
+use latch_core::define_component;
+use latch_core::ecs::{StorageError, World};
+use latch_core::spawn;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Score(u32);
+define_component!(Score, 9263, "QueryViewTest::Score");
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Rank(u8);
+define_component!(Rank, 9264, "QueryViewTest::Rank");
+
+#[test]
+fn collects_single_and_paired_components_across_archetypes() {
+    let mut world = World::new();
+    let a = spawn!(world, Score(3));
+    let b = spawn!(world, Score(1), Rank(2));
+    let c = spawn!(world, Score(2), Rank(1));
+    let gone = spawn!(world, Score(9));
+    world.despawn(gone).unwrap();
+
+    let mut scores = world.collect_query::<Score>();
+    scores.sort_by_key(|(_, score)| score.0);
+    assert_eq!(scores, [(b, Score(1)), (c, Score(2)), (a, Score(3))]);
+
+    assert_eq!(
+        world.collect_query2::<Score, Rank>(),
+        [(b, Score(1), Rank(2)), (c, Score(2), Rank(1))]
+    );
+}
+
+#[test]
+fn view_iterates_repeatedly_without_rematching() {
+    let mut world = World::new();
+    for i in 0..5 {
+        spawn!(world, Score(i), Rank(i as u8));
+    }
+
+    let view = world.query_view(&[Score::ID, Rank::ID]);
+    assert_eq!(view.archetypes().count(), 1);
+    assert_eq!(view.row_count(), 5);
+    assert_eq!(view.entities().count(), 5);
+    let total: u32 = view.iter::<Score>().unwrap().map(|(_, s)| s.0).sum();
+    assert_eq!(total, 10);
+    assert_eq!(view.iter2::<Score, Rank>().unwrap().count(), 5);
+
+    assert!(matches!(
+        world.query_view(&[Rank::ID]).collect::<Score>(),
+        Err(StorageError::ColumnMissing { .. })
+    ));
+}
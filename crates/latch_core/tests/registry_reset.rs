@@ -0,0 +1,38 @@
+use latch_core::define_component;
+use latch_core::ecs::{meta_of, meta_of_name, register_component, reset_registry, World};
+use latch_core::spawn;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Probe(u32);
+define_component!(Probe, 9289, "RegistryResetTest::Probe");
+
+#[test]
+fn reset_allows_a_name_to_change_layout() {
+    let registry = reset_registry();
+    register_component("RegistryResetTest::Layout", 4, 4, 4, true, Vec::new()).unwrap();
+    assert_eq!(meta_of_name("RegistryResetTest::Layout").unwrap().size, 4);
+    drop(registry);
+
+    let _registry = reset_registry();
+    assert!(meta_of_name("RegistryResetTest::Layout").is_none());
+    register_component("RegistryResetTest::Layout", 8, 4, 8, true, Vec::new()).unwrap();
+    assert_eq!(meta_of_name("RegistryResetTest::Layout").unwrap().size, 8);
+}
+
+#[test]
+fn defined_components_register_again_after_reset() {
+    let registry = reset_registry();
+    let mut world = World::new();
+    spawn!(world, Probe(1));
+    assert!(meta_of(Probe::ID).is_some());
+    drop(world);
+    drop(registry);
+
+    let _registry = reset_registry();
+    assert!(meta_of(Probe::ID).is_none());
+    let mut world = World::new();
+    let entity = spawn!(world, Probe(2));
+    assert_eq!(meta_of(Probe::ID).unwrap().size, 4);
+    let loc = world.locate(entity).unwrap();
+    assert_eq!(world.column::<Probe>(loc.archetype).unwrap(), &[Probe(2)]);
+}
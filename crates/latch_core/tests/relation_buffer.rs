@@ -0,0 +1,120 @@
+use latch_core::ecs::query::RelationDelta;
+use latch_core::ecs::{Entity, OverflowPolicy, RelationBuffer, RelationRecord, RelationType};
+
+const CONTACT: RelationType = RelationType::new(1);
+
+#[test]
+fn push_grows_past_initial_page_without_dropping() {
+    let mut buffer = RelationBuffer::new(4, 4);
+    let hub = Entity::new(0, 0);
+    for i in 1..=20u32 {
+        let record = RelationRecord::new(hub, Entity::new(i, 0), CONTACT, None);
+        let delta = Some(RelationDelta {
+            dx: i as i64,
+            dy: 0,
+        });
+        buffer.push_relation(record, &[i as u8; 3], delta, None, None);
+    }
+
+    assert_eq!(buffer.len(), 20);
+    assert_eq!(buffer.iter().count(), 20);
+    assert!(buffer.capacity() >= 20);
+    assert_eq!(buffer.high_water_mark(), 20);
+
+    let hub_edges = buffer.relations_for(hub);
+    assert_eq!(hub_edges.len(), 20);
+    assert_eq!(hub_edges[19].other, Entity::new(20, 0));
+    let payload = hub_edges[19].payload.unwrap();
+    assert_eq!(buffer.payload_slice(payload).unwrap(), vec![20u8; 3]);
+    assert_eq!(buffer.relations_for(Entity::new(7, 0))[0].other, hub);
+
+    buffer.clear();
+    assert!(buffer.is_empty());
+    assert_eq!(buffer.high_water_mark(), 20);
+    buffer.reset_high_water_mark();
+    assert_eq!(buffer.high_water_mark(), 0);
+}
+
+#[test]
+fn iter_type_yields_only_that_type_in_buffer_order() {
+    const TRIGGER: RelationType = RelationType::new(2);
+    let mut buffer = RelationBuffer::new(4, 4);
+    for i in 0..12u32 {
+        let relation_type = if i % 3 == 0 { TRIGGER } else { CONTACT };
+        let record =
+            RelationRecord::new(Entity::new(i, 0), Entity::new(100, 0), relation_type, None);
+        buffer.push_relation(record, &[], None, None, None);
+    }
+
+    let triggers: Vec<u32> = buffer
+        .iter_type(TRIGGER)
+        .map(|record| record.entity_a.index())
+        .collect();
+    assert_eq!(triggers, vec![0, 3, 6, 9]);
+    assert_eq!(buffer.iter_type(CONTACT).count(), 8);
+    assert_eq!(buffer.iter_type(RelationType::new(9)).count(), 0);
+    assert_eq!(buffer.iter().count(), 12);
+}
+
+fn push_star(buffer: &mut RelationBuffer, hub: Entity, distances: &[i64]) {
+    for (i, &dx) in distances.iter().enumerate() {
+        let record = RelationRecord::new(hub, Entity::new(i as u32 + 1, 0), CONTACT, None);
+        buffer.push_relation(record, &[], Some(RelationDelta { dx, dy: 0 }), None, None);
+    }
+}
+
+fn kept_others(buffer: &RelationBuffer, hub: Entity) -> Vec<u32> {
+    let mut others: Vec<u32> = buffer
+        .relations_for(hub)
+        .iter()
+        .map(|entry| entry.other.index())
+        .collect();
+    others.sort_unstable();
+    others
+}
+
+#[test]
+fn overflow_policies_bound_per_entity_views() {
+    let hub = Entity::new(0, 0);
+    let distances = [50, -10, 40, 20, -30, 5];
+
+    let mut grow = RelationBuffer::new(4, 4).with_per_entity_cap(3, OverflowPolicy::Grow);
+    push_star(&mut grow, hub, &distances);
+    assert_eq!(grow.relations_for(hub).len(), 6);
+    assert_eq!(grow.dropped_relations(), 0);
+
+    let mut drop = RelationBuffer::new(4, 4).with_per_entity_cap(3, OverflowPolicy::DropExcess);
+    push_star(&mut drop, hub, &distances);
+    assert_eq!(kept_others(&drop, hub), vec![1, 2, 3]);
+    assert_eq!(drop.dropped_relations(), 3);
+    // The cap limits views only; every record is still there.
+    assert_eq!(drop.iter().count(), 6);
+
+    let mut nearest = RelationBuffer::new(4, 4).with_per_entity_cap(3, OverflowPolicy::KeepNearest);
+    push_star(&mut nearest, hub, &distances);
+    assert_eq!(kept_others(&nearest, hub), vec![2, 4, 6]);
+    assert_eq!(nearest.dropped_relations(), 3);
+    nearest.reset_dropped_relations();
+    assert_eq!(nearest.dropped_relations(), 0);
+}
+
+#[test]
+fn keep_nearest_ignores_emission_order() {
+    let hub = Entity::new(0, 0);
+    let mut forward = RelationBuffer::new(4, 4).with_per_entity_cap(2, OverflowPolicy::KeepNearest);
+    let mut reverse = RelationBuffer::new(4, 4).with_per_entity_cap(2, OverflowPolicy::KeepNearest);
+    let neighbors: Vec<(u32, i64)> = vec![(1, 7), (2, -7), (3, 3), (4, 7), (5, 9)];
+    for &(other, dx) in &neighbors {
+        let record = RelationRecord::new(hub, Entity::new(other, 0), CONTACT, None);
+        forward.push_relation(record, &[], Some(RelationDelta { dx, dy: 0 }), None, None);
+    }
+    for &(other, dx) in neighbors.iter().rev() {
+        let record = RelationRecord::new(hub, Entity::new(other, 0), CONTACT, None);
+        reverse.push_relation(record, &[], Some(RelationDelta { dx, dy: 0 }), None, None);
+    }
+    forward.sort_canonical();
+    reverse.sort_canonical();
+    assert_eq!(forward.relations_for(hub), reverse.relations_for(hub));
+    // Equal distances are broken by entity id.
+    assert_eq!(kept_others(&forward, hub), vec![1, 3]);
+}
@@ -0,0 +1,82 @@
+use latch_core::define_component;
+use latch_core::ecs::{
+    QueryRegistry, RelationBuffer, RelationType, SpatialHashConfig, SpatialHashGrid,
+    VisibilityAccelerator, VisibilityConfig, World,
+};
+use latch_core::spawn;
+
+#[allow(dead_code)]
+#[derive(Clone, Copy)]
+struct Body([i32; 2]);
+define_component!(Body, 9280, "RelationDeltaTest::Body");
+
+#[allow(dead_code)]
+#[derive(Clone, Copy)]
+struct Lamp([i32; 2]);
+define_component!(Lamp, 9281, "RelationDeltaTest::Lamp");
+
+const NEAR: RelationType = RelationType::new(21);
+const SEES: RelationType = RelationType::new(22);
+
+#[test]
+fn spatial_hash_deltas_do_not_wrap_at_coordinate_extremes() {
+    let mut world = World::new();
+    let west = spawn!(world, Body([-2_000_000_000, 0]));
+    let middle = spawn!(world, Body([0, 0]));
+    let east = spawn!(world, Body([2_000_000_000, 0]));
+
+    // One cell each side of the origin, so `west` and `east` are neighbours
+    // 4e9 units apart.
+    let mut queries = QueryRegistry::new();
+    queries.register(Box::new(SpatialHashGrid::new(SpatialHashConfig::new(
+        Body::ID,
+        i32::MAX,
+        i32::MAX,
+        NEAR,
+    ))));
+    let mut buffer = RelationBuffer::new(16, 16);
+    queries.rebuild_all(&world, &mut buffer);
+
+    assert_eq!(buffer.len(), 2);
+    assert!(buffer
+        .relations_for(west)
+        .iter()
+        .all(|entry| entry.other == middle));
+    let deltas: Vec<i64> = buffer
+        .relations_for(middle)
+        .iter()
+        .map(|entry| {
+            let delta = entry.delta.unwrap();
+            assert_eq!(delta.dy, 0);
+            if entry.other == east {
+                assert_eq!(delta.dx.abs(), 2_000_000_000);
+            }
+            delta.dx
+        })
+        .collect();
+    assert_eq!(deltas.len(), 2);
+    assert_eq!(deltas[0], -deltas[1]);
+}
+
+#[test]
+fn visibility_handles_observers_at_the_edge_of_the_world() {
+    let mut world = World::new();
+    let eye = spawn!(world, Body([i32::MAX - 10, i32::MIN + 10]));
+    let lamp = spawn!(world, Lamp([i32::MAX - 60, i32::MIN + 40]));
+
+    let mut queries = QueryRegistry::new();
+    queries.register(Box::new(VisibilityAccelerator::new(VisibilityConfig::new(
+        Body::ID,
+        Lamp::ID,
+        Lamp::ID,
+        100,
+        SEES,
+    ))));
+    let mut buffer = RelationBuffer::new(16, 16);
+    queries.rebuild_all(&world, &mut buffer);
+
+    let entry = buffer.relations_for(eye)[0];
+    assert_eq!(entry.other, lamp);
+    let delta = entry.delta.unwrap();
+    assert_eq!((delta.dx, delta.dy), (-50, 30));
+}
@@ -0,0 +1,72 @@
+use latch_core::define_component;
+use latch_core::ecs::{
+    QueryRegistry, RelationBuffer, RelationRecord, RelationType, SpatialHashConfig,
+    SpatialHashGrid, World,
+};
+use latch_core::spawn;
+
+#[allow(dead_code)]
+#[derive(Clone, Copy)]
+struct Pos {
+    x: i32,
+    y: i32,
+}
+define_component!(Pos, 9401, "RelationOrderTest::Pos");
+
+#[allow(dead_code)]
+#[derive(Clone, Copy)]
+struct Tag(u8);
+define_component!(Tag, 9402, "RelationOrderTest::Tag");
+
+const CONTACT: RelationType = RelationType::new(7);
+
+fn rebuild(queries: &mut QueryRegistry, world: &World) -> RelationBuffer {
+    let mut buffer = RelationBuffer::new(16, 16);
+    queries.rebuild_all(world, &mut buffer);
+    buffer
+}
+
+#[test]
+fn relation_order_is_canonical_and_repeatable() {
+    let mut world = World::new();
+    // Two archetypes so emission interleaves storage order with cell order.
+    for i in 0..24 {
+        let pos = Pos {
+            x: (i % 6) * 10,
+            y: (i / 6) * 10,
+        };
+        if i % 3 == 0 {
+            spawn!(world, pos, Tag(0));
+        } else {
+            spawn!(world, pos);
+        }
+    }
+
+    let mut queries = QueryRegistry::new();
+    queries.register(Box::new(SpatialHashGrid::new(SpatialHashConfig::new(
+        Pos::ID,
+        16,
+        12,
+        CONTACT,
+    ))));
+
+    let first = rebuild(&mut queries, &world);
+    let second = rebuild(&mut queries, &world);
+    let first_records: Vec<RelationRecord> = first.iter().collect();
+    assert!(!first_records.is_empty());
+    assert_eq!(first_records, second.iter().collect::<Vec<_>>());
+
+    let keys: Vec<_> = first_records
+        .iter()
+        .map(|r| (r.entity_a.to_bits(), r.entity_b.to_bits()))
+        .collect();
+    assert!(keys.windows(2).all(|w| w[0] <= w[1]));
+
+    for record in &first_records {
+        let entries = first.relations_for(record.entity_a);
+        assert_eq!(entries, second.relations_for(record.entity_a));
+        assert!(entries
+            .windows(2)
+            .all(|w| w[0].other.to_bits() <= w[1].other.to_bits()));
+    }
+}
@@ -0,0 +1,41 @@
+use latch_core::define_component;
+use latch_core::ecs::{sort_instances_by_layer, RenderLayer, World};
+use latch_core::spawn;
+
+#[derive(Clone, Copy)]
+struct Pos(i32);
+define_component!(Pos, 9110, "RenderLayerTest::Pos");
+
+#[allow(dead_code)]
+#[derive(Clone, Copy)]
+struct Tint(u8);
+define_component!(Tint, 9111, "RenderLayerTest::Tint");
+
+#[test]
+fn instances_sort_by_layer_stably_across_archetypes() {
+    let mut world = World::new();
+    // Untagged entities land on the default layer.
+    let plain = spawn!(world, Pos(0), Tint(0));
+    spawn!(world, Pos(1), Tint(1));
+    let layered = spawn!(world, Pos(2), Tint(2), RenderLayer(3));
+    spawn!(world, Pos(3), Tint(3), RenderLayer(1));
+    spawn!(world, Pos(4), Tint(4), RenderLayer(3));
+    spawn!(world, Pos(5), Tint(5), RenderLayer(1));
+
+    let mut instances = Vec::new();
+    let mut layers = Vec::new();
+    // Visit the layered archetype first so the unsorted order is wrong.
+    for entity in [layered, plain] {
+        let archetype = world.locate(entity).unwrap().archetype;
+        world
+            .storage(archetype)
+            .unwrap()
+            .gather_instances2_layered::<Pos, Tint, _>(&mut instances, &mut layers, |p, _| p.0)
+            .unwrap();
+    }
+    assert_eq!(instances, vec![2, 3, 4, 5, 0, 1]);
+    assert_eq!(layers.len(), instances.len());
+
+    sort_instances_by_layer(&mut instances, &layers);
+    assert_eq!(instances, vec![0, 1, 3, 5, 2, 4]);
+}
@@ -0,0 +1,149 @@
+use latch_core::time::{
+    ActionId, ActionState, InputRecorder, ReplayDecodeError, SimulationTime, TickInput,
+    REPLAY_MAGIC, REPLAY_VERSION_RAW, TICK_DURATION,
+};
+use std::time::Duration;
+
+#[test]
+fn encode_decode_round_trips_action_states() {
+    let mut actions = ActionState {
+        pointer: [0.25, -0.5],
+        ..Default::default()
+    };
+    actions.set_pressed(ActionId(3), true);
+
+    let mut recorder = InputRecorder::new();
+    recorder.start_recording();
+    recorder.record(TickInput {
+        tick: 0,
+        mouse_x: 0.0,
+        mouse_y: 0.0,
+        mouse_pressed: false,
+        actions: None,
+        tick_duration: TICK_DURATION,
+    });
+    recorder.record(TickInput {
+        tick: 1,
+        mouse_x: 0.25,
+        mouse_y: -0.5,
+        mouse_pressed: true,
+        actions: Some(actions),
+        tick_duration: Duration::from_secs(1) / 30,
+    });
+
+    let decoded = InputRecorder::decode(&recorder.encode()).unwrap();
+    assert_eq!(decoded, recorder.export());
+    assert!(decoded[1].actions.unwrap().is_pressed(ActionId(3)));
+    assert!(!decoded[1].actions.unwrap().is_pressed(ActionId(0)));
+    assert_eq!(decoded[1].tick_duration, Duration::from_secs(1) / 30);
+}
+
+#[test]
+fn slow_ticks_keep_their_duration() {
+    let mut recorder = InputRecorder::new();
+    recorder.start_recording();
+    recorder.record(TickInput {
+        tick: 0,
+        mouse_x: 0.0,
+        mouse_y: 0.0,
+        mouse_pressed: false,
+        actions: None,
+        tick_duration: Duration::from_secs(10),
+    });
+
+    let decoded = InputRecorder::decode(&recorder.encode()).unwrap();
+    assert_eq!(decoded[0].tick_duration, Duration::from_secs(10));
+}
+
+#[test]
+fn tick_rate_is_configurable() {
+    let time = SimulationTime::with_tick_hz(30.0);
+    assert_eq!(time.tick_duration(), Duration::from_nanos(33_333_333));
+    assert!((time.delta_time() - 1.0 / 30.0).abs() < 1e-6);
+    assert!((time.tick_hz() - 30.0).abs() < 1e-3);
+    assert_eq!(SimulationTime::new().tick_duration(), TICK_DURATION);
+
+    let replay = SimulationTime::with_tick_duration(time.tick_duration());
+    assert_eq!(replay.tick_duration(), time.tick_duration());
+}
+
+#[test]
+fn playback_can_seek_and_inspect_history() {
+    let mut recorder = InputRecorder::new();
+    recorder.start_recording();
+    for tick in [0, 1, 2, 4, 5] {
+        recorder.record(TickInput {
+            tick,
+            mouse_x: tick as f32,
+            mouse_y: 0.0,
+            mouse_pressed: false,
+            actions: None,
+            tick_duration: TICK_DURATION,
+        });
+    }
+    recorder.stop_recording();
+    assert_eq!(recorder.len(), 5);
+    assert_eq!(recorder.input_at(4).unwrap().mouse_x, 4.0);
+    assert!(recorder.input_at(3).is_none());
+    assert!(recorder.input_at(9).is_none());
+
+    recorder.start_playback();
+    assert_eq!(recorder.playback(0).unwrap().tick, 0);
+    assert_eq!(recorder.playback(1).unwrap().tick, 1);
+    recorder.seek(4);
+    assert!(recorder.playback(2).is_none());
+    assert_eq!(recorder.playback(4).unwrap().tick, 4);
+    recorder.seek(1);
+    assert_eq!(recorder.playback_tick(), Some(1));
+    assert_eq!(recorder.playback(1).unwrap().tick, 1);
+    assert_eq!(recorder.playback(2).unwrap().tick, 2);
+    recorder.seek(3);
+    assert_eq!(recorder.playback_tick(), Some(4));
+    recorder.seek(6);
+    assert_eq!(recorder.playback_tick(), None);
+}
+
+#[test]
+fn version_one_replays_still_load() {
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(&REPLAY_MAGIC);
+    bytes.extend_from_slice(&REPLAY_VERSION_RAW.to_le_bytes());
+    bytes.extend_from_slice(&1u64.to_le_bytes());
+    bytes.extend_from_slice(&7u64.to_le_bytes());
+    bytes.extend_from_slice(&0.5f32.to_le_bytes());
+    bytes.extend_from_slice(&(-1.0f32).to_le_bytes());
+    bytes.push(1);
+
+    let decoded = InputRecorder::decode(&bytes).unwrap();
+    assert_eq!(
+        decoded,
+        vec![TickInput {
+            tick: 7,
+            mouse_x: 0.5,
+            mouse_y: -1.0,
+            mouse_pressed: true,
+            actions: None,
+            tick_duration: TICK_DURATION,
+        }]
+    );
+}
+
+#[test]
+fn rejects_unknown_versions_and_truncation() {
+    let mut bytes = REPLAY_MAGIC.to_vec();
+    bytes.extend_from_slice(&99u16.to_le_bytes());
+    assert!(matches!(
+        InputRecorder::decode(&bytes),
+        Err(ReplayDecodeError::UnsupportedVersion(99))
+    ));
+
+    let encoded = InputRecorder::new().encode();
+    assert!(matches!(
+        InputRecorder::decode(&encoded[..encoded.len() - 1]),
+        Err(ReplayDecodeError::Truncated { .. })
+    ));
+    assert!(matches!(
+        InputRecorder::decode(b"nope"),
+        Err(ReplayDecodeError::BadMagic)
+    ));
+}
@@ -0,0 +1,108 @@
+use latch_core::define_component;
+use latch_core::ecs::{CommandBuffer, EntityBuilder, World, WorldError};
+use latch_core::spawn;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Owner(u64);
+define_component!(Owner, 9190, "ReserveEntitiesTest::Owner");
+
+#[test]
+fn reserved_entities_are_not_alive_until_applied() {
+    let mut world = World::new();
+    let existing = spawn!(world, Owner(0));
+
+    let reserved = world.reserve_entities(3).unwrap();
+    assert_eq!(reserved.len(), 3);
+    for entity in &reserved {
+        assert_ne!(entity.index(), existing.index());
+        assert!(matches!(
+            world.locate(*entity),
+            Err(WorldError::EntityNotAlive { .. })
+        ));
+    }
+    assert_eq!(world.live_entity_count(), 1);
+
+    // Two workers, each with a disjoint slice of the reservation.
+    let mut first = CommandBuffer::new();
+    first.spawn(
+        reserved[0],
+        EntityBuilder::new().with(Owner(reserved[1].to_bits())),
+    );
+    first.despawn(existing);
+    let mut second = CommandBuffer::new();
+    second.spawn(
+        reserved[1],
+        EntityBuilder::new().with(Owner(reserved[0].to_bits())),
+    );
+    second.despawn(existing);
+
+    world.apply_commands(first).unwrap();
+    world.apply_commands(second).unwrap();
+
+    assert!(world.validate(existing).is_none());
+    assert!(world.validate(reserved[0]).is_some());
+    assert!(world.validate(reserved[1]).is_some());
+    assert!(world.validate(reserved[2]).is_none());
+    assert_eq!(world.live_entity_count(), 2);
+
+    let mut refill = CommandBuffer::new();
+    refill.spawn(reserved[0], EntityBuilder::new().with(Owner(0)));
+    assert!(matches!(
+        world.apply_commands(refill),
+        Err(WorldError::NotReserved { .. })
+    ));
+}
+
+#[test]
+fn cancelled_reservations_go_stale_and_are_reused() {
+    let mut world = World::new();
+    let reserved = world.reserve_entities(2).unwrap();
+    world.cancel_reservation(reserved[1]).unwrap();
+
+    assert!(matches!(
+        world.spawn_reserved(reserved[1], EntityBuilder::new().with(Owner(1))),
+        Err(WorldError::StaleEntity { .. })
+    ));
+
+    let spawned = spawn!(world, Owner(2));
+    assert_eq!(spawned.index(), reserved[1].index());
+    assert_ne!(spawned, reserved[1]);
+
+    world
+        .spawn_reserved(reserved[0], EntityBuilder::new().with(Owner(3)))
+        .unwrap();
+    assert_eq!(world.live_entity_count(), 2);
+}
+
+#[test]
+fn despawning_a_reservation_wins_in_either_buffer_order() {
+    let record = |entity| {
+        let mut spawn = CommandBuffer::new();
+        spawn.spawn(entity, EntityBuilder::new().with(Owner(1)));
+        let mut despawn = CommandBuffer::new();
+        despawn.despawn(entity);
+        (spawn, despawn)
+    };
+
+    // Spawn first: the despawn removes the spawned entity.
+    let mut world = World::new();
+    let entity = world.reserve_entities(1).unwrap()[0];
+    let (spawn, despawn) = record(entity);
+    world.apply_commands(spawn).unwrap();
+    world.apply_commands(despawn).unwrap();
+    world.flush_despawns().unwrap();
+    assert!(world.validate(entity).is_none());
+    assert_eq!(world.live_entity_count(), 0);
+
+    // Despawn first: the reservation is cancelled and the spawn rejected.
+    let mut world = World::new();
+    let entity = world.reserve_entities(1).unwrap()[0];
+    let (spawn, despawn) = record(entity);
+    world.apply_commands(despawn).unwrap();
+    assert!(matches!(
+        world.apply_commands(spawn),
+        Err(WorldError::StaleEntity { .. })
+    ));
+    assert!(world.validate(entity).is_none());
+    assert_eq!(world.live_entity_count(), 0);
+}
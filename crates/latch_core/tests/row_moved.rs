@@ -0,0 +1,58 @@
+use latch_core::define_component;
+use latch_core::ecs::{Entity, World};
+use latch_core::spawn;
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Slot(u32);
+define_component!(Slot, 9299, "RowMovedTest::Slot");
+
+fn recorder(world: &mut World) -> Arc<Mutex<Vec<(Entity, usize, usize)>>> {
+    let moves = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&moves);
+    world.on_row_moved(move |entity, old_row, new_row| {
+        sink.lock().unwrap().push((entity, old_row, new_row));
+    });
+    moves
+}
+
+#[test]
+fn each_moved_entity_is_reported_once_with_its_rows() {
+    let mut world = World::new();
+    let entities: Vec<Entity> = (0..10).map(|i| spawn!(world, Slot(i))).collect();
+    let moves = recorder(&mut world);
+
+    // Holes near the tail make swap-remove refill some rows twice.
+    for &row in &[1, 4, 7, 9] {
+        world.despawn(entities[row]).unwrap();
+    }
+    let before: Vec<(Entity, usize)> = entities
+        .iter()
+        .filter_map(|&e| world.validate(e).map(|loc| (e, loc.index)))
+        .collect();
+    world.flush_despawns().unwrap();
+
+    let mut expected: Vec<(Entity, usize, usize)> = before
+        .iter()
+        .map(|&(e, old_row)| (e, old_row, world.locate(e).unwrap().index))
+        .filter(|&(_, old_row, new_row)| old_row != new_row)
+        .collect();
+    let mut reported = moves.lock().unwrap().clone();
+    expected.sort_by_key(|&(e, ..)| e.index());
+    reported.sort_by_key(|&(e, ..)| e.index());
+    assert!(!expected.is_empty());
+    assert_eq!(reported, expected);
+}
+
+#[test]
+fn cleared_hook_is_not_called() {
+    let mut world = World::new();
+    let first = spawn!(world, Slot(0));
+    spawn!(world, Slot(1));
+    let moves = recorder(&mut world);
+    world.clear_on_row_moved();
+
+    world.despawn(first).unwrap();
+    world.flush_despawns().unwrap();
+    assert!(moves.lock().unwrap().is_empty());
+}
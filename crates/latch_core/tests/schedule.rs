@@ -0,0 +1,85 @@
+use latch_core::define_component;
+use latch_core::ecs::{Schedule, ScheduleError, SystemDescriptor, TickPhase, World};
+use latch_core::spawn;
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Counter(u32);
+define_component!(Counter, 9210, "ScheduleTest::Counter");
+
+fn increment(world: &mut World, components: &[latch_core::ecs::ComponentId]) {
+    world.for_each(components, |storage| {
+        let column = storage.column_mut(Counter::ID).unwrap();
+        for page_idx in 0..column.page_count() {
+            let range = column.page_range(page_idx);
+            let (read, write) = column.slice_rw_typed::<Counter>(range).unwrap();
+            for (out, value) in write.iter_mut().zip(read) {
+                *out = Counter(value.0 + 1);
+            }
+        }
+    });
+}
+
+#[test]
+fn default_tick_publishes_writes_and_flushes_despawns() {
+    let mut world = World::new();
+    let kept = spawn!(world, Counter(0));
+    let doomed = spawn!(world, Counter(100));
+
+    let mut schedule = Schedule::new();
+    let seen_ticks = Arc::new(Mutex::new(Vec::new()));
+    let log = Arc::clone(&seen_ticks);
+    schedule
+        .add_system(
+            &mut world,
+            SystemDescriptor::new("increment").writes([Counter::ID]),
+            move |world, ctx| {
+                log.lock().unwrap().push(ctx.tick);
+                increment(world, ctx.components);
+            },
+        )
+        .unwrap();
+
+    world.despawn(doomed).unwrap();
+    let timings = world.tick(&mut schedule).unwrap();
+    let order: Vec<TickPhase> = timings.phases.iter().map(|t| t.phase).collect();
+    assert_eq!(order, Schedule::DEFAULT_PHASES);
+    assert_eq!(timings.tick, 0);
+
+    world.tick(&mut schedule).unwrap();
+    assert_eq!(schedule.tick(), 2);
+    assert_eq!(*seen_ticks.lock().unwrap(), vec![0, 1]);
+
+    let loc = world.locate(kept).unwrap();
+    assert_eq!(
+        world.column::<Counter>(loc.archetype).unwrap(),
+        &[Counter(2)]
+    );
+    assert_eq!(world.entity_count(), 1);
+}
+
+#[test]
+fn phase_orders_must_publish_every_stage() {
+    let mut schedule = Schedule::new();
+    let late = schedule.add_stage();
+
+    assert!(matches!(
+        schedule.set_phases(vec![TickPhase::SwapBuffers, TickPhase::Stage(late)]),
+        Err(ScheduleError::UnpublishedStage { stage }) if stage == late
+    ));
+    assert!(matches!(
+        schedule.set_phases(vec![TickPhase::Stage(7), TickPhase::SwapBuffers]),
+        Err(ScheduleError::UnknownStage { stage: 7 })
+    ));
+    assert_eq!(schedule.phases(), Schedule::DEFAULT_PHASES);
+
+    let order = vec![
+        TickPhase::Stage(Schedule::UPDATE),
+        TickPhase::SwapBuffers,
+        TickPhase::RebuildQueries,
+        TickPhase::Stage(late),
+        TickPhase::SwapBuffers,
+    ];
+    schedule.set_phases(order.clone()).unwrap();
+    assert_eq!(schedule.phases(), order);
+}
@@ -0,0 +1,68 @@
+use latch_core::define_component;
+use latch_core::ecs::{Schedule, SharedWorld, SnapshotCompression, World, WorldSnapshot};
+use latch_core::spawn;
+use std::thread;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Health(u32);
+define_component!(Health, 9288, "SharedWorldTest::Health");
+
+#[test]
+fn shared_world_is_send_and_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<World>();
+    assert_send_sync::<SharedWorld>();
+}
+
+#[test]
+fn readers_observe_whole_ticks() {
+    let shared = SharedWorld::new(World::new());
+    let mut schedule = Schedule::new();
+
+    let reader = {
+        let shared = shared.clone();
+        thread::spawn(move || {
+            let mut counts = Vec::new();
+            for _ in 0..50 {
+                // The snapshot and the count are read under one lock, so no
+                // spawn can land between them.
+                let (decoded, count) = shared.with_read(|world| {
+                    let bytes = world.snapshot(SnapshotCompression::None).unwrap();
+                    (
+                        WorldSnapshot::decode(&bytes).unwrap().len(),
+                        world.live_entity_count(),
+                    )
+                });
+                assert_eq!(decoded, count);
+                counts.push(count);
+                thread::yield_now();
+            }
+            counts
+        })
+    };
+
+    for i in 0..20 {
+        {
+            let mut world = shared.write();
+            spawn!(world, Health(i));
+        }
+        shared.tick(&mut schedule).unwrap();
+    }
+
+    let counts = reader.join().unwrap();
+    assert!(counts.windows(2).all(|pair| pair[0] <= pair[1]));
+    assert_eq!(shared.live_entity_count(), 20);
+
+    let hash = shared.state_hash();
+    let world = shared.into_inner().ok().expect("no other handle");
+    assert_eq!(world.state_hash(), hash);
+}
+
+#[test]
+fn into_inner_fails_while_shared() {
+    let shared = SharedWorld::new(World::new());
+    let other = shared.clone();
+    let shared = shared.into_inner().err().expect("still shared");
+    drop(other);
+    assert!(shared.into_inner().is_ok());
+}
@@ -0,0 +1,50 @@
+use latch_core::define_component;
+use latch_core::ecs::{Entity, World};
+use latch_core::spawn;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Ttl(u16);
+define_component!(Ttl, 9180, "ShrinkSlotsTest::Ttl");
+
+#[test]
+fn shrink_slots_releases_trailing_free_slots() {
+    let mut world = World::new();
+    let keeper = spawn!(world, Ttl(0));
+    let transient: Vec<Entity> = (0..10_000).map(|_| spawn!(world, Ttl(1))).collect();
+    assert_eq!(world.allocated_slots(), 10_001);
+
+    for &entity in &transient {
+        world.despawn(entity).unwrap();
+    }
+    // Pending despawns still occupy their slots.
+    assert_eq!(world.shrink_slots(), 0);
+
+    world.flush_despawns().unwrap();
+    assert!(world.slot_utilization() < 0.01);
+    assert_eq!(world.shrink_slots(), 10_000);
+    assert_eq!(world.allocated_slots(), 1);
+    assert_eq!(world.slot_utilization(), 1.0);
+    assert!(world.locate(keeper).is_ok());
+
+    // Recreated slots never revive handles to the truncated ones.
+    let fresh = spawn!(world, Ttl(2));
+    assert_eq!(fresh.index(), transient[0].index());
+    assert!(world.locate(transient[0]).is_err());
+    assert!(world.locate(fresh).is_ok());
+}
+
+#[test]
+fn shrink_slots_keeps_interior_holes_reusable() {
+    let mut world = World::new();
+    let entities: Vec<Entity> = (0..4).map(|i| spawn!(world, Ttl(i))).collect();
+    world.despawn(entities[1]).unwrap();
+    world.despawn(entities[3]).unwrap();
+    world.flush_despawns().unwrap();
+
+    assert_eq!(world.shrink_slots(), 1);
+    assert_eq!(world.allocated_slots(), 3);
+
+    let reused = spawn!(world, Ttl(9));
+    assert_eq!(reused.index(), entities[1].index());
+    assert_eq!(world.allocated_slots(), 3);
+}
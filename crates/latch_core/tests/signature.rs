@@ -0,0 +1,36 @@
+use latch_core::ecs::{ArchetypeLayout, ComponentSignature};
+
+#[test]
+fn layout_matches_subset_queries_across_words() {
+    let layout = ArchetypeLayout::new(vec![3, 70, 200]);
+    assert!(layout.contains(70));
+    assert!(!layout.contains(71));
+    assert!(!layout.contains(10_000));
+
+    assert!(layout.matches(&ComponentSignature::from_components(&[3, 200])));
+    assert!(layout.matches(&ComponentSignature::from_components(&[])));
+    assert!(!layout.matches(&ComponentSignature::from_components(&[3, 4])));
+    assert!(!layout.matches(&ComponentSignature::from_components(&[300])));
+    assert_eq!(layout.signature().len(), 3);
+}
+
+#[test]
+fn sparse_ids_match_without_a_dense_bitset() {
+    let huge = u32::MAX;
+    let signature = ComponentSignature::from_components(&[huge, 5, huge - 64, 5]);
+    assert_eq!(signature.len(), 3);
+    assert!(signature.contains(huge));
+    assert!(signature.contains(huge - 64));
+    assert!(!signature.contains(huge - 1));
+    assert!(!signature.contains(64));
+
+    let layout = ArchetypeLayout::new(vec![1, 5, 130, huge]);
+    assert!(layout.matches(&ComponentSignature::from_components(&[huge, 1])));
+    assert!(layout.matches(&ComponentSignature::from_components(&[130, 5])));
+    assert!(!layout.matches(&ComponentSignature::from_components(&[huge - 1])));
+    assert!(!layout.matches(&ComponentSignature::from_components(&[2, huge])));
+    assert_eq!(
+        ComponentSignature::from_components(&[huge, 5]),
+        ComponentSignature::from_components(&[5, huge, 5])
+    );
+}
@@ -0,0 +1,46 @@
+use latch_core::define_component;
+use latch_core::ecs::{Component, SimdAlign, World};
+use latch_core::spawn;
+
+#[derive(Clone, Copy)]
+struct Wide([f32; 3]);
+
+impl Component for Wide {
+    const NAME: &'static str = "SimdAlignTest::Wide";
+
+    fn simd_align() -> SimdAlign {
+        SimdAlign::Align32
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Narrow(u8);
+define_component!(Narrow, 9001, "SimdAlignTest::Narrow");
+
+#[test]
+fn over_aligned_columns_start_pages_on_simd_boundary() {
+    let mut world = World::new();
+    let entity = spawn!(world, Wide([1.0; 3]), Narrow(1));
+    for i in 0..100 {
+        spawn!(world, Wide([i as f32; 3]), Narrow(i as u8));
+    }
+
+    let archetype = world.locate(entity).unwrap().archetype;
+    let storage = world.storage_mut(archetype).unwrap();
+    let column = storage.column_mut(Wide::id()).unwrap();
+    assert_eq!(column.page_align(), 32);
+    assert_eq!(column.align(), std::mem::align_of::<Wide>());
+    assert_eq!(column.column_slice_read::<Wide>().unwrap()[0].0, [1.0; 3]);
+
+    for page in 0..column.page_count() {
+        let range = column.page_range(page);
+        let read = column.slice_read_typed::<Wide>(range.clone()).unwrap();
+        assert_eq!(read.as_ptr() as usize % 32, 0);
+        let write = column.slice_write_typed::<Wide>(range).unwrap();
+        assert_eq!(write.as_ptr() as usize % 32, 0);
+    }
+
+    let narrow = storage.column(Narrow::id()).unwrap();
+    assert_eq!(narrow.page_align(), 1);
+    assert_eq!(narrow.column_slice_read::<Narrow>().unwrap()[0].0, 1);
+}
@@ -0,0 +1,138 @@
+use latch_core::define_component;
+use latch_core::ecs::{
+    SnapshotCompression, SnapshotError, SnapshotHeader, World, SNAPSHOT_FLAG_DEFLATE,
+};
+use latch_core::spawn;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Position {
+    x: i32,
+    y: i32,
+}
+define_component!(Position, 9230, "SnapshotTest::Position");
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Color {
+    r: u8,
+    g: u8,
+    b: u8,
+}
+define_component!(Color, 9231, "SnapshotTest::Color");
+
+fn sample_world() -> World {
+    let mut world = World::new();
+    for i in 0..1000 {
+        let position = Position { x: i, y: -i };
+        if i % 3 == 0 {
+            spawn!(world, position);
+        } else {
+            spawn!(
+                world,
+                position,
+                Color {
+                    r: 255,
+                    g: 128,
+                    b: 0
+                }
+            );
+        }
+    }
+    world
+}
+
+fn positions(world: &World) -> Vec<Position> {
+    let mut out = Vec::new();
+    for archetype in world.archetypes_matching(&[Position::ID]) {
+        out.extend_from_slice(world.column::<Position>(archetype).unwrap());
+    }
+    out
+}
+
+#[test]
+fn restore_round_trips_entities_and_values() {
+    let mut world = sample_world();
+    let removed = spawn!(world, Position { x: 7, y: 7 });
+    world.despawn(removed).unwrap();
+    world.flush_despawns().unwrap();
+
+    let bytes = world.snapshot(SnapshotCompression::None).unwrap();
+    let mut restored = World::new();
+    restored.restore(&bytes).unwrap();
+
+    assert_eq!(restored.entity_count(), world.entity_count());
+    assert_eq!(restored.archetype_stats(), world.archetype_stats());
+    assert_eq!(positions(&restored), positions(&world));
+    assert!(restored.validate(removed).is_none());
+
+    // The free list survives, so both worlds recycle the same id next.
+    let a = spawn!(world, Position { x: 0, y: 0 });
+    let b = spawn!(restored, Position { x: 0, y: 0 });
+    assert_eq!(a, b);
+}
+
+#[test]
+fn deflate_shrinks_repetitive_columns() {
+    let world = sample_world();
+    let raw = world.snapshot(SnapshotCompression::None).unwrap();
+    let packed = world.snapshot(SnapshotCompression::Best).unwrap();
+
+    assert!(!SnapshotHeader::parse(&raw).unwrap().is_compressed());
+    let header = SnapshotHeader::parse(&packed).unwrap();
+    assert_eq!(header.flags, SNAPSHOT_FLAG_DEFLATE);
+    assert_eq!(header.body_len as usize, raw.len() - 16);
+    assert!(packed.len() < raw.len());
+
+    let mut restored = World::new();
+    restored.restore(&packed).unwrap();
+    assert_eq!(positions(&restored), positions(&world));
+}
+
+#[test]
+fn snapshot_rejects_pending_despawns() {
+    let mut world = sample_world();
+    let entity = spawn!(world, Position { x: 1, y: 1 });
+    world.despawn(entity).unwrap();
+    assert!(matches!(
+        world.snapshot(SnapshotCompression::None),
+        Err(SnapshotError::PendingDespawns)
+    ));
+}
+
+#[test]
+fn failed_restore_leaves_world_untouched() {
+    let world = sample_world();
+    let mut bytes = world.snapshot(SnapshotCompression::None).unwrap();
+    bytes.truncate(bytes.len() - 1);
+
+    let mut target = World::new();
+    let entity = spawn!(target, Position { x: 5, y: 6 });
+    assert!(target.restore(&bytes).is_err());
+    assert!(matches!(
+        target.restore(b"LS"),
+        Err(SnapshotError::Truncated { .. })
+    ));
+    assert!(matches!(
+        target.restore(b"NOPE\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00"),
+        Err(SnapshotError::BadMagic)
+    ));
+    assert_eq!(target.entity_count(), 1);
+    assert!(target.validate(entity).is_some());
+}
+
+#[test]
+fn inflation_stops_past_the_declared_body_length() {
+    let packed = sample_world().snapshot(SnapshotCompression::Best).unwrap();
+    let real_len = SnapshotHeader::parse(&packed).unwrap().body_len;
+
+    // Same deflate stream, but the header understates the body.
+    let mut lying = packed.clone();
+    lying[8..16].copy_from_slice(&16u64.to_le_bytes());
+    assert!(real_len > 16);
+    assert!(matches!(
+        World::new().restore(&lying),
+        Err(SnapshotError::LengthMismatch {
+            expected: 16,
+            actual: 17
+        })
+    ));
+}
@@ -0,0 +1,90 @@
+use latch_core::define_component;
+use latch_core::ecs::{
+    register_external_component_with_fields, reset_registry, ChangeRecord, Component,
+    EntityBuilder, FieldMeta, SnapshotCompression, World, WorldSnapshot,
+};
+use latch_core::spawn;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Tag(u16);
+define_component!(Tag, 9287, "SnapshotDiffTest::Tag");
+
+fn health(hp: i32, max: i32) -> Vec<u8> {
+    [hp.to_le_bytes(), max.to_le_bytes()].concat()
+}
+
+#[test]
+fn diff_reports_field_changes_and_spawns() {
+    let _registry = reset_registry();
+    let health_id = register_external_component_with_fields(
+        "SnapshotDiffTest::Health",
+        8,
+        4,
+        8,
+        vec![FieldMeta::new("hp", 0, 4), FieldMeta::new("max", 4, 4)],
+        true,
+    )
+    .unwrap()
+    .id;
+    let spawn_unit = |world: &mut World, hp: i32| {
+        let builder = EntityBuilder::new()
+            .with(Tag(1))
+            .with_raw_bytes(health_id, health(hp, 100))
+            .unwrap();
+        world.spawn(builder).unwrap()
+    };
+
+    let mut world = World::new();
+    let hurt = spawn_unit(&mut world, 100);
+    let doomed = spawn_unit(&mut world, 50);
+    let untouched = spawn!(world, Tag(7));
+    let before = world.snapshot(SnapshotCompression::Fast).unwrap();
+
+    world.despawn(doomed).unwrap();
+    world.flush_despawns().unwrap();
+    let location = world.locate(hurt).unwrap();
+    world
+        .storage_mut(location.archetype)
+        .unwrap()
+        .write_component(health_id, location.index, &health(75, 100), None)
+        .unwrap();
+    let fresh = spawn!(world, Tag(9));
+    let after = world.snapshot(SnapshotCompression::None).unwrap();
+
+    let before = WorldSnapshot::decode(&before).unwrap();
+    let after = WorldSnapshot::decode(&after).unwrap();
+    assert_eq!(before.len(), 3);
+    assert_eq!(
+        after.component(untouched, Tag::id()),
+        Some(&7u16.to_le_bytes()[..])
+    );
+
+    let changes = after.diff(&after);
+    assert!(changes.is_empty());
+
+    let changes = before.diff(&after);
+    let summary: Vec<String> = changes.iter().map(ToString::to_string).collect();
+    assert_eq!(changes.len(), 3, "{summary:?}");
+    match &changes[0] {
+        ChangeRecord::Changed {
+            entity,
+            component_id,
+            fields,
+            ..
+        } => {
+            assert_eq!(*entity, hurt);
+            assert_eq!(*component_id, health_id);
+            assert_eq!(fields.len(), 1);
+            assert_eq!(&*fields[0].name, "hp");
+        }
+        other => panic!("unexpected {other}"),
+    }
+    assert!(summary[0].ends_with("SnapshotDiffTest::Health: hp 100 -> 75"));
+    // `fresh` reuses `doomed`'s slot, so it is a removal and an addition.
+    assert_eq!(fresh.index(), doomed.index());
+    assert!(
+        matches!(&changes[1], ChangeRecord::Removed { entity, components }
+        if *entity == doomed && components.len() == 2)
+    );
+    assert!(matches!(&changes[2], ChangeRecord::Added { entity, .. } if *entity == fresh));
+}
@@ -0,0 +1,70 @@
+use latch_core::define_component;
+use latch_core::ecs::{
+    QueryRegistry, RelationBuffer, RelationType, SpatialHashConfig, SpatialHashGrid,
+    TriggerAccelerator, World,
+};
+use latch_core::spawn;
+
+#[allow(dead_code)]
+#[derive(Clone, Copy)]
+struct Pos {
+    x: i32,
+    y: i32,
+}
+define_component!(Pos, 9265, "SpatialHashMetricsTest::Pos");
+
+const COLLISION: RelationType = RelationType::new(11);
+const PROXIMITY: RelationType = RelationType::new(12);
+
+#[test]
+fn each_grid_keeps_its_own_metrics() {
+    let mut world = World::new();
+    for i in 0..4 {
+        spawn!(world, Pos { x: i * 5, y: 0 });
+    }
+
+    let mut queries = QueryRegistry::new();
+    queries.register(Box::new(SpatialHashGrid::new(SpatialHashConfig::new(
+        Pos::ID,
+        16,
+        8,
+        COLLISION,
+    ))));
+    queries.register(Box::new(SpatialHashGrid::new(SpatialHashConfig::new(
+        Pos::ID,
+        16,
+        8,
+        PROXIMITY,
+    ))));
+    let mut buffer = RelationBuffer::new(64, 64);
+    queries.rebuild_all(&world, &mut buffer);
+
+    let collision = queries
+        .get_as::<SpatialHashGrid>(COLLISION)
+        .unwrap()
+        .metrics_snapshot();
+    if cfg!(feature = "metrics") {
+        assert_eq!(collision.total_calls, 1);
+        assert_eq!(collision.entities, 4);
+    }
+
+    queries
+        .get_as_mut::<SpatialHashGrid>(PROXIMITY)
+        .unwrap()
+        .reset_metrics();
+    let proximity = queries
+        .get_as::<SpatialHashGrid>(PROXIMITY)
+        .unwrap()
+        .metrics_snapshot();
+    assert_eq!(proximity.total_calls, 0);
+    assert_eq!(proximity.entities, 0);
+    assert_eq!(
+        queries
+            .get_as::<SpatialHashGrid>(COLLISION)
+            .unwrap()
+            .metrics_snapshot()
+            .entities,
+        collision.entities
+    );
+    assert!(queries.get_as::<TriggerAccelerator>(COLLISION).is_none());
+}
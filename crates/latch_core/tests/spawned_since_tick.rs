@@ -0,0 +1,59 @@
+use latch_core::define_component;
+use latch_core::ecs::{CommandBuffer, EntityBuilder, Schedule, SystemDescriptor, World};
+use latch_core::spawn;
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Marker(u32);
+define_component!(Marker, 9240, "SpawnedSinceTickTest::Marker");
+
+#[test]
+fn reports_only_spawns_since_the_last_swap() {
+    let mut world = World::new();
+    let old = spawn!(world, Marker(0));
+    world.swap_buffers();
+    assert!(world.spawned_since_last_tick().is_empty());
+
+    let a = spawn!(world, Marker(1));
+    let reserved = world.reserve_entities(1).unwrap()[0];
+    let mut commands = CommandBuffer::new();
+    commands.spawn(reserved, EntityBuilder::new().with(Marker(2)));
+    world.apply_commands(commands).unwrap();
+
+    assert_eq!(world.spawned_since_last_tick(), &[a, reserved]);
+    assert!(!world.spawned_since_last_tick().contains(&old));
+
+    world.swap_buffers();
+    assert!(world.spawned_since_last_tick().is_empty());
+}
+
+#[test]
+fn tick_systems_see_entities_spawned_between_ticks() {
+    let mut world = World::new();
+    let mut schedule = Schedule::new();
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let log = Arc::clone(&seen);
+    schedule
+        .add_system(
+            &mut world,
+            SystemDescriptor::new("on_spawn").reads([Marker::ID]),
+            move |world, _ctx| {
+                log.lock()
+                    .unwrap()
+                    .push(world.spawned_since_last_tick().to_vec());
+            },
+        )
+        .unwrap();
+
+    let first = spawn!(world, Marker(1));
+    world.tick(&mut schedule).unwrap();
+    world.tick(&mut schedule).unwrap();
+    let second = spawn!(world, Marker(2));
+    world.tick(&mut schedule).unwrap();
+
+    assert_eq!(
+        *seen.lock().unwrap(),
+        vec![vec![first], Vec::new(), vec![second]]
+    );
+    assert!(world.spawned_since_last_tick().is_empty());
+}
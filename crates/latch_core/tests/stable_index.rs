@@ -0,0 +1,72 @@
+use latch_core::define_component;
+use latch_core::ecs::{Entity, SnapshotCompression, World};
+use latch_core::spawn;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Sprite(u32);
+define_component!(Sprite, 9272, "StableIndexTest::Sprite");
+
+#[test]
+fn stable_indices_survive_row_moves_and_are_reused() {
+    let mut world = World::new();
+    let entities: Vec<Entity> = (0..4).map(|i| spawn!(world, Sprite(i))).collect();
+    let indices: Vec<u32> = entities
+        .iter()
+        .map(|&entity| world.stable_index_of(entity).unwrap())
+        .collect();
+    assert_eq!(indices, vec![0, 1, 2, 3]);
+
+    // Swap-remove moves the last row into row 0; its index stays put.
+    world.despawn(entities[0]).unwrap();
+    assert_eq!(world.stable_index_of(entities[0]), None);
+    assert_eq!(world.free_stable_indices(), &[0]);
+    world.flush_despawns().unwrap();
+    for (&entity, &index) in entities.iter().zip(&indices).skip(1) {
+        assert_eq!(world.stable_index_of(entity), Some(index));
+    }
+
+    let fresh = spawn!(world, Sprite(9));
+    assert_eq!(world.stable_index_of(fresh), Some(0));
+    assert!(world.free_stable_indices().is_empty());
+    assert_eq!(world.stable_index_capacity(), 4);
+}
+
+#[test]
+fn compaction_reports_every_move() {
+    let mut world = World::new();
+    let entities: Vec<Entity> = (0..6).map(|i| spawn!(world, Sprite(i))).collect();
+    for &entity in &[entities[1], entities[3], entities[5]] {
+        world.despawn(entity).unwrap();
+    }
+    world.flush_despawns().unwrap();
+    assert_eq!(world.stable_index_capacity(), 6);
+
+    let moves = world.compact_stable_indices();
+    assert_eq!(moves.len(), 1);
+    assert_eq!(moves[0].entity, entities[4]);
+    assert_eq!((moves[0].from, moves[0].to), (4, 1));
+    assert_eq!(world.stable_index_capacity(), 3);
+    assert!(world.free_stable_indices().is_empty());
+
+    let mut indices: Vec<u32> = [entities[0], entities[2], entities[4]]
+        .iter()
+        .map(|&entity| world.stable_index_of(entity).unwrap())
+        .collect();
+    indices.sort_unstable();
+    assert_eq!(indices, vec![0, 1, 2]);
+}
+
+#[test]
+fn restore_reassigns_dense_indices() {
+    let mut world = World::new();
+    let entities: Vec<Entity> = (0..3).map(|i| spawn!(world, Sprite(i))).collect();
+    world.despawn(entities[0]).unwrap();
+    world.flush_despawns().unwrap();
+    let bytes = world.snapshot(SnapshotCompression::None).unwrap();
+
+    let mut restored = World::new();
+    restored.restore(&bytes).unwrap();
+    assert_eq!(restored.stable_index_of(entities[1]), Some(0));
+    assert_eq!(restored.stable_index_of(entities[2]), Some(1));
+    assert_eq!(restored.stable_index_capacity(), 2);
+}
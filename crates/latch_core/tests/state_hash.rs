@@ -0,0 +1,86 @@
+use latch_core::define_component;
+use latch_core::ecs::{verify_determinism, HashDifference, ReplayVerifier, World};
+use latch_core::spawn;
+use std::cell::Cell;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Step(u32);
+define_component!(Step, 9260, "StateHashTest::Step");
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Team(u32);
+define_component!(Team, 9261, "StateHashTest::Team");
+
+fn setup() -> World {
+    let mut world = World::new();
+    for i in 0..3 {
+        spawn!(world, Step(0), Team(i));
+    }
+    spawn!(world, Team(7));
+    world
+}
+
+#[test]
+fn equal_worlds_hash_equal_and_changes_show_up() {
+    let a = setup();
+    let mut b = setup();
+    assert_eq!(a.state_hash(), b.state_hash());
+
+    b.set_all(Step(1)).unwrap();
+    assert_eq!(a.state_hash(), b.state_hash(), "next buffer is not hashed");
+    b.swap_buffers();
+    assert_ne!(a.state_hash(), b.state_hash());
+    assert_eq!(b.state_hash(), b.state_hashes().combined());
+}
+
+#[test]
+fn deterministic_runs_verify() {
+    let result = verify_determinism(20, setup, |world, tick| {
+        world.set_all(Step(tick as u32 * 3)).unwrap();
+        world.swap_buffers();
+    });
+    assert_eq!(result, Ok(()));
+}
+
+#[test]
+fn first_divergent_tick_and_column_are_reported() {
+    let runs = Cell::new(0);
+    let divergence = verify_determinism(
+        20,
+        || {
+            runs.set(runs.get() + 1);
+            setup()
+        },
+        |world, tick| {
+            let skew = u32::from(runs.get() == 2 && tick >= 5);
+            world.set_all(Step(tick as u32 + skew)).unwrap();
+            world.swap_buffers();
+        },
+    )
+    .unwrap_err();
+
+    assert_eq!(divergence.tick, 5);
+    assert_ne!(divergence.expected, divergence.actual);
+    let archetype = setup().archetypes_with(Step::ID)[0];
+    assert_eq!(
+        divergence.differences,
+        [HashDifference::Column {
+            archetype,
+            component_id: Step::ID,
+        }]
+    );
+}
+
+#[test]
+fn verifier_reports_spawns_and_skips_unrecorded_ticks() {
+    let mut verifier = ReplayVerifier::new();
+    let world = setup();
+    verifier.record(0, &world);
+
+    let mut replay = setup();
+    assert!(verifier.verify(1, &replay).is_ok());
+    spawn!(replay, Team(9));
+    let divergence = verifier.verify(0, &replay).unwrap_err();
+    assert!(divergence.differences.contains(&HashDifference::Entities));
+    assert_eq!(verifier.divergence(), Some(&divergence));
+}
@@ -0,0 +1,47 @@
+use latch_core::define_component;
+use latch_core::ecs::{Component, SystemDescriptor, World};
+
+#[derive(Clone, Copy)]
+#[allow(dead_code)]
+struct Brain(u32);
+define_component!(Brain, 9050, "SystemIntervalTest::Brain");
+
+#[test]
+fn interval_systems_run_once_per_window_at_stable_phase() {
+    let every_tick = SystemDescriptor::new("physics").writes([1]);
+    assert!((0..10).all(|tick| every_tick.should_run(tick)));
+
+    let ai = SystemDescriptor::new("ai").writes([1]).every_n_ticks(6);
+    let runs: Vec<u64> = (0..24).filter(|&tick| ai.should_run(tick)).collect();
+    assert_eq!(runs.len(), 4);
+    assert!(runs.windows(2).all(|pair| pair[1] - pair[0] == 6));
+    assert_eq!(runs[0], u64::from(ai.phase()));
+    assert_eq!(
+        ai.phase(),
+        SystemDescriptor::new("ai").every_n_ticks(6).phase()
+    );
+
+    let pinned = SystemDescriptor::new("cleanup")
+        .writes([1])
+        .every_n_ticks(60)
+        .tick_phase(61);
+    assert_eq!(pinned.phase(), 1);
+    assert!(pinned.should_run(121) && !pinned.should_run(120));
+}
+
+#[test]
+fn world_gates_registered_systems_by_tick() {
+    let mut world = World::new();
+    let handle = world
+        .register_system(
+            SystemDescriptor::new("pathfinding")
+                .writes([Brain::id()])
+                .every_n_ticks(4)
+                .tick_phase(2),
+        )
+        .unwrap();
+    let due: Vec<bool> = (0..4)
+        .map(|tick| world.system_should_run(handle, tick).unwrap())
+        .collect();
+    assert_eq!(due, [false, false, true, false]);
+}
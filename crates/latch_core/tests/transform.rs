@@ -0,0 +1,43 @@
+use latch_core::ecs::World;
+use latch_core::glam::Vec3;
+use latch_core::spawn;
+use latch_core::transform::{fixed_to_world, gather_model_matrices, world_to_fixed, Transform};
+
+#[test]
+fn model_matrix_scales_rotates_then_translates() {
+    let transform = Transform::from_xy(10.0, -2.0)
+        .with_rotation_z(std::f32::consts::FRAC_PI_2)
+        .with_scale(Vec3::new(2.0, 3.0, 1.0));
+    let cols = transform.model_cols();
+
+    // +x scaled by 2 then rotated a quarter turn lands on +y.
+    let eps = 1e-6;
+    assert!((cols[0][0]).abs() < eps && (cols[0][1] - 2.0).abs() < eps);
+    assert!((cols[1][0] + 3.0).abs() < eps && (cols[1][1]).abs() < eps);
+    assert_eq!(cols[3], [10.0, -2.0, 0.0, 1.0]);
+    assert_eq!(Transform::default().model_cols()[0], [1.0, 0.0, 0.0, 0.0]);
+}
+
+#[test]
+fn fixed_positions_round_trip_through_world_space() {
+    let units_per_world = 256;
+    for position in [[0, 0], [1, -1], [12_345, -678], [1 << 23, -(1 << 23)]] {
+        let transform = Transform::from_fixed(position, units_per_world);
+        assert_eq!(transform.fixed_position(units_per_world), position);
+    }
+    assert_eq!(fixed_to_world(384, 256), 1.5);
+    assert_eq!(world_to_fixed(-1.5, 256), -384);
+    assert_eq!(world_to_fixed(f32::MAX, 256), i32::MAX);
+}
+
+#[test]
+fn gather_model_matrices_visits_every_transform() {
+    let mut world = World::new();
+    spawn!(world, Transform::from_xy(1.0, 0.0));
+    spawn!(world, Transform::from_xy(2.0, 0.0));
+
+    let mut out = vec![[[9.0; 4]; 4]];
+    assert_eq!(gather_model_matrices(&world, &mut out).unwrap(), 2);
+    assert_eq!(out[0][3], [1.0, 0.0, 0.0, 1.0]);
+    assert_eq!(out[1][3], [2.0, 0.0, 0.0, 1.0]);
+}
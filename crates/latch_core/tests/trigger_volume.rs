@@ -0,0 +1,97 @@
+use latch_core::define_component;
+use latch_core::ecs::{
+    QueryRegistry, RelationBuffer, RelationType, TriggerAccelerator, TriggerConfig, TriggerPhase,
+    World,
+};
+use latch_core::spawn;
+
+#[allow(dead_code)]
+#[derive(Clone, Copy)]
+struct Zone([i32; 4]);
+define_component!(Zone, 9060, "TriggerVolumeTest::Zone");
+
+#[allow(dead_code)]
+#[derive(Clone, Copy)]
+struct Pos([i32; 2]);
+define_component!(Pos, 9061, "TriggerVolumeTest::Pos");
+
+const TRIGGER: RelationType = RelationType::new(3);
+
+fn phases(queries: &mut QueryRegistry, world: &World) -> Vec<TriggerPhase> {
+    let mut buffer = RelationBuffer::new(16, 16);
+    queries.rebuild_all(world, &mut buffer);
+    buffer
+        .iter()
+        .map(|record| {
+            let payload = buffer.payload_slice(record.payload.unwrap()).unwrap();
+            TriggerPhase::from_payload(&payload).unwrap()
+        })
+        .collect()
+}
+
+#[test]
+fn occupants_enter_stay_and_exit_volumes() {
+    let mut world = World::new();
+    let zone = spawn!(world, Zone([0, 0, 10, 10]));
+    let walker = spawn!(world, Pos([5, 5]));
+    spawn!(world, Pos([50, 50]));
+
+    let mut queries = QueryRegistry::new();
+    queries.register(Box::new(TriggerAccelerator::new(TriggerConfig::new(
+        Zone::ID,
+        Pos::ID,
+        0,
+        TRIGGER,
+    ))));
+
+    assert_eq!(phases(&mut queries, &world), [TriggerPhase::Enter]);
+    assert_eq!(phases(&mut queries, &world), [TriggerPhase::Stay]);
+
+    world.despawn(walker).unwrap();
+    world.flush_despawns().unwrap();
+    assert_eq!(phases(&mut queries, &world), [TriggerPhase::Exit]);
+    assert!(phases(&mut queries, &world).is_empty());
+
+    // A circle grazing the volume edge counts as inside.
+    let mut radius_queries = QueryRegistry::new();
+    radius_queries.register(Box::new(TriggerAccelerator::new(TriggerConfig::new(
+        Zone::ID,
+        Pos::ID,
+        3,
+        TRIGGER,
+    ))));
+    let grazing = spawn!(world, Pos([12, 5]));
+    let mut buffer = RelationBuffer::new(16, 16);
+    radius_queries.rebuild_all(&world, &mut buffer);
+    let record = buffer.iter().next().unwrap();
+    assert_eq!((record.entity_a, record.entity_b), (zone, grazing));
+}
+
+#[test]
+fn extreme_coordinates_do_not_overflow() {
+    let mut world = World::new();
+    let wide = spawn!(world, Zone([0, 0, i32::MAX, 10]));
+    spawn!(
+        world,
+        Zone([i32::MAX - 1, i32::MAX - 1, i32::MAX, i32::MAX])
+    );
+    spawn!(world, Pos([i32::MIN, 5]));
+    spawn!(world, Pos([i32::MIN, i32::MIN]));
+    let reaching = spawn!(world, Pos([-i32::MAX, 5]));
+
+    let mut queries = QueryRegistry::new();
+    queries.register(Box::new(TriggerAccelerator::new(TriggerConfig::new(
+        Zone::ID,
+        Pos::ID,
+        i32::MAX,
+        TRIGGER,
+    ))));
+    let mut buffer = RelationBuffer::new(16, 16);
+    queries.rebuild_all(&world, &mut buffer);
+    let pairs: Vec<_> = buffer
+        .iter()
+        .map(|record| (record.entity_a, record.entity_b))
+        .collect();
+    // Only the occupant exactly one radius from the wide zone touches it.
+    assert_eq!(pairs, [(wide, reaching)]);
+}
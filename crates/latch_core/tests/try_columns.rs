@@ -0,0 +1,69 @@
+use latch_core::define_component;
+use latch_core::ecs::{Component, StorageError, World};
+use latch_core::{columns, columns_mut, spawn, try_columns, try_columns_mut};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Pos(i32);
+define_component!(Pos, 9030, "TryColumnsTest::Pos");
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Vel(i32);
+define_component!(Vel, 9031, "TryColumnsTest::Vel");
+
+#[derive(Clone, Copy)]
+#[allow(dead_code)]
+struct Tag(u8);
+define_component!(Tag, 9032, "TryColumnsTest::Tag");
+
+#[test]
+fn try_columns_propagate_through_try_for_each() {
+    let mut world = World::new();
+    spawn!(world, Pos(1), Vel(2));
+
+    world
+        .try_for_each(&[Pos::id(), Vel::id()], |storage| {
+            let (pos, vel) = try_columns!(storage, Pos, Vel)?;
+            assert_eq!((pos, vel), (&[Pos(1)][..], &[Vel(2)][..]));
+            let (pos_out, vel_out) = try_columns_mut!(storage, Pos, Vel)?;
+            pos_out[0] = Pos(3);
+            vel_out[0] = Vel(4);
+            Ok::<_, StorageError>(())
+        })
+        .unwrap();
+
+    let err = world
+        .try_for_each(&[Pos::id()], |storage| {
+            try_columns!(storage, Pos, Tag).map(|_| ())
+        })
+        .unwrap_err();
+    assert!(
+        matches!(err, StorageError::ColumnMissing { component_id } if component_id == Tag::id())
+    );
+
+    let err = world
+        .try_for_each(&[Pos::id()], |storage| {
+            try_columns_mut!(storage, Pos, Pos).map(|_| ())
+        })
+        .unwrap_err();
+    assert!(matches!(err, StorageError::DuplicateColumnRequest { .. }));
+}
+
+#[test]
+#[should_panic(expected = "columns!: cannot borrow component 'TryColumnsTest::Tag'")]
+fn columns_panic_names_the_missing_component() {
+    let mut world = World::new();
+    spawn!(world, Pos(1), Vel(2));
+    world.for_each(&[Pos::id()], |storage| {
+        let _ = columns!(storage, Pos, Tag);
+    });
+}
+
+#[test]
+#[should_panic(expected = "columns_mut!: cannot borrow component 'TryColumnsTest::Pos'")]
+fn columns_mut_panics_on_duplicate_components() {
+    let mut world = World::new();
+    spawn!(world, Pos(1), Vel(2));
+    world.for_each(&[Pos::id()], |storage| {
+        let _ = columns_mut!(storage, Pos, Pos);
+    });
+}
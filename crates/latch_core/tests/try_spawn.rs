@@ -0,0 +1,86 @@
+use latch_core::define_component;
+use latch_core::ecs::{Component, ComponentHandle, EntityBuilderError, World, WorldError};
+use latch_core::try_spawn;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Hull(u32);
+define_component!(Hull, 9440, "TrySpawnTest::Hull");
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Shield(u64);
+define_component!(Shield, 9441, "TrySpawnTest::Shield");
+
+/// Hand-written component whose handle points at an id nothing registered.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Unregistered(u32);
+
+impl Component for Unregistered {
+    const NAME: &'static str = "TrySpawnTest::Unregistered";
+
+    fn handle() -> ComponentHandle {
+        ComponentHandle {
+            id: 9442,
+            size: 4,
+            align: 4,
+            stride: 4,
+            pod: true,
+        }
+    }
+}
+
+/// Hand-written component claiming `Shield`'s id with a narrower layout.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct NarrowShield(u16);
+
+impl Component for NarrowShield {
+    const NAME: &'static str = "TrySpawnTest::NarrowShield";
+
+    fn handle() -> ComponentHandle {
+        ComponentHandle {
+            id: Shield::ID,
+            size: 2,
+            align: 2,
+            stride: 2,
+            pod: true,
+        }
+    }
+}
+
+#[test]
+fn try_spawn_returns_the_entity() {
+    let mut world = World::new();
+    let entity = try_spawn!(world, Hull(7), Shield(9)).unwrap();
+    assert_eq!(world.live_entity_count(), 1);
+    let loc = world.locate(entity).unwrap();
+    assert_eq!(world.column::<Hull>(loc.archetype).unwrap(), &[Hull(7)]);
+    assert_eq!(world.column::<Shield>(loc.archetype).unwrap(), &[Shield(9)]);
+}
+
+#[test]
+fn try_spawn_reports_unregistered_components() {
+    let mut world = World::new();
+    let err = try_spawn!(world, Hull(1), Unregistered(2)).unwrap_err();
+    assert!(matches!(
+        err,
+        WorldError::Builder(EntityBuilderError::ComponentNotRegistered { component_id: 9442 })
+    ));
+    assert_eq!(world.live_entity_count(), 0);
+}
+
+#[test]
+fn try_spawn_reports_mismatched_components() {
+    let mut world = World::new();
+    // Registers `Shield` with its real 8-byte layout.
+    try_spawn!(world, Shield(1)).unwrap();
+    let err = try_spawn!(world, NarrowShield(2)).unwrap_err();
+    assert!(matches!(
+        err,
+        WorldError::Builder(EntityBuilderError::StrideMismatch {
+            component_id: Shield::ID,
+            expected: 8,
+            actual: 2,
+            ..
+        })
+    ));
+    assert_eq!(world.live_entity_count(), 1);
+}
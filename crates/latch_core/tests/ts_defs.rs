@@ -0,0 +1,25 @@
+use latch_core::ecs::{emit_ts_defs, register_component, reset_registry, FieldMeta};
+
+#[test]
+fn emits_interface_and_offset_enums() {
+    let _registry = reset_registry();
+    let handle = register_component(
+        "TsDefsTest::Health",
+        8,
+        4,
+        8,
+        true,
+        vec![FieldMeta::new("hp", 0, 4), FieldMeta::new("max hp", 4, 4)],
+    )
+    .unwrap();
+    let defs = emit_ts_defs(&[handle.id, u32::MAX]);
+
+    assert!(defs.contains("export interface TsDefsTest_Health {"));
+    assert!(defs.contains("  hp: number;"));
+    assert!(defs.contains("  \"max hp\": number;"));
+    assert!(defs.contains(&format!("  ID = {},", handle.id)));
+    assert!(defs.contains("  STRIDE = 8,"));
+    assert!(defs
+        .contains("export const enum TsDefsTest_HealthOffsets {\n  hp = 0,\n  \"max hp\" = 4,\n}"));
+    assert!(defs.contains(&format!("// component id {} is not registered", u32::MAX)));
+}
@@ -0,0 +1,52 @@
+use latch_core::define_component;
+use latch_core::ecs::{
+    QueryRegistry, RelationBuffer, RelationType, VisibilityAccelerator, VisibilityConfig, World,
+};
+use latch_core::spawn;
+use std::f64::consts::FRAC_PI_4;
+
+/// Position followed by facing.
+#[allow(dead_code)]
+#[derive(Clone, Copy)]
+struct Eye([i32; 4]);
+define_component!(Eye, 9070, "VisibilityTest::Eye");
+
+#[allow(dead_code)]
+#[derive(Clone, Copy)]
+struct Target([i32; 2]);
+define_component!(Target, 9071, "VisibilityTest::Target");
+
+#[allow(dead_code)]
+#[derive(Clone, Copy)]
+struct Wall([i32; 2]);
+define_component!(Wall, 9072, "VisibilityTest::Wall");
+
+const SEES: RelationType = RelationType::new(4);
+
+#[test]
+fn observers_see_unblocked_targets_inside_cone_and_radius() {
+    let mut world = World::new();
+    let eye = spawn!(world, Eye([0, 0, 1, 0]));
+    let ahead = spawn!(world, Target([50, 5]));
+    spawn!(world, Target([-50, 0])); // behind
+    spawn!(world, Target([500, 0])); // out of range
+    spawn!(world, Target([0, 60])); // blocked, and outside the cone anyway
+    spawn!(world, Target([60, -30])); // behind the wall
+    spawn!(world, Wall([30, -15]));
+
+    let mut queries = QueryRegistry::new();
+    queries.register(Box::new(VisibilityAccelerator::new(
+        VisibilityConfig::new(Eye::ID, Target::ID, Wall::ID, 100, SEES)
+            .with_blocker_radius(4)
+            .with_cone(FRAC_PI_4)
+            .with_cell_size(32),
+    )));
+    let mut buffer = RelationBuffer::new(16, 16);
+    queries.rebuild_all(&world, &mut buffer);
+
+    let seen: Vec<_> = buffer.iter().map(|r| (r.entity_a, r.entity_b)).collect();
+    assert_eq!(seen, [(eye, ahead)]);
+    let entry = buffer.relations_for(eye)[0];
+    let delta = entry.delta.unwrap();
+    assert_eq!((delta.dx, delta.dy), (50, 5));
+}
@@ -0,0 +1,29 @@
+use latch_core::define_component;
+use latch_core::ecs::{WeakEntity, World};
+use latch_core::spawn;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Health(u32);
+define_component!(Health, 9130, "WeakEntityTest::Health");
+
+#[test]
+fn weak_entity_upgrades_only_while_alive() {
+    let mut world = World::new();
+    let target = spawn!(world, Health(10));
+    let other = spawn!(world, Health(20));
+    let weak = WeakEntity::new(target);
+
+    assert_eq!(weak.upgrade(&world), Some(target));
+    assert_eq!(world.validate(target), world.locate(target).ok());
+
+    world.despawn(target).unwrap();
+    assert_eq!(weak.upgrade(&world), None);
+    assert!(world.validate(target).is_none());
+
+    world.flush_despawns().unwrap();
+    let recycled = spawn!(world, Health(30));
+    assert_eq!(recycled.index(), target.index());
+    assert_eq!(weak.upgrade(&world), None);
+    assert!(world.validate(recycled).is_some());
+    assert_eq!(WeakEntity::from(other).upgrade(&world), Some(other));
+}
@@ -0,0 +1,56 @@
+use latch_core::define_component;
+use latch_core::ecs::{Component, WorldBuilder};
+use latch_core::spawn;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Shell(u32);
+define_component!(Shell, 9278, "WorldBuilderTest::Shell");
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Fuse(u32);
+define_component!(Fuse, 9279, "WorldBuilderTest::Fuse");
+
+#[test]
+fn spawning_into_preallocated_archetype_does_not_allocate() {
+    let expected = 1000;
+    let mut world = WorldBuilder::new()
+        .prealloc_archetype(&[Shell::id(), Fuse::id()], expected)
+        .build()
+        .unwrap();
+    let archetype = world.archetype_exact(&[Fuse::id(), Shell::id()]).unwrap();
+    let generation = world.archetype_generation();
+    let capacity = world.storage(archetype).unwrap().capacity();
+    assert!(capacity >= expected);
+    let slots_capacity = world.slots_capacity();
+    assert!(slots_capacity >= expected);
+
+    for i in 0..expected as u32 {
+        spawn!(world, Shell(i), Fuse(i));
+    }
+    let storage = world.storage(archetype).unwrap();
+    assert_eq!(storage.entity_count(), expected);
+    assert_eq!(storage.capacity(), capacity);
+    assert_eq!(world.archetype_generation(), generation);
+    assert_eq!(world.allocated_slots(), expected);
+    assert_eq!(world.slots_capacity(), slots_capacity);
+
+    // Past the reservation, pages are allocated on demand as before.
+    for i in 0..=(capacity - expected) as u32 {
+        spawn!(world, Shell(i), Fuse(i));
+    }
+    assert!(world.storage(archetype).unwrap().capacity() > capacity);
+}
+
+#[test]
+fn defragment_releases_unused_reservations() {
+    let mut world = WorldBuilder::new()
+        .prealloc_archetype(&[Shell::id()], 500)
+        .build()
+        .unwrap();
+    let archetype = world.archetype_exact(&[Shell::id()]).unwrap();
+    spawn!(world, Shell(1));
+    world.defragment().unwrap();
+    let storage = world.storage(archetype).unwrap();
+    assert_eq!(storage.page_count(), 1);
+    assert_eq!(storage.capacity(), storage.rows_per_page());
+}
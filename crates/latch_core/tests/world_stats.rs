@@ -0,0 +1,40 @@
+use latch_core::define_component;
+use latch_core::ecs::{Component, World};
+use latch_core::spawn;
+
+#[derive(Clone, Copy)]
+#[allow(dead_code)]
+struct Pos(i32);
+define_component!(Pos, 9020, "WorldStatsTest::Pos");
+
+#[derive(Clone, Copy)]
+#[allow(dead_code)]
+struct Vel(i32);
+define_component!(Vel, 9021, "WorldStatsTest::Vel");
+
+#[test]
+fn stats_break_down_live_entities_by_archetype_and_component() {
+    let mut world = World::new();
+    for i in 0..3 {
+        spawn!(world, Pos(i));
+    }
+    let moving: Vec<_> = (0..2).map(|i| spawn!(world, Pos(i), Vel(i))).collect();
+    world.despawn(moving[0]).unwrap();
+
+    assert_eq!(world.component_population(Pos::id()), 4);
+    assert_eq!(world.component_population(Vel::id()), 1);
+
+    let stats = world.archetype_stats();
+    assert_eq!(stats.len(), 2);
+    let total: usize = stats.iter().map(|(_, _, count)| count).sum();
+    assert_eq!(total, world.entity_count());
+    let (_, components, count) = stats
+        .iter()
+        .find(|(_, components, _)| components.len() == 2)
+        .unwrap();
+    assert!(components.contains(&Vel::id()));
+    assert_eq!(*count, 1);
+
+    world.flush_despawns().unwrap();
+    assert_eq!(world.component_population(Pos::id()), 4);
+}
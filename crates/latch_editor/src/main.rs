@@ -3,11 +3,10 @@
 //! Editor application with GUI, scene view, and development tools
 
 use anyhow::Result;
-use tracing_subscriber;
+use latch_runtime::{init_logging, LogConfig};
 
 fn main() -> Result<()> {
-    // Initialize logging
-    tracing_subscriber::fmt::init();
+    init_logging(&LogConfig::from_env()?)?;
 
     tracing::info!("Latch Editor v{}", latch_core::VERSION);
     tracing::info!("Editor initialized successfully");
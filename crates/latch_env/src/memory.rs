@@ -1,5 +1,6 @@
 // memory.rs
-//! Cross-platform helpers to query cache line size, L1/L2/L3 sizes, and total RAM.
+//! Cross-platform helpers to query cache line size, L1/L2/L3 sizes, total RAM,
+//! OS page size, huge-page size and NUMA node count.
 //! Falls back to conservative defaults when unavailable.
 
 use std::sync::OnceLock;
@@ -11,12 +12,16 @@ pub struct Memory {
     pub l2: usize,         // bytes
     pub l3: usize,         // bytes
     pub total_ram: u64,    // bytes
+    pub page_size: usize,  // bytes
+    /// Transparent/large page size in bytes, `None` if unsupported.
+    pub huge_page_size: Option<usize>,
+    pub numa_nodes: usize,
 }
 
 impl Memory {
     pub fn detect() -> Self {
         static INSTANCE: OnceLock<Memory> = OnceLock::new();
-        *INSTANCE.get_or_init(|| Self::detect_impl())
+        *INSTANCE.get_or_init(Self::detect_impl)
     }
 
     fn detect_impl() -> Self {
@@ -25,9 +30,22 @@ impl Memory {
             l1: l1_size().unwrap_or(32 * 1024),
             l2: l2_size().unwrap_or(256 * 1024),
             l3: l3_size().unwrap_or(4 * 1024 * 1024),
-            total_ram: total_ram_bytes().unwrap_or(1 * 1024 * 1024 * 1024),
+            total_ram: total_ram_bytes().unwrap_or(1024 * 1024 * 1024),
+            page_size: page_size_bytes().unwrap_or(4096),
+            huge_page_size: huge_page_bytes(),
+            numa_nodes: numa_node_count().unwrap_or(1),
         }
     }
+
+    /// OS virtual-memory page size; page allocations should be multiples of it.
+    pub fn page_size() -> usize {
+        Self::detect().page_size
+    }
+
+    /// Number of NUMA nodes (1 on uniform-memory systems).
+    pub fn numa_nodes() -> usize {
+        Self::detect().numa_nodes
+    }
 }
 
 /* -------------------------- Windows -------------------------- */
@@ -126,6 +144,41 @@ fn total_ram_bytes() -> Option<u64> {
     }
 }
 
+#[cfg(target_os = "windows")]
+fn page_size_bytes() -> Option<usize> {
+    use windows_sys::Win32::System::SystemInformation::{GetSystemInfo, SYSTEM_INFO};
+    unsafe {
+        let mut info: SYSTEM_INFO = std::mem::zeroed();
+        GetSystemInfo(&mut info);
+        if info.dwPageSize != 0 {
+            Some(info.dwPageSize as usize)
+        } else {
+            None
+        }
+    }
+}
+#[cfg(target_os = "windows")]
+fn huge_page_bytes() -> Option<usize> {
+    use windows_sys::Win32::System::Memory::GetLargePageMinimum;
+    // Zero when the processor does not support large pages.
+    let n = unsafe { GetLargePageMinimum() };
+    if n != 0 {
+        Some(n)
+    } else {
+        None
+    }
+}
+#[cfg(target_os = "windows")]
+fn numa_node_count() -> Option<usize> {
+    use windows_sys::Win32::System::SystemInformation::GetNumaHighestNodeNumber;
+    let mut highest = 0u32;
+    if unsafe { GetNumaHighestNodeNumber(&mut highest) } != 0 {
+        Some(highest as usize + 1)
+    } else {
+        None
+    }
+}
+
 /* --------------------- macOS / iOS (Darwin) --------------------- */
 
 #[cfg(any(target_os = "macos", target_os = "ios"))]
@@ -191,6 +244,26 @@ fn total_ram_bytes() -> Option<u64> {
     sysctl_u64("hw.memsize")
 }
 
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+fn page_size_bytes() -> Option<usize> {
+    let n = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    if n > 0 {
+        Some(n as usize)
+    } else {
+        None
+    }
+}
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+fn huge_page_bytes() -> Option<usize> {
+    // Darwin exposes superpages only through mmap flags; no size to query.
+    None
+}
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+fn numa_node_count() -> Option<usize> {
+    // Apple platforms are uniform-memory.
+    Some(1)
+}
+
 /* --------------------- Linux / Android --------------------- */
 
 #[cfg(any(target_os = "linux", target_os = "android"))]
@@ -286,6 +359,56 @@ fn total_ram_bytes() -> Option<u64> {
     None
 }
 
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn page_size_bytes() -> Option<usize> {
+    // The auxiliary vector carries AT_PAGESZ (what sysconf(_SC_PAGESIZE)
+    // returns) as native-endian (key, value) word pairs.
+    const AT_NULL: usize = 0;
+    const AT_PAGESZ: usize = 6;
+    const WORD: usize = std::mem::size_of::<usize>();
+    let auxv = std::fs::read("/proc/self/auxv").ok()?;
+    for pair in auxv.chunks_exact(2 * WORD) {
+        let key = usize::from_ne_bytes(pair[..WORD].try_into().ok()?);
+        let val = usize::from_ne_bytes(pair[WORD..].try_into().ok()?);
+        match key {
+            AT_NULL => break,
+            AT_PAGESZ if val > 0 => return Some(val),
+            _ => {}
+        }
+    }
+    None
+}
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn huge_page_bytes() -> Option<usize> {
+    // Transparent huge page size, e.g. "2097152"
+    let s = read_to_string("/sys/kernel/mm/transparent_hugepage/hpage_pmd_size")?;
+    s.trim().parse::<usize>().ok().filter(|&n| n > 0)
+}
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn numa_node_count() -> Option<usize> {
+    // Node list such as "0", "0-1" or "0,2-3"
+    let s = read_to_string("/sys/devices/system/node/online")?;
+    let mut count = 0usize;
+    for part in s.trim().split(',').filter(|p| !p.is_empty()) {
+        count += match part.split_once('-') {
+            Some((lo, hi)) => {
+                let lo: usize = lo.trim().parse().ok()?;
+                let hi: usize = hi.trim().parse().ok()?;
+                hi.checked_sub(lo)? + 1
+            }
+            None => {
+                part.trim().parse::<usize>().ok()?;
+                1
+            }
+        };
+    }
+    if count > 0 {
+        Some(count)
+    } else {
+        None
+    }
+}
+
 /* --------------------- Other / WASM / Fallbacks --------------------- */
 
 #[cfg(not(any(
@@ -338,3 +461,33 @@ fn l3_size() -> Option<usize> {
 fn total_ram_bytes() -> Option<u64> {
     None
 }
+#[cfg(not(any(
+    target_os = "windows",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "android"
+)))]
+fn page_size_bytes() -> Option<usize> {
+    None
+}
+#[cfg(not(any(
+    target_os = "windows",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "android"
+)))]
+fn huge_page_bytes() -> Option<usize> {
+    None
+}
+#[cfg(not(any(
+    target_os = "windows",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "android"
+)))]
+fn numa_node_count() -> Option<usize> {
+    None
+}
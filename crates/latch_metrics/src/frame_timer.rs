@@ -6,6 +6,7 @@ use std::time::{Duration, Instant};
 pub struct FrameTimer {
     frame_start: Instant,
     frame_times: RingBuffer<Duration>,
+    gpu_times: RingBuffer<Duration>,
 }
 
 impl FrameTimer {
@@ -13,6 +14,7 @@ impl FrameTimer {
         Self {
             frame_start: Instant::now(),
             frame_times: RingBuffer::new(capacity),
+            gpu_times: RingBuffer::new(capacity),
         }
     }
 
@@ -42,4 +44,21 @@ impl FrameTimer {
         let (min, max) = self.frame_times.min_max();
         (min.as_secs_f64() * 1000.0, max.as_secs_f64() * 1000.0)
     }
+
+    /// Record GPU execution time for a frame (e.g. from timestamp queries).
+    ///
+    /// Samples usually arrive a frame or two after `end`, once the GPU has
+    /// finished and the readback buffer is mapped.
+    pub fn record_gpu_time(&mut self, elapsed: Duration) {
+        self.gpu_times.push(elapsed);
+    }
+
+    /// Average GPU frame time, or `None` if no GPU sample was ever recorded
+    /// (timestamp queries unsupported or not enabled).
+    pub fn gpu_frame_time_ms(&self) -> Option<f64> {
+        if self.gpu_times.is_empty() {
+            return None;
+        }
+        Some(self.gpu_times.average().as_secs_f64() * 1000.0)
+    }
 }
@@ -90,6 +90,10 @@ impl FrameTimer {
     pub fn frame_time_ms(&self) -> f64 {
         0.0
     }
+    pub fn record_gpu_time(&mut self, _elapsed: std::time::Duration) {}
+    pub fn gpu_frame_time_ms(&self) -> Option<f64> {
+        None
+    }
 }
 
 #[cfg(not(feature = "metrics"))]
@@ -110,6 +114,7 @@ impl<T> RingBuffer<T> {
 }
 
 #[cfg(not(feature = "metrics"))]
+#[derive(Default)]
 pub struct Counter;
 
 #[cfg(not(feature = "metrics"))]
@@ -124,6 +129,7 @@ impl Counter {
 }
 
 #[cfg(not(feature = "metrics"))]
+#[derive(Default)]
 pub struct SystemProfiler;
 
 #[cfg(not(feature = "metrics"))]
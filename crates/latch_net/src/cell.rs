@@ -1,7 +1,9 @@
 //! Cell-based world partitioning
 
 use crate::CellId;
+use latch_core::ecs::{ArchetypeStorage, CellPartition, ComponentId};
 use latch_core::math::Vec3;
+use latch_core::transform::Transform;
 
 /// Cell configuration
 pub struct CellConfig {
@@ -19,7 +21,44 @@ pub fn world_pos_to_cell(pos: Vec3, config: &CellConfig) -> CellId {
     let x = (pos.x / config.cell_size).floor() as i32;
     let z = (pos.z / config.cell_size).floor() as i32;
 
-    // Simple 2D grid encoding (z * large_prime + x)
-    let id = ((z as i64) << 32) | (x as i64 & 0xFFFFFFFF);
-    CellId(id as u64)
+    CellId::from_coords(x, z)
+}
+
+/// Partitions entities by their `Transform` translation, for per-cell
+/// checksums (`World::checksum_cells`) exchanged with replication acks and
+/// for simulating only owned cells (`World::for_each_in_cells`).
+#[derive(Default)]
+pub struct CellGrid {
+    pub config: CellConfig,
+}
+
+impl CellGrid {
+    pub fn new(config: CellConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl CellPartition for CellGrid {
+    type Cell = CellId;
+
+    fn required_components(&self) -> &[ComponentId] {
+        &[Transform::ID]
+    }
+
+    fn assign_cells(&self, storage: &ArchetypeStorage, cells: &mut Vec<Option<CellId>>) {
+        let Ok(column) = storage.column(Transform::ID) else {
+            return;
+        };
+        for page_idx in 0..column.page_count() {
+            let Ok(transforms) = column.slice_read_typed::<Transform>(column.page_range(page_idx))
+            else {
+                return;
+            };
+            cells.extend(
+                transforms
+                    .iter()
+                    .map(|transform| Some(world_pos_to_cell(transform.translation, &self.config))),
+            );
+        }
+    }
 }
@@ -0,0 +1,10 @@
+//! Stable hashing for identifiers shared between nodes.
+//!
+//! Ids are hashed with `latch_core::hash::StableHasher`, so every node
+//! buckets the same id identically whatever its host or build.
+
+/// Map `hash` onto `buckets` slots (`buckets` must be non-zero).
+#[inline]
+pub const fn bucket_of(hash: u64, buckets: u64) -> u64 {
+    hash % buckets
+}
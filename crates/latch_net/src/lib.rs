@@ -5,15 +5,69 @@
 pub mod authority;
 pub mod cell;
 pub mod discovery;
+pub mod hash;
 pub mod replication;
+pub mod rpc;
+pub mod transport;
+pub mod wire;
+
+pub use rpc::{Rpc, RpcError, RpcId};
+pub use transport::{Transport, TransportError};
+
+use latch_core::hash::stable_hash;
 
 /// Network protocol version
-pub const PROTOCOL_VERSION: u32 = 1;
+///
+/// Bump whenever the wire layout changes. Raise `MIN_PROTOCOL_VERSION` as
+/// well once older layouts can no longer be decoded; peers whose ranges do
+/// not overlap are rejected during `wire::negotiate`.
+pub const PROTOCOL_VERSION: u32 = 2;
+
+/// Oldest protocol version this build can still talk to.
+///
+/// Version 2 introduced the little-endian wire format; version 1 streams
+/// were host-endian and are not accepted.
+pub const MIN_PROTOCOL_VERSION: u32 = 2;
 
 /// Cell ID (spatial partition identifier)
+///
+/// Packs the cell's grid coordinates: `y` in the high 32 bits, `x` in the
+/// low 32 bits, so every node derives the same id from the same cell.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct CellId(pub u64);
 
+impl CellId {
+    #[inline]
+    pub const fn from_coords(x: i32, y: i32) -> Self {
+        Self(((y as u32 as u64) << 32) | x as u32 as u64)
+    }
+
+    #[inline]
+    pub const fn to_coords(self) -> (i32, i32) {
+        (self.0 as u32 as i32, (self.0 >> 32) as u32 as i32)
+    }
+
+    /// Host-independent hash for directory bucketing.
+    #[inline]
+    pub fn stable_hash(self) -> u64 {
+        stable_hash(&self.0.to_le_bytes())
+    }
+}
+
 /// Server node ID
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct NodeId(pub u64);
+
+impl NodeId {
+    /// Host-independent hash for directory bucketing.
+    #[inline]
+    pub fn stable_hash(self) -> u64 {
+        stable_hash(&self.0.to_le_bytes())
+    }
+
+    /// Directory bucket for this node; `buckets` must be non-zero.
+    #[inline]
+    pub fn bucket(self, buckets: u64) -> u64 {
+        hash::bucket_of(self.stable_hash(), buckets)
+    }
+}
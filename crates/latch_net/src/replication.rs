@@ -1,5 +1,13 @@
 //! State replication and rollback networking
 
+use crate::wire::{WireError, WireReader, WireWriter};
+use latch_core::ecs::{
+    meta_of, ComponentId, Entity, EntityBuilder, EntityBuilderError, StorageError, TopologyChanges,
+    World, WorldError,
+};
+use std::collections::{HashMap, HashSet};
+use thiserror::Error;
+
 /// Tick number for rollback
 pub type Tick = u64;
 
@@ -20,3 +28,230 @@ impl Default for InputBuffer {
         Self::new(2) // 2-tick buffer (~33ms)
     }
 }
+
+/// Spawn and despawn records for clients.
+///
+/// Value deltas only describe entities a client already has, so every
+/// replication message also carries the tick's topology changes (see
+/// `World::set_topology_tracking`): `encode_delta` turns them into
+/// `DeltaRecord`s and `apply_delta` replays them on a client world,
+/// tracking which local entity stands for each server entity in a
+/// `ReplicaMap`.
+///
+/// Layout: record count (u32), then per record a tag (u8) and the server
+/// entity (u64 bits); spawns add a byte string of (component id u32,
+/// wire-encoded value) pairs in ascending id order. The ids are compared,
+/// not the server's `ArchetypeId`, so the receiving peer checks the
+/// entity's shape without trusting a hash computed elsewhere.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DeltaRecord {
+    Spawn {
+        id: Entity,
+        /// Component ids and values, as `WireWriter::put_component` writes
+        /// them.
+        bytes: Vec<u8>,
+    },
+    Despawn {
+        id: Entity,
+    },
+}
+
+const SPAWN: u8 = 1;
+const DESPAWN: u8 = 2;
+
+#[derive(Debug, Error)]
+pub enum ReplicationError {
+    #[error(transparent)]
+    Wire(#[from] WireError),
+    #[error("unknown delta record tag {0}")]
+    UnknownRecord(u8),
+    #[error("component {component_id} is not registered on this peer")]
+    ComponentNotRegistered { component_id: ComponentId },
+    #[error("entity {entity:?} was sent with components {sent:?}, not ascending and distinct")]
+    ComponentOrder {
+        entity: Entity,
+        sent: Vec<ComponentId>,
+    },
+    #[error("entity {entity:?} was spawned twice")]
+    AlreadyReplicated { entity: Entity },
+    #[error(transparent)]
+    Builder(#[from] EntityBuilderError),
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+    #[error(transparent)]
+    World(#[from] WorldError),
+}
+
+/// Append the records for `changes` to `out`: despawns first, then a spawn
+/// with every current component value for each spawned entity still alive.
+///
+/// Returns the number of records written.
+pub fn encode_delta(
+    world: &World,
+    changes: &TopologyChanges,
+    out: &mut WireWriter,
+) -> Result<usize, ReplicationError> {
+    let mut records = Vec::with_capacity(changes.despawned.len() + changes.spawned.len());
+    records.extend(
+        changes
+            .despawned
+            .iter()
+            .map(|&id| DeltaRecord::Despawn { id }),
+    );
+    for &id in &changes.spawned {
+        let (Some(location), Some(component_ids)) =
+            (world.validate(id), world.component_ids_of(id))
+        else {
+            continue;
+        };
+        let storage = world
+            .storage(location.archetype)
+            .ok_or(WorldError::MissingArchetype {
+                archetype_id: location.archetype,
+            })?;
+        let mut bytes = WireWriter::new();
+        for &component_id in component_ids {
+            let meta = meta_of(component_id)
+                .ok_or(ReplicationError::ComponentNotRegistered { component_id })?;
+            let value = storage
+                .column(component_id)?
+                .slice_read(location.index..location.index + 1)
+                .map_err(StorageError::from)?;
+            bytes.put_u32(component_id);
+            bytes.put_component(&meta, value);
+        }
+        records.push(DeltaRecord::Spawn {
+            id,
+            bytes: bytes.into_bytes(),
+        });
+    }
+
+    out.put_u32(records.len() as u32);
+    for record in &records {
+        match record {
+            DeltaRecord::Spawn { id, bytes } => {
+                out.put_u8(SPAWN);
+                out.put_u64(id.to_bits());
+                out.put_bytes(bytes);
+            }
+            DeltaRecord::Despawn { id } => {
+                out.put_u8(DESPAWN);
+                out.put_u64(id.to_bits());
+            }
+        }
+    }
+    Ok(records.len())
+}
+
+/// Read the records `encode_delta` wrote.
+pub fn decode_delta(input: &mut WireReader<'_>) -> Result<Vec<DeltaRecord>, ReplicationError> {
+    let count = input.get_u32()? as usize;
+    // Each record takes at least 9 bytes; don't trust the count further.
+    let mut records = Vec::with_capacity(count.min(input.remaining() / 9));
+    for _ in 0..count {
+        let tag = input.get_u8()?;
+        let id = Entity::from_bits(input.get_u64()?);
+        records.push(match tag {
+            SPAWN => DeltaRecord::Spawn {
+                id,
+                bytes: input.get_bytes()?.to_vec(),
+            },
+            DESPAWN => DeltaRecord::Despawn { id },
+            tag => return Err(ReplicationError::UnknownRecord(tag)),
+        });
+    }
+    Ok(records)
+}
+
+/// Server entity → local entity, for a client world fed by `apply_delta`.
+#[derive(Clone, Debug, Default)]
+pub struct ReplicaMap {
+    local: HashMap<Entity, Entity>,
+}
+
+impl ReplicaMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Local entity replicating the server entity `remote`.
+    pub fn local(&self, remote: Entity) -> Option<Entity> {
+        self.local.get(&remote).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.local.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.local.is_empty()
+    }
+}
+
+/// Decode a delta and apply it to `world`.
+///
+/// Every record is decoded and checked, and every spawned entity built,
+/// before the world is touched, so a truncated, corrupt or unreadable
+/// message (unknown component, duplicate spawn, despawn of a stale local
+/// entity) leaves it untouched. Only `World::spawn` itself failing, e.g.
+/// for a layout the page budget cannot fit, can stop part way. Despawns of
+/// entities the client never received (it joined later) are ignored; the
+/// despawned rows are removed by the next `flush_despawns`.
+pub fn apply_delta(
+    world: &mut World,
+    replicas: &mut ReplicaMap,
+    input: &mut WireReader<'_>,
+) -> Result<(), ReplicationError> {
+    let records = decode_delta(input)?;
+
+    let mut spawns = Vec::new();
+    let mut despawns = Vec::new();
+    let mut spawned = HashSet::new();
+    for record in records {
+        match record {
+            DeltaRecord::Spawn { id, bytes } => {
+                if replicas.local.contains_key(&id) || !spawned.insert(id) {
+                    return Err(ReplicationError::AlreadyReplicated { entity: id });
+                }
+                spawns.push((id, spawn_builder(id, &bytes)?));
+            }
+            DeltaRecord::Despawn { id } => {
+                if let Some(&local) = replicas.local.get(&id) {
+                    world.locate(local)?;
+                    despawns.push(id);
+                }
+            }
+        }
+    }
+
+    // `encode_delta` sends despawns first; keep that order.
+    for id in despawns {
+        if let Some(local) = replicas.local.remove(&id) {
+            world.despawn(local)?;
+        }
+    }
+    for (id, builder) in spawns {
+        let local = world.spawn(builder)?;
+        replicas.local.insert(id, local);
+    }
+    Ok(())
+}
+
+fn spawn_builder(entity: Entity, bytes: &[u8]) -> Result<EntityBuilder, ReplicationError> {
+    let mut input = WireReader::new(bytes);
+    let mut builder = EntityBuilder::new();
+    let mut sent = Vec::new();
+    while !input.is_empty() {
+        let component_id = input.get_u32()?;
+        let meta = meta_of(component_id)
+            .ok_or(ReplicationError::ComponentNotRegistered { component_id })?;
+        let mut value = vec![0; meta.stride];
+        input.get_component(&meta, &mut value)?;
+        builder = builder.with_raw_bytes(component_id, value)?;
+        sent.push(component_id);
+    }
+    if !sent.windows(2).all(|pair| pair[0] < pair[1]) {
+        return Err(ReplicationError::ComponentOrder { entity, sent });
+    }
+    Ok(builder)
+}
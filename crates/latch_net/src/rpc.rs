@@ -0,0 +1,219 @@
+//! Reliable client→server commands.
+//!
+//! Complements state replication (server→client) with intent messages
+//! such as player actions. Each frame is `[id: u16][seq: u32][len: u32]`
+//! followed by the payload, all little-endian via `wire`. Sequence numbers
+//! are tracked per peer so a replayed or reordered command is rejected
+//! before it reaches gameplay code.
+
+use crate::transport::{Transport, TransportError};
+use crate::wire::{WireError, WireReader, WireWriter};
+use crate::NodeId;
+use latch_core::ecs::World;
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// Application-assigned message identifier.
+pub type RpcId = u16;
+
+/// Default upper bound on a single RPC payload.
+pub const DEFAULT_MAX_RPC_PAYLOAD: usize = 4 * 1024;
+
+const HEADER_BYTES: usize = 2 + 4 + 4;
+
+/// Handler invoked with the payload of a validated frame.
+pub type RpcHandler = Box<dyn FnMut(&mut World, &[u8]) -> Result<(), RpcError> + Send>;
+
+#[derive(Debug, Error)]
+pub enum RpcError {
+    #[error("rpc {id} already has a handler")]
+    DuplicateHandler { id: RpcId },
+    #[error("no handler registered for rpc {id}")]
+    UnknownMessage { id: RpcId },
+    #[error("rpc payload of {len} bytes exceeds limit of {max}")]
+    PayloadTooLarge { len: usize, max: usize },
+    #[error("rpc from {from:?} has sequence {got}, expected {expected}")]
+    OutOfOrder {
+        from: NodeId,
+        expected: u32,
+        got: u32,
+    },
+    #[error("rpc frame has {trailing} trailing bytes")]
+    TrailingBytes { trailing: usize },
+    #[error("rpc {id} rejected: {reason}")]
+    Rejected { id: RpcId, reason: Box<str> },
+    #[error(transparent)]
+    Wire(#[from] WireError),
+    #[error(transparent)]
+    Transport(#[from] TransportError),
+}
+
+/// Handler registry plus per-peer sequencing state.
+pub struct Rpc {
+    handlers: HashMap<RpcId, RpcHandler>,
+    max_payload: usize,
+    next_send: HashMap<NodeId, u32>,
+    next_recv: HashMap<NodeId, u32>,
+}
+
+impl Rpc {
+    pub fn new() -> Self {
+        Self::with_max_payload(DEFAULT_MAX_RPC_PAYLOAD)
+    }
+
+    pub fn with_max_payload(max_payload: usize) -> Self {
+        Self {
+            handlers: HashMap::new(),
+            max_payload,
+            next_send: HashMap::new(),
+            next_recv: HashMap::new(),
+        }
+    }
+
+    #[inline]
+    pub fn max_payload(&self) -> usize {
+        self.max_payload
+    }
+
+    pub fn register<F>(&mut self, id: RpcId, handler: F) -> Result<(), RpcError>
+    where
+        F: FnMut(&mut World, &[u8]) -> Result<(), RpcError> + Send + 'static,
+    {
+        if self.handlers.contains_key(&id) {
+            return Err(RpcError::DuplicateHandler { id });
+        }
+        self.handlers.insert(id, Box::new(handler));
+        Ok(())
+    }
+
+    /// Frame `payload` for `to`, consuming the next outgoing sequence number.
+    pub fn encode_rpc(
+        &mut self,
+        to: NodeId,
+        id: RpcId,
+        payload: &[u8],
+    ) -> Result<Vec<u8>, RpcError> {
+        let frame = self.frame(to, id, payload)?;
+        self.advance_send(to);
+        Ok(frame)
+    }
+
+    /// Frame `payload` with `to`'s next sequence number without consuming it.
+    fn frame(&self, to: NodeId, id: RpcId, payload: &[u8]) -> Result<Vec<u8>, RpcError> {
+        self.check_len(payload.len())?;
+        let seq = self.next_send.get(&to).copied().unwrap_or(0);
+        let mut out = WireWriter::with_capacity(HEADER_BYTES + payload.len());
+        out.put_u16(id);
+        out.put_u32(seq);
+        out.put_bytes(payload);
+        Ok(out.into_bytes())
+    }
+
+    fn advance_send(&mut self, to: NodeId) {
+        let seq = self.next_send.entry(to).or_insert(0);
+        *seq = seq.wrapping_add(1);
+    }
+
+    /// Validate one frame from `from` and run its handler against `world`.
+    ///
+    /// The peer's expected sequence only advances once the frame has been
+    /// parsed, so a malformed frame can be resent. A well-formed frame with
+    /// no handler still consumes its sequence number; otherwise every later
+    /// frame from that peer would be rejected as out of order.
+    pub fn dispatch(
+        &mut self,
+        world: &mut World,
+        from: NodeId,
+        frame: &[u8],
+    ) -> Result<RpcId, RpcError> {
+        let mut input = WireReader::new(frame);
+        let id = input.get_u16()?;
+        let seq = input.get_u32()?;
+        let payload = input.get_bytes()?;
+        self.check_len(payload.len())?;
+        if !input.is_empty() {
+            return Err(RpcError::TrailingBytes {
+                trailing: input.remaining(),
+            });
+        }
+
+        let expected = self.next_recv.get(&from).copied().unwrap_or(0);
+        if seq != expected {
+            return Err(RpcError::OutOfOrder {
+                from,
+                expected,
+                got: seq,
+            });
+        }
+        self.next_recv.insert(from, expected.wrapping_add(1));
+        let handler = self
+            .handlers
+            .get_mut(&id)
+            .ok_or(RpcError::UnknownMessage { id })?;
+        handler(world, payload)?;
+        Ok(id)
+    }
+
+    /// Encode and send one command over `transport`.
+    ///
+    /// The sequence number is only consumed once the transport accepts the
+    /// frame, so a failed send can be retried without desyncing the peer.
+    pub fn send(
+        &mut self,
+        transport: &mut dyn Transport,
+        to: NodeId,
+        id: RpcId,
+        payload: &[u8],
+    ) -> Result<(), RpcError> {
+        let frame = self.frame(to, id, payload)?;
+        transport.send(to, &frame)?;
+        self.advance_send(to);
+        Ok(())
+    }
+
+    /// Dispatch every pending message on `transport`, returning the first
+    /// error after draining; later frames still run so one bad command
+    /// cannot stall a peer's queue.
+    pub fn poll(
+        &mut self,
+        transport: &mut dyn Transport,
+        world: &mut World,
+    ) -> Result<usize, RpcError> {
+        let mut dispatched = 0;
+        let mut first_error = None;
+        while let Some((from, frame)) = transport.recv() {
+            match self.dispatch(world, from, &frame) {
+                Ok(_) => dispatched += 1,
+                Err(err) => {
+                    first_error.get_or_insert(err);
+                }
+            }
+        }
+        match first_error {
+            Some(err) => Err(err),
+            None => Ok(dispatched),
+        }
+    }
+
+    /// Forget sequencing state for a disconnected peer.
+    pub fn reset_peer(&mut self, node: NodeId) {
+        self.next_send.remove(&node);
+        self.next_recv.remove(&node);
+    }
+
+    fn check_len(&self, len: usize) -> Result<(), RpcError> {
+        if len > self.max_payload {
+            return Err(RpcError::PayloadTooLarge {
+                len,
+                max: self.max_payload,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl Default for Rpc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
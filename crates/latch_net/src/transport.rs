@@ -0,0 +1,25 @@
+//! Abstract message transport between nodes.
+//!
+//! Higher layers (RPC, replication, gossip) only need reliable, ordered,
+//! message-framed delivery per peer; QUIC streams satisfy this in
+//! production and in-memory queues in tests.
+
+use crate::NodeId;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum TransportError {
+    #[error("peer {node:?} is not connected")]
+    Disconnected { node: NodeId },
+    #[error("transport failure: {reason}")]
+    Io { reason: Box<str> },
+}
+
+/// Reliable, ordered, message-framed delivery to other nodes.
+pub trait Transport {
+    /// Queue one message for `to`.
+    fn send(&mut self, to: NodeId, message: &[u8]) -> Result<(), TransportError>;
+
+    /// Next received message and its sender, if any is pending.
+    fn recv(&mut self) -> Option<(NodeId, Vec<u8>)>;
+}
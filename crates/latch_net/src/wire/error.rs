@@ -0,0 +1,28 @@
+use latch_core::ecs::ComponentCodecError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum WireError {
+    #[error("wire message truncated: needed {needed} bytes, {available} available")]
+    Truncated { needed: usize, available: usize },
+    #[error("peer did not send a latch handshake (magic {found:#010x})")]
+    BadMagic { found: u32 },
+    #[error(
+        "protocol version mismatch: local supports {local_min}..={local_max}, \
+         peer supports {remote_min}..={remote_max}"
+    )]
+    VersionMismatch {
+        local_min: u32,
+        local_max: u32,
+        remote_min: u32,
+        remote_max: u32,
+    },
+    #[error("component {component_id} payload is {len} bytes, expected stride {stride}")]
+    StrideMismatch {
+        component_id: u32,
+        len: usize,
+        stride: usize,
+    },
+    #[error(transparent)]
+    Component(#[from] ComponentCodecError),
+}
@@ -0,0 +1,64 @@
+use super::{WireError, WireReader, WireWriter};
+use crate::{MIN_PROTOCOL_VERSION, PROTOCOL_VERSION};
+
+/// First word of every handshake ("LTCH" little-endian); lets a node reject
+/// foreign traffic before interpreting version fields.
+pub const HANDSHAKE_MAGIC: u32 = u32::from_le_bytes(*b"LTCH");
+
+/// Version range a node advertises when a connection opens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hello {
+    pub min_version: u32,
+    pub max_version: u32,
+}
+
+impl Hello {
+    /// The range this build speaks.
+    pub const fn local() -> Self {
+        Self {
+            min_version: MIN_PROTOCOL_VERSION,
+            max_version: PROTOCOL_VERSION,
+        }
+    }
+
+    pub fn encode(&self, out: &mut WireWriter) {
+        out.put_u32(HANDSHAKE_MAGIC);
+        out.put_u32(self.min_version);
+        out.put_u32(self.max_version);
+    }
+
+    pub fn decode(input: &mut WireReader<'_>) -> Result<Self, WireError> {
+        let magic = input.get_u32()?;
+        if magic != HANDSHAKE_MAGIC {
+            return Err(WireError::BadMagic { found: magic });
+        }
+        Ok(Self {
+            min_version: input.get_u32()?,
+            max_version: input.get_u32()?,
+        })
+    }
+}
+
+impl Default for Hello {
+    fn default() -> Self {
+        Self::local()
+    }
+}
+
+/// Pick the highest protocol version both sides support.
+///
+/// Both peers run the same function on the exchanged `Hello`s, so they agree
+/// on the result without another round trip. Disjoint ranges fail with
+/// `WireError::VersionMismatch` instead of risking a misparsed stream.
+pub fn negotiate(local: Hello, remote: Hello) -> Result<u32, WireError> {
+    let version = local.max_version.min(remote.max_version);
+    if version < local.min_version.max(remote.min_version) {
+        return Err(WireError::VersionMismatch {
+            local_min: local.min_version,
+            local_max: local.max_version,
+            remote_min: remote.min_version,
+            remote_max: remote.max_version,
+        });
+    }
+    Ok(version)
+}
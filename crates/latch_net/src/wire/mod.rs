@@ -0,0 +1,31 @@
+//! Endian-normalized wire encoding shared by every cross-machine message.
+//!
+//! All multi-byte scalars travel little-endian regardless of host byte
+//! order. Component payloads go through their registered `ComponentCodec`
+//! when present; otherwise the native bytes are copied with each scalar
+//! field (2, 4 or 8 bytes per `FieldMeta`) normalized to little-endian.
+//! Components holding arrays or nested multi-byte data should register a
+//! codec, since field metadata alone cannot describe their element layout.
+
+mod error;
+mod handshake;
+mod reader;
+mod writer;
+
+pub use error::WireError;
+pub use handshake::{negotiate, Hello, HANDSHAKE_MAGIC};
+pub use reader::WireReader;
+pub use writer::WireWriter;
+
+/// Swap every 2/4/8-byte field in `value` between native and little-endian
+/// order. The operation is its own inverse and a no-op on little-endian hosts.
+pub(crate) fn normalize_fields(meta: &latch_core::ecs::ComponentMeta, value: &mut [u8]) {
+    if cfg!(target_endian = "little") {
+        return;
+    }
+    for field in meta.fields.iter() {
+        if matches!(field.size, 2 | 4 | 8) {
+            value[field.offset..field.offset + field.size].reverse();
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use super::{normalize_fields, WireError};
+use latch_core::ecs::ComponentMeta;
+
+/// Cursor over a little-endian wire buffer produced by `WireWriter`.
+#[derive(Debug, Clone)]
+pub struct WireReader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> WireReader<'a> {
+    pub fn new(buf: &'a [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.buf.len() - self.pos
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], WireError> {
+        let bytes = self
+            .buf
+            .get(self.pos..self.pos + len)
+            .ok_or(WireError::Truncated {
+                needed: len,
+                available: self.remaining(),
+            })?;
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N], WireError> {
+        let mut out = [0u8; N];
+        out.copy_from_slice(self.take(N)?);
+        Ok(out)
+    }
+
+    #[inline]
+    pub fn get_u8(&mut self) -> Result<u8, WireError> {
+        Ok(self.take_array::<1>()?[0])
+    }
+
+    #[inline]
+    pub fn get_u16(&mut self) -> Result<u16, WireError> {
+        self.take_array().map(u16::from_le_bytes)
+    }
+
+    #[inline]
+    pub fn get_u32(&mut self) -> Result<u32, WireError> {
+        self.take_array().map(u32::from_le_bytes)
+    }
+
+    #[inline]
+    pub fn get_u64(&mut self) -> Result<u64, WireError> {
+        self.take_array().map(u64::from_le_bytes)
+    }
+
+    #[inline]
+    pub fn get_i32(&mut self) -> Result<i32, WireError> {
+        self.take_array().map(i32::from_le_bytes)
+    }
+
+    #[inline]
+    pub fn get_i64(&mut self) -> Result<i64, WireError> {
+        self.take_array().map(i64::from_le_bytes)
+    }
+
+    #[inline]
+    pub fn get_f32(&mut self) -> Result<f32, WireError> {
+        self.get_u32().map(f32::from_bits)
+    }
+
+    /// Read a byte string written by `WireWriter::put_bytes`.
+    pub fn get_bytes(&mut self) -> Result<&'a [u8], WireError> {
+        let len = self.get_u32()? as usize;
+        self.take(len)
+    }
+
+    /// Decode one component value into `value` (`stride` native bytes).
+    pub fn get_component(
+        &mut self,
+        meta: &ComponentMeta,
+        value: &mut [u8],
+    ) -> Result<(), WireError> {
+        if value.len() != meta.stride {
+            return Err(WireError::StrideMismatch {
+                component_id: meta.id,
+                len: value.len(),
+                stride: meta.stride,
+            });
+        }
+        if meta.codec.is_some() {
+            let consumed = meta.decode(&self.buf[self.pos..], value)?;
+            // A codec claiming more than it was given must not move the
+            // cursor past the buffer.
+            if consumed > self.remaining() {
+                return Err(WireError::Truncated {
+                    needed: consumed,
+                    available: self.remaining(),
+                });
+            }
+            self.pos += consumed;
+            return Ok(());
+        }
+        value.copy_from_slice(self.take(meta.stride)?);
+        normalize_fields(meta, value);
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+use latch_core::define_component;
+use latch_core::ecs::World;
+use latch_core::math::Vec3;
+use latch_core::spawn;
+use latch_core::transform::Transform;
+use latch_net::cell::{CellConfig, CellGrid};
+use latch_net::CellId;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Health(u32);
+define_component!(Health, 9262, "CellChecksumTest::Health");
+
+fn world_with(far_health: u32) -> World {
+    let mut world = World::new();
+    spawn!(
+        world,
+        Transform::from_translation(Vec3::new(1.0, 0.0, 1.0)),
+        Health(10)
+    );
+    spawn!(world, Transform::from_translation(Vec3::new(5.0, 0.0, 2.0)));
+    spawn!(
+        world,
+        Transform::from_translation(Vec3::new(30.0, 0.0, 1.0)),
+        Health(far_health)
+    );
+    spawn!(world, Health(99));
+    world
+}
+
+#[test]
+fn only_the_diverged_cell_mismatches() {
+    let grid = CellGrid::new(CellConfig { cell_size: 16.0 });
+    let a = world_with(20).checksum_cells(&grid);
+    let b = world_with(21).checksum_cells(&grid);
+
+    let near = CellId::from_coords(0, 0);
+    let far = CellId::from_coords(1, 0);
+    assert_eq!(a.len(), 2);
+    assert_eq!(a[&near], b[&near]);
+    assert_ne!(a[&far], b[&far]);
+}
+
+#[test]
+fn storage_checksum_tracks_the_current_buffer() {
+    let a = world_with(20);
+    let mut b = world_with(20);
+    let archetype = a.archetypes_with(Health::ID)[0];
+    let checksum = a.storage(archetype).unwrap().checksum();
+    assert_eq!(b.storage(archetype).unwrap().checksum(), checksum);
+
+    b.set_all(Health(0)).unwrap();
+    assert_eq!(b.storage(archetype).unwrap().checksum(), checksum);
+    b.swap_buffers();
+    assert_ne!(b.storage(archetype).unwrap().checksum(), checksum);
+}
+
+#[test]
+fn unflushed_despawns_do_not_count() {
+    let grid = CellGrid::new(CellConfig { cell_size: 16.0 });
+    let flushed = world_with(20);
+    let mut pending = world_with(20);
+    let doomed = spawn!(
+        pending,
+        Transform::from_translation(Vec3::new(2.0, 0.0, 3.0)),
+        Health(5)
+    );
+    pending.despawn(doomed).unwrap();
+
+    let near = CellId::from_coords(0, 0);
+    let expected = flushed.checksum_cells(&grid);
+    assert_eq!(pending.checksum_cells(&grid), expected);
+    let rows = |world: &World| world.cell_index(&grid).rows(near).len();
+    assert_eq!(rows(&pending), rows(&flushed));
+
+    pending.flush_despawns().unwrap();
+    assert_eq!(pending.checksum_cells(&grid), expected);
+}
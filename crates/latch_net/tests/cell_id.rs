@@ -0,0 +1,18 @@
+use latch_core::hash::stable_hash;
+use latch_net::{CellId, NodeId};
+
+#[test]
+fn cell_coords_round_trip() {
+    for &(x, y) in &[(0, 0), (1, -1), (i32::MIN, i32::MAX), (-7, 42)] {
+        assert_eq!(CellId::from_coords(x, y).to_coords(), (x, y));
+    }
+    assert_eq!(CellId::from_coords(-1, 1).0, 0x0000_0001_ffff_ffff);
+}
+
+#[test]
+fn stable_hashes_match_fnv1a_reference() {
+    assert_eq!(stable_hash(b""), 0xcbf2_9ce4_8422_2325);
+    assert_eq!(stable_hash(b"a"), 0xaf63_dc4c_8601_ec8c);
+    assert_eq!(NodeId(7).stable_hash(), stable_hash(&7u64.to_le_bytes()));
+    assert!(NodeId(7).bucket(16) < 16);
+}
@@ -0,0 +1,45 @@
+use latch_core::define_component;
+use latch_core::ecs::World;
+use latch_core::math::Vec3;
+use latch_core::spawn;
+use latch_core::transform::Transform;
+use latch_net::cell::{CellConfig, CellGrid};
+use latch_net::CellId;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Ticks(u32);
+define_component!(Ticks, 9271, "CellIterationTest::Ticks");
+
+fn at(x: f32, z: f32) -> Transform {
+    Transform::from_translation(Vec3::new(x, 0.0, z))
+}
+
+#[test]
+fn only_entities_in_owned_cells_are_visited() {
+    let mut world = World::new();
+    let owned = spawn!(world, at(1.0, 1.0), Ticks(0));
+    let also_owned = spawn!(world, at(20.0, 4.0), Ticks(0));
+    spawn!(world, at(40.0, 1.0), Ticks(0));
+    spawn!(world, at(2.0, 2.0));
+    spawn!(world, Ticks(0));
+
+    let grid = CellGrid::new(CellConfig { cell_size: 16.0 });
+    let cells = [CellId::from_coords(0, 0), CellId::from_coords(1, 0)];
+    let mut visited = Vec::new();
+    world.for_each_in_cells(&grid, &cells, &[Ticks::ID], |storage, rows| {
+        for &row in rows {
+            visited.push(storage.entity_id_at(row).unwrap());
+        }
+    });
+    visited.sort_unstable();
+    assert_eq!(visited, [owned.index(), also_owned.index()]);
+
+    let index = world.cell_index(&grid);
+    assert_eq!(index.rows(CellId::from_coords(2, 0)).len(), 1);
+    assert_eq!(
+        index.rows(cells[0]).len(),
+        2,
+        "includes entities without Ticks"
+    );
+    assert_eq!(index.cells().count(), 3);
+}
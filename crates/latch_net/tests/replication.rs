@@ -0,0 +1,139 @@
+use latch_core::define_component;
+use latch_core::ecs::{Component, Entity, Schedule, World};
+use latch_core::spawn;
+use latch_net::replication::{apply_delta, decode_delta, encode_delta, DeltaRecord, ReplicaMap};
+use latch_net::wire::{WireReader, WireWriter};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Position {
+    x: i32,
+    y: i32,
+}
+define_component!(Position, 9297, "ReplicationTest::Position");
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Hull(u16);
+define_component!(Hull, 9298, "ReplicationTest::Hull");
+
+/// Take the server's topology log and encode it.
+fn delta(server: &mut World) -> Vec<u8> {
+    let changes = server.take_topology_changes();
+    let mut out = WireWriter::new();
+    encode_delta(server, &changes, &mut out).unwrap();
+    out.into_bytes()
+}
+
+#[test]
+fn spawn_delta_reproduces_entity_on_fresh_client() {
+    let mut server = World::new();
+    server.set_topology_tracking(true);
+    let mut schedule = Schedule::new();
+
+    let ship = spawn!(server, Position { x: 3, y: -7 }, Hull(250));
+    let marker = spawn!(server, Position { x: 1, y: 1 });
+    let short_lived = spawn!(server, Hull(1));
+    server.despawn(short_lived).unwrap();
+    // The log outlives the buffer swap that clears `spawned_since_last_tick`.
+    server.tick(&mut schedule).unwrap();
+
+    let bytes = delta(&mut server);
+    let records = decode_delta(&mut WireReader::new(&bytes)).unwrap();
+    assert_eq!(records.len(), 2, "short-lived entity must not be sent");
+
+    let mut client = World::new();
+    let mut replicas = ReplicaMap::new();
+    apply_delta(&mut client, &mut replicas, &mut WireReader::new(&bytes)).unwrap();
+    assert_eq!(client.live_entity_count(), 2);
+
+    let local_ship = replicas.local(ship).expect("ship replicated");
+    assert_eq!(
+        client.component_ids_of(local_ship).unwrap(),
+        [Position::id(), Hull::id()]
+    );
+    let mut seen = Vec::new();
+    client.for_each_row(Hull::id(), |entity, bytes| {
+        seen.push((entity, u16::from_ne_bytes([bytes[0], bytes[1]])))
+    });
+    assert_eq!(seen, [(local_ship, 250)]);
+    let mut positions = Vec::new();
+    client.for_each_row(Position::id(), |entity, bytes| {
+        let x = i32::from_ne_bytes(bytes[0..4].try_into().unwrap());
+        let y = i32::from_ne_bytes(bytes[4..8].try_into().unwrap());
+        positions.push((entity, x, y));
+    });
+    positions.sort_by_key(|&(_, x, _)| x);
+    let local_marker = replicas.local(marker).unwrap();
+    assert_eq!(positions, [(local_marker, 1, 1), (local_ship, 3, -7)]);
+
+    server.despawn(ship).unwrap();
+    let bytes = delta(&mut server);
+    assert_eq!(
+        decode_delta(&mut WireReader::new(&bytes)).unwrap(),
+        [DeltaRecord::Despawn { id: ship }]
+    );
+    apply_delta(&mut client, &mut replicas, &mut WireReader::new(&bytes)).unwrap();
+    assert_eq!(client.live_entity_count(), 1);
+    assert_eq!(replicas.local(ship), None);
+    assert!(client.validate(local_marker).is_some());
+}
+
+#[test]
+fn truncated_delta_applies_nothing() {
+    let mut server = World::new();
+    server.set_topology_tracking(true);
+    spawn!(server, Position { x: 0, y: 0 });
+    spawn!(server, Hull(9));
+    let bytes = delta(&mut server);
+
+    let mut client = World::new();
+    let mut replicas = ReplicaMap::new();
+    let truncated = &bytes[..bytes.len() - 1];
+    assert!(apply_delta(&mut client, &mut replicas, &mut WireReader::new(truncated)).is_err());
+    assert_eq!(client.live_entity_count(), 0);
+    assert!(replicas.is_empty());
+}
+
+#[test]
+fn unreadable_spawn_applies_nothing() {
+    let mut server = World::new();
+    server.set_topology_tracking(true);
+    spawn!(server, Position { x: 5, y: 5 });
+    let valid = delta(&mut server);
+
+    // The valid spawn, then one whose component this peer does not know.
+    let mut unknown = WireWriter::new();
+    unknown.put_u8(1);
+    unknown.put_u64(Entity::new(40, 0).to_bits());
+    unknown.put_bytes(&u32::MAX.to_le_bytes());
+    let mut bytes = 2u32.to_le_bytes().to_vec();
+    bytes.extend_from_slice(&valid[4..]);
+    bytes.extend_from_slice(unknown.as_bytes());
+
+    let mut client = World::new();
+    let mut replicas = ReplicaMap::new();
+    assert!(apply_delta(&mut client, &mut replicas, &mut WireReader::new(&bytes)).is_err());
+    assert_eq!(client.live_entity_count(), 0);
+    assert!(replicas.is_empty());
+}
+
+#[test]
+fn despawned_spawns_drop_out_and_keep_spawn_order() {
+    let mut server = World::new();
+    server.set_topology_tracking(true);
+    let spawned: Vec<Entity> = (0..6).map(|i| spawn!(server, Hull(i))).collect();
+    server.despawn(spawned[1]).unwrap();
+    server.despawn(spawned[4]).unwrap();
+
+    let expected = [spawned[0], spawned[2], spawned[3], spawned[5]];
+    let peeked = server.topology_changes().unwrap();
+    assert_eq!(peeked.spawned, expected);
+    assert!(peeked.despawned.is_empty());
+
+    let taken = server.take_topology_changes();
+    assert_eq!(taken, peeked);
+    assert!(server.topology_changes().unwrap().is_empty());
+
+    // Once taken, a spawn is a despawn like any other.
+    server.despawn(spawned[0]).unwrap();
+    assert_eq!(server.take_topology_changes().despawned, [spawned[0]]);
+}
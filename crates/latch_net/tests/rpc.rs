@@ -0,0 +1,171 @@
+use latch_core::ecs::World;
+use latch_net::wire::WireReader;
+use latch_net::{NodeId, Rpc, RpcError, Transport, TransportError};
+use std::collections::VecDeque;
+
+const MOVE: u16 = 1;
+const CLIENT: NodeId = NodeId(7);
+const SERVER: NodeId = NodeId(1);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct MoveIntent(i32);
+
+/// Loops every sent message back as if it came from `CLIENT`.
+#[derive(Default)]
+struct Loopback {
+    queue: VecDeque<Vec<u8>>,
+}
+
+impl Transport for Loopback {
+    fn send(&mut self, _to: NodeId, message: &[u8]) -> Result<(), TransportError> {
+        self.queue.push_back(message.to_vec());
+        Ok(())
+    }
+
+    fn recv(&mut self) -> Option<(NodeId, Vec<u8>)> {
+        self.queue.pop_front().map(|message| (CLIENT, message))
+    }
+}
+
+fn server() -> (Rpc, World) {
+    let mut world = World::new();
+    world.add_events::<MoveIntent>();
+    let mut rpc = Rpc::with_max_payload(16);
+    rpc.register(MOVE, |world, payload| {
+        let dx = WireReader::new(payload).get_i32()?;
+        world.send_event(MoveIntent(dx));
+        Ok(())
+    })
+    .unwrap();
+    (rpc, world)
+}
+
+#[test]
+fn commands_reach_handlers_in_order() {
+    let (mut rpc, mut world) = server();
+    let mut client = Rpc::new();
+    let mut transport = Loopback::default();
+    for dx in [3i32, -4] {
+        client
+            .send(&mut transport, SERVER, MOVE, &dx.to_le_bytes())
+            .unwrap();
+    }
+
+    assert_eq!(rpc.poll(&mut transport, &mut world).unwrap(), 2);
+    world.swap_buffers();
+    let moves: Vec<_> = world
+        .events::<MoveIntent>()
+        .unwrap()
+        .drain()
+        .copied()
+        .collect();
+    assert_eq!(moves, [MoveIntent(3), MoveIntent(-4)]);
+}
+
+#[test]
+fn replayed_oversized_and_unknown_frames_are_rejected() {
+    let (mut rpc, mut world) = server();
+    let mut client = Rpc::new();
+    let frame = client
+        .encode_rpc(SERVER, MOVE, &1i32.to_le_bytes())
+        .unwrap();
+    rpc.dispatch(&mut world, CLIENT, &frame).unwrap();
+    assert!(matches!(
+        rpc.dispatch(&mut world, CLIENT, &frame),
+        Err(RpcError::OutOfOrder {
+            expected: 1,
+            got: 0,
+            ..
+        })
+    ));
+
+    let big = client.encode_rpc(SERVER, MOVE, &[0u8; 32]).unwrap();
+    assert!(matches!(
+        rpc.dispatch(&mut world, CLIENT, &big),
+        Err(RpcError::PayloadTooLarge { len: 32, max: 16 })
+    ));
+    assert!(matches!(
+        rpc.encode_rpc(SERVER, MOVE, &[0u8; 32]),
+        Err(RpcError::PayloadTooLarge { .. })
+    ));
+
+    let unknown = Rpc::new().encode_rpc(SERVER, 99, &[]).unwrap();
+    assert!(matches!(
+        rpc.dispatch(&mut world, NodeId(8), &unknown),
+        Err(RpcError::UnknownMessage { id: 99 })
+    ));
+}
+
+#[test]
+fn unknown_message_consumes_its_sequence_number() {
+    let (mut rpc, mut world) = server();
+    let mut client = Rpc::new();
+    let unknown = client.encode_rpc(SERVER, 99, &[]).unwrap();
+    let valid = client
+        .encode_rpc(SERVER, MOVE, &5i32.to_le_bytes())
+        .unwrap();
+
+    assert!(matches!(
+        rpc.dispatch(&mut world, CLIENT, &unknown),
+        Err(RpcError::UnknownMessage { id: 99 })
+    ));
+    assert_eq!(rpc.dispatch(&mut world, CLIENT, &valid).unwrap(), MOVE);
+    world.swap_buffers();
+    let moves: Vec<_> = world
+        .events::<MoveIntent>()
+        .unwrap()
+        .drain()
+        .copied()
+        .collect();
+    assert_eq!(moves, [MoveIntent(5)]);
+}
+
+/// Loopback that rejects the first `failures` sends.
+struct Flaky {
+    failures: usize,
+    inner: Loopback,
+}
+
+impl Transport for Flaky {
+    fn send(&mut self, to: NodeId, message: &[u8]) -> Result<(), TransportError> {
+        if self.failures > 0 {
+            self.failures -= 1;
+            return Err(TransportError::Disconnected { node: to });
+        }
+        self.inner.send(to, message)
+    }
+
+    fn recv(&mut self) -> Option<(NodeId, Vec<u8>)> {
+        self.inner.recv()
+    }
+}
+
+#[test]
+fn failed_send_does_not_consume_a_sequence_number() {
+    let (mut rpc, mut world) = server();
+    let mut client = Rpc::new();
+    let mut transport = Flaky {
+        failures: 1,
+        inner: Loopback::default(),
+    };
+
+    assert!(matches!(
+        client.send(&mut transport, SERVER, MOVE, &1i32.to_le_bytes()),
+        Err(RpcError::Transport(TransportError::Disconnected { .. }))
+    ));
+    for dx in [2i32, 3] {
+        client
+            .send(&mut transport, SERVER, MOVE, &dx.to_le_bytes())
+            .unwrap();
+    }
+
+    assert_eq!(rpc.poll(&mut transport, &mut world).unwrap(), 2);
+    world.swap_buffers();
+    let moves: Vec<_> = world
+        .events::<MoveIntent>()
+        .unwrap()
+        .drain()
+        .copied()
+        .collect();
+    assert_eq!(moves, [MoveIntent(2), MoveIntent(3)]);
+}
@@ -0,0 +1,130 @@
+use latch_core::ecs::{
+    register_component, register_component_with_codec, reset_registry, ComponentCodec,
+    ComponentCodecError, FieldMeta,
+};
+use latch_net::wire::{negotiate, Hello, WireError, WireReader, WireWriter};
+use latch_net::{MIN_PROTOCOL_VERSION, PROTOCOL_VERSION};
+
+#[test]
+fn scalars_are_little_endian_on_the_wire() {
+    let mut out = WireWriter::new();
+    out.put_u16(0x0102);
+    out.put_u32(0x0304_0506);
+    out.put_i32(-2);
+    out.put_bytes(b"hi");
+    assert_eq!(
+        out.as_bytes(),
+        [2, 1, 6, 5, 4, 3, 0xfe, 0xff, 0xff, 0xff, 2, 0, 0, 0, b'h', b'i']
+    );
+
+    let mut input = WireReader::new(out.as_bytes());
+    assert_eq!(input.get_u16().unwrap(), 0x0102);
+    assert_eq!(input.get_u32().unwrap(), 0x0304_0506);
+    assert_eq!(input.get_i32().unwrap(), -2);
+    assert_eq!(input.get_bytes().unwrap(), b"hi");
+    assert!(input.is_empty());
+    assert!(matches!(
+        input.get_u8(),
+        Err(WireError::Truncated {
+            needed: 1,
+            available: 0
+        })
+    ));
+}
+
+#[test]
+fn components_round_trip_with_little_endian_fields() {
+    let _registry = reset_registry();
+    let handle = register_component(
+        "WireTest::Position",
+        8,
+        4,
+        8,
+        true,
+        vec![FieldMeta::new("x", 0, 4), FieldMeta::new("y", 4, 4)],
+    )
+    .unwrap();
+    let meta = latch_core::ecs::meta_of(handle.id).unwrap();
+
+    let mut value = [0u8; 8];
+    value[..4].copy_from_slice(&7i32.to_ne_bytes());
+    value[4..].copy_from_slice(&(-1i32).to_ne_bytes());
+
+    let mut out = WireWriter::new();
+    out.put_component(&meta, &value);
+    assert_eq!(out.as_bytes(), [7, 0, 0, 0, 0xff, 0xff, 0xff, 0xff]);
+
+    let mut decoded = [0u8; 8];
+    WireReader::new(out.as_bytes())
+        .get_component(&meta, &mut decoded)
+        .unwrap();
+    assert_eq!(decoded, value);
+}
+
+#[test]
+fn overreporting_codec_is_truncated_not_a_panic() {
+    fn serialize(value: &[u8], out: &mut Vec<u8>) {
+        out.extend_from_slice(value);
+    }
+    fn deserialize(input: &[u8], value: &mut [u8]) -> Result<usize, ComponentCodecError> {
+        value.copy_from_slice(&input[..value.len()]);
+        Ok(input.len() + 16)
+    }
+
+    let _registry = reset_registry();
+    let handle = register_component_with_codec(
+        "WireTest::Liar",
+        4,
+        4,
+        4,
+        Vec::new(),
+        ComponentCodec::new(serialize, deserialize),
+    )
+    .unwrap();
+    let meta = latch_core::ecs::meta_of(handle.id).unwrap();
+
+    let bytes = [1, 2, 3, 4];
+    let mut reader = WireReader::new(&bytes);
+    let mut value = [0u8; 4];
+    assert!(matches!(
+        reader.get_component(&meta, &mut value),
+        Err(WireError::Truncated {
+            needed: 20,
+            available: 4
+        })
+    ));
+    assert_eq!(reader.remaining(), 4);
+    assert!(reader.get_u64().is_err());
+}
+
+#[test]
+fn handshake_negotiates_highest_shared_version() {
+    let mut out = WireWriter::new();
+    Hello::local().encode(&mut out);
+    let remote = Hello::decode(&mut WireReader::new(out.as_bytes())).unwrap();
+    assert_eq!(negotiate(Hello::local(), remote).unwrap(), PROTOCOL_VERSION);
+
+    let newer = Hello {
+        min_version: MIN_PROTOCOL_VERSION,
+        max_version: PROTOCOL_VERSION + 3,
+    };
+    assert_eq!(negotiate(Hello::local(), newer).unwrap(), PROTOCOL_VERSION);
+}
+
+#[test]
+fn handshake_rejects_disjoint_versions_and_foreign_peers() {
+    let legacy = Hello {
+        min_version: 1,
+        max_version: 1,
+    };
+    assert!(matches!(
+        negotiate(Hello::local(), legacy),
+        Err(WireError::VersionMismatch { remote_max: 1, .. })
+    ));
+
+    let garbage = [0u8; 12];
+    assert!(matches!(
+        Hello::decode(&mut WireReader::new(&garbage)),
+        Err(WireError::BadMagic { found: 0 })
+    ));
+}
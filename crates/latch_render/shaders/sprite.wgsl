@@ -0,0 +1,56 @@
+// Sprite batch shader: one textured, tinted quad per instance
+
+struct Camera {
+    center : vec2<f32>,
+    half_extent : vec2<f32>,
+}
+
+@group(0) @binding(0)
+var<uniform> camera : Camera;
+@group(0) @binding(1)
+var atlas : texture_2d<f32>;
+@group(0) @binding(2)
+var atlas_sampler : sampler;
+
+struct SpriteInput {
+    @location(0) position : vec2<f32>,
+    @location(1) size : vec2<f32>,
+    @location(2) uv_min : vec2<f32>,
+    @location(3) uv_max : vec2<f32>,
+    @location(4) color : vec4<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position : vec4<f32>,
+    @location(0) uv : vec2<f32>,
+    @location(1) color : vec4<f32>,
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index : u32, sprite : SpriteInput) -> VertexOutput {
+    // Two triangles in texture orientation: (0, 0) is the top-left corner.
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(0.0, 0.0),
+    );
+    let corner = corners[vertex_index];
+
+    // World space is +Y up, texture space +V down.
+    let offset = vec2<f32>(corner.x - 0.5, 0.5 - corner.y) * sprite.size;
+    let world = sprite.position + offset;
+
+    var out : VertexOutput;
+    out.clip_position = vec4<f32>((world - camera.center) / camera.half_extent, 0.0, 1.0);
+    out.uv = mix(sprite.uv_min, sprite.uv_max, corner);
+    out.color = sprite.color;
+    return out;
+}
+
+@fragment
+fn fs_main(input : VertexOutput) -> @location(0) vec4<f32> {
+    return textureSample(atlas, atlas_sampler, input.uv) * input.color;
+}
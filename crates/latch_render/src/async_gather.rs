@@ -0,0 +1,118 @@
+//! Instance gather overlapped with main-thread frame work
+//!
+//! Building instance data reads the world and nothing else, while acquiring
+//! the next surface texture mostly waits for the GPU to finish the previous
+//! frame. `AsyncGather` runs the two side by side: the gather is spawned
+//! onto the rayon pool, the caller's work runs inline on the current thread
+//! (so winit/wgpu calls stay on the main thread) and can poll or wait on a
+//! `GatherHandle`. The world is only borrowed for the call, so ticks can
+//! never race a running gather.
+
+use latch_core::ecs::World;
+use std::cell::Cell;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// Reusable instance buffer filled on a rayon worker.
+pub struct AsyncGather<T> {
+    instances: Vec<T>,
+    gather_time: Duration,
+}
+
+impl<T: Send> AsyncGather<T> {
+    pub fn new() -> Self {
+        Self {
+            instances: Vec::new(),
+            gather_time: Duration::ZERO,
+        }
+    }
+
+    /// Clear the buffer and spawn `gather` to refill it from `world` on the
+    /// rayon pool, then run `overlap` on the calling thread.
+    ///
+    /// `overlap` gets a handle to the running gather; it may check
+    /// `is_ready` or `wait` for it. Returns once both are done, with the
+    /// gathered instances and the result of `overlap`.
+    pub fn gather_while<G, O, R>(&mut self, world: &World, gather: G, overlap: O) -> (&[T], R)
+    where
+        G: FnOnce(&World, &mut Vec<T>) + Send,
+        O: FnOnce(&GatherHandle) -> R,
+    {
+        self.instances.clear();
+        let instances = &mut self.instances;
+        let (done, finished) = mpsc::sync_channel(1);
+        let handle = GatherHandle {
+            finished,
+            elapsed: Cell::new(None),
+        };
+        let result = rayon::in_place_scope(|scope| {
+            scope.spawn(move |_| {
+                let start = Instant::now();
+                gather(world, instances);
+                let _ = done.send(start.elapsed());
+            });
+            overlap(&handle)
+        });
+        // The scope has joined the gather; a panic in it propagated above.
+        self.gather_time = handle.wait();
+        (&self.instances, result)
+    }
+
+    /// Instances of the last gather.
+    pub fn instances(&self) -> &[T] {
+        &self.instances
+    }
+
+    /// Time the last `gather` closure took on its worker.
+    pub fn gather_time(&self) -> Duration {
+        self.gather_time
+    }
+}
+
+impl<T: Send> Default for AsyncGather<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Gather spawned by `AsyncGather::gather_while`, as seen from `overlap`.
+pub struct GatherHandle {
+    finished: mpsc::Receiver<Duration>,
+    elapsed: Cell<Option<Duration>>,
+}
+
+impl GatherHandle {
+    /// Whether the gather has finished; never blocks.
+    pub fn is_ready(&self) -> bool {
+        self.poll().is_some()
+    }
+
+    /// Block until the gather finishes, returning how long it took.
+    ///
+    /// On a rayon worker this runs other pool jobs (possibly the gather
+    /// itself) while waiting, so it cannot deadlock a busy pool.
+    pub fn wait(&self) -> Duration {
+        loop {
+            if let Some(elapsed) = self.poll() {
+                return elapsed;
+            }
+            match rayon::yield_now() {
+                // Not a pool thread: the pool picks the gather up on its own.
+                None => {
+                    let elapsed = self.finished.recv().expect("instance gather panicked");
+                    self.elapsed.set(Some(elapsed));
+                    return elapsed;
+                }
+                Some(rayon::Yield::Executed) => {}
+                Some(rayon::Yield::Idle) => std::thread::yield_now(),
+            }
+        }
+    }
+
+    fn poll(&self) -> Option<Duration> {
+        if self.elapsed.get().is_none() {
+            self.elapsed.set(self.finished.try_recv().ok());
+        }
+        self.elapsed.get()
+    }
+}
@@ -0,0 +1,93 @@
+//! Rendering backend abstraction
+//!
+//! Phase 0: wgpu-based prototype
+//! Phase 1: Custom backends for D3D9, GL2.1, software rasterizer
+
+pub mod software;
+
+use crate::{BackendType, DeviceCapabilities};
+use software::SoftwareRasterizer;
+
+/// Result of walking the backend fallback chain.
+pub enum SelectedAdapter {
+    /// Hardware (or driver-provided fallback) adapter from wgpu.
+    Gpu(wgpu::Adapter),
+    /// CPU rasterizer used when wgpu exposes no adapter at all.
+    Software(SoftwareRasterizer),
+}
+
+impl SelectedAdapter {
+    pub fn backend_type(&self) -> BackendType {
+        match self {
+            SelectedAdapter::Gpu(adapter) => backend_type_of(adapter.get_info().backend),
+            SelectedAdapter::Software(_) => BackendType::Software,
+        }
+    }
+
+    pub fn is_software(&self) -> bool {
+        matches!(self, SelectedAdapter::Software(_))
+    }
+}
+
+/// Pick the best available adapter, falling back to the software rasterizer.
+///
+/// Order: preferred GPU adapter → wgpu's own fallback adapter (e.g. WARP,
+/// llvmpipe) → `SoftwareRasterizer` sized to `width` x `height`.
+pub async fn select_adapter(
+    instance: &wgpu::Instance,
+    compatible_surface: Option<&wgpu::Surface<'_>>,
+    width: u32,
+    height: u32,
+) -> SelectedAdapter {
+    for force_fallback_adapter in [false, true] {
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::default(),
+                compatible_surface,
+                force_fallback_adapter,
+            })
+            .await;
+        if let Some(adapter) = adapter {
+            return SelectedAdapter::Gpu(adapter);
+        }
+    }
+
+    tracing::warn!("no GPU adapter available, using software rasterizer");
+    SelectedAdapter::Software(SoftwareRasterizer::new(width, height))
+}
+
+/// Probe available rendering capabilities
+pub fn probe_capabilities() -> DeviceCapabilities {
+    // Placeholder: wgpu will handle backend selection initially
+    DeviceCapabilities {
+        backend: BackendType::Metal, // Will detect automatically
+        max_texture_size: 8192,
+        supports_compute: true,
+        supports_instancing: true,
+        supports_push_constants: false,
+        supports_timestamp_queries: false,
+    }
+}
+
+/// Capabilities reported by the software rasterizer.
+pub fn software_capabilities() -> DeviceCapabilities {
+    DeviceCapabilities {
+        backend: BackendType::Software,
+        max_texture_size: 4096,
+        supports_compute: false,
+        supports_instancing: true,
+        supports_push_constants: false,
+        supports_timestamp_queries: false,
+    }
+}
+
+fn backend_type_of(backend: wgpu::Backend) -> BackendType {
+    match backend {
+        wgpu::Backend::Metal => BackendType::Metal,
+        wgpu::Backend::Dx12 => BackendType::DirectX12,
+        wgpu::Backend::Vulkan => BackendType::Vulkan,
+        wgpu::Backend::Gl => BackendType::OpenGL,
+        wgpu::Backend::BrowserWebGpu => BackendType::WebGL,
+        wgpu::Backend::Empty => BackendType::Software,
+    }
+}
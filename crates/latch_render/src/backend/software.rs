@@ -0,0 +1,140 @@
+//! CPU software rasterizer
+//!
+//! Minimal fallback used when no GPU adapter is available (headless CI,
+//! GPU-less machines). It consumes the same NDC vertex positions and
+//! per-instance offsets/colors the GPU pipelines use and writes RGBA8
+//! pixels into a CPU-side framebuffer. Correctness over speed: no SIMD,
+//! no tiling, no depth buffer.
+
+/// Per-instance data understood by the software rasterizer.
+///
+/// `offset` is added to every vertex of the instance (NDC units), matching
+/// how the instanced GPU shaders translate the base mesh.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SoftwareInstance {
+    pub offset: [f32; 2],
+    pub color: [u8; 4],
+}
+
+/// CPU framebuffer with triangle fill support.
+pub struct SoftwareRasterizer {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl SoftwareRasterizer {
+    const BYTES_PER_PIXEL: usize = 4;
+
+    pub fn new(width: u32, height: u32) -> Self {
+        let width = width.max(1);
+        let height = height.max(1);
+        Self {
+            width,
+            height,
+            pixels: vec![0; width as usize * height as usize * Self::BYTES_PER_PIXEL],
+        }
+    }
+
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Resize the framebuffer, discarding its contents.
+    pub fn resize(&mut self, width: u32, height: u32) {
+        *self = Self::new(width, height);
+    }
+
+    /// Fill the whole framebuffer with a single RGBA color.
+    pub fn clear(&mut self, color: [u8; 4]) {
+        for pixel in self.pixels.chunks_exact_mut(Self::BYTES_PER_PIXEL) {
+            pixel.copy_from_slice(&color);
+        }
+    }
+
+    /// Rasterize a single filled triangle given in NDC (-1..1, +Y up).
+    ///
+    /// Both windings are filled, mirroring the `cull_mode: None` pipelines.
+    pub fn draw_triangle(&mut self, v0: [f32; 2], v1: [f32; 2], v2: [f32; 2], color: [u8; 4]) {
+        let p0 = self.ndc_to_pixel(v0);
+        let p1 = self.ndc_to_pixel(v1);
+        let p2 = self.ndc_to_pixel(v2);
+
+        let area = edge(p0, p1, p2);
+        if area == 0.0 || !area.is_finite() {
+            return;
+        }
+
+        let min_x = p0[0].min(p1[0]).min(p2[0]).floor().max(0.0) as u32;
+        let min_y = p0[1].min(p1[1]).min(p2[1]).floor().max(0.0) as u32;
+        let max_x = (p0[0].max(p1[0]).max(p2[0]).ceil() as i64).clamp(0, self.width as i64) as u32;
+        let max_y =
+            (p0[1].max(p1[1]).max(p2[1]).ceil() as i64).clamp(0, self.height as i64) as u32;
+
+        for y in min_y..max_y {
+            for x in min_x..max_x {
+                let p = [x as f32 + 0.5, y as f32 + 0.5];
+                let w0 = edge(p1, p2, p);
+                let w1 = edge(p2, p0, p);
+                let w2 = edge(p0, p1, p);
+                let inside = if area > 0.0 {
+                    w0 >= 0.0 && w1 >= 0.0 && w2 >= 0.0
+                } else {
+                    w0 <= 0.0 && w1 <= 0.0 && w2 <= 0.0
+                };
+                if inside {
+                    self.put_pixel(x, y, color);
+                }
+            }
+        }
+    }
+
+    /// Draw a triangle-list mesh once per instance.
+    ///
+    /// `vertices` is interpreted as a triangle list; trailing vertices that do
+    /// not form a full triangle are ignored.
+    pub fn draw_instanced(&mut self, vertices: &[[f32; 2]], instances: &[SoftwareInstance]) {
+        for instance in instances {
+            for tri in vertices.chunks_exact(3) {
+                let [ox, oy] = instance.offset;
+                self.draw_triangle(
+                    [tri[0][0] + ox, tri[0][1] + oy],
+                    [tri[1][0] + ox, tri[1][1] + oy],
+                    [tri[2][0] + ox, tri[2][1] + oy],
+                    instance.color,
+                );
+            }
+        }
+    }
+
+    /// Borrow the framebuffer as tightly packed RGBA8 rows, top row first.
+    #[inline]
+    pub fn read_pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    #[inline]
+    fn ndc_to_pixel(&self, ndc: [f32; 2]) -> [f32; 2] {
+        [
+            (ndc[0] * 0.5 + 0.5) * self.width as f32,
+            (1.0 - (ndc[1] * 0.5 + 0.5)) * self.height as f32,
+        ]
+    }
+
+    #[inline]
+    fn put_pixel(&mut self, x: u32, y: u32, color: [u8; 4]) {
+        let offset = (y as usize * self.width as usize + x as usize) * Self::BYTES_PER_PIXEL;
+        self.pixels[offset..offset + Self::BYTES_PER_PIXEL].copy_from_slice(&color);
+    }
+}
+
+#[inline]
+fn edge(a: [f32; 2], b: [f32; 2], p: [f32; 2]) -> f32 {
+    (b[0] - a[0]) * (p[1] - a[1]) - (b[1] - a[1]) * (p[0] - a[0])
+}
@@ -0,0 +1,43 @@
+//! 2D camera
+//!
+//! Describes the visible world-space region in integer game units so the
+//! instance-gather path can cull off-screen rows before upload.
+
+use latch_core::ecs::CullBounds;
+
+/// Orthographic 2D camera centred on `center`, seeing `half_extent` units
+/// in each direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Camera2D {
+    pub center: [i32; 2],
+    pub half_extent: [i32; 2],
+}
+
+impl Camera2D {
+    pub const fn new(center: [i32; 2], half_extent: [i32; 2]) -> Self {
+        Self {
+            center,
+            half_extent,
+        }
+    }
+
+    /// World-space AABB covered by the camera.
+    pub fn visible_bounds(&self) -> CullBounds {
+        CullBounds::new(
+            [
+                self.center[0].saturating_sub(self.half_extent[0]),
+                self.center[1].saturating_sub(self.half_extent[1]),
+            ],
+            [
+                self.center[0].saturating_add(self.half_extent[0]),
+                self.center[1].saturating_add(self.half_extent[1]),
+            ],
+        )
+    }
+
+    /// Visible AABB grown by `margin` units, typically the largest sprite
+    /// radius so partially visible instances are kept.
+    pub fn cull_bounds(&self, margin: i32) -> CullBounds {
+        self.visible_bounds().expanded(margin)
+    }
+}
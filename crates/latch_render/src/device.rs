@@ -0,0 +1,164 @@
+//! Device creation with features and limits negotiated up front
+//!
+//! Requesting `Features::empty()` and `Limits::default()` and hoping for the
+//! best turns a missing capability into a validation error deep inside the
+//! first pipeline or texture that needs it. Applications instead declare
+//! what they need; `request_device` checks the adapter against it and fails
+//! at init with the exact feature or limit that is missing.
+
+use crate::DeviceCapabilities;
+use thiserror::Error;
+
+/// Push-constant budget requested when `supports_push_constants` is set
+/// (the minimum every supporting backend guarantees).
+const PUSH_CONSTANT_BYTES: u32 = 128;
+
+#[derive(Debug, Error)]
+pub enum DeviceRequestError {
+    #[error("adapter '{adapter}' lacks required features: {missing:?}")]
+    MissingFeatures {
+        adapter: String,
+        missing: wgpu::Features,
+    },
+    #[error("adapter '{adapter}' limit {limit} is {supported}, but {requested} is required")]
+    LimitTooLow {
+        adapter: String,
+        limit: &'static str,
+        requested: u64,
+        supported: u64,
+    },
+    #[error("failed to create device: {0}")]
+    RequestDevice(#[from] wgpu::RequestDeviceError),
+}
+
+/// wgpu features needed to honour `caps`.
+pub fn required_features(caps: &DeviceCapabilities) -> wgpu::Features {
+    let mut features = wgpu::Features::empty();
+    if caps.supports_push_constants {
+        features |= wgpu::Features::PUSH_CONSTANTS;
+    }
+    if caps.supports_timestamp_queries {
+        features |= wgpu::Features::TIMESTAMP_QUERY;
+    }
+    features
+}
+
+/// wgpu limits needed to honour `caps`.
+///
+/// Starts from the WebGL2 downlevel limits when compute is not needed, so
+/// such apps still run on GL-only adapters.
+pub fn required_limits(caps: &DeviceCapabilities) -> wgpu::Limits {
+    let mut limits = if caps.supports_compute {
+        wgpu::Limits::default()
+    } else {
+        wgpu::Limits::downlevel_webgl2_defaults()
+    };
+    limits.max_texture_dimension_1d = caps.max_texture_size;
+    limits.max_texture_dimension_2d = caps.max_texture_size;
+    if caps.supports_push_constants {
+        limits.max_push_constant_size = PUSH_CONSTANT_BYTES;
+    }
+    limits
+}
+
+/// What an application needs from the device.
+#[derive(Debug, Clone)]
+pub struct DeviceRequirements {
+    pub label: Option<&'static str>,
+    /// Must be supported, or `request_device` fails.
+    pub features: wgpu::Features,
+    /// Enabled when the adapter has them (e.g. GPU timing); check
+    /// `device.features()` afterwards.
+    pub optional_features: wgpu::Features,
+    pub limits: wgpu::Limits,
+}
+
+impl DeviceRequirements {
+    /// Requirements implied by the capabilities an app declares it needs.
+    pub fn from_capabilities(caps: &DeviceCapabilities) -> Self {
+        Self {
+            label: None,
+            features: required_features(caps),
+            optional_features: wgpu::Features::empty(),
+            limits: required_limits(caps),
+        }
+    }
+
+    pub fn with_label(mut self, label: &'static str) -> Self {
+        self.label = Some(label);
+        self
+    }
+
+    pub fn with_optional_features(mut self, features: wgpu::Features) -> Self {
+        self.optional_features |= features;
+        self
+    }
+
+    /// Check `adapter` against these requirements without creating a device.
+    pub fn check(&self, adapter: &wgpu::Adapter) -> Result<(), DeviceRequestError> {
+        let name = || adapter.get_info().name;
+
+        let missing = self.features - adapter.features();
+        if !missing.is_empty() {
+            return Err(DeviceRequestError::MissingFeatures {
+                adapter: name(),
+                missing,
+            });
+        }
+
+        let mut too_low = None;
+        self.limits.check_limits_with_fail_fn(
+            &adapter.limits(),
+            true,
+            |limit, requested, supported| {
+                too_low.get_or_insert((limit, requested, supported));
+            },
+        );
+        if let Some((limit, requested, supported)) = too_low {
+            return Err(DeviceRequestError::LimitTooLow {
+                adapter: name(),
+                limit,
+                requested,
+                supported,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl Default for DeviceRequirements {
+    /// No extra features, default limits.
+    fn default() -> Self {
+        Self {
+            label: None,
+            features: wgpu::Features::empty(),
+            optional_features: wgpu::Features::empty(),
+            limits: wgpu::Limits::default(),
+        }
+    }
+}
+
+/// Create a device after checking `adapter` against `requirements`.
+///
+/// Required features and limits that the adapter lacks are reported as
+/// `DeviceRequestError` before any device exists; optional features are
+/// enabled where available.
+pub async fn request_device(
+    adapter: &wgpu::Adapter,
+    requirements: &DeviceRequirements,
+) -> Result<(wgpu::Device, wgpu::Queue), DeviceRequestError> {
+    requirements.check(adapter)?;
+    let features = requirements.features | (requirements.optional_features & adapter.features());
+    let (device, queue) = adapter
+        .request_device(
+            &wgpu::DeviceDescriptor {
+                label: requirements.label,
+                required_features: features,
+                required_limits: requirements.limits.clone(),
+                memory_hints: wgpu::MemoryHints::default(),
+            },
+            None,
+        )
+        .await?;
+    Ok((device, queue))
+}
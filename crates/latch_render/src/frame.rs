@@ -0,0 +1,187 @@
+//! Per-frame surface acquisition and render pass setup
+//!
+//! A `Frame` wraps one swapchain image: it acquires the surface texture,
+//! owns the command encoder, and on drop submits the encoder and presents.
+//! Recoverable surface errors (lost or outdated after a resize, timeouts)
+//! are handled by `recover_surface` and surface as `Ok(None)`, meaning
+//! "skip this frame"; only unrecoverable ones reach the caller.
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum FrameError {
+    /// The surface cannot be recovered (e.g. out of memory); stop rendering.
+    #[error("failed to acquire surface texture: {0}")]
+    Surface(#[from] wgpu::SurfaceError),
+}
+
+/// Handle a failed `get_current_texture`.
+///
+/// Lost and outdated surfaces are reconfigured with `config`, and timeouts
+/// are ignored; in both cases the frame should be skipped and the next one
+/// will acquire normally. Anything else is returned as an error.
+pub fn recover_surface(
+    surface: &wgpu::Surface<'_>,
+    device: &wgpu::Device,
+    config: &wgpu::SurfaceConfiguration,
+    error: wgpu::SurfaceError,
+) -> Result<(), FrameError> {
+    match error {
+        wgpu::SurfaceError::Lost | wgpu::SurfaceError::Outdated => {
+            surface.configure(device, config);
+            Ok(())
+        }
+        wgpu::SurfaceError::Timeout => Ok(()),
+        error => Err(FrameError::Surface(error)),
+    }
+}
+
+/// How `Frame::begin_pass_with` sets up its render pass.
+#[derive(Debug, Clone, Default)]
+pub struct PassConfig<'a> {
+    pub label: Option<&'a str>,
+    /// Color the frame is cleared to; `None` keeps what earlier passes drew.
+    pub clear_color: Option<wgpu::Color>,
+    /// Depth buffer matching the surface size, cleared to `1.0`.
+    pub depth: Option<&'a wgpu::TextureView>,
+    pub timestamp_writes: Option<wgpu::RenderPassTimestampWrites<'a>>,
+}
+
+impl<'a> PassConfig<'a> {
+    /// Pass clearing the frame to `color`.
+    pub fn clear(color: wgpu::Color) -> Self {
+        Self {
+            clear_color: Some(color),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_label(mut self, label: &'a str) -> Self {
+        self.label = Some(label);
+        self
+    }
+
+    pub fn with_depth(mut self, depth: &'a wgpu::TextureView) -> Self {
+        self.depth = Some(depth);
+        self
+    }
+}
+
+/// One acquired surface texture and the commands recorded for it.
+///
+/// Dropping the frame submits the encoder and presents the texture, so let
+/// every `RenderPass` end before the frame goes out of scope.
+pub struct Frame<'a> {
+    queue: &'a wgpu::Queue,
+    encoder: Option<wgpu::CommandEncoder>,
+    texture: Option<wgpu::SurfaceTexture>,
+    view: wgpu::TextureView,
+    clear_color: wgpu::Color,
+}
+
+impl<'a> Frame<'a> {
+    /// Acquire the next texture of `surface`.
+    ///
+    /// Returns `Ok(None)` when the frame should be skipped; see
+    /// `recover_surface`. `clear_color` is used by `begin_pass`.
+    pub fn acquire(
+        surface: &wgpu::Surface<'_>,
+        device: &wgpu::Device,
+        queue: &'a wgpu::Queue,
+        config: &wgpu::SurfaceConfiguration,
+        clear_color: wgpu::Color,
+    ) -> Result<Option<Self>, FrameError> {
+        let texture = match surface.get_current_texture() {
+            Ok(texture) => texture,
+            Err(error) => {
+                recover_surface(surface, device, config, error)?;
+                return Ok(None);
+            }
+        };
+        let view = texture
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+        let encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Frame Encoder"),
+        });
+        Ok(Some(Self {
+            queue,
+            encoder: Some(encoder),
+            texture: Some(texture),
+            view,
+            clear_color,
+        }))
+    }
+
+    /// View of the surface texture.
+    #[inline]
+    pub fn view(&self) -> &wgpu::TextureView {
+        &self.view
+    }
+
+    #[inline]
+    pub fn clear_color(&self) -> wgpu::Color {
+        self.clear_color
+    }
+
+    /// Encoder submitted with the frame, for copies, compute passes or
+    /// query resolves outside a render pass.
+    pub fn encoder(&mut self) -> &mut wgpu::CommandEncoder {
+        self.encoder
+            .as_mut()
+            .expect("encoder is only taken on drop")
+    }
+
+    /// Render pass clearing to the frame's clear color, without depth.
+    pub fn begin_pass(&mut self) -> wgpu::RenderPass<'_> {
+        let config = PassConfig::clear(self.clear_color);
+        self.begin_pass_with(&config)
+    }
+
+    /// Render pass drawing into the surface texture as `config` describes.
+    pub fn begin_pass_with(&mut self, config: &PassConfig<'_>) -> wgpu::RenderPass<'_> {
+        let load = match config.clear_color {
+            Some(color) => wgpu::LoadOp::Clear(color),
+            None => wgpu::LoadOp::Load,
+        };
+        let encoder = self
+            .encoder
+            .as_mut()
+            .expect("encoder is only taken on drop");
+        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: config.label.or(Some("Frame Pass")),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &self.view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: config.depth.map(|view| {
+                wgpu::RenderPassDepthStencilAttachment {
+                    view,
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(1.0),
+                        store: wgpu::StoreOp::Store,
+                    }),
+                    stencil_ops: None,
+                }
+            }),
+            timestamp_writes: config.timestamp_writes.clone(),
+            occlusion_query_set: None,
+        })
+    }
+}
+
+impl Drop for Frame<'_> {
+    /// Submit the recorded commands and present.
+    fn drop(&mut self) {
+        if let Some(encoder) = self.encoder.take() {
+            self.queue.submit(std::iter::once(encoder.finish()));
+        }
+        if let Some(texture) = self.texture.take() {
+            texture.present();
+        }
+    }
+}
@@ -0,0 +1,170 @@
+//! GPU frame timing
+//!
+//! Wraps a two-entry timestamp query set around a render pass and reads the
+//! result back asynchronously, so the CPU never stalls waiting on the GPU.
+//! Only available when the device was created with `Features::TIMESTAMP_QUERY`.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Bytes needed to resolve the begin/end timestamps.
+const RESOLVE_SIZE: u64 = 2 * wgpu::QUERY_SIZE as u64;
+
+/// Outcome of the in-flight `map_async`, written by its callback.
+const MAP_PENDING: u8 = 0;
+const MAP_DONE: u8 = 1;
+const MAP_FAILED: u8 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// Ready to record a new measurement.
+    Idle,
+    /// Queries resolved into the readback buffer; waiting for `request_readback`.
+    Resolved,
+    /// `map_async` issued; waiting for the GPU to finish.
+    Mapping,
+}
+
+/// Measures GPU execution time of one render pass per frame.
+///
+/// Per frame: pass `timestamp_writes()` to the render pass, call `resolve`
+/// on the same encoder, submit, then `request_readback`. `poll` returns the
+/// elapsed time once the readback completes (typically a frame or two later).
+/// While a readback is in flight, `timestamp_writes` returns `None` and that
+/// frame is simply not measured. A readback whose mapping fails is dropped
+/// (counted by `failed_readbacks`) and the next frame is measured again.
+pub struct GpuTimer {
+    query_set: wgpu::QuerySet,
+    resolve_buffer: wgpu::Buffer,
+    readback_buffer: wgpu::Buffer,
+    /// Nanoseconds per timestamp tick.
+    period: f32,
+    state: State,
+    map_status: Arc<AtomicU8>,
+    failed_readbacks: u64,
+}
+
+impl GpuTimer {
+    /// Create a timer, or `None` if the device lacks timestamp query support.
+    pub fn new(device: &wgpu::Device, queue: &wgpu::Queue) -> Option<Self> {
+        if !device.features().contains(wgpu::Features::TIMESTAMP_QUERY) {
+            return None;
+        }
+
+        let query_set = device.create_query_set(&wgpu::QuerySetDescriptor {
+            label: Some("GPU Timer Queries"),
+            ty: wgpu::QueryType::Timestamp,
+            count: 2,
+        });
+        let resolve_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("GPU Timer Resolve"),
+            size: RESOLVE_SIZE,
+            usage: wgpu::BufferUsages::QUERY_RESOLVE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let readback_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("GPU Timer Readback"),
+            size: RESOLVE_SIZE,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        Some(Self {
+            query_set,
+            resolve_buffer,
+            readback_buffer,
+            period: queue.get_timestamp_period(),
+            state: State::Idle,
+            map_status: Arc::new(AtomicU8::new(MAP_PENDING)),
+            failed_readbacks: 0,
+        })
+    }
+
+    /// Timestamp writes for the measured render pass, or `None` while the
+    /// previous measurement is still being read back.
+    pub fn timestamp_writes(&self) -> Option<wgpu::RenderPassTimestampWrites<'_>> {
+        if self.state != State::Idle {
+            return None;
+        }
+        Some(wgpu::RenderPassTimestampWrites {
+            query_set: &self.query_set,
+            beginning_of_pass_write_index: Some(0),
+            end_of_pass_write_index: Some(1),
+        })
+    }
+
+    /// Resolve this frame's timestamps into the readback buffer.
+    ///
+    /// Must be recorded on the encoder that contains the measured pass, after
+    /// the pass ends. No-op if `timestamp_writes` returned `None` this frame.
+    pub fn resolve(&mut self, encoder: &mut wgpu::CommandEncoder) {
+        if self.state != State::Idle {
+            return;
+        }
+        encoder.resolve_query_set(&self.query_set, 0..2, &self.resolve_buffer, 0);
+        encoder.copy_buffer_to_buffer(
+            &self.resolve_buffer,
+            0,
+            &self.readback_buffer,
+            0,
+            RESOLVE_SIZE,
+        );
+        self.state = State::Resolved;
+    }
+
+    /// Start mapping the readback buffer. Call after submitting the encoder
+    /// passed to `resolve`.
+    pub fn request_readback(&mut self) {
+        if self.state != State::Resolved {
+            return;
+        }
+        self.map_status.store(MAP_PENDING, Ordering::Release);
+        let map_status = Arc::clone(&self.map_status);
+        self.readback_buffer
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, move |result| {
+                let status = if result.is_ok() { MAP_DONE } else { MAP_FAILED };
+                map_status.store(status, Ordering::Release);
+            });
+        self.state = State::Mapping;
+    }
+
+    /// Poll for a completed measurement without blocking.
+    pub fn poll(&mut self, device: &wgpu::Device) -> Option<Duration> {
+        if self.state != State::Mapping {
+            return None;
+        }
+        device.poll(wgpu::Maintain::Poll);
+        match self.map_status.load(Ordering::Acquire) {
+            MAP_DONE => {}
+            MAP_FAILED => {
+                // The buffer was never mapped; drop this measurement and
+                // let the next frame record a new one.
+                self.failed_readbacks += 1;
+                self.state = State::Idle;
+                tracing::warn!("GPU timer readback failed to map; skipping measurement");
+                return None;
+            }
+            _ => return None,
+        }
+
+        let (begin, end) = {
+            let view = self.readback_buffer.slice(..).get_mapped_range();
+            let begin = u64::from_le_bytes(view[0..8].try_into().unwrap());
+            let end = u64::from_le_bytes(view[8..16].try_into().unwrap());
+            (begin, end)
+        };
+        self.readback_buffer.unmap();
+        self.state = State::Idle;
+
+        let ticks = end.saturating_sub(begin);
+        let nanos = (ticks as f64 * self.period as f64) as u64;
+        Some(Duration::from_nanos(nanos))
+    }
+
+    /// Readbacks dropped because mapping the buffer failed.
+    pub fn failed_readbacks(&self) -> u64 {
+        self.failed_readbacks
+    }
+}
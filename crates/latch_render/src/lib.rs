@@ -2,8 +2,31 @@
 //!
 //! Cross-platform rendering with automatic backend selection and fallbacks
 
+pub mod async_gather;
 pub mod backend;
+pub mod camera;
+pub mod device;
+pub mod frame;
+pub mod gpu_timer;
+pub mod quality;
+pub mod sprite_batch;
+pub mod stream_buffer;
+pub mod vertex;
 pub mod window;
+pub mod window_manager;
+
+pub use async_gather::{AsyncGather, GatherHandle};
+pub use camera::Camera2D;
+pub use device::{
+    request_device, required_features, required_limits, DeviceRequestError, DeviceRequirements,
+};
+pub use frame::{recover_surface, Frame, FrameError, PassConfig};
+pub use gpu_timer::GpuTimer;
+pub use quality::{QualityChange, QualityPreset, QualitySettings};
+pub use sprite_batch::{SpriteBatch, SpriteInstance};
+pub use stream_buffer::StreamBuffer;
+pub use vertex::VertexLayout;
+pub use window_manager::{RoutedEvent, SurfaceContext, WindowManager, WindowManagerError};
 
 pub use wgpu;
 pub use winit;
@@ -34,4 +57,6 @@ pub struct DeviceCapabilities {
     pub max_texture_size: u32,
     pub supports_compute: bool,
     pub supports_instancing: bool,
+    pub supports_push_constants: bool,
+    pub supports_timestamp_queries: bool,
 }
@@ -0,0 +1,176 @@
+//! Renderer quality presets
+//!
+//! Players pick a `GraphicsQuality` in the settings menu; `QualityPreset`
+//! turns it into the concrete values the renderer is built with. Apply them
+//! when creating the surface (`WindowConfig::present_mode`) and pipelines
+//! (`QualitySettings::multisample_state`), and on a runtime change use
+//! `QualitySettings::changes_from` to rebuild only what depends on the
+//! values that moved.
+
+use crate::window::PresentModePreference;
+use latch_services::settings::{CustomQuality, GraphicsQuality, GraphicsSettings};
+
+/// Concrete renderer settings behind a preset.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QualitySettings {
+    /// MSAA samples per pixel; 1 disables multisampling.
+    pub msaa_samples: u32,
+    pub present_mode: PresentModePreference,
+    /// LOD bias for texture sampling. wgpu samplers have no bias field, so
+    /// shaders apply it with `textureSampleBias`.
+    pub texture_mip_bias: f32,
+    /// Capacity of per-frame instance buffers.
+    pub max_instances: u32,
+}
+
+impl QualitySettings {
+    /// Multisample state for render pipelines drawing to the main target.
+    pub fn multisample_state(&self) -> wgpu::MultisampleState {
+        wgpu::MultisampleState {
+            count: self.msaa_samples,
+            mask: !0,
+            alpha_to_coverage_enabled: false,
+        }
+    }
+
+    /// Highest sample count the adapter supports for `format` that does not
+    /// exceed `msaa_samples`. Use the result for both pipelines and the
+    /// multisampled color target.
+    pub fn supported_msaa_samples(
+        &self,
+        adapter: &wgpu::Adapter,
+        format: wgpu::TextureFormat,
+    ) -> u32 {
+        let flags = adapter.get_texture_format_features(format).flags;
+        [16, 8, 4, 2]
+            .into_iter()
+            .find(|&count| count <= self.msaa_samples && flags.sample_count_supported(count))
+            .unwrap_or(1)
+    }
+
+    /// Clamp `msaa_samples` to what the adapter supports for `format`.
+    pub fn for_adapter(mut self, adapter: &wgpu::Adapter, format: wgpu::TextureFormat) -> Self {
+        self.msaa_samples = self.supported_msaa_samples(adapter, format);
+        self
+    }
+
+    /// What has to be rebuilt when switching from `previous` to `self`.
+    pub fn changes_from(&self, previous: &QualitySettings) -> QualityChange {
+        QualityChange {
+            reconfigure_surface: self.present_mode != previous.present_mode,
+            rebuild_pipelines: self.msaa_samples != previous.msaa_samples,
+            update_mip_bias: self.texture_mip_bias != previous.texture_mip_bias,
+            resize_instance_buffers: self.max_instances != previous.max_instances,
+        }
+    }
+}
+
+/// Work implied by a quality change; see `QualitySettings::changes_from`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QualityChange {
+    /// Present mode changed: call `WindowManager::set_present_mode`.
+    pub reconfigure_surface: bool,
+    /// Sample count changed: recreate render pipelines and multisampled
+    /// color targets.
+    pub rebuild_pipelines: bool,
+    /// Rewrite the uniform that carries the mip bias to shaders.
+    pub update_mip_bias: bool,
+    /// Reallocate instance buffers for the new capacity.
+    pub resize_instance_buffers: bool,
+}
+
+impl QualityChange {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// User-facing quality knob.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum QualityPreset {
+    Low,
+    #[default]
+    Medium,
+    High,
+    /// Escape hatch exposing every field.
+    Custom(QualitySettings),
+}
+
+impl QualityPreset {
+    /// Fixed presets in ascending order, for menus and the editor.
+    pub const PRESETS: [QualityPreset; 3] = [Self::Low, Self::Medium, Self::High];
+
+    /// Preset selected in the graphics settings.
+    pub fn from_settings(graphics: &GraphicsSettings) -> Self {
+        match graphics.quality {
+            GraphicsQuality::Low => Self::Low,
+            GraphicsQuality::Medium => Self::Medium,
+            GraphicsQuality::High => Self::High,
+            GraphicsQuality::Custom => Self::Custom(custom_settings(&graphics.custom_quality)),
+        }
+    }
+
+    /// Write this preset back into the graphics settings.
+    pub fn store(&self, graphics: &mut GraphicsSettings) {
+        graphics.quality = match self {
+            Self::Low => GraphicsQuality::Low,
+            Self::Medium => GraphicsQuality::Medium,
+            Self::High => GraphicsQuality::High,
+            Self::Custom(settings) => {
+                graphics.custom_quality = CustomQuality {
+                    msaa_samples: settings.msaa_samples,
+                    vsync: settings.present_mode == PresentModePreference::Vsync,
+                    texture_mip_bias: settings.texture_mip_bias,
+                    max_instances: settings.max_instances,
+                };
+                GraphicsQuality::Custom
+            }
+        };
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Low => "Low",
+            Self::Medium => "Medium",
+            Self::High => "High",
+            Self::Custom(_) => "Custom",
+        }
+    }
+
+    pub fn settings(&self) -> QualitySettings {
+        match *self {
+            Self::Low => QualitySettings {
+                msaa_samples: 1,
+                present_mode: PresentModePreference::Vsync,
+                texture_mip_bias: 1.0,
+                max_instances: 100_000,
+            },
+            Self::Medium => QualitySettings {
+                msaa_samples: 2,
+                present_mode: PresentModePreference::Vsync,
+                texture_mip_bias: 0.0,
+                max_instances: 1_000_000,
+            },
+            Self::High => QualitySettings {
+                msaa_samples: 4,
+                present_mode: PresentModePreference::Mailbox,
+                texture_mip_bias: 0.0,
+                max_instances: 5_000_000,
+            },
+            Self::Custom(settings) => settings,
+        }
+    }
+}
+
+fn custom_settings(custom: &CustomQuality) -> QualitySettings {
+    QualitySettings {
+        msaa_samples: custom.msaa_samples.max(1),
+        present_mode: if custom.vsync {
+            PresentModePreference::Vsync
+        } else {
+            PresentModePreference::Mailbox
+        },
+        texture_mip_bias: custom.texture_mip_bias,
+        max_instances: custom.max_instances,
+    }
+}
@@ -0,0 +1,288 @@
+//! Textured quad batches
+//!
+//! Most of a 2D frame is sprites: axis-aligned quads showing a region of a
+//! texture atlas, tinted per instance. `SpriteBatch` owns the pipeline, the
+//! camera and atlas bindings and a ring of instance buffers, and draws any
+//! number of `SpriteInstance`s with one instanced draw. Instances come from
+//! the ECS gather helpers (`ArchetypeStorage::gather_instances2_layered`
+//! and its culled variant), together with the `RenderLayer` of each row so
+//! the batch can draw lower layers first.
+
+use crate::{vertex_layout, Camera2D, StreamBuffer, VertexLayout};
+use latch_core::ecs::{sort_instances_by_layer, RenderLayer};
+use wgpu::util::DeviceExt;
+
+vertex_layout! {
+    /// One sprite: a `size`-unit quad centred on `position` (world units,
+    /// +Y up) showing the atlas region `uv_min..uv_max`, multiplied by
+    /// `color`.
+    #[derive(Copy, Clone, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+    pub struct SpriteInstance {
+        pub position: [f32; 2] = 0 => Float32x2,
+        pub size: [f32; 2] = 1 => Float32x2,
+        /// Texture coordinates of the region's top-left corner.
+        pub uv_min: [f32; 2] = 2 => Float32x2,
+        /// Texture coordinates of the region's bottom-right corner.
+        pub uv_max: [f32; 2] = 3 => Float32x2,
+        /// Straight-alpha RGBA tint.
+        pub color: [u8; 4] = 4 => Unorm8x4,
+    }
+}
+
+impl SpriteInstance {
+    /// Sprite showing the whole atlas, tinted by `color`.
+    pub fn new(position: [f32; 2], size: [f32; 2], color: [u8; 4]) -> Self {
+        Self {
+            position,
+            size,
+            uv_min: [0.0, 0.0],
+            uv_max: [1.0, 1.0],
+            color,
+        }
+    }
+
+    /// Show the atlas region `min..max` (texture coordinates) instead.
+    pub fn with_uv(mut self, min: [f32; 2], max: [f32; 2]) -> Self {
+        self.uv_min = min;
+        self.uv_max = max;
+        self
+    }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct CameraUniform {
+    center: [f32; 2],
+    half_extent: [f32; 2],
+}
+
+impl From<&Camera2D> for CameraUniform {
+    fn from(camera: &Camera2D) -> Self {
+        Self {
+            center: camera.center.map(|c| c as f32),
+            half_extent: camera.half_extent.map(|e| e as f32),
+        }
+    }
+}
+
+/// Pipeline, atlas and instance buffers for drawing sprites.
+///
+/// Per frame: `prepare` (or `prepare_layered`) with the gathered instances
+/// before the render pass, then `draw` inside it. Sprites are alpha blended
+/// in submission order, so later instances cover earlier ones.
+pub struct SpriteBatch {
+    pipeline: wgpu::RenderPipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    bind_group: wgpu::BindGroup,
+    camera_buffer: wgpu::Buffer,
+    instances: StreamBuffer,
+    instance_count: u32,
+}
+
+impl SpriteBatch {
+    const INITIAL_INSTANCES: u64 = 1024;
+
+    /// Batch drawing into `target_format` attachments with `atlas` sampled
+    /// through `sampler`.
+    pub fn new(
+        device: &wgpu::Device,
+        target_format: wgpu::TextureFormat,
+        atlas: &wgpu::TextureView,
+        sampler: &wgpu::Sampler,
+    ) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Sprite Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/sprite.wgsl").into()),
+        });
+
+        let camera_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Sprite Camera"),
+            contents: bytemuck::bytes_of(&CameraUniform::from(&Camera2D::new([0, 0], [1, 1]))),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Sprite Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+        let bind_group =
+            Self::create_bind_group(device, &bind_group_layout, &camera_buffer, atlas, sampler);
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Sprite Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Sprite Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[SpriteInstance::instance_buffer_layout()],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: target_format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                cull_mode: None,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        let instances = StreamBuffer::new(
+            device,
+            "Sprite Instances",
+            wgpu::BufferUsages::VERTEX,
+            Self::INITIAL_INSTANCES * std::mem::size_of::<SpriteInstance>() as u64,
+            StreamBuffer::DEFAULT_RING_SIZE,
+        );
+
+        Self {
+            pipeline,
+            bind_group_layout,
+            bind_group,
+            camera_buffer,
+            instances,
+            instance_count: 0,
+        }
+    }
+
+    /// Sample a different atlas from the next `draw` on.
+    pub fn set_atlas(
+        &mut self,
+        device: &wgpu::Device,
+        atlas: &wgpu::TextureView,
+        sampler: &wgpu::Sampler,
+    ) {
+        self.bind_group = Self::create_bind_group(
+            device,
+            &self.bind_group_layout,
+            &self.camera_buffer,
+            atlas,
+            sampler,
+        );
+    }
+
+    /// Upload `camera` and `instances` for the next `draw`, in the given
+    /// order.
+    ///
+    /// The instance buffers grow to fit; see `StreamBuffer::write`.
+    pub fn prepare(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        camera: &Camera2D,
+        instances: &[SpriteInstance],
+    ) {
+        queue.write_buffer(
+            &self.camera_buffer,
+            0,
+            bytemuck::bytes_of(&CameraUniform::from(camera)),
+        );
+        self.instances
+            .write(device, queue, bytemuck::cast_slice(instances));
+        self.instance_count = instances.len() as u32;
+    }
+
+    /// Like `prepare`, after stable-sorting `instances` by `layers` (one
+    /// per instance, as the layered gather helpers produce them).
+    pub fn prepare_layered(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        camera: &Camera2D,
+        instances: &mut [SpriteInstance],
+        layers: &[RenderLayer],
+    ) {
+        sort_instances_by_layer(instances, layers);
+        self.prepare(device, queue, camera, instances);
+    }
+
+    /// Sprites uploaded by the last `prepare`.
+    pub fn len(&self) -> u32 {
+        self.instance_count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.instance_count == 0
+    }
+
+    /// Draw the prepared sprites into `pass`.
+    pub fn draw(&self, pass: &mut wgpu::RenderPass<'_>) {
+        if self.instance_count == 0 {
+            return;
+        }
+        pass.set_pipeline(&self.pipeline);
+        pass.set_bind_group(0, &self.bind_group, &[]);
+        pass.set_vertex_buffer(0, self.instances.current().slice(..));
+        pass.draw(0..6, 0..self.instance_count);
+    }
+
+    fn create_bind_group(
+        device: &wgpu::Device,
+        layout: &wgpu::BindGroupLayout,
+        camera_buffer: &wgpu::Buffer,
+        atlas: &wgpu::TextureView,
+        sampler: &wgpu::Sampler,
+    ) -> wgpu::BindGroup {
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Sprite Bind Group"),
+            layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: camera_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(atlas),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::Sampler(sampler),
+                },
+            ],
+        })
+    }
+}
@@ -0,0 +1,126 @@
+//! Ring of GPU buffers for data uploaded every frame
+//!
+//! Writing into a buffer that an in-flight frame still reads makes the
+//! driver order the upload after that frame's draw, which stalls the queue
+//! when the CPU runs ahead. `StreamBuffer` rotates among N buffers so each
+//! upload lands in one the GPU has (most likely) finished with, and the draw
+//! binds `current()`.
+
+/// A vertex (or other) buffer written once per update and read by the frames
+/// in between.
+///
+/// Per update: `write` the new contents, then bind `current()` until the
+/// next `write`. With a ring size of N, a write reuses the buffer that was
+/// current N updates ago, so N should exceed the number of frames the GPU
+/// may have queued (`desired_maximum_frame_latency`).
+pub struct StreamBuffer {
+    label: &'static str,
+    usage: wgpu::BufferUsages,
+    buffers: Vec<wgpu::Buffer>,
+    /// Size of every buffer in the ring, in bytes.
+    capacity: u64,
+    current: usize,
+    /// Bytes written by the last `write`.
+    len: u64,
+}
+
+impl StreamBuffer {
+    /// Double buffering plus one buffer of slack for the present queue.
+    pub const DEFAULT_RING_SIZE: usize = 3;
+
+    /// Create `ring_size` buffers of `capacity` bytes; `COPY_DST` is added
+    /// to `usage`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ring_size` is zero.
+    pub fn new(
+        device: &wgpu::Device,
+        label: &'static str,
+        usage: wgpu::BufferUsages,
+        capacity: u64,
+        ring_size: usize,
+    ) -> Self {
+        assert!(
+            ring_size > 0,
+            "stream buffer ring needs at least one buffer"
+        );
+        let usage = usage | wgpu::BufferUsages::COPY_DST;
+        let mut stream = Self {
+            label,
+            usage,
+            buffers: Vec::new(),
+            capacity,
+            current: 0,
+            len: 0,
+        };
+        let buffers = (0..ring_size).map(|_| stream.create(device)).collect();
+        stream.buffers = buffers;
+        stream
+    }
+
+    fn create(&self, device: &wgpu::Device) -> wgpu::Buffer {
+        device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(self.label),
+            size: self.capacity,
+            usage: self.usage,
+            mapped_at_creation: false,
+        })
+    }
+
+    /// Number of buffers in the ring.
+    pub fn ring_size(&self) -> usize {
+        self.buffers.len()
+    }
+
+    /// Size of each buffer in bytes.
+    pub fn capacity(&self) -> u64 {
+        self.capacity
+    }
+
+    /// Bytes written by the last `write`.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Grow every buffer in the ring to at least `capacity` bytes.
+    ///
+    /// Returns whether the buffers were recreated; their previous contents
+    /// are lost, so callers drawing from `current()` must `write` again.
+    pub fn reserve(&mut self, device: &wgpu::Device, capacity: u64) -> bool {
+        if capacity <= self.capacity {
+            return false;
+        }
+        self.capacity = capacity;
+        let buffers = (0..self.buffers.len())
+            .map(|_| self.create(device))
+            .collect();
+        self.buffers = buffers;
+        self.len = 0;
+        true
+    }
+
+    /// Advance to the next buffer in the ring and upload `data` into it.
+    ///
+    /// Grows the ring to the next power of two when `data` does not fit.
+    /// Returns whether the buffers were recreated.
+    pub fn write(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, data: &[u8]) -> bool {
+        let size = data.len() as u64;
+        let grown = self.reserve(device, size.next_power_of_two());
+        self.current = (self.current + 1) % self.buffers.len();
+        if !data.is_empty() {
+            queue.write_buffer(&self.buffers[self.current], 0, data);
+        }
+        self.len = size;
+        grown
+    }
+
+    /// The buffer holding the last `write`.
+    pub fn current(&self) -> &wgpu::Buffer {
+        &self.buffers[self.current]
+    }
+}
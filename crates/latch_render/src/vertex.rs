@@ -0,0 +1,105 @@
+//! Vertex and instance buffer layouts generated from Rust structs
+//!
+//! Hand-written `vertex_attr_array!` lists drift from the `#[repr(C)]`
+//! structs they describe, and a mismatch is silent GPU corruption. The
+//! `vertex_layout!` macro declares the struct and its attribute list
+//! together, computing offsets with `offset_of!` and checking at compile
+//! time that each field is exactly as large as its vertex format.
+
+/// A `#[repr(C)]` struct whose fields map one-to-one onto vertex attributes.
+///
+/// Implemented by `vertex_layout!`; implementing it by hand forfeits the
+/// compile-time size checks.
+pub trait VertexLayout: Copy + 'static {
+    /// One attribute per field, in declaration order.
+    const ATTRIBUTES: &'static [wgpu::VertexAttribute];
+
+    /// Buffer layout stepping by `step_mode`.
+    fn buffer_layout(step_mode: wgpu::VertexStepMode) -> wgpu::VertexBufferLayout<'static> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<Self>() as wgpu::BufferAddress,
+            step_mode,
+            attributes: Self::ATTRIBUTES,
+        }
+    }
+
+    /// Buffer layout advancing once per vertex.
+    fn vertex_buffer_layout() -> wgpu::VertexBufferLayout<'static> {
+        Self::buffer_layout(wgpu::VertexStepMode::Vertex)
+    }
+
+    /// Buffer layout advancing once per instance.
+    fn instance_buffer_layout() -> wgpu::VertexBufferLayout<'static> {
+        Self::buffer_layout(wgpu::VertexStepMode::Instance)
+    }
+}
+
+/// Declare a `#[repr(C)]` vertex/instance struct and implement `VertexLayout`.
+///
+/// Each field names its shader location and `wgpu::VertexFormat` variant.
+/// The format cannot be inferred from the Rust type alone (`[u8; 4]` may be
+/// `Uint8x4` or `Unorm8x4`), but a field whose size differs from its format
+/// fails to compile.
+///
+/// # Example
+/// ```ignore
+/// latch_render::vertex_layout! {
+///     #[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+///     struct InstanceDynamic {
+///         position: [i32; 2] = 1 => Sint32x2,
+///         velocity: [i16; 2] = 2 => Snorm16x2,
+///     }
+/// }
+///
+/// let layout = InstanceDynamic::instance_buffer_layout();
+/// ```
+#[macro_export]
+macro_rules! vertex_layout {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $(
+                $(#[$field_meta:meta])*
+                $field_vis:vis $field:ident : $ty:ty = $location:literal => $format:ident
+            ),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[repr(C)]
+        $vis struct $name {
+            $(
+                $(#[$field_meta])*
+                $field_vis $field: $ty,
+            )+
+        }
+
+        impl $crate::vertex::VertexLayout for $name {
+            const ATTRIBUTES: &'static [$crate::wgpu::VertexAttribute] = &[
+                $(
+                    $crate::wgpu::VertexAttribute {
+                        format: $crate::wgpu::VertexFormat::$format,
+                        offset: ::std::mem::offset_of!($name, $field) as $crate::wgpu::BufferAddress,
+                        shader_location: $location,
+                    },
+                )+
+            ];
+        }
+
+        const _: () = {
+            $(
+                assert!(
+                    ::std::mem::size_of::<$ty>() as u64
+                        == $crate::wgpu::VertexFormat::$format.size(),
+                    concat!(
+                        "field `",
+                        stringify!($name),
+                        "::",
+                        stringify!($field),
+                        "` does not match vertex format ",
+                        stringify!($format)
+                    )
+                );
+            )+
+        };
+    };
+}
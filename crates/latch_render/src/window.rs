@@ -4,15 +4,80 @@
 
 use winit::{
     application::ApplicationHandler,
+    dpi::{LogicalPosition, LogicalSize},
     event::WindowEvent,
     event_loop::{ActiveEventLoop, EventLoop},
-    window::{Window, WindowId},
+    window::{Fullscreen, Window, WindowId},
 };
 
+/// How the window occupies the screen.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FullscreenMode {
+    #[default]
+    Windowed,
+    /// Borderless window covering the current monitor.
+    Borderless,
+}
+
+/// Requested presentation behaviour, resolved against what the surface
+/// actually supports by `select`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PresentModePreference {
+    /// Wait for vertical blank; no tearing, capped to the display rate.
+    #[default]
+    Vsync,
+    /// Present immediately; uncapped, may tear.
+    Immediate,
+    /// Replace the queued frame; uncapped without tearing.
+    Mailbox,
+}
+
+impl PresentModePreference {
+    /// Pick the closest supported `wgpu::PresentMode`.
+    ///
+    /// Uncapped preferences fall back to each other before settling on
+    /// `Fifo` (e.g. macOS offers no `Mailbox`), which every surface supports.
+    pub fn select(self, supported: &[wgpu::PresentMode]) -> wgpu::PresentMode {
+        use wgpu::PresentMode;
+        let candidates: &[PresentMode] = match self {
+            Self::Vsync => &[PresentMode::Fifo],
+            Self::Immediate => &[PresentMode::Immediate, PresentMode::Mailbox],
+            Self::Mailbox => &[PresentMode::Mailbox, PresentMode::Immediate],
+        };
+        candidates
+            .iter()
+            .copied()
+            .find(|mode| supported.contains(mode))
+            .unwrap_or(PresentMode::Fifo)
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct WindowConfig {
     pub title: String,
     pub width: u32,
     pub height: u32,
+    pub fullscreen: FullscreenMode,
+    pub resizable: bool,
+    pub present_mode: PresentModePreference,
+    /// Minimum inner size in logical pixels.
+    pub min_size: Option<(u32, u32)>,
+    /// Maximum inner size in logical pixels.
+    pub max_size: Option<(u32, u32)>,
+    /// Initial outer position in logical pixels; platform default if `None`.
+    pub position: Option<(i32, i32)>,
+    /// Color each frame starts from (`Frame::begin_pass`).
+    pub clear_color: wgpu::Color,
+}
+
+impl WindowConfig {
+    /// Present mode for a surface with the given capabilities.
+    pub fn surface_present_mode(
+        &self,
+        capabilities: &wgpu::SurfaceCapabilities,
+    ) -> wgpu::PresentMode {
+        self.present_mode.select(&capabilities.present_modes)
+    }
 }
 
 impl Default for WindowConfig {
@@ -21,15 +86,36 @@ impl Default for WindowConfig {
             title: "Latch Engine".to_string(),
             width: 1280,
             height: 720,
+            fullscreen: FullscreenMode::Windowed,
+            resizable: true,
+            present_mode: PresentModePreference::Vsync,
+            min_size: None,
+            max_size: None,
+            position: None,
+            clear_color: wgpu::Color::BLACK,
         }
     }
 }
 
 /// Create window attributes from config
 pub fn window_attributes(config: WindowConfig) -> winit::window::WindowAttributes {
-    Window::default_attributes()
+    let mut attrs = Window::default_attributes()
         .with_title(config.title)
-        .with_inner_size(winit::dpi::LogicalSize::new(config.width, config.height))
+        .with_inner_size(LogicalSize::new(config.width, config.height))
+        .with_resizable(config.resizable);
+    if let Some((width, height)) = config.min_size {
+        attrs = attrs.with_min_inner_size(LogicalSize::new(width, height));
+    }
+    if let Some((width, height)) = config.max_size {
+        attrs = attrs.with_max_inner_size(LogicalSize::new(width, height));
+    }
+    if let Some((x, y)) = config.position {
+        attrs = attrs.with_position(LogicalPosition::new(x, y));
+    }
+    if config.fullscreen == FullscreenMode::Borderless {
+        attrs = attrs.with_fullscreen(Some(Fullscreen::Borderless(None)));
+    }
+    attrs
 }
 
 /// Helper to create a window within the event loop (winit 0.30+ API)
@@ -63,11 +149,7 @@ impl SimpleWindowApp {
 impl ApplicationHandler for SimpleWindowApp {
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
         if self.window.is_none() {
-            let attrs = window_attributes(WindowConfig {
-                title: self.config.title.clone(),
-                width: self.config.width,
-                height: self.config.height,
-            });
+            let attrs = window_attributes(self.config.clone());
             self.window = Some(
                 event_loop
                     .create_window(attrs)
@@ -0,0 +1,324 @@
+//! Multiple windows sharing one GPU device
+//!
+//! The editor (main window plus detached panels) and split-screen games need
+//! several surfaces. `WindowManager` owns one `wgpu::Device`/`Queue` and a
+//! `SurfaceContext` per winit window, and routes `window_event` calls to the
+//! right surface by `WindowId`.
+
+use crate::backend::{select_adapter, SelectedAdapter};
+use crate::device::{request_device, DeviceRequestError, DeviceRequirements};
+use crate::frame::{Frame, FrameError};
+use crate::window::{window_attributes, PresentModePreference, WindowConfig};
+use std::collections::HashMap;
+use std::sync::Arc;
+use thiserror::Error;
+use winit::{
+    dpi::PhysicalSize,
+    event::WindowEvent,
+    event_loop::ActiveEventLoop,
+    window::{Window, WindowId},
+};
+
+#[derive(Debug, Error)]
+pub enum WindowManagerError {
+    #[error("failed to create window: {0}")]
+    CreateWindow(#[from] winit::error::OsError),
+    #[error("failed to create surface: {0}")]
+    CreateSurface(#[from] wgpu::CreateSurfaceError),
+    #[error("no GPU adapter can present to the window")]
+    NoAdapter,
+    #[error(transparent)]
+    RequestDevice(#[from] DeviceRequestError),
+    #[error("the shared adapter cannot present to this window's surface")]
+    UnsupportedSurface,
+}
+
+/// A window and the surface presenting into it.
+pub struct SurfaceContext {
+    window: Arc<Window>,
+    surface: wgpu::Surface<'static>,
+    config: wgpu::SurfaceConfiguration,
+}
+
+impl SurfaceContext {
+    #[inline]
+    pub fn window(&self) -> &Arc<Window> {
+        &self.window
+    }
+
+    #[inline]
+    pub fn surface(&self) -> &wgpu::Surface<'static> {
+        &self.surface
+    }
+
+    #[inline]
+    pub fn config(&self) -> &wgpu::SurfaceConfiguration {
+        &self.config
+    }
+
+    #[inline]
+    pub fn format(&self) -> wgpu::TextureFormat {
+        self.config.format
+    }
+
+    /// Reconfigure the surface for a new size (zero sizes are ignored, e.g.
+    /// while minimized).
+    pub fn resize(&mut self, device: &wgpu::Device, size: PhysicalSize<u32>) {
+        if size.width == 0 || size.height == 0 {
+            return;
+        }
+        self.config.width = size.width;
+        self.config.height = size.height;
+        self.surface.configure(device, &self.config);
+    }
+
+    /// Next frame to render into.
+    pub fn acquire(&self) -> Result<wgpu::SurfaceTexture, wgpu::SurfaceError> {
+        self.surface.get_current_texture()
+    }
+}
+
+/// What the caller should do after `WindowManager::handle_window_event`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoutedEvent {
+    /// The manager consumed the event (e.g. resized the surface).
+    Handled,
+    /// The window needs a new frame.
+    Redraw,
+    /// The window was closed and its surface destroyed.
+    Closed,
+    /// Not handled by the manager; forward to application logic.
+    Ignored,
+    /// The id does not belong to a managed window.
+    UnknownWindow,
+}
+
+/// Tracks every open window and its surface on a shared device.
+pub struct WindowManager {
+    instance: wgpu::Instance,
+    adapter: wgpu::Adapter,
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    windows: HashMap<WindowId, SurfaceContext>,
+    configs: HashMap<WindowId, WindowConfig>,
+}
+
+impl WindowManager {
+    /// Open the first window and create the shared device for it.
+    ///
+    /// The adapter is chosen to be compatible with this window's surface;
+    /// windows opened later must be presentable by the same adapter. When
+    /// `select_adapter` falls through to the software rasterizer there is
+    /// nothing to present with and this fails with `NoAdapter`; headless
+    /// callers draw into `SoftwareRasterizer` directly instead.
+    pub async fn new(
+        event_loop: &ActiveEventLoop,
+        config: WindowConfig,
+    ) -> Result<(Self, WindowId), WindowManagerError> {
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
+            backends: wgpu::Backends::all(),
+            ..Default::default()
+        });
+
+        let window = Arc::new(event_loop.create_window(window_attributes(config.clone()))?);
+        let surface = instance.create_surface(Arc::clone(&window))?;
+
+        let size = window.inner_size();
+        let selected = select_adapter(&instance, Some(&surface), size.width, size.height).await;
+        let SelectedAdapter::Gpu(adapter) = selected else {
+            return Err(WindowManagerError::NoAdapter);
+        };
+
+        let requirements = DeviceRequirements::default().with_label("Latch Shared Device");
+        let (device, queue) = request_device(&adapter, &requirements).await?;
+
+        let mut manager = Self {
+            instance,
+            adapter,
+            device,
+            queue,
+            windows: HashMap::new(),
+            configs: HashMap::new(),
+        };
+        let id = manager.insert(window, surface, config);
+        Ok((manager, id))
+    }
+
+    #[inline]
+    pub fn device(&self) -> &wgpu::Device {
+        &self.device
+    }
+
+    #[inline]
+    pub fn queue(&self) -> &wgpu::Queue {
+        &self.queue
+    }
+
+    #[inline]
+    pub fn adapter(&self) -> &wgpu::Adapter {
+        &self.adapter
+    }
+
+    /// Open another window presenting through the shared device.
+    pub fn create_window(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        config: WindowConfig,
+    ) -> Result<WindowId, WindowManagerError> {
+        let window = Arc::new(event_loop.create_window(window_attributes(config.clone()))?);
+        let surface = self.instance.create_surface(Arc::clone(&window))?;
+        if !self.adapter.is_surface_supported(&surface) {
+            return Err(WindowManagerError::UnsupportedSurface);
+        }
+        Ok(self.insert(window, surface, config))
+    }
+
+    /// Close a window and drop its surface. Returns `false` if unknown.
+    pub fn destroy_window(&mut self, id: WindowId) -> bool {
+        self.configs.remove(&id);
+        self.windows.remove(&id).is_some()
+    }
+
+    /// Configuration the window was created with.
+    pub fn window_config(&self, id: WindowId) -> Option<&WindowConfig> {
+        self.configs.get(&id)
+    }
+
+    pub fn get(&self, id: WindowId) -> Option<&SurfaceContext> {
+        self.windows.get(&id)
+    }
+
+    pub fn get_mut(&mut self, id: WindowId) -> Option<&mut SurfaceContext> {
+        self.windows.get_mut(&id)
+    }
+
+    pub fn ids(&self) -> impl Iterator<Item = WindowId> + '_ {
+        self.windows.keys().copied()
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.windows.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.windows.is_empty()
+    }
+
+    pub fn request_redraw_all(&self) {
+        for context in self.windows.values() {
+            context.window.request_redraw();
+        }
+    }
+
+    /// Apply window-level bookkeeping for `event` and tell the caller what
+    /// remains to be done.
+    ///
+    /// Resizes reconfigure that window's surface, close requests destroy it,
+    /// and redraw requests are reported so the caller renders that window.
+    pub fn handle_window_event(&mut self, id: WindowId, event: &WindowEvent) -> RoutedEvent {
+        let Some(context) = self.windows.get_mut(&id) else {
+            return RoutedEvent::UnknownWindow;
+        };
+        match event {
+            WindowEvent::Resized(size) => {
+                context.resize(&self.device, *size);
+                RoutedEvent::Handled
+            }
+            WindowEvent::ScaleFactorChanged { .. } => {
+                let size = context.window.inner_size();
+                context.resize(&self.device, size);
+                RoutedEvent::Handled
+            }
+            WindowEvent::CloseRequested | WindowEvent::Destroyed => {
+                self.destroy_window(id);
+                RoutedEvent::Closed
+            }
+            WindowEvent::RedrawRequested => RoutedEvent::Redraw,
+            _ => RoutedEvent::Ignored,
+        }
+    }
+
+    /// Acquire the next frame of a window, cleared to its configured
+    /// `clear_color` by `Frame::begin_pass`.
+    ///
+    /// Returns `Ok(None)` for unknown windows and for frames that should be
+    /// skipped while the surface recovers.
+    pub fn begin_frame(&self, id: WindowId) -> Result<Option<Frame<'_>>, FrameError> {
+        let (Some(context), Some(config)) = (self.windows.get(&id), self.configs.get(&id)) else {
+            return Ok(None);
+        };
+        Frame::acquire(
+            &context.surface,
+            &self.device,
+            &self.queue,
+            &context.config,
+            config.clear_color,
+        )
+    }
+
+    /// Reconfigure a window's surface after it was lost or outdated.
+    pub fn reconfigure(&mut self, id: WindowId) {
+        if let Some(context) = self.windows.get_mut(&id) {
+            let size = context.window.inner_size();
+            context.resize(&self.device, size);
+        }
+    }
+
+    /// Switch every window to `preference`, reconfiguring surfaces whose
+    /// resolved present mode changes. Called when the quality preset or the
+    /// vsync setting changes at runtime.
+    pub fn set_present_mode(&mut self, preference: PresentModePreference) {
+        for (id, context) in &mut self.windows {
+            if let Some(config) = self.configs.get_mut(id) {
+                config.present_mode = preference;
+            }
+            let caps = context.surface.get_capabilities(&self.adapter);
+            let present_mode = preference.select(&caps.present_modes);
+            if context.config.present_mode != present_mode {
+                context.config.present_mode = present_mode;
+                context.surface.configure(&self.device, &context.config);
+            }
+        }
+    }
+
+    fn insert(
+        &mut self,
+        window: Arc<Window>,
+        surface: wgpu::Surface<'static>,
+        config: WindowConfig,
+    ) -> WindowId {
+        let caps = surface.get_capabilities(&self.adapter);
+        let format = caps
+            .formats
+            .iter()
+            .copied()
+            .find(|format| format.is_srgb())
+            .unwrap_or(caps.formats[0]);
+        let size = window.inner_size();
+        let surface_config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format,
+            width: size.width.max(1),
+            height: size.height.max(1),
+            present_mode: config.surface_present_mode(&caps),
+            alpha_mode: caps.alpha_modes[0],
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        };
+        surface.configure(&self.device, &surface_config);
+
+        let id = window.id();
+        self.windows.insert(
+            id,
+            SurfaceContext {
+                window,
+                surface,
+                config: surface_config,
+            },
+        );
+        self.configs.insert(id, config);
+        id
+    }
+}
@@ -0,0 +1,58 @@
+use latch_core::define_component;
+use latch_core::ecs::World;
+use latch_core::spawn;
+use latch_render::AsyncGather;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Position(i32);
+define_component!(Position, 9310, "AsyncGatherTest::Position");
+
+fn gather_positions(world: &World, out: &mut Vec<i32>) {
+    for archetype in world.archetypes_matching(&[Position::ID]) {
+        out.extend(
+            world
+                .column::<Position>(archetype)
+                .unwrap()
+                .iter()
+                .map(|p| p.0),
+        );
+    }
+}
+
+#[test]
+fn overlap_runs_on_the_calling_thread() {
+    let mut world = World::new();
+    for x in 0..100 {
+        spawn!(world, Position(x));
+    }
+    let caller = std::thread::current().id();
+    let mut gather = AsyncGather::new();
+
+    for _ in 0..2 {
+        let (instances, overlap_thread) = gather.gather_while(&world, gather_positions, |handle| {
+            let thread = std::thread::current().id();
+            handle.wait();
+            assert!(handle.is_ready());
+            thread
+        });
+        assert_eq!(overlap_thread, caller);
+        assert_eq!(instances, (0..100).collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn waiting_on_a_rayon_worker_does_not_deadlock() {
+    let mut world = World::new();
+    spawn!(world, Position(7));
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(1)
+        .build()
+        .unwrap();
+
+    let instances = pool.install(|| {
+        let mut gather = AsyncGather::new();
+        gather.gather_while(&world, gather_positions, |handle| handle.wait());
+        gather.instances().to_vec()
+    });
+    assert_eq!(instances, [7]);
+}
@@ -0,0 +1,69 @@
+use latch_render::backend::software::{SoftwareInstance, SoftwareRasterizer};
+
+const SIZE: u32 = 8;
+const CLEAR: [u8; 4] = [0, 0, 0, 255];
+const RED: [u8; 4] = [255, 0, 0, 255];
+
+fn pixel(rasterizer: &SoftwareRasterizer, x: u32, y: u32) -> [u8; 4] {
+    let start = ((y * rasterizer.width() + x) * 4) as usize;
+    rasterizer.read_pixels()[start..start + 4]
+        .try_into()
+        .unwrap()
+}
+
+#[test]
+fn triangle_fills_pixels_whose_centres_it_covers() {
+    // Lower-left half of the viewport; the hypotenuse runs through the
+    // centres of the diagonal pixels, which count as covered.
+    for winding in [
+        [[-1.0, -1.0], [1.0, -1.0], [-1.0, 1.0]],
+        [[-1.0, 1.0], [1.0, -1.0], [-1.0, -1.0]],
+    ] {
+        let mut rasterizer = SoftwareRasterizer::new(SIZE, SIZE);
+        rasterizer.clear(CLEAR);
+        rasterizer.draw_triangle(winding[0], winding[1], winding[2], RED);
+
+        assert_eq!(rasterizer.read_pixels().len(), (SIZE * SIZE * 4) as usize);
+        for y in 0..SIZE {
+            for x in 0..SIZE {
+                let expected = if y >= x { RED } else { CLEAR };
+                assert_eq!(pixel(&rasterizer, x, y), expected, "pixel ({x}, {y})");
+            }
+        }
+    }
+}
+
+#[test]
+fn instances_offset_the_mesh_in_ndc() {
+    // A quad over the top-left 2x2 pixels, drawn once in place and once
+    // shifted right by half the viewport.
+    let quad = [
+        [-1.0, 1.0],
+        [-0.5, 1.0],
+        [-0.5, 0.5],
+        [-1.0, 1.0],
+        [-0.5, 0.5],
+        [-1.0, 0.5],
+    ];
+    let instances = [
+        SoftwareInstance {
+            offset: [0.0, 0.0],
+            color: RED,
+        },
+        SoftwareInstance {
+            offset: [1.0, 0.0],
+            color: RED,
+        },
+    ];
+    let mut rasterizer = SoftwareRasterizer::new(SIZE, SIZE);
+    rasterizer.clear(CLEAR);
+    rasterizer.draw_instanced(&quad, &instances);
+
+    for y in 0..SIZE {
+        for x in 0..SIZE {
+            let covered = y < 2 && (x < 2 || (4..6).contains(&x));
+            let expected = if covered { RED } else { CLEAR };
+            assert_eq!(pixel(&rasterizer, x, y), expected, "pixel ({x}, {y})");
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use latch_core::ecs::RenderLayer;
+use latch_render::{request_device, Camera2D, DeviceRequirements, SpriteBatch, SpriteInstance};
+
+const SIZE: u32 = 32;
+const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
+const CLEAR: [u8; 4] = [0, 0, 0, 255];
+const RED: [u8; 4] = [255, 0, 0, 255];
+const BLUE: [u8; 4] = [0, 0, 255, 255];
+const WHITE: [u8; 4] = [255, 255, 255, 255];
+
+/// Device on any adapter, including software ones; `None` without one.
+fn headless_device() -> Option<(wgpu::Device, wgpu::Queue)> {
+    let instance = wgpu::Instance::default();
+    pollster::block_on(async {
+        for force_fallback_adapter in [false, true] {
+            let adapter = instance
+                .request_adapter(&wgpu::RequestAdapterOptions {
+                    force_fallback_adapter,
+                    ..Default::default()
+                })
+                .await;
+            if let Some(adapter) = adapter {
+                let requirements = DeviceRequirements {
+                    limits: wgpu::Limits::downlevel_defaults(),
+                    ..Default::default()
+                };
+                return request_device(&adapter, &requirements).await.ok();
+            }
+        }
+        None
+    })
+}
+
+/// 2x1 atlas: a white texel on the left, a red one on the right.
+fn atlas(device: &wgpu::Device, queue: &wgpu::Queue) -> wgpu::TextureView {
+    let size = wgpu::Extent3d {
+        width: 2,
+        height: 1,
+        depth_or_array_layers: 1,
+    };
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Test Atlas"),
+        size,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: FORMAT,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+        view_formats: &[],
+    });
+    queue.write_texture(
+        texture.as_image_copy(),
+        &[WHITE, RED].concat(),
+        wgpu::ImageDataLayout {
+            offset: 0,
+            bytes_per_row: Some(8),
+            rows_per_image: None,
+        },
+        size,
+    );
+    texture.create_view(&wgpu::TextureViewDescriptor::default())
+}
+
+/// Render `sprites` into a `SIZE`² target and read back tight RGBA8 rows.
+fn render(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    sprites: &mut [SpriteInstance],
+    layers: &[RenderLayer],
+) -> Vec<u8> {
+    let sampler = device.create_sampler(&wgpu::SamplerDescriptor::default());
+    let atlas = atlas(device, queue);
+    let mut batch = SpriteBatch::new(device, FORMAT, &atlas, &sampler);
+    // One world unit per pixel, origin at the bottom-left corner.
+    let half = SIZE as i32 / 2;
+    let camera = Camera2D::new([half, half], [half, half]);
+    batch.prepare_layered(device, queue, &camera, sprites, layers);
+
+    let extent = wgpu::Extent3d {
+        width: SIZE,
+        height: SIZE,
+        depth_or_array_layers: 1,
+    };
+    let target = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Golden Target"),
+        size: extent,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: FORMAT,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    });
+    let view = target.create_view(&wgpu::TextureViewDescriptor::default());
+    let padded_row = (SIZE * 4).next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT);
+    let readback = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Golden Readback"),
+        size: (padded_row * SIZE) as u64,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    {
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Golden Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        batch.draw(&mut pass);
+    }
+    encoder.copy_texture_to_buffer(
+        target.as_image_copy(),
+        wgpu::ImageCopyBuffer {
+            buffer: &readback,
+            layout: wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(padded_row),
+                rows_per_image: None,
+            },
+        },
+        extent,
+    );
+    queue.submit(Some(encoder.finish()));
+
+    let slice = readback.slice(..);
+    slice.map_async(wgpu::MapMode::Read, |result| result.expect("map readback"));
+    device.poll(wgpu::Maintain::Wait);
+    let mapped = slice.get_mapped_range();
+    mapped
+        .chunks_exact(padded_row as usize)
+        .flat_map(|row| &row[..(SIZE * 4) as usize])
+        .copied()
+        .collect()
+}
+
+/// Fill pixels `x` by `y` (top row first) of a tight RGBA8 image.
+fn fill(image: &mut [u8], x: std::ops::Range<u32>, y: std::ops::Range<u32>, color: [u8; 4]) {
+    for row in y {
+        for column in x.clone() {
+            let offset = ((row * SIZE + column) * 4) as usize;
+            image[offset..offset + 4].copy_from_slice(&color);
+        }
+    }
+}
+
+#[test]
+fn layered_sprites_match_golden_image() {
+    let Some((device, queue)) = headless_device() else {
+        eprintln!("no wgpu adapter available; skipping sprite batch golden test");
+        return;
+    };
+
+    // Submitted top sprite first: the layer sort must still draw it last.
+    let mut sprites = [
+        SpriteInstance::new([12.0, 20.0], [8.0, 8.0], BLUE).with_uv([0.0, 0.0], [0.5, 1.0]),
+        SpriteInstance::new([16.0, 16.0], [16.0, 16.0], WHITE).with_uv([0.5, 0.0], [1.0, 1.0]),
+    ];
+    let layers = [RenderLayer(1), RenderLayer(0)];
+    let image = render(&device, &queue, &mut sprites, &layers);
+
+    // World y grows upwards, image rows downwards.
+    let mut golden: Vec<u8> = CLEAR.repeat((SIZE * SIZE) as usize);
+    fill(&mut golden, 8..24, 8..24, RED);
+    fill(&mut golden, 8..16, 8..16, BLUE);
+
+    let mismatch = image
+        .chunks_exact(4)
+        .zip(golden.chunks_exact(4))
+        .position(|(got, want)| got != want);
+    if let Some(pixel) = mismatch {
+        let (x, y) = (pixel as u32 % SIZE, pixel as u32 / SIZE);
+        panic!(
+            "pixel ({x}, {y}) is {:?}, golden image has {:?}",
+            &image[pixel * 4..pixel * 4 + 4],
+            &golden[pixel * 4..pixel * 4 + 4]
+        );
+    }
+}
@@ -7,7 +7,10 @@
 //!
 //! Run with: cargo run --example poc1_triangle
 
+use latch_render::backend::software::SoftwareRasterizer;
+use latch_render::backend::{select_adapter, SelectedAdapter};
 use latch_render::window::{create_event_loop, window_attributes, WindowConfig};
+use latch_render::{request_device, DeviceRequirements, Frame};
 use std::sync::Arc;
 use winit::{
     application::ApplicationHandler,
@@ -18,7 +21,7 @@ use winit::{
 
 struct TriangleApp {
     window: Option<Arc<Window>>,
-    renderer: Option<TriangleRenderer>,
+    renderer: Option<Renderer>,
 }
 
 impl TriangleApp {
@@ -38,7 +41,15 @@ impl ApplicationHandler for TriangleApp {
                 title: "PoC 1: Triangle Rendering".to_string(),
                 width: 800,
                 height: 600,
+                clear_color: wgpu::Color {
+                    r: 0.1,
+                    g: 0.2,
+                    b: 0.3,
+                    a: 1.0,
+                },
+                ..Default::default()
             };
+            let clear_color = config.clear_color;
 
             let window = Arc::new(
                 event_loop
@@ -47,11 +58,17 @@ impl ApplicationHandler for TriangleApp {
             );
 
             println!("Initializing renderer...");
-            let renderer = pollster::block_on(TriangleRenderer::new(Arc::clone(&window)));
+            let renderer =
+                pollster::block_on(TriangleRenderer::new(Arc::clone(&window), clear_color));
 
+            match &renderer {
+                Renderer::Gpu(_) => println!("Ready! You should see a colored triangle."),
+                Renderer::Software { .. } => {
+                    println!("No GPU adapter: rasterizing off-screen on the CPU.")
+                }
+            }
             self.window = Some(window);
             self.renderer = Some(renderer);
-            println!("Ready! You should see a colored triangle.");
         }
     }
 
@@ -67,7 +84,7 @@ impl ApplicationHandler for TriangleApp {
                 event_loop.exit();
             }
             WindowEvent::RedrawRequested => {
-                if let Some(renderer) = &self.renderer {
+                if let Some(renderer) = &mut self.renderer {
                     renderer.render();
                 }
             }
@@ -87,6 +104,46 @@ impl ApplicationHandler for TriangleApp {
     }
 }
 
+/// Triangle renderer on the GPU, or on the CPU when there is no adapter.
+enum Renderer {
+    Gpu(TriangleRenderer),
+    /// Nothing is presented; the frame loop still runs end to end.
+    Software {
+        rasterizer: SoftwareRasterizer,
+        clear_color: [u8; 4],
+    },
+}
+
+impl Renderer {
+    /// Same vertices as `TRIANGLE_SHADER`, flat-shaded.
+    const TRIANGLE: [[f32; 2]; 3] = [[0.0, 0.5], [-0.5, -0.5], [0.5, -0.5]];
+
+    fn resize(&mut self, width: u32, height: u32) {
+        match self {
+            Renderer::Gpu(renderer) => renderer.resize(width, height),
+            Renderer::Software { rasterizer, .. } => {
+                if width > 0 && height > 0 {
+                    rasterizer.resize(width, height);
+                }
+            }
+        }
+    }
+
+    fn render(&mut self) {
+        match self {
+            Renderer::Gpu(renderer) => renderer.render(),
+            Renderer::Software {
+                rasterizer,
+                clear_color,
+            } => {
+                rasterizer.clear(*clear_color);
+                let [v0, v1, v2] = Self::TRIANGLE;
+                rasterizer.draw_triangle(v0, v1, v2, [255, 255, 255, 255]);
+            }
+        }
+    }
+}
+
 /// Triangle renderer using wgpu
 struct TriangleRenderer {
     surface: wgpu::Surface<'static>,
@@ -94,10 +151,11 @@ struct TriangleRenderer {
     queue: wgpu::Queue,
     config: wgpu::SurfaceConfiguration,
     render_pipeline: wgpu::RenderPipeline,
+    clear_color: wgpu::Color,
 }
 
 impl TriangleRenderer {
-    async fn new(window: Arc<Window>) -> Self {
+    async fn new(window: Arc<Window>, clear_color: wgpu::Color) -> Renderer {
         let size = window.inner_size();
 
         // Create wgpu instance
@@ -109,31 +167,30 @@ impl TriangleRenderer {
         // Create surface with Arc<Window> for 'static lifetime
         let surface = instance.create_surface(window).unwrap();
 
-        // Request adapter
-        let adapter = instance
-            .request_adapter(&wgpu::RequestAdapterOptions {
-                power_preference: wgpu::PowerPreference::default(),
-                compatible_surface: Some(&surface),
-                force_fallback_adapter: false,
-            })
-            .await
-            .expect("Failed to find suitable GPU adapter");
+        // Request adapter, falling back to the CPU rasterizer
+        let adapter = match select_adapter(&instance, Some(&surface), size.width, size.height).await
+        {
+            SelectedAdapter::Gpu(adapter) => adapter,
+            SelectedAdapter::Software(rasterizer) => {
+                return Renderer::Software {
+                    rasterizer,
+                    clear_color: [
+                        (clear_color.r * 255.0) as u8,
+                        (clear_color.g * 255.0) as u8,
+                        (clear_color.b * 255.0) as u8,
+                        255,
+                    ],
+                };
+            }
+        };
 
         println!("GPU Adapter: {:?}", adapter.get_info());
 
         // Request device and queue
-        let (device, queue) = adapter
-            .request_device(
-                &wgpu::DeviceDescriptor {
-                    label: Some("Main Device"),
-                    required_features: wgpu::Features::empty(),
-                    required_limits: wgpu::Limits::default(),
-                    memory_hints: wgpu::MemoryHints::default(),
-                },
-                None,
-            )
+        let requirements = DeviceRequirements::default().with_label("Main Device");
+        let (device, queue) = request_device(&adapter, &requirements)
             .await
-            .expect("Failed to create device");
+            .unwrap_or_else(|e| panic!("Failed to create device: {e}"));
 
         // Configure surface
         let surface_caps = surface.get_capabilities(&adapter);
@@ -208,13 +265,14 @@ impl TriangleRenderer {
             cache: None,
         });
 
-        Self {
+        Renderer::Gpu(Self {
             surface,
             device,
             queue,
             config,
             render_pipeline,
-        }
+            clear_color,
+        })
     }
 
     fn resize(&mut self, width: u32, height: u32) {
@@ -226,51 +284,24 @@ impl TriangleRenderer {
     }
 
     fn render(&self) {
-        let output = match self.surface.get_current_texture() {
-            Ok(texture) => texture,
+        let mut frame = match Frame::acquire(
+            &self.surface,
+            &self.device,
+            &self.queue,
+            &self.config,
+            self.clear_color,
+        ) {
+            Ok(Some(frame)) => frame,
+            Ok(None) => return,
             Err(e) => {
                 eprintln!("Failed to get surface texture: {:?}", e);
                 return;
             }
         };
 
-        let view = output
-            .texture
-            .create_view(&wgpu::TextureViewDescriptor::default());
-
-        let mut encoder = self
-            .device
-            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                label: Some("Render Encoder"),
-            });
-
-        {
-            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some("Render Pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &view,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color {
-                            r: 0.1,
-                            g: 0.2,
-                            b: 0.3,
-                            a: 1.0,
-                        }),
-                        store: wgpu::StoreOp::Store,
-                    },
-                })],
-                depth_stencil_attachment: None,
-                timestamp_writes: None,
-                occlusion_query_set: None,
-            });
-
-            render_pass.set_pipeline(&self.render_pipeline);
-            render_pass.draw(0..3, 0..1); // Draw 1 triangle (3 vertices)
-        }
-
-        self.queue.submit(std::iter::once(encoder.finish()));
-        output.present();
+        let mut render_pass = frame.begin_pass();
+        render_pass.set_pipeline(&self.render_pipeline);
+        render_pass.draw(0..3, 0..1); // Draw 1 triangle (3 vertices)
     }
 }
 
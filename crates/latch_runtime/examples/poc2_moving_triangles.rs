@@ -12,10 +12,21 @@
 // - Visual confirmation of replay matching original
 
 use latch_core::define_component;
-use latch_core::ecs::{ComponentId, SystemDescriptor, SystemHandle, World};
+use latch_core::ecs::{
+    ComponentId, QueryCache, ReplayVerifier, Schedule, SnapshotCompression, SystemDescriptor, World,
+};
 use latch_core::spawn;
-use latch_core::time::{InputRecorder, SimulationTime, TickInput, TICK_DURATION_SECS};
+use latch_core::time::{
+    ActionId, ActionState, InputRecorder, SimulationTime, TickInput, TICK_DURATION_SECS,
+};
 use latch_metrics::{FrameTimer, SystemProfiler};
+use latch_render::backend::software::{SoftwareInstance, SoftwareRasterizer};
+use latch_render::backend::{select_adapter, SelectedAdapter};
+use latch_render::window::{window_attributes, PresentModePreference, WindowConfig};
+use latch_render::{
+    request_device, vertex_layout, AsyncGather, DeviceRequirements, GpuTimer, StreamBuffer,
+    VertexLayout,
+};
 
 use winit::{
     application::ApplicationHandler,
@@ -36,6 +47,8 @@ use wgpu::util::DeviceExt;
 struct RenderTimings {
     acquire_texture_us: u64,
     build_instances_us: u64,
+    /// Gather time hidden behind the surface acquire.
+    overlap_saved_us: u64,
     upload_instances_us: u64,
     update_uniforms_us: u64,
     encode_commands_us: u64,
@@ -55,6 +68,12 @@ struct RenderTimings {
 
 const UNITS_PER_METER: i32 = 100_000; // 10 micrometers precision
 const UNITS_PER_NDC: i32 = 10 * UNITS_PER_METER; // 1 NDC = 10 meters
+/// Action recorded for the left mouse button.
+const ACTION_PRIMARY: ActionId = ActionId(0);
+
+// Dynamic instance buffers in flight; compare the reported GPU frame time
+// with 1 (single buffer) to see the upload stall this avoids.
+const INSTANCE_RING_SIZE: usize = StreamBuffer::DEFAULT_RING_SIZE;
 
 #[derive(Clone, Copy, Debug)]
 struct Position {
@@ -82,127 +101,64 @@ define_component!(Color, 3, "Color");
 // Systems
 // ============================================================================
 
-#[derive(Clone, Copy)]
-struct TilePair {
-    len: usize,
-    pos_read: *const Position,
-    pos_write: *mut Position,
-    vel_read: *const Velocity,
-    vel_write: *mut Velocity,
-}
-
-// SAFETY: Each tile covers a disjoint range of rows, so sharing the raw pointers across
-// threads is safe as long as every tile is processed exactly once.
-unsafe impl Send for TilePair {}
-unsafe impl Sync for TilePair {}
-
 struct PhysicsSystem {
-    handle: SystemHandle,
     component_filter: Vec<ComponentId>,
-    tiles: Vec<TilePair>,
 }
 
 impl PhysicsSystem {
-    fn new(world: &mut World) -> Self {
-        let descriptor = SystemDescriptor::new("physics")
+    fn descriptor() -> SystemDescriptor {
+        SystemDescriptor::new("physics")
             .reads([Position::ID, Velocity::ID])
-            .writes([Position::ID, Velocity::ID]);
-
-        let component_filter = descriptor.all_components().to_vec();
-        let handle = world
-            .register_system(descriptor)
-            .expect("failed to register physics system");
+            .writes([Position::ID, Velocity::ID])
+    }
 
+    fn new() -> Self {
         Self {
-            handle,
-            component_filter,
-            tiles: Vec::new(),
+            component_filter: Self::descriptor().all_components().to_vec(),
         }
     }
 
     fn run(&mut self, world: &mut World, _dt: f32) {
-        use rayon::prelude::*;
-        use std::slice;
-
-        if cfg!(debug_assertions) {
-            debug_assert!(world.system_descriptor(self.handle).is_some());
-        }
-
+        // One huge archetype: split the work by page rather than by archetype.
         world.for_each(&self.component_filter, |storage| {
-            let (pos_col, vel_col) = storage
-                .columns_mut_pair(Position::ID, Velocity::ID)
-                .expect("archetype missing position/velocity columns");
-
-            debug_assert_eq!(pos_col.page_count(), vel_col.page_count());
-
-            self.tiles.clear();
-            self.tiles.reserve(pos_col.page_count());
-
-            for page_idx in 0..pos_col.page_count() {
-                let range = pos_col.page_range(page_idx);
-                if range.is_empty() {
-                    continue;
-                }
-
-                let start = range.start;
-                let end = range.end;
-
-                let (pos_read, pos_write) = pos_col
-                    .slice_rw_typed::<Position>(start..end)
-                    .expect("position tile slice");
-                let (vel_read, vel_write) = vel_col
-                    .slice_rw_typed::<Velocity>(start..end)
-                    .expect("velocity tile slice");
-
-                debug_assert_eq!(pos_read.len(), vel_read.len());
-
-                self.tiles.push(TilePair {
-                    len: pos_read.len(),
-                    pos_read: pos_read.as_ptr(),
-                    pos_write: pos_write.as_mut_ptr(),
-                    vel_read: vel_read.as_ptr(),
-                    vel_write: vel_write.as_mut_ptr(),
-                });
-            }
-
-            self.tiles.par_iter().for_each(|tile| unsafe {
-                let pos_in = slice::from_raw_parts(tile.pos_read, tile.len);
-                let pos_out = slice::from_raw_parts_mut(tile.pos_write, tile.len);
-                let vel_in = slice::from_raw_parts(tile.vel_read, tile.len);
-                let vel_out = slice::from_raw_parts_mut(tile.vel_write, tile.len);
-
-                for i in 0..tile.len {
-                    let src_pos = pos_in[i];
-                    let src_vel = vel_in[i];
-                    let dst_pos = &mut pos_out[i];
-                    let dst_vel = &mut vel_out[i];
-
-                    let bound = UNITS_PER_NDC;
-
-                    dst_pos.x = src_pos.x + src_vel.x as i32;
-                    dst_pos.y = src_pos.y + src_vel.y as i32;
-
-                    if dst_pos.x < -bound {
-                        dst_pos.x += ((-bound) - dst_pos.x) * 2;
-                        dst_vel.x = src_vel.x.saturating_neg();
-                    } else if dst_pos.x > bound {
-                        dst_pos.x += (bound - dst_pos.x) * 2;
-                        dst_vel.x = src_vel.x.saturating_neg();
-                    } else {
-                        dst_vel.x = src_vel.x;
-                    }
+            storage
+                .par_pages_mut(&[Position::ID, Velocity::ID], |tile| {
+                    let (pos_in, pos_out) = tile.rw::<Position>().expect("position tile");
+                    let (vel_in, vel_out) = tile.rw::<Velocity>().expect("velocity tile");
+
+                    for i in 0..tile.len() {
+                        let src_pos = pos_in[i];
+                        let src_vel = vel_in[i];
+                        let dst_pos = &mut pos_out[i];
+                        let dst_vel = &mut vel_out[i];
+
+                        let bound = UNITS_PER_NDC;
+
+                        dst_pos.x = src_pos.x + src_vel.x as i32;
+                        dst_pos.y = src_pos.y + src_vel.y as i32;
+
+                        if dst_pos.x < -bound {
+                            dst_pos.x += ((-bound) - dst_pos.x) * 2;
+                            dst_vel.x = src_vel.x.saturating_neg();
+                        } else if dst_pos.x > bound {
+                            dst_pos.x += (bound - dst_pos.x) * 2;
+                            dst_vel.x = src_vel.x.saturating_neg();
+                        } else {
+                            dst_vel.x = src_vel.x;
+                        }
 
-                    if dst_pos.y < -bound {
-                        dst_pos.y += ((-bound) - dst_pos.y) * 2;
-                        dst_vel.y = src_vel.y.saturating_neg();
-                    } else if dst_pos.y > bound {
-                        dst_pos.y += (bound - dst_pos.y) * 2;
-                        dst_vel.y = src_vel.y.saturating_neg();
-                    } else {
-                        dst_vel.y = src_vel.y;
+                        if dst_pos.y < -bound {
+                            dst_pos.y += ((-bound) - dst_pos.y) * 2;
+                            dst_vel.y = src_vel.y.saturating_neg();
+                        } else if dst_pos.y > bound {
+                            dst_pos.y += (bound - dst_pos.y) * 2;
+                            dst_vel.y = src_vel.y.saturating_neg();
+                        } else {
+                            dst_vel.y = src_vel.y;
+                        }
                     }
-                }
-            });
+                })
+                .expect("archetype missing position/velocity columns");
         });
     }
 }
@@ -211,26 +167,29 @@ impl PhysicsSystem {
 // Renderer
 // ============================================================================
 
-#[repr(C)]
-#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
-struct Vertex {
-    position: [f32; 2],
+vertex_layout! {
+    #[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+    struct Vertex {
+        position: [f32; 2] = 0 => Float32x2,
+    }
 }
 
 // Split instance data into STATIC (uploaded once) and DYNAMIC (uploaded every tick)
 
-#[repr(C)]
-#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
-struct InstanceStatic {
-    color: [u8; 4], // 4 bytes - RGB + padding (u8: 0-255)
+vertex_layout! {
+    #[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+    struct InstanceStatic {
+        color: [u8; 4] = 3 => Unorm8x4, // 4 bytes - RGB + padding (normalized to 0.0-1.0)
+    }
 }
 // Total: 4 bytes per instance - uploaded ONCE at startup!
 
-#[repr(C)]
-#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
-struct InstanceDynamic {
-    position: [i32; 2], // 8 bytes - integer game units (10 µm precision)
-    velocity: [i16; 2], // 4 bytes - MUST update when bouncing!
+vertex_layout! {
+    #[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+    struct InstanceDynamic {
+        position: [i32; 2] = 1 => Sint32x2, // 8 bytes - integer game units (10 µm precision)
+        velocity: [i16; 2] = 2 => Snorm16x2, // 4 bytes - MUST update when bouncing!
+    }
 }
 // Total: 12 bytes per instance - uploaded every physics tick (60 Hz)
 // 5M triangles = 60 MB per upload
@@ -244,6 +203,47 @@ struct Uniforms {
     _padding: [f32; 2], // Align to 16 bytes
 }
 
+/// Base triangle (NDC, centred on the origin) drawn once per entity.
+const TRIANGLE: [[f32; 2]; 3] = [[0.0, 0.02], [-0.02, -0.02], [0.02, -0.02]];
+
+/// Entity triangles on the GPU, or on the CPU when there is no adapter.
+enum Renderer {
+    Gpu(TriangleRenderer),
+    /// Nothing is presented, but the simulation and frame loop still run.
+    Software(SoftwareRasterizer),
+}
+
+impl Renderer {
+    fn poll_gpu_time(&mut self) -> Option<std::time::Duration> {
+        match self {
+            Renderer::Gpu(renderer) => renderer.poll_gpu_time(),
+            Renderer::Software(_) => None,
+        }
+    }
+}
+
+/// Rasterize every entity at its tick position (no interpolation).
+fn render_software(rasterizer: &mut SoftwareRasterizer, world: &World) {
+    let mut instances = Vec::new();
+    for archetype in world.archetypes_matching(&[Position::ID, Color::ID]) {
+        if let Some(storage) = world.storage(archetype) {
+            storage
+                .gather_instances2::<Position, Color, _>(&mut instances, |pos, color| {
+                    SoftwareInstance {
+                        offset: [
+                            pos.x as f32 / UNITS_PER_NDC as f32,
+                            pos.y as f32 / UNITS_PER_NDC as f32,
+                        ],
+                        color: [color.r, color.g, color.b, 255],
+                    }
+                })
+                .expect("gather software instances");
+        }
+    }
+    rasterizer.clear([26, 26, 26, 255]);
+    rasterizer.draw_instanced(&TRIANGLE, &instances);
+}
+
 struct TriangleRenderer {
     surface: wgpu::Surface<'static>,
     device: wgpu::Device,
@@ -253,16 +253,19 @@ struct TriangleRenderer {
     pipeline: wgpu::RenderPipeline,
     vertex_buffer: wgpu::Buffer,
     instance_static_buffer: wgpu::Buffer, // Velocity + Color (uploaded ONCE)
-    instance_dynamic_buffer: wgpu::Buffer, // Position (uploaded every tick)
+    instance_dynamic_buffer: StreamBuffer, // Position (uploaded every tick, ring of buffers)
     instance_buffer_capacity: usize,
     last_instance_count: usize, // Track actual instances uploaded
+    instance_gather: AsyncGather<InstanceDynamic>,
+    query_cache: QueryCache,
     uniform_buffer: wgpu::Buffer,
     uniform_bind_group: wgpu::BindGroup,
     last_physics_tick: u64,
+    gpu_timer: Option<GpuTimer>,
 }
 
 impl TriangleRenderer {
-    async fn new(window: Arc<Window>) -> Self {
+    async fn new(window: Arc<Window>) -> Renderer {
         let size = window.inner_size();
 
         let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
@@ -272,27 +275,18 @@ impl TriangleRenderer {
 
         let surface = instance.create_surface(window.clone()).unwrap();
 
-        let adapter = instance
-            .request_adapter(&wgpu::RequestAdapterOptions {
-                power_preference: wgpu::PowerPreference::default(),
-                compatible_surface: Some(&surface),
-                force_fallback_adapter: false,
-            })
-            .await
-            .unwrap();
-
-        let (device, queue) = adapter
-            .request_device(
-                &wgpu::DeviceDescriptor {
-                    label: None,
-                    required_features: wgpu::Features::empty(),
-                    required_limits: wgpu::Limits::default(),
-                    memory_hints: wgpu::MemoryHints::default(),
-                },
-                None,
-            )
+        let selected = select_adapter(&instance, Some(&surface), size.width, size.height).await;
+        let adapter = match selected {
+            SelectedAdapter::Gpu(adapter) => adapter,
+            SelectedAdapter::Software(rasterizer) => return Renderer::Software(rasterizer),
+        };
+
+        // Opt into GPU timing when the adapter supports it.
+        let requirements =
+            DeviceRequirements::default().with_optional_features(wgpu::Features::TIMESTAMP_QUERY);
+        let (device, queue) = request_device(&adapter, &requirements)
             .await
-            .unwrap();
+            .unwrap_or_else(|e| panic!("{e}"));
 
         let surface_caps = surface.get_capabilities(&adapter);
         let surface_format = surface_caps.formats[0];
@@ -302,7 +296,8 @@ impl TriangleRenderer {
             format: surface_format,
             width: size.width,
             height: size.height,
-            present_mode: wgpu::PresentMode::Immediate, // No V-Sync (Mailbox not supported on macOS)
+            // Uncapped for benchmarking; falls back to Mailbox/Fifo where unsupported.
+            present_mode: PresentModePreference::Immediate.select(&surface_caps.present_modes),
             alpha_mode: surface_caps.alpha_modes[0],
             view_formats: vec![],
             desired_maximum_frame_latency: 2,
@@ -365,28 +360,11 @@ impl TriangleRenderer {
                 entry_point: Some("vs_main"),
                 buffers: &[
                     // Vertex buffer (base triangle shape)
-                    wgpu::VertexBufferLayout {
-                        array_stride: std::mem::size_of::<Vertex>() as wgpu::BufferAddress,
-                        step_mode: wgpu::VertexStepMode::Vertex,
-                        attributes: &wgpu::vertex_attr_array![0 => Float32x2],
-                    },
+                    Vertex::vertex_buffer_layout(),
                     // Instance buffer 1: DYNAMIC data (position + velocity - updated every tick)
-                    wgpu::VertexBufferLayout {
-                        array_stride: std::mem::size_of::<InstanceDynamic>() as wgpu::BufferAddress,
-                        step_mode: wgpu::VertexStepMode::Instance,
-                        attributes: &wgpu::vertex_attr_array![
-                            1 => Sint32x2,   // position (i32x2 game units - converted in shader)
-                            2 => Snorm16x2,  // velocity (i16x2 - changes on bounce!)
-                        ],
-                    },
+                    InstanceDynamic::instance_buffer_layout(),
                     // Instance buffer 2: STATIC data (color only - uploaded once)
-                    wgpu::VertexBufferLayout {
-                        array_stride: std::mem::size_of::<InstanceStatic>() as wgpu::BufferAddress,
-                        step_mode: wgpu::VertexStepMode::Instance,
-                        attributes: &wgpu::vertex_attr_array![
-                            3 => Unorm8x4    // color (u8x4 normalized to 0.0-1.0)
-                        ],
-                    },
+                    InstanceStatic::instance_buffer_layout(),
                 ],
                 compilation_options: wgpu::PipelineCompilationOptions::default(),
             },
@@ -420,18 +398,7 @@ impl TriangleRenderer {
         });
 
         // Create static vertex buffer with base triangle shape (centered at origin)
-        let size = 0.02;
-        let triangle_vertices = [
-            Vertex {
-                position: [0.0, size],
-            }, // Top
-            Vertex {
-                position: [-size, -size],
-            }, // Bottom left
-            Vertex {
-                position: [size, -size],
-            }, // Bottom right
-        ];
+        let triangle_vertices = TRIANGLE.map(|position| Vertex { position });
 
         let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("Vertex Buffer"),
@@ -450,15 +417,18 @@ impl TriangleRenderer {
             mapped_at_creation: false,
         });
 
-        // Dynamic buffer: Position (uploaded every tick)
-        let instance_dynamic_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Instance Dynamic Buffer"),
-            size: (std::mem::size_of::<InstanceDynamic>() * initial_capacity) as u64,
-            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
+        // Dynamic buffers: Position (uploaded every tick into the next buffer of the ring)
+        let instance_dynamic_buffer = StreamBuffer::new(
+            &device,
+            "Instance Dynamic Buffer",
+            wgpu::BufferUsages::VERTEX,
+            (std::mem::size_of::<InstanceDynamic>() * initial_capacity) as u64,
+            INSTANCE_RING_SIZE,
+        );
 
-        Self {
+        let gpu_timer = GpuTimer::new(&device, &queue);
+
+        Renderer::Gpu(Self {
             surface,
             device,
             queue,
@@ -469,10 +439,13 @@ impl TriangleRenderer {
             instance_dynamic_buffer,
             instance_buffer_capacity: initial_capacity,
             last_instance_count: 0,
+            instance_gather: AsyncGather::new(),
+            query_cache: QueryCache::new(),
             uniform_buffer,
             uniform_bind_group,
             last_physics_tick: 0,
-        }
+            gpu_timer,
+        })
     }
 
     fn render(
@@ -485,125 +458,75 @@ impl TriangleRenderer {
 
         let instance_count;
         let mut uploaded = false;
+        // Surface texture acquired alongside the instance gather on physics ticks
+        let mut acquired = None;
 
         // Only rebuild and upload DYNAMIC data when physics ticks (not every render frame!)
         if tick != self.last_physics_tick {
             self.last_physics_tick = tick;
             uploaded = true;
 
-            let build_start = std::time::Instant::now();
-
-            // Micro-benchmark: timing each phase
-            let mut bench_reserve_us = 0u64;
-            let mut bench_copy_us = 0u64;
-
             // Build position + velocity (DYNAMIC) and color (STATIC)
-            let mut dynamic_data: Vec<InstanceDynamic> = Vec::new();
             let mut static_data_built = self.last_instance_count > 0;
             let mut static_data: Vec<InstanceStatic> = Vec::new();
 
             // PHASE 1: Query archetypes
             let query_start = std::time::Instant::now();
-            let position_archs = world.archetypes_with(Position::ID);
-            let velocity_archs = world.archetypes_with(Velocity::ID);
-            let color_archs = world.archetypes_with(Color::ID);
+            let archetypes = self
+                .query_cache
+                .matching(world, &[Position::ID, Velocity::ID, Color::ID]);
             let bench_query_us = query_start.elapsed().as_micros() as u64;
 
-            // PHASE 2 & 3: Reserve + Copy loop
-            for &arch_id in position_archs {
-                if !velocity_archs.contains(&arch_id) || !color_archs.contains(&arch_id) {
-                    continue;
-                }
-
-                if let Some(storage) = world.storage(arch_id) {
-                    let positions_col = storage
-                        .column(Position::ID)
-                        .expect("position column missing");
-                    let velocities_col = storage
-                        .column(Velocity::ID)
-                        .expect("velocity column missing");
-                    let colors_col = storage.column(Color::ID).expect("color column missing");
-
-                    debug_assert_eq!(positions_col.page_count(), velocities_col.page_count());
-                    debug_assert_eq!(positions_col.page_count(), colors_col.page_count());
-
-                    let reserve_start = std::time::Instant::now();
-                    let archetype_count = positions_col.len();
-                    dynamic_data.reserve(archetype_count);
-                    if !static_data_built {
-                        static_data.reserve(archetype_count);
-                    }
-                    bench_reserve_us += reserve_start.elapsed().as_micros() as u64;
-
-                    for page_idx in 0..positions_col.page_count() {
-                        let range = positions_col.page_range(page_idx);
-                        if range.is_empty() {
-                            continue;
-                        }
-
-                        let start = range.start;
-                        let end = range.end;
-
-                        let positions = positions_col
-                            .slice_read_typed::<Position>(start..end)
-                            .expect("position tile slice");
-                        let velocities = velocities_col
-                            .slice_read_typed::<Velocity>(start..end)
-                            .expect("velocity tile slice");
-                        let colors = colors_col
-                            .slice_read_typed::<Color>(start..end)
-                            .expect("color tile slice");
-
-                        let count = positions.len();
-
-                        // PHASE 3: Copy data using unsafe direct memory operations
-                        // This eliminates Vec::push bounds checks (5M × 2 = 10M checks!)
-                        let copy_start = std::time::Instant::now();
-
-                        unsafe {
-                            // Get raw pointers to the end of our vectors
-                            let dynamic_ptr = dynamic_data.as_mut_ptr().add(dynamic_data.len());
-                            let dynamic_start_len = dynamic_data.len();
-
-                            // Copy dynamic data (position + velocity)
-                            for i in 0..count {
-                                std::ptr::write(
-                                    dynamic_ptr.add(i),
-                                    InstanceDynamic {
-                                        position: [positions[i].x, positions[i].y],
-                                        velocity: [velocities[i].x, velocities[i].y],
+            // PHASE 2: Gather instance data on a rayon worker while this (main)
+            // thread waits for the GPU to release a surface texture
+            let surface = &self.surface;
+            let overlap_start = std::time::Instant::now();
+            let (gathered, (texture, acquire_time)) = self.instance_gather.gather_while(
+                world,
+                |world, dynamic_data| {
+                    for &arch_id in archetypes {
+                        if let Some(storage) = world.storage(arch_id) {
+                            storage
+                                .gather_instances2::<Position, Velocity, _>(
+                                    dynamic_data,
+                                    |pos, vel| InstanceDynamic {
+                                        position: [pos.x, pos.y],
+                                        velocity: [vel.x, vel.y],
                                     },
-                                );
-                            }
-                            dynamic_data.set_len(dynamic_start_len + count);
+                                )
+                                .expect("gather dynamic instances");
 
                             // Copy static data (color only, first time)
                             if !static_data_built {
-                                let static_ptr = static_data.as_mut_ptr().add(static_data.len());
-                                let static_start_len = static_data.len();
-
-                                for i in 0..count {
-                                    std::ptr::write(
-                                        static_ptr.add(i),
+                                storage
+                                    .gather_instances::<Color, _>(&mut static_data, |color| {
                                         InstanceStatic {
-                                            color: [colors[i].r, colors[i].g, colors[i].b, 0],
-                                        },
-                                    );
-                                }
-                                static_data.set_len(static_start_len + count);
+                                            color: [color.r, color.g, color.b, 0],
+                                        }
+                                    })
+                                    .expect("gather static instances");
                             }
                         }
-
-                        bench_copy_us += copy_start.elapsed().as_micros() as u64;
                     }
-                }
-            }
+                },
+                |_gather| {
+                    let acquire_start = std::time::Instant::now();
+                    (surface.get_current_texture(), acquire_start.elapsed())
+                },
+            );
+            let overlap_us = overlap_start.elapsed().as_micros() as u64;
+            instance_count = gathered.len();
+            acquired = Some(texture);
+
+            let bench_copy_us = self.instance_gather.gather_time().as_micros() as u64;
+            timings.acquire_texture_us = acquire_time.as_micros() as u64;
+            timings.build_instances_us = bench_query_us + bench_copy_us;
+            // Time the main thread would have spent gathering, then acquiring
+            timings.overlap_saved_us =
+                (bench_copy_us + timings.acquire_texture_us).saturating_sub(overlap_us);
 
-            instance_count = dynamic_data.len();
             self.last_instance_count = instance_count;
 
-            timings.build_instances_us = build_start.elapsed().as_micros() as u64;
-
             // Print micro-benchmark results every 60 ticks (~1 second)
             if tick % 60 == 0 {
                 println!(
@@ -615,11 +538,6 @@ impl TriangleRenderer {
                     bench_query_us,
                     (bench_query_us as f64 / timings.build_instances_us as f64) * 100.0
                 );
-                println!(
-                    "  Reserve: {:6} µs ({:5.1}%)",
-                    bench_reserve_us,
-                    (bench_reserve_us as f64 / timings.build_instances_us as f64) * 100.0
-                );
                 println!(
                     "  Copy:    {:6} µs ({:5.1}%)",
                     bench_copy_us,
@@ -641,8 +559,9 @@ impl TriangleRenderer {
                     std::mem::size_of::<InstanceStatic>()
                 );
                 println!(
-                    "  Dynamic: {} KB ({} bytes/instance)",
+                    "  Dynamic: {} KB x {} ({} bytes/instance)",
                     (new_capacity * std::mem::size_of::<InstanceDynamic>()) / 1024,
+                    self.instance_dynamic_buffer.ring_size(),
                     std::mem::size_of::<InstanceDynamic>()
                 );
 
@@ -653,12 +572,10 @@ impl TriangleRenderer {
                     mapped_at_creation: false,
                 });
 
-                self.instance_dynamic_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
-                    label: Some("Instance Dynamic Buffer"),
-                    size: (std::mem::size_of::<InstanceDynamic>() * new_capacity) as u64,
-                    usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
-                    mapped_at_creation: false,
-                });
+                self.instance_dynamic_buffer.reserve(
+                    &self.device,
+                    (std::mem::size_of::<InstanceDynamic>() * new_capacity) as u64,
+                );
 
                 self.instance_buffer_capacity = new_capacity;
                 static_data_built = false; // Force rebuild of static data with new buffer
@@ -676,14 +593,12 @@ impl TriangleRenderer {
                 );
             }
 
-            // Upload DYNAMIC data (every physics tick!)
-            if !dynamic_data.is_empty() {
-                self.queue.write_buffer(
-                    &self.instance_dynamic_buffer,
-                    0,
-                    bytemuck::cast_slice(&dynamic_data),
-                );
-            }
+            // Upload DYNAMIC data (every physics tick!) into a buffer the GPU is done with
+            self.instance_dynamic_buffer.write(
+                &self.device,
+                &self.queue,
+                bytemuck::cast_slice(self.instance_gather.instances()),
+            );
 
             timings.upload_instances_us = upload_start.elapsed().as_micros() as u64;
         } else {
@@ -702,9 +617,15 @@ impl TriangleRenderer {
             .write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
         timings.update_uniforms_us = uniform_start.elapsed().as_micros() as u64;
 
-        let acquire_start = std::time::Instant::now();
-        let output = self.surface.get_current_texture()?;
-        timings.acquire_texture_us = acquire_start.elapsed().as_micros() as u64;
+        let output = match acquired {
+            Some(texture) => texture?,
+            None => {
+                let acquire_start = std::time::Instant::now();
+                let texture = self.surface.get_current_texture()?;
+                timings.acquire_texture_us = acquire_start.elapsed().as_micros() as u64;
+                texture
+            }
+        };
 
         let view = output
             .texture
@@ -735,21 +656,28 @@ impl TriangleRenderer {
                 })],
                 depth_stencil_attachment: None,
                 occlusion_query_set: None,
-                timestamp_writes: None,
+                timestamp_writes: self.gpu_timer.as_ref().and_then(GpuTimer::timestamp_writes),
             });
 
             render_pass.set_pipeline(&self.pipeline);
             render_pass.set_bind_group(0, &self.uniform_bind_group, &[]);
             render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
-            render_pass.set_vertex_buffer(1, self.instance_dynamic_buffer.slice(..)); // Position
+            render_pass.set_vertex_buffer(1, self.instance_dynamic_buffer.current().slice(..)); // Position
             render_pass.set_vertex_buffer(2, self.instance_static_buffer.slice(..)); // Velocity + Color
             render_pass.draw(0..3, 0..(instance_count as u32)); // 3 vertices, N instances
         }
 
+        if let Some(gpu_timer) = &mut self.gpu_timer {
+            gpu_timer.resolve(&mut encoder);
+        }
+
         timings.encode_commands_us = encode_start.elapsed().as_micros() as u64;
 
         let submit_start = std::time::Instant::now();
         self.queue.submit(std::iter::once(encoder.finish()));
+        if let Some(gpu_timer) = &mut self.gpu_timer {
+            gpu_timer.request_readback();
+        }
         timings.submit_us = submit_start.elapsed().as_micros() as u64;
 
         let present_start = std::time::Instant::now();
@@ -758,6 +686,11 @@ impl TriangleRenderer {
 
         Ok((uploaded, instance_count, timings))
     }
+
+    /// Latest completed GPU pass time, if timestamp queries are available.
+    fn poll_gpu_time(&mut self) -> Option<std::time::Duration> {
+        self.gpu_timer.as_mut()?.poll(&self.device)
+    }
 }
 
 // ============================================================================
@@ -766,11 +699,17 @@ impl TriangleRenderer {
 
 struct App {
     window: Option<Arc<Window>>,
-    renderer: Option<TriangleRenderer>,
+    renderer: Option<Renderer>,
     world: World,
-    physics: PhysicsSystem,
+    schedule: Schedule,
     time: SimulationTime,
     recorder: InputRecorder,
+    /// World before the first tick; restored when the replay starts.
+    initial_state: Vec<u8>,
+    /// Per-tick state hashes of the recording, checked during replay.
+    verifier: ReplayVerifier,
+    /// Ticks simulated in the current mode.
+    sim_tick: u64,
     mouse_pos: (f32, f32),
     mouse_pressed: bool,
     mode: Mode,
@@ -786,6 +725,7 @@ struct App {
 enum Mode {
     Recording,
     Replaying,
+    Verified,
 }
 
 impl App {
@@ -828,18 +768,30 @@ impl App {
             spawn!(world, pos, vel, color);
         }
 
-        let physics = PhysicsSystem::new(&mut world);
+        let mut schedule = Schedule::new();
+        let mut physics = PhysicsSystem::new();
+        schedule
+            .add_system(&mut world, PhysicsSystem::descriptor(), move |world, _| {
+                physics.run(world, TICK_DURATION_SECS);
+            })
+            .expect("failed to register physics system");
 
         let mut recorder = InputRecorder::new();
         recorder.start_recording();
+        let initial_state = world
+            .snapshot(SnapshotCompression::None)
+            .expect("failed to snapshot initial world");
 
         Self {
             window: None,
             renderer: None,
             world,
-            physics,
+            schedule,
             time: SimulationTime::new(),
             recorder,
+            initial_state,
+            verifier: ReplayVerifier::new(),
+            sim_tick: 0,
             mouse_pos: (0.0, 0.0),
             mouse_pressed: false,
             mode: Mode::Recording,
@@ -857,34 +809,62 @@ impl App {
         // Begin frame timing
         self.frame_timer.begin();
 
-        // Record input
+        // Record input (mouse position is already in NDC)
+        let mut actions = ActionState {
+            pointer: [self.mouse_pos.0, self.mouse_pos.1],
+            ..Default::default()
+        };
+        actions.set_pressed(ACTION_PRIMARY, self.mouse_pressed);
         let input = TickInput {
             tick: self.time.tick_count(),
             mouse_x: self.mouse_pos.0,
             mouse_y: self.mouse_pos.1,
             mouse_pressed: self.mouse_pressed,
+            actions: Some(actions),
+            tick_duration: self.time.tick_duration(),
         };
         self.recorder.record(input);
 
-        // Run physics (writes to "next" buffer)
+        // Run physics (writes to "next" buffer), then swap so "next" becomes
+        // "current" for the next tick; `World::tick` keeps that order.
         self.profiler.time_system("physics", || {
-            self.physics.run(&mut self.world, TICK_DURATION_SECS);
+            self.world
+                .tick(&mut self.schedule)
+                .expect("simulation tick failed");
         });
 
-        // Swap buffers: make "next" become "current" for the next tick
-        // This ensures deterministic parallel updates
-        self.world.swap_buffers();
+        // Hash the world every tick while recording and compare while
+        // replaying; the first mismatch names the archetype and component.
+        let tick = self.sim_tick;
+        self.sim_tick += 1;
+        match self.mode {
+            Mode::Recording => self.verifier.record(tick, &self.world),
+            Mode::Replaying => match self.verifier.verify(tick, &self.world) {
+                Err(divergence) => {
+                    eprintln!("❌ {divergence}");
+                    self.mode = Mode::Verified;
+                }
+                Ok(()) if self.sim_tick >= 1000 => {
+                    println!("✅ Replay matched the recording for 1000 ticks");
+                    self.mode = Mode::Verified;
+                }
+                Ok(()) => {}
+            },
+            Mode::Verified => {}
+        }
 
         // Check if we've recorded 1000 frames
-        if matches!(self.mode, Mode::Recording) && self.time.tick_count() >= 1000 {
+        if matches!(self.mode, Mode::Recording) && self.sim_tick >= 1000 {
             println!("✅ Recorded 1000 frames. Starting replay...");
             self.recorder.stop_recording();
             self.mode = Mode::Replaying;
 
             // Reset simulation
             self.time.reset();
-
-            // TODO: Save world state to compare after replay
+            self.sim_tick = 0;
+            self.world
+                .restore(&self.initial_state)
+                .expect("failed to restore initial world");
 
             self.recorder.start_playback();
         }
@@ -894,10 +874,16 @@ impl App {
 impl ApplicationHandler for App {
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
         if self.window.is_none() {
-            let window_attrs = Window::default_attributes().with_title("PoC 2: Moving Triangles");
+            let window_attrs = window_attributes(WindowConfig {
+                title: "PoC 2: Moving Triangles".to_string(),
+                ..Default::default()
+            });
             let window = Arc::new(event_loop.create_window(window_attrs).unwrap());
 
             let renderer = pollster::block_on(TriangleRenderer::new(window.clone()));
+            if let Renderer::Software(_) = renderer {
+                println!("No GPU adapter: rasterizing off-screen on the CPU.");
+            }
 
             self.window = Some(window);
             self.renderer = Some(renderer);
@@ -922,7 +908,13 @@ impl ApplicationHandler for App {
                 }
 
                 // Render
-                if let Some(renderer) = &mut self.renderer {
+                if let Some(Renderer::Software(rasterizer)) = &mut self.renderer {
+                    let world = &self.world;
+                    self.profiler
+                        .time_system("render", || render_software(rasterizer, world));
+                }
+                if let Some(Renderer::Gpu(renderer)) = &mut self.renderer {
+                    let _frame = latch_runtime::frame_span(self.render_frame_count).entered();
                     self.profiler.time_system("render", || {
                         match renderer.render(
                             &self.world,
@@ -943,6 +935,7 @@ impl ApplicationHandler for App {
                                     timings.acquire_texture_us;
                                 self.render_timings.build_instances_us +=
                                     timings.build_instances_us;
+                                self.render_timings.overlap_saved_us += timings.overlap_saved_us;
                                 self.render_timings.upload_instances_us +=
                                     timings.upload_instances_us;
                                 self.render_timings.update_uniforms_us +=
@@ -964,6 +957,10 @@ impl ApplicationHandler for App {
                     });
                 }
 
+                if let Some(gpu_time) = self.renderer.as_mut().and_then(|r| r.poll_gpu_time()) {
+                    self.frame_timer.record_gpu_time(gpu_time);
+                }
+
                 // End frame
                 self.frame_timer.end();
 
@@ -981,6 +978,14 @@ impl ApplicationHandler for App {
                     let (min_ms, max_ms) = self.frame_timer.frame_time_range_ms();
                     println!("Frame time range: {:.2}-{:.2} ms", min_ms, max_ms);
 
+                    match self.frame_timer.gpu_frame_time_ms() {
+                        Some(gpu_ms) => println!(
+                            "GPU frame time: {:.2} ms (instance ring: {} buffers)",
+                            gpu_ms, INSTANCE_RING_SIZE
+                        ),
+                        None => println!("GPU frame time: n/a (timestamp queries unsupported)"),
+                    }
+
                     println!(
                         "Entities: {} live, {} total",
                         self.world.live_entity_count(),
@@ -1018,8 +1023,9 @@ impl ApplicationHandler for App {
                             self.render_timings.acquire_texture_us as f64 / frames
                         );
                         println!(
-                            "  build_instances: {:.1}",
-                            self.render_timings.build_instances_us as f64 / frames
+                            "  build_instances: {:.1} (off-thread, {:.1} overlapped with acquire)",
+                            self.render_timings.build_instances_us as f64 / frames,
+                            self.render_timings.overlap_saved_us as f64 / frames
                         );
                         println!(
                             "  upload_instances: {:.1}",
@@ -1084,6 +1090,8 @@ impl ApplicationHandler for App {
 // ============================================================================
 
 fn main() {
+    latch_runtime::init_default_logging();
+
     let event_loop = EventLoop::new().unwrap();
     event_loop.set_control_flow(ControlFlow::Poll);
 
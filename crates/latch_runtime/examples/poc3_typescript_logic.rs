@@ -62,7 +62,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             EntityBuilder::new()
                 .with(Position { x, y })
                 .with(Velocity { x: vx, y: vy }),
-        );
+        )?;
     }
 
     // CRITICAL: Swap buffers so we can read what we just wrote!
@@ -175,15 +175,12 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                         world.column::<Position>(arch_id),
                         world.column::<Velocity>(arch_id),
                     ) {
-                        let count = positions.len();
-                        total_entities += count;
+                        total_entities += positions.len();
 
                         // Pack into flat arrays for JavaScript
-                        for i in 0..count {
-                            all_positions.push(positions[i].x);
-                            all_positions.push(positions[i].y);
-                            all_velocities.push(velocities[i].x);
-                            all_velocities.push(velocities[i].y);
+                        for (position, velocity) in positions.iter().zip(velocities) {
+                            all_positions.extend([position.x, position.y]);
+                            all_velocities.extend([velocity.x, velocity.y]);
                         }
                     }
                 }
@@ -269,16 +266,16 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 continue;
             }
 
-            if let Some(storage) = world.archetype_storage_mut(arch_id) {
+            if let Some(storage) = world.storage_mut(arch_id) {
                 // Use columns_mut! macro to get both slices safely
                 let (positions, velocities) = columns_mut!(storage, Position, Velocity);
 
-                for i in 0..positions.len() {
+                for (position, velocity) in positions.iter_mut().zip(velocities.iter_mut()) {
                     let data_idx = entity_idx * 2;
-                    positions[i].x = modified_positions[data_idx + 0];
-                    positions[i].y = modified_positions[data_idx + 1];
-                    velocities[i].x = modified_velocities[data_idx + 0];
-                    velocities[i].y = modified_velocities[data_idx + 1];
+                    position.x = modified_positions[data_idx];
+                    position.y = modified_positions[data_idx + 1];
+                    velocity.x = modified_velocities[data_idx];
+                    velocity.y = modified_velocities[data_idx + 1];
                     entity_idx += 1;
                 }
             }
@@ -293,6 +290,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     println!("4. Verifying final state...\n");
+    world.debug_assert_swapped();
 
     let position_archs = world.archetypes_with(Position::ID);
     let velocity_archs = world.archetypes_with(Velocity::ID);
@@ -307,10 +305,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             world.column::<Position>(arch_id),
             world.column::<Velocity>(arch_id),
         ) {
-            for i in 0..positions.len().min(5) {
+            for (position, velocity) in positions.iter().zip(velocities).take(5) {
                 println!(
                     "   Entity {}: pos=({}, {}), vel=({}, {})",
-                    entity_count, positions[i].x, positions[i].y, velocities[i].x, velocities[i].y
+                    entity_count, position.x, position.y, velocity.x, velocity.y
                 );
                 entity_count += 1;
             }
@@ -118,10 +118,7 @@ impl SharedMemory {
     /// Write Rust slice into WASM memory at offset
     fn write_slice<T: Copy>(&mut self, store: &mut Store<()>, offset: usize, data: &[T]) {
         let bytes = unsafe {
-            std::slice::from_raw_parts(
-                data.as_ptr() as *const u8,
-                data.len() * std::mem::size_of::<T>(),
-            )
+            std::slice::from_raw_parts(data.as_ptr() as *const u8, std::mem::size_of_val(data))
         };
         self.memory.write(store, offset, bytes).unwrap();
     }
@@ -131,7 +128,7 @@ impl SharedMemory {
         let bytes = unsafe {
             std::slice::from_raw_parts_mut(
                 data.as_mut_ptr() as *mut u8,
-                data.len() * std::mem::size_of::<T>(),
+                std::mem::size_of_val(data),
             )
         };
         self.memory.read(store, offset, bytes).unwrap();
@@ -164,7 +161,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             EntityBuilder::new()
                 .with(Position { x, y })
                 .with(Velocity { x: vx, y: 100 }),
-        );
+        )?;
     }
     println!("   ✓ Spawned 1000 entities\n");
 
@@ -185,9 +182,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     linker.define("env", "memory", shared_mem.memory)?;
 
     // Instantiate
-    let instance = linker
-        .instantiate(&mut store, &module)?
-        .ensure_no_start(&mut store)?;
+    let instance = linker.instantiate_and_start(&mut store, &module)?;
 
     println!("   ✓ WASM module loaded with shared memory\n");
 
@@ -197,16 +192,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let mut entities: Vec<(Position, Velocity)> = Vec::new();
 
     // Find all archetypes with both Position and Velocity
-    let archetypes = world.archetypes_with_all(&[Position::ID, Velocity::ID]);
-    for arch_id in archetypes {
-        if let Some(storage) = world.archetype_storage(arch_id) {
-            if let (Some(positions), Some(velocities)) = (
-                storage.column_as_slice::<Position>(),
-                storage.column_as_slice::<Velocity>(),
+    for arch_id in world.archetypes_matching(&[Position::ID, Velocity::ID]) {
+        if let Some(storage) = world.storage(arch_id) {
+            if let (Ok(positions), Ok(velocities)) = (
+                storage.column_slice::<Position>(),
+                storage.column_slice::<Velocity>(),
             ) {
-                for i in 0..positions.len() {
-                    entities.push((positions[i], velocities[i]));
-                }
+                entities.extend(positions.iter().copied().zip(velocities.iter().copied()));
             }
         }
     }
@@ -250,10 +242,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     println!("4. Verifying results...\n");
 
-    for i in 0..5.min(count) {
+    for (i, position) in positions.iter().take(5).enumerate() {
         println!(
             "   Entity {}: pos=({:.7}, {:.7})",
-            i, positions[i].x, positions[i].y
+            i, position.x, position.y
         );
     }
 
@@ -13,14 +13,22 @@
 // - Query performance scales better than O(n²)
 
 use latch_core::define_component;
-use latch_core::ecs::query::{reset_spatial_hash_metrics, spatial_hash_metrics_snapshot};
 use latch_core::ecs::{
-    ComponentId, EntityId, QueryRegistry, RelationBuffer, RelationType, SpatialHashConfig,
-    SpatialHashGrid, SystemDescriptor, SystemHandle, World,
+    ComponentId, CullStats, EntityId, OverflowPolicy, QueryRegistry, RelationBuffer, RelationType,
+    RenderLayer, SpatialHashConfig, SpatialHashGrid, SystemDescriptor, SystemHandle, World,
 };
+use latch_core::math::{mul_q16, resolve_circle_contact, to_q16};
+use latch_core::memory::FrameArena;
+use latch_core::physics::{CollisionConfig, PhysicsConfig};
 use latch_core::spawn;
 use latch_core::time::SimulationTime;
 use latch_metrics::{FrameTimer, SystemProfiler};
+use latch_render::backend::software::{SoftwareInstance, SoftwareRasterizer};
+use latch_render::backend::{select_adapter, SelectedAdapter};
+use latch_render::window::{window_attributes, PresentModePreference, WindowConfig};
+use latch_render::{
+    request_device, Camera2D, DeviceRequirements, Frame, FrameError, SpriteBatch, SpriteInstance,
+};
 
 use winit::{
     application::ApplicationHandler,
@@ -31,7 +39,6 @@ use winit::{
 
 use std::sync::Arc;
 use wgpu;
-use wgpu::util::DeviceExt;
 
 // ============================================================================
 // Components
@@ -42,13 +49,14 @@ const UNITS_PER_NDC: i32 = 2 * UNITS_PER_METER;
 const PARTICLE_RADIUS: i32 = 1000; // 5 mm radius particles
 const PARTICLE_DIAMETER: i32 = PARTICLE_RADIUS * 2;
 const FLOOR_Y: i32 = -UNITS_PER_NDC / 2; // keep pile within view
+const CAMERA: Camera2D = Camera2D::new([0, 0], [UNITS_PER_NDC, UNITS_PER_NDC]);
 const DEBUG_ENTITY_ID: Option<EntityId> = None;
 const DEBUG_NEIGHBOR_LIMIT: usize = 8;
 const COLLISION_RELATION: RelationType = RelationType::new(1);
-const AXIS_JITTER_EPSILON: f32 = 0.000_1;
-const AXIS_JITTER_PUSH: f32 = 1.0;
-const COLLISION_LINEAR_DAMPING: f32 = 0.96;
-const COLLISION_TANGENT_FRICTION: f32 = 0.2;
+// Equal discs touch at most 6 neighbours; the rest are overlaps in dense piles.
+const MAX_CONTACTS_PER_PARTICLE: usize = 12;
+const AXIS_JITTER_EPSILON_Q16: i32 = 7; // ~0.0001 in Q16
+const AXIS_JITTER_PUSH: i32 = 1;
 
 #[derive(Clone, Copy, Debug)]
 struct Position {
@@ -79,7 +87,6 @@ define_component!(Color, 3, "Color");
 struct MovementSystem {
     #[allow(dead_code)]
     handle: SystemHandle,
-    component_filter: Vec<ComponentId>,
 }
 
 impl MovementSystem {
@@ -88,99 +95,66 @@ impl MovementSystem {
             .reads([Position::ID, Velocity::ID])
             .writes([Position::ID, Velocity::ID]);
 
-        let component_filter = descriptor.all_components().to_vec();
         let handle = world
             .register_system(descriptor)
             .expect("failed to register movement system");
 
-        Self {
-            handle,
-            component_filter,
-        }
+        Self { handle }
     }
 
     fn run(&mut self, world: &mut World) {
-        const GRAVITY: i16 = -50; // Downward acceleration
-
-        world.for_each(&self.component_filter, |storage| {
-            let page_count = {
-                let column = storage
-                    .column(Position::ID)
-                    .expect("position column missing");
-                column.page_count()
-            };
-            for page_idx in 0..page_count {
-                let range = {
-                    let column = storage
-                        .column(Position::ID)
-                        .expect("position column missing");
-                    column.page_range(page_idx)
-                };
-                if range.is_empty() {
-                    continue;
+        let config = *world
+            .resource::<PhysicsConfig>()
+            .expect("PhysicsConfig resource missing");
+
+        world
+            .for_each_rw2::<Position, Velocity>(|_, position, velocity| {
+                let mut pos_x = position.x as f32;
+                let mut pos_y = position.y as f32;
+                let mut vel_x = velocity.x as f32;
+                let mut vel_y_f = velocity.y as f32;
+
+                vel_y_f = (vel_y_f + config.gravity as f32).max(-10_000.0);
+                vel_y_f = vel_y_f.clamp(
+                    -(PARTICLE_DIAMETER - 1) as f32,
+                    (PARTICLE_DIAMETER - 1) as f32,
+                );
+
+                pos_x += vel_x;
+                pos_y += vel_y_f;
+
+                let min_x = (config.bounds_min[0] + PARTICLE_RADIUS) as f32;
+                let max_x = (config.bounds_max[0] - PARTICLE_RADIUS) as f32;
+                let floor = (config.bounds_min[1] + PARTICLE_RADIUS) as f32;
+
+                if pos_x < min_x {
+                    pos_x = min_x;
+                    vel_x = 0.0;
+                } else if pos_x > max_x {
+                    pos_x = max_x;
+                    vel_x = 0.0;
                 }
 
-                let (pos_col, vel_col) = storage
-                    .columns_mut_pair(Position::ID, Velocity::ID)
-                    .expect("archetype missing position/velocity columns");
-
-                let (pos_read, pos_write) = pos_col
-                    .slice_rw_typed::<Position>(range.clone())
-                    .expect("position tile slice");
-                let (vel_read, vel_write) = vel_col
-                    .slice_rw_typed::<Velocity>(range.clone())
-                    .expect("velocity tile slice");
-
-                for i in 0..pos_read.len() {
-                    let src_pos = pos_read[i];
-                    let src_vel = vel_read[i];
-
-                    let mut pos_x = src_pos.x as f32;
-                    let mut pos_y = src_pos.y as f32;
-                    let mut vel_x = src_vel.x as f32;
-                    let mut vel_y_f = src_vel.y as f32;
-
-                    vel_y_f = (vel_y_f + GRAVITY as f32).max(-10_000.0);
-                    vel_y_f = vel_y_f.clamp(
-                        -(PARTICLE_DIAMETER - 1) as f32,
-                        (PARTICLE_DIAMETER - 1) as f32,
-                    );
-
-                    pos_x += vel_x;
-                    pos_y += vel_y_f;
-
-                    let horizontal_bound = (UNITS_PER_NDC - PARTICLE_RADIUS) as f32;
-                    let floor = (FLOOR_Y + PARTICLE_RADIUS) as f32;
-
-                    if pos_x < -horizontal_bound {
-                        pos_x = -horizontal_bound;
-                        vel_x = 0.0;
-                    } else if pos_x > horizontal_bound {
-                        pos_x = horizontal_bound;
-                        vel_x = 0.0;
-                    }
-
-                    if pos_y < floor {
-                        let penetration = floor - pos_y;
-                        pos_y = floor;
-                        if penetration > 0.0 && vel_y_f < 0.0 {
-                            vel_y_f = 0.0;
-                        }
+                if pos_y < floor {
+                    let penetration = floor - pos_y;
+                    pos_y = floor;
+                    if penetration > 0.0 && vel_y_f < 0.0 {
+                        vel_y_f = 0.0;
                     }
+                }
 
-                    let new_x = pos_x.round() as i32;
-                    let new_y = pos_y.round() as i32;
-                    let new_vel_x = vel_x.round() as i32;
-                    let new_vel_y = vel_y_f.round() as i32;
+                let new_x = pos_x.round() as i32;
+                let new_y = pos_y.round() as i32;
+                let new_vel_x = vel_x.round() as i32;
+                let new_vel_y = vel_y_f.round() as i32;
 
-                    pos_write[i] = Position { x: new_x, y: new_y };
-                    vel_write[i] = Velocity {
-                        x: new_vel_x.clamp(i16::MIN as i32, i16::MAX as i32) as i16,
-                        y: new_vel_y.clamp(i16::MIN as i32, i16::MAX as i32) as i16,
-                    };
-                }
-            }
-        });
+                *position = Position { x: new_x, y: new_y };
+                *velocity = Velocity {
+                    x: new_vel_x.clamp(i16::MIN as i32, i16::MAX as i32) as i16,
+                    y: new_vel_y.clamp(i16::MIN as i32, i16::MAX as i32) as i16,
+                };
+            })
+            .expect("movement system");
     }
 }
 
@@ -188,11 +162,11 @@ struct CollisionSystem {
     #[allow(dead_code)]
     handle: SystemHandle,
     component_filter: Vec<ComponentId>,
-    iterations: usize,
+    scratch: FrameArena,
 }
 
 impl CollisionSystem {
-    fn new(world: &mut World, iterations: usize) -> Self {
+    fn new(world: &mut World) -> Self {
         let descriptor = SystemDescriptor::new("collision")
             .reads([Position::ID, Velocity::ID])
             .writes([Position::ID, Velocity::ID]);
@@ -205,11 +179,24 @@ impl CollisionSystem {
         Self {
             handle,
             component_filter,
-            iterations: iterations.max(1),
+            scratch: FrameArena::new(),
         }
     }
 
     fn run(&mut self, world: &mut World, relations: &RelationBuffer) {
+        let config = *world
+            .resource::<PhysicsConfig>()
+            .expect("PhysicsConfig resource missing");
+        let solver = *world
+            .resource::<CollisionConfig>()
+            .expect("CollisionConfig resource missing");
+        // Convert tunables to fixed point once so the loop below is integer-only.
+        let friction_q16 = to_q16(config.tangent_friction);
+        let damping_q16 = to_q16(config.linear_damping) as i64;
+        let correction_q16 = to_q16(solver.correction) as i64;
+        let response_q16 = to_q16(solver.contact_damping) as i64;
+        self.scratch.reset();
+        let scratch = &self.scratch;
         world.for_each(&self.component_filter, |storage| {
             let archetype_id = storage.plan().layout.id();
             let entity_count = storage.entity_count();
@@ -217,10 +204,11 @@ impl CollisionSystem {
                 return;
             }
 
-            let entity_ids: Vec<EntityId> = storage
-                .entity_ids_slice(0..entity_count)
-                .expect("entity id slice")
-                .to_vec();
+            let entity_ids: &[EntityId] = scratch.alloc_slice_copy(
+                storage
+                    .entity_ids_slice(0..entity_count)
+                    .expect("entity id slice"),
+            );
 
             let (pos_col, vel_col) = storage
                 .columns_mut_pair(Position::ID, Velocity::ID)
@@ -232,21 +220,20 @@ impl CollisionSystem {
                 .column_slice_write::<Velocity>()
                 .expect("velocity column slice");
 
-            for _ in 0..self.iterations {
+            for _ in 0..solver.passes() {
                 for row_index in 0..entity_count {
                     let entity_id = entity_ids[row_index];
-                    let jitter_sign = if entity_id % 2 == 0 { -1.0 } else { 1.0 };
+                    let jitter_sign = if entity_id % 2 == 0 { -1 } else { 1 };
                     let debug_this_entity = DEBUG_ENTITY_ID
                         .map(|target| target == entity_id)
                         .unwrap_or(false);
                     let neighbors = relations.relations_for_entity_id(entity_id);
 
-                    let base_pos_x = pos_write[row_index].x as f32;
-                    let base_pos_y = pos_write[row_index].y as f32;
-                    let mut pos_x = base_pos_x;
-                    let mut pos_y = base_pos_y;
-                    let mut vel_x = vel_write[row_index].x as f32;
-                    let mut vel_y_f = vel_write[row_index].y as f32;
+                    let base = pos_write[row_index];
+                    let mut pos_x = base.x;
+                    let mut pos_y = base.y;
+                    let mut vel_x = vel_write[row_index].x as i32;
+                    let mut vel_y = vel_write[row_index].y as i32;
 
                     if debug_this_entity {
                         println!(
@@ -264,62 +251,46 @@ impl CollisionSystem {
                         let (neighbor_x, neighbor_y) = if let Some(loc) = relation.other_location {
                             if loc.archetype == archetype_id && loc.row < pos_write.len() {
                                 let neighbor = pos_write[loc.row];
-                                (neighbor.x as f32, neighbor.y as f32)
+                                (neighbor.x, neighbor.y)
                             } else if let Some(delta) = relation.delta {
                                 (
-                                    base_pos_x + delta.dx as f32,
-                                    base_pos_y + delta.dy as f32,
+                                    (base.x as i64 + delta.dx) as i32,
+                                    (base.y as i64 + delta.dy) as i32,
                                 )
                             } else {
                                 continue;
                             }
                         } else if let Some(delta) = relation.delta {
                             (
-                                base_pos_x + delta.dx as f32,
-                                base_pos_y + delta.dy as f32,
+                                (base.x as i64 + delta.dx) as i32,
+                                (base.y as i64 + delta.dy) as i32,
                             )
                         } else {
                             continue;
                         };
 
-                        let dx = pos_x - neighbor_x;
-                        let dy = pos_y - neighbor_y;
-                        let dist_sq = dx.mul_add(dx, dy * dy);
-                        if dist_sq <= 1.0 {
+                        let Some(contact) = resolve_circle_contact(
+                            // Pairs too far apart to subtract are never in contact.
+                            [
+                                pos_x.saturating_sub(neighbor_x),
+                                pos_y.saturating_sub(neighbor_y),
+                            ],
+                            [vel_x, vel_y],
+                            PARTICLE_DIAMETER,
+                            friction_q16,
+                        ) else {
                             continue;
-                        }
-                        let dist = dist_sq.sqrt();
-                        let penetration = (PARTICLE_DIAMETER as f32) - dist;
-                        if penetration <= 0.0 {
-                            continue;
-                        }
-
-                        let normal_x = dx / dist;
-                        let normal_y = dy / dist;
-                        let correction = penetration * 0.5;
-                        pos_x += normal_x * correction;
-                        pos_y += normal_y * correction;
+                        };
 
-                        if normal_x.abs() <= AXIS_JITTER_EPSILON {
+                        pos_x += mul_q16(contact.position[0] as i64, correction_q16) as i32;
+                        pos_y += mul_q16(contact.position[1] as i64, correction_q16) as i32;
+                        if contact.normal[0].abs() <= AXIS_JITTER_EPSILON_Q16 {
                             pos_x += jitter_sign * AXIS_JITTER_PUSH;
-                        } else if normal_y.abs() <= AXIS_JITTER_EPSILON {
+                        } else if contact.normal[1].abs() <= AXIS_JITTER_EPSILON_Q16 {
                             pos_y += jitter_sign * AXIS_JITTER_PUSH;
                         }
-
-                        let rel_vel = vel_x * normal_x + vel_y_f * normal_y;
-                        if rel_vel < 0.0 {
-                            vel_x -= normal_x * rel_vel;
-                            vel_y_f -= normal_y * rel_vel;
-                        }
-
-                        let tangent_x = -normal_y;
-                        let tangent_y = normal_x;
-                        let rel_tangent = vel_x * tangent_x + vel_y_f * tangent_y;
-                        if rel_tangent.abs() > f32::EPSILON {
-                            let friction_impulse = rel_tangent * COLLISION_TANGENT_FRICTION;
-                            vel_x -= tangent_x * friction_impulse;
-                            vel_y_f -= tangent_y * friction_impulse;
-                        }
+                        vel_x += mul_q16(contact.velocity[0] as i64, response_q16) as i32;
+                        vel_y += mul_q16(contact.velocity[1] as i64, response_q16) as i32;
 
                         if debug_this_entity && idx < DEBUG_NEIGHBOR_LIMIT {
                             let (dx_dbg, dy_dbg) = relation
@@ -327,50 +298,43 @@ impl CollisionSystem {
                                 .map(|d| (d.dx, d.dy))
                                 .unwrap_or((0, 0));
                             println!(
-                                "  -> neighbor={} delta=({}, {}), pen={:.2}, normal=({:.2}, {:.2})",
+                                "  -> neighbor={} delta=({}, {}), pen={}, normal_q16=({}, {})",
                                 relation.other.index(),
                                 dx_dbg,
                                 dy_dbg,
-                                penetration,
-                                normal_x,
-                                normal_y
+                                contact.penetration,
+                                contact.normal[0],
+                                contact.normal[1]
                             );
                         }
                     }
 
-                    let horizontal_bound = (UNITS_PER_NDC - PARTICLE_RADIUS) as f32;
-                    let floor = (FLOOR_Y + PARTICLE_RADIUS) as f32;
+                    let min_x = config.bounds_min[0] + PARTICLE_RADIUS;
+                    let max_x = config.bounds_max[0] - PARTICLE_RADIUS;
+                    let floor = config.bounds_min[1] + PARTICLE_RADIUS;
 
-                    if pos_x < -horizontal_bound {
-                        pos_x = -horizontal_bound;
-                        vel_x = 0.0;
-                    } else if pos_x > horizontal_bound {
-                        pos_x = horizontal_bound;
-                        vel_x = 0.0;
+                    if pos_x < min_x {
+                        pos_x = min_x;
+                        vel_x = 0;
+                    } else if pos_x > max_x {
+                        pos_x = max_x;
+                        vel_x = 0;
                     }
 
                     if pos_y < floor {
-                        let penetration = floor - pos_y;
                         pos_y = floor;
-                        if penetration > 0.0 && vel_y_f < 0.0 {
-                            vel_y_f = 0.0;
+                        if vel_y < 0 {
+                            vel_y = 0;
                         }
                     }
 
-                    vel_x *= COLLISION_LINEAR_DAMPING;
-                    vel_y_f *= COLLISION_LINEAR_DAMPING;
+                    vel_x = mul_q16(vel_x as i64, damping_q16) as i32;
+                    vel_y = mul_q16(vel_y as i64, damping_q16) as i32;
 
-                    pos_write[row_index] = Position {
-                        x: pos_x.round() as i32,
-                        y: pos_y.round() as i32,
-                    };
+                    pos_write[row_index] = Position { x: pos_x, y: pos_y };
                     vel_write[row_index] = Velocity {
-                        x: vel_x
-                            .round()
-                            .clamp(i16::MIN as f32, i16::MAX as f32) as i16,
-                        y: vel_y_f
-                            .round()
-                            .clamp(i16::MIN as f32, i16::MAX as f32) as i16,
+                        x: vel_x.clamp(i16::MIN as i32, i16::MAX as i32) as i16,
+                        y: vel_y.clamp(i16::MIN as i32, i16::MAX as i32) as i16,
                     };
                 }
             }
@@ -382,46 +346,124 @@ impl CollisionSystem {
 // Renderer
 // ============================================================================
 
-#[repr(C)]
-#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
-struct Vertex {
-    position: [f32; 2],
-    uv: [f32; 2],
+/// Side of the generated circle texture, in texels.
+const CIRCLE_TEXTURE_SIZE: u32 = 64;
+
+/// White disc on a transparent background; sprites tint it per particle.
+fn circle_atlas(device: &wgpu::Device, queue: &wgpu::Queue) -> wgpu::TextureView {
+    let size = wgpu::Extent3d {
+        width: CIRCLE_TEXTURE_SIZE,
+        height: CIRCLE_TEXTURE_SIZE,
+        depth_or_array_layers: 1,
+    };
+    let radius = CIRCLE_TEXTURE_SIZE as f32 / 2.0;
+    let mut texels = Vec::with_capacity((CIRCLE_TEXTURE_SIZE * CIRCLE_TEXTURE_SIZE * 4) as usize);
+    for y in 0..CIRCLE_TEXTURE_SIZE {
+        for x in 0..CIRCLE_TEXTURE_SIZE {
+            let dx = x as f32 + 0.5 - radius;
+            let dy = y as f32 + 0.5 - radius;
+            // One texel of antialiasing at the rim
+            let coverage = (radius - (dx * dx + dy * dy).sqrt()).clamp(0.0, 1.0);
+            texels.extend_from_slice(&[255, 255, 255, (coverage * 255.0) as u8]);
+        }
+    }
+
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Circle Atlas"),
+        size,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+        view_formats: &[],
+    });
+    queue.write_texture(
+        texture.as_image_copy(),
+        &texels,
+        wgpu::ImageDataLayout {
+            offset: 0,
+            bytes_per_row: Some(CIRCLE_TEXTURE_SIZE * 4),
+            rows_per_image: None,
+        },
+        size,
+    );
+    texture.create_view(&wgpu::TextureViewDescriptor::default())
+}
+
+/// Half the particle's side length in NDC, for the software quad.
+const PARTICLE_HALF_NDC: f32 = PARTICLE_RADIUS as f32 / UNITS_PER_NDC as f32;
+
+/// Particle quad (two triangles, NDC, centred on the origin).
+const PARTICLE_QUAD: [[f32; 2]; 6] = [
+    [-PARTICLE_HALF_NDC, -PARTICLE_HALF_NDC],
+    [PARTICLE_HALF_NDC, -PARTICLE_HALF_NDC],
+    [PARTICLE_HALF_NDC, PARTICLE_HALF_NDC],
+    [-PARTICLE_HALF_NDC, -PARTICLE_HALF_NDC],
+    [PARTICLE_HALF_NDC, PARTICLE_HALF_NDC],
+    [-PARTICLE_HALF_NDC, PARTICLE_HALF_NDC],
+];
+
+/// Particle sprites on the GPU, or square quads on the CPU when there is no adapter.
+enum Renderer {
+    Gpu(ParticleRenderer),
+    /// Nothing is presented, but the simulation and frame loop still run.
+    Software(SoftwareRasterizer),
 }
 
-#[repr(C)]
-#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
-struct InstanceData {
-    position: [i32; 2],
-    velocity: [i16; 2], // Add velocity for shader compatibility
-    color: [u8; 4],
-    radius: f32,
+impl Renderer {
+    fn render(&mut self, world: &World) -> Result<usize, FrameError> {
+        match self {
+            Renderer::Gpu(renderer) => renderer.render(world),
+            Renderer::Software(rasterizer) => Ok(render_software(rasterizer, world)),
+        }
+    }
+
+    fn cull_stats(&self) -> Option<CullStats> {
+        match self {
+            Renderer::Gpu(renderer) => Some(renderer.cull_stats),
+            Renderer::Software(_) => None,
+        }
+    }
 }
 
-#[repr(C)]
-#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
-struct Uniforms {
-    interpolation_alpha: f32,
-    dt: f32,
-    _padding: [f32; 2],
+/// Rasterize every particle as a flat-colored square; returns the instance count.
+fn render_software(rasterizer: &mut SoftwareRasterizer, world: &World) -> usize {
+    let mut instances = Vec::new();
+    for arch_id in world.archetypes_matching(&[Position::ID, Color::ID]) {
+        if let Some(storage) = world.storage(arch_id) {
+            storage
+                .gather_instances2::<Position, Color, _>(&mut instances, |pos, color| {
+                    SoftwareInstance {
+                        offset: [
+                            pos.x as f32 / UNITS_PER_NDC as f32,
+                            pos.y as f32 / UNITS_PER_NDC as f32,
+                        ],
+                        color: [color.r, color.g, color.b, 255],
+                    }
+                })
+                .expect("position and color columns");
+        }
+    }
+    rasterizer.clear([13, 13, 13, 255]);
+    rasterizer.draw_instanced(&PARTICLE_QUAD, &instances);
+    instances.len()
 }
 
 struct ParticleRenderer {
     surface: wgpu::Surface<'static>,
     device: wgpu::Device,
     queue: wgpu::Queue,
-    #[allow(dead_code)]
     config: wgpu::SurfaceConfiguration,
-    pipeline: wgpu::RenderPipeline,
-    vertex_buffer: wgpu::Buffer,
-    instance_buffer: wgpu::Buffer,
-    instance_buffer_capacity: usize,
-    uniform_buffer: wgpu::Buffer,
-    uniform_bind_group: wgpu::BindGroup,
+    clear_color: wgpu::Color,
+    sprites: SpriteBatch,
+    instances: Vec<SpriteInstance>,
+    layers: Vec<RenderLayer>,
+    cull_stats: CullStats,
 }
 
 impl ParticleRenderer {
-    async fn new(window: Arc<Window>) -> Self {
+    async fn new(window: Arc<Window>, clear_color: wgpu::Color) -> Renderer {
         let size = window.inner_size();
 
         let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
@@ -431,27 +473,15 @@ impl ParticleRenderer {
 
         let surface = instance.create_surface(window.clone()).unwrap();
 
-        let adapter = instance
-            .request_adapter(&wgpu::RequestAdapterOptions {
-                power_preference: wgpu::PowerPreference::default(),
-                compatible_surface: Some(&surface),
-                force_fallback_adapter: false,
-            })
-            .await
-            .unwrap();
-
-        let (device, queue) = adapter
-            .request_device(
-                &wgpu::DeviceDescriptor {
-                    label: None,
-                    required_features: wgpu::Features::empty(),
-                    required_limits: wgpu::Limits::default(),
-                    memory_hints: wgpu::MemoryHints::default(),
-                },
-                None,
-            )
+        let selected = select_adapter(&instance, Some(&surface), size.width, size.height).await;
+        let adapter = match selected {
+            SelectedAdapter::Gpu(adapter) => adapter,
+            SelectedAdapter::Software(rasterizer) => return Renderer::Software(rasterizer),
+        };
+
+        let (device, queue) = request_device(&adapter, &DeviceRequirements::default())
             .await
-            .unwrap();
+            .unwrap_or_else(|e| panic!("{e}"));
 
         let surface_caps = surface.get_capabilities(&adapter);
         let surface_format = surface_caps.formats[0];
@@ -461,294 +491,90 @@ impl ParticleRenderer {
             format: surface_format,
             width: size.width,
             height: size.height,
-            present_mode: wgpu::PresentMode::Immediate,
+            present_mode: PresentModePreference::Immediate.select(&surface_caps.present_modes),
             alpha_mode: surface_caps.alpha_modes[0],
             view_formats: vec![],
             desired_maximum_frame_latency: 2,
         };
         surface.configure(&device, &config);
 
-        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
-            label: Some("Particle Shader"),
-            source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/sand_circle.wgsl").into()),
-        });
-
-        // Create uniform buffer
-        let uniforms = Uniforms {
-            interpolation_alpha: 0.0,
-            dt: 0.0,
-            _padding: [0.0, 0.0],
-        };
-
-        let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("Uniform Buffer"),
-            contents: bytemuck::cast_slice(&[uniforms]),
-            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
-        });
-
-        // Create bind group layout for uniforms
-        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-            label: Some("Uniform Bind Group Layout"),
-            entries: &[wgpu::BindGroupLayoutEntry {
-                binding: 0,
-                visibility: wgpu::ShaderStages::VERTEX,
-                ty: wgpu::BindingType::Buffer {
-                    ty: wgpu::BufferBindingType::Uniform,
-                    has_dynamic_offset: false,
-                    min_binding_size: None,
-                },
-                count: None,
-            }],
-        });
-
-        let uniform_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("Uniform Bind Group"),
-            layout: &bind_group_layout,
-            entries: &[wgpu::BindGroupEntry {
-                binding: 0,
-                resource: uniform_buffer.as_entire_binding(),
-            }],
-        });
-
-        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-            label: Some("Render Pipeline Layout"),
-            bind_group_layouts: &[&bind_group_layout],
-            push_constant_ranges: &[],
-        });
-
-        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("Render Pipeline"),
-            layout: Some(&pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: &shader,
-                entry_point: Some("vs_main"),
-                buffers: &[
-                    wgpu::VertexBufferLayout {
-                        array_stride: std::mem::size_of::<Vertex>() as wgpu::BufferAddress,
-                        step_mode: wgpu::VertexStepMode::Vertex,
-                        attributes: &wgpu::vertex_attr_array![0 => Float32x2, 1 => Float32x2],
-                    },
-                    wgpu::VertexBufferLayout {
-                        array_stride: std::mem::size_of::<InstanceData>() as wgpu::BufferAddress,
-                        step_mode: wgpu::VertexStepMode::Instance,
-                        attributes: &wgpu::vertex_attr_array![
-                            2 => Sint32x2,   // position
-                            3 => Snorm16x2,  // velocity
-                            4 => Unorm8x4,   // color
-                            5 => Float32     // radius
-                        ],
-                    },
-                ],
-                compilation_options: wgpu::PipelineCompilationOptions::default(),
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &shader,
-                entry_point: Some("fs_main"),
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: config.format,
-                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-                compilation_options: wgpu::PipelineCompilationOptions::default(),
-            }),
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                strip_index_format: None,
-                front_face: wgpu::FrontFace::Ccw,
-                cull_mode: None,
-                polygon_mode: wgpu::PolygonMode::Fill,
-                unclipped_depth: false,
-                conservative: false,
-            },
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState {
-                count: 1,
-                mask: !0,
-                alpha_to_coverage_enabled: false,
-            },
-            multiview: None,
-            cache: None,
-        });
-
-        let quad_vertices = [
-            Vertex {
-                position: [-1.0, -1.0],
-                uv: [-1.0, -1.0],
-            },
-            Vertex {
-                position: [1.0, -1.0],
-                uv: [1.0, -1.0],
-            },
-            Vertex {
-                position: [1.0, 1.0],
-                uv: [1.0, 1.0],
-            },
-            Vertex {
-                position: [-1.0, -1.0],
-                uv: [-1.0, -1.0],
-            },
-            Vertex {
-                position: [1.0, 1.0],
-                uv: [1.0, 1.0],
-            },
-            Vertex {
-                position: [-1.0, 1.0],
-                uv: [-1.0, 1.0],
-            },
-        ];
-
-        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("Vertex Buffer"),
-            contents: bytemuck::cast_slice(&quad_vertices),
-            usage: wgpu::BufferUsages::VERTEX,
-        });
-
-        let initial_capacity = 1024;
-        let instance_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Instance Buffer"),
-            size: (std::mem::size_of::<InstanceData>() * initial_capacity) as u64,
-            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
+        let atlas = circle_atlas(&device, &queue);
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Circle Sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
         });
+        let sprites = SpriteBatch::new(&device, config.format, &atlas, &sampler);
 
-        Self {
+        Renderer::Gpu(Self {
             surface,
             device,
             queue,
             config,
-            pipeline,
-            vertex_buffer,
-            instance_buffer,
-            instance_buffer_capacity: initial_capacity,
-            uniform_buffer,
-            uniform_bind_group,
-        }
+            clear_color,
+            sprites,
+            instances: Vec::new(),
+            layers: Vec::new(),
+            cull_stats: CullStats::default(),
+        })
     }
 
-    fn render(&mut self, world: &World) -> Result<usize, wgpu::SurfaceError> {
-        let mut instance_data: Vec<InstanceData> = Vec::new();
-
-        let position_archs = world.archetypes_with(Position::ID);
-        let color_archs = world.archetypes_with(Color::ID);
+    fn render(&mut self, world: &World) -> Result<usize, FrameError> {
+        let bounds = CAMERA.cull_bounds(PARTICLE_RADIUS);
+        let diameter = PARTICLE_DIAMETER as f32;
+        self.instances.clear();
+        self.layers.clear();
+        self.cull_stats = CullStats::default();
 
-        for &arch_id in position_archs {
-            if !color_archs.contains(&arch_id) {
+        for arch_id in world.archetypes_matching(&[Position::ID, Color::ID]) {
+            let Some(storage) = world.storage(arch_id) else {
                 continue;
-            }
-
-            if let Some(storage) = world.storage(arch_id) {
-                let positions_col = storage.column(Position::ID).expect("position column");
-                let colors_col = storage.column(Color::ID).expect("color column");
-
-                for page_idx in 0..positions_col.page_count() {
-                    let range = positions_col.page_range(page_idx);
-                    if range.is_empty() {
-                        continue;
-                    }
-
-                    let start = range.start;
-                    let end = range.end;
-
-                    let positions = positions_col
-                        .slice_read_typed::<Position>(start..end)
-                        .expect("position slice");
-                    let colors = colors_col
-                        .slice_read_typed::<Color>(start..end)
-                        .expect("color slice");
-                    let velocities = storage
-                        .column(Velocity::ID)
-                        .ok()
-                        .and_then(|col| col.slice_read_typed::<Velocity>(start..end).ok());
-
-                    for i in 0..positions.len() {
-                        let velocity = velocities
-                            .as_ref()
-                            .map(|slice| slice[i])
-                            .unwrap_or(Velocity { x: 0, y: 0 });
-                        instance_data.push(InstanceData {
-                            position: [positions[i].x, positions[i].y],
-                            velocity: [velocity.x, velocity.y],
-                            color: [colors[i].r, colors[i].g, colors[i].b, 255],
-                            radius: PARTICLE_RADIUS as f32,
-                        });
-                    }
-                }
-            }
-        }
-
-        let instance_count = instance_data.len();
-
-        if instance_count > self.instance_buffer_capacity {
-            let new_capacity = instance_count.next_power_of_two();
-            self.instance_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
-                label: Some("Instance Buffer"),
-                size: (std::mem::size_of::<InstanceData>() * new_capacity) as u64,
-                usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
-                mapped_at_creation: false,
-            });
-            self.instance_buffer_capacity = new_capacity;
+            };
+            // Skip particles outside the camera before touching the color column
+            let stats = storage
+                .gather_instances2_culled_layered::<Position, Color, _>(
+                    &mut self.instances,
+                    &mut self.layers,
+                    bounds,
+                    |pos| [pos.x, pos.y],
+                    |pos, color| {
+                        SpriteInstance::new(
+                            [pos.x as f32, pos.y as f32],
+                            [diameter, diameter],
+                            [color.r, color.g, color.b, 255],
+                        )
+                    },
+                )
+                .expect("position and color columns");
+            self.cull_stats.accumulate(stats);
         }
 
-        if !instance_data.is_empty() {
-            self.queue.write_buffer(
-                &self.instance_buffer,
-                0,
-                bytemuck::cast_slice(&instance_data),
-            );
-        }
+        self.sprites.prepare_layered(
+            &self.device,
+            &self.queue,
+            &CAMERA,
+            &mut self.instances,
+            &self.layers,
+        );
 
-        // Update uniforms (no interpolation for this demo)
-        let uniforms = Uniforms {
-            interpolation_alpha: 0.0,
-            dt: 0.0,
-            _padding: [0.0, 0.0],
+        let Some(mut frame) = Frame::acquire(
+            &self.surface,
+            &self.device,
+            &self.queue,
+            &self.config,
+            self.clear_color,
+        )?
+        else {
+            return Ok(0);
         };
-        self.queue
-            .write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
-
-        let output = self.surface.get_current_texture()?;
-        let view = output
-            .texture
-            .create_view(&wgpu::TextureViewDescriptor::default());
-
-        let mut encoder = self
-            .device
-            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                label: Some("Render Encoder"),
-            });
 
         {
-            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some("Render Pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &view,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color {
-                            r: 0.05,
-                            g: 0.05,
-                            b: 0.05,
-                            a: 1.0,
-                        }),
-                        store: wgpu::StoreOp::Store,
-                    },
-                })],
-                depth_stencil_attachment: None,
-                occlusion_query_set: None,
-                timestamp_writes: None,
-            });
-
-            render_pass.set_pipeline(&self.pipeline);
-            render_pass.set_bind_group(0, &self.uniform_bind_group, &[]);
-            render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
-            render_pass.set_vertex_buffer(1, self.instance_buffer.slice(..));
-            render_pass.draw(0..6, 0..(instance_count as u32));
+            let mut render_pass = frame.begin_pass();
+            self.sprites.draw(&mut render_pass);
         }
 
-        self.queue.submit(std::iter::once(encoder.finish()));
-        output.present();
-
-        Ok(instance_count)
+        Ok(self.instances.len())
     }
 }
 
@@ -758,7 +584,7 @@ impl ParticleRenderer {
 
 struct App {
     window: Option<Arc<Window>>,
-    renderer: Option<ParticleRenderer>,
+    renderer: Option<Renderer>,
     world: World,
     queries: QueryRegistry,
     relation_buffer: RelationBuffer,
@@ -773,6 +599,8 @@ struct App {
 impl App {
     fn new() -> Self {
         let mut world = World::new();
+        world.insert_resource(PhysicsConfig::default());
+        world.insert_resource(CollisionConfig::default());
 
         // Spawn sand particles starting near the floor so rows build upward
         // Allow rows to extend above the camera so sand continues pouring in
@@ -806,7 +634,7 @@ impl App {
         }
 
         let movement = MovementSystem::new(&mut world);
-        let collision = CollisionSystem::new(&mut world, 10);
+        let collision = CollisionSystem::new(&mut world);
 
         let mut queries = QueryRegistry::new();
         let spatial_config = SpatialHashConfig::new(
@@ -818,7 +646,10 @@ impl App {
         let spatial_hash = Box::new(SpatialHashGrid::new(spatial_config));
         queries.register(spatial_hash);
 
-        let relation_buffer = RelationBuffer::new(2048, 256);
+        // KeepNearest resolves the closest contacts first and, unlike
+        // DropExcess, does not depend on spatial hash emission order.
+        let relation_buffer = RelationBuffer::new(2048, 256)
+            .with_per_entity_cap(MAX_CONTACTS_PER_PARTICLE, OverflowPolicy::KeepNearest);
 
         Self {
             window: None,
@@ -866,10 +697,23 @@ impl App {
 impl ApplicationHandler for App {
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
         if self.window.is_none() {
-            let window_attrs = Window::default_attributes().with_title("PoC 4: Falling Sand");
-            let window = Arc::new(event_loop.create_window(window_attrs).unwrap());
+            let config = WindowConfig {
+                title: "PoC 4: Falling Sand".to_string(),
+                clear_color: wgpu::Color {
+                    r: 0.05,
+                    g: 0.05,
+                    b: 0.05,
+                    a: 1.0,
+                },
+                ..Default::default()
+            };
+            let clear_color = config.clear_color;
+            let window = Arc::new(event_loop.create_window(window_attributes(config)).unwrap());
 
-            let renderer = pollster::block_on(ParticleRenderer::new(window.clone()));
+            let renderer = pollster::block_on(ParticleRenderer::new(window.clone(), clear_color));
+            if let Renderer::Software(_) = renderer {
+                println!("No GPU adapter: rasterizing off-screen on the CPU.");
+            }
 
             self.window = Some(window);
             self.renderer = Some(renderer);
@@ -899,13 +743,10 @@ impl ApplicationHandler for App {
                                 // Success
                                 let _ = instance_count;
                             }
-                            Err(wgpu::SurfaceError::Lost) => {
-                                // Reconfigure surface
-                            }
-                            Err(wgpu::SurfaceError::OutOfMemory) => {
+                            Err(e) => {
+                                eprintln!("Render error: {e}");
                                 event_loop.exit();
                             }
-                            Err(e) => eprintln!("Render error: {:?}", e),
                         }
                     });
                 }
@@ -924,6 +765,20 @@ impl ApplicationHandler for App {
                     );
 
                     println!("Entities: {}", self.world.entity_count());
+                    for (archetype, components, count) in self.world.archetype_stats() {
+                        println!(
+                            "  Archetype {:#018x}: {} entities, components {:?}",
+                            archetype, count, components
+                        );
+                    }
+                    if let Some(cull) = self.renderer.as_ref().and_then(Renderer::cull_stats) {
+                        println!(
+                            "Culling: visible={}, culled={}, total={}",
+                            cull.visible,
+                            cull.culled(),
+                            cull.total
+                        );
+                    }
 
                     let rebuild_ms =
                         self.profiler.get_timing("rebuild_queries").as_secs_f64() * 1000.0;
@@ -935,7 +790,12 @@ impl ApplicationHandler for App {
                         movement_ms, collision_ms, rebuild_ms, render_ms
                     );
 
-                    let hash_metrics = spatial_hash_metrics_snapshot();
+                    let grid = self
+                        .queries
+                        .get_as_mut::<SpatialHashGrid>(COLLISION_RELATION)
+                        .expect("spatial hash registered for collisions");
+                    let hash_metrics = grid.metrics_snapshot();
+                    grid.reset_metrics();
                     if hash_metrics.total_calls > 0 {
                         let avg_total_ms = (hash_metrics.total_ns as f64)
                             / (hash_metrics.total_calls as f64)
@@ -965,7 +825,14 @@ impl ApplicationHandler for App {
                         );
                     }
 
-                    reset_spatial_hash_metrics();
+                    println!(
+                        "Relations: high_water={}, capacity={}, dropped={}",
+                        self.relation_buffer.high_water_mark(),
+                        self.relation_buffer.capacity(),
+                        self.relation_buffer.dropped_relations()
+                    );
+                    self.relation_buffer.reset_high_water_mark();
+                    self.relation_buffer.reset_dropped_relations();
 
                     self.profiler.reset();
                 }
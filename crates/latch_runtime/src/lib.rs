@@ -0,0 +1,8 @@
+//! Latch Engine Runtime
+//!
+//! Process-level setup shared by the `latch` binary, the editor and the
+//! examples.
+
+pub mod logging;
+
+pub use logging::{frame_span, init_default_logging, init_logging, LogConfig, LogError};
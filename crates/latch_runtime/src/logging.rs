@@ -0,0 +1,200 @@
+//! Log filtering and output
+//!
+//! `init_logging` installs the global `tracing` subscriber: one filter built
+//! from `tracing` directives (`"info,latch_net=debug"`), output to stdout and
+//! optionally to a file, as human-readable lines or one JSON object per line.
+//! `World::tick` opens a `tick` span and `frame_span` a `frame` span, so
+//! events carry the tick and frame they happened in; JSON lines list them
+//! under `spans`.
+
+use latch_services::settings::{LogFormat, LoggingSettings};
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use thiserror::Error;
+use tracing::Subscriber;
+use tracing_subscriber::filter::{EnvFilter, ParseError};
+use tracing_subscriber::fmt::{self, MakeWriter};
+use tracing_subscriber::layer::{Layer, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::util::{SubscriberInitExt, TryInitError};
+
+/// Filter directives; falls back to `RUST_LOG`.
+pub const LOG_FILTER_ENV: &str = "LATCH_LOG";
+/// `pretty` or `json`.
+pub const LOG_FORMAT_ENV: &str = "LATCH_LOG_FORMAT";
+/// Path of a file to append log lines to.
+pub const LOG_FILE_ENV: &str = "LATCH_LOG_FILE";
+
+#[derive(Debug, Error)]
+pub enum LogError {
+    #[error("invalid log filter {filter:?}: {source}")]
+    Filter {
+        filter: String,
+        #[source]
+        source: ParseError,
+    },
+    #[error("unknown log format {0:?} (expected \"pretty\" or \"json\")")]
+    Format(String),
+    #[error("cannot open log file {}: {source}", path.display())]
+    File {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("a global logger is already installed")]
+    AlreadyInitialized(#[from] TryInitError),
+}
+
+/// What `init_logging` installs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogConfig {
+    /// `tracing` filter directives: a default level and per-module levels,
+    /// e.g. `"warn,latch_core=info,latch_net::session=trace"`.
+    pub filter: String,
+    pub format: LogFormat,
+    /// Also append log lines (never colored) to this file.
+    pub file: Option<PathBuf>,
+}
+
+impl Default for LogConfig {
+    /// `info` and above as readable lines on stdout.
+    fn default() -> Self {
+        Self::from_settings(&LoggingSettings::default())
+    }
+}
+
+impl LogConfig {
+    /// The `logging` section of the settings store.
+    pub fn from_settings(settings: &LoggingSettings) -> Self {
+        Self {
+            filter: settings.filter.clone(),
+            format: settings.format,
+            file: settings.file.as_ref().map(PathBuf::from),
+        }
+    }
+
+    /// The defaults overridden by the environment (see `with_env`).
+    pub fn from_env() -> Result<Self, LogError> {
+        Self::default().with_env()
+    }
+
+    /// Override fields set in the environment: `LATCH_LOG` (or `RUST_LOG`),
+    /// `LATCH_LOG_FORMAT` and `LATCH_LOG_FILE`.
+    ///
+    /// Apply it on top of `from_settings` so a server operator can raise a
+    /// module's level without editing the settings file.
+    pub fn with_env(mut self) -> Result<Self, LogError> {
+        if let Some(filter) = env_var(LOG_FILTER_ENV).or_else(|| env_var("RUST_LOG")) {
+            self.filter = filter;
+        }
+        if let Some(format) = env_var(LOG_FORMAT_ENV) {
+            self.format = parse_format(&format)?;
+        }
+        if let Some(file) = env_var(LOG_FILE_ENV) {
+            self.file = Some(file.into());
+        }
+        Ok(self)
+    }
+
+    pub fn with_filter(mut self, filter: impl Into<String>) -> Self {
+        self.filter = filter.into();
+        self
+    }
+
+    pub fn with_format(mut self, format: LogFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    pub fn with_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.file = Some(path.into());
+        self
+    }
+}
+
+/// Install the global subscriber described by `config`.
+///
+/// Fails without installing anything if the filter does not parse, the log
+/// file cannot be opened, or a subscriber is already installed.
+pub fn init_logging(config: &LogConfig) -> Result<(), LogError> {
+    let filter = EnvFilter::try_new(&config.filter).map_err(|source| LogError::Filter {
+        filter: config.filter.clone(),
+        source,
+    })?;
+    let file = config.file.as_deref().map(open_log_file).transpose()?;
+
+    let stdout = output_layer(config.format, io::stdout, true);
+    let file = file.map(|file| output_layer(config.format, Mutex::new(file), false));
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(stdout)
+        .with(file)
+        .try_init()?;
+    Ok(())
+}
+
+/// `LogConfig::from_env`, falling back to the defaults when the environment
+/// is invalid; does nothing if a subscriber is already installed.
+///
+/// For examples and tools that only want readable logs.
+pub fn init_default_logging() {
+    let config = LogConfig::from_env().unwrap_or_else(|err| {
+        eprintln!("{err}; logging with defaults");
+        LogConfig::default()
+    });
+    if let Err(err) = init_logging(&config) {
+        if !matches!(err, LogError::AlreadyInitialized(_)) {
+            eprintln!("{err}; logging with defaults");
+            let _ = init_logging(&LogConfig::default());
+        }
+    }
+}
+
+/// Span for one rendered frame.
+///
+/// Enter it around the frame's work; events inside it, including those of
+/// a `World::tick` run during the frame, carry `frame`.
+pub fn frame_span(frame: u64) -> tracing::Span {
+    tracing::info_span!("frame", frame)
+}
+
+fn output_layer<S, W>(format: LogFormat, writer: W, ansi: bool) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    let layer = fmt::layer().with_writer(writer);
+    match format {
+        LogFormat::Pretty => layer.with_ansi(ansi).boxed(),
+        LogFormat::Json => layer
+            .json()
+            .with_current_span(false)
+            .with_span_list(true)
+            .boxed(),
+    }
+}
+
+fn open_log_file(path: &Path) -> Result<File, LogError> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|source| LogError::File {
+            path: path.to_path_buf(),
+            source,
+        })
+}
+
+fn parse_format(format: &str) -> Result<LogFormat, LogError> {
+    match format.to_ascii_lowercase().as_str() {
+        "pretty" => Ok(LogFormat::Pretty),
+        "json" => Ok(LogFormat::Json),
+        _ => Err(LogError::Format(format.to_owned())),
+    }
+}
+
+fn env_var(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|value| !value.is_empty())
+}
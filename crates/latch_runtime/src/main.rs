@@ -3,11 +3,11 @@
 //! Minimal binary that links engine crates and boots the game
 
 use anyhow::Result;
-use tracing_subscriber;
+use latch_runtime::{init_logging, LogConfig};
 
 fn main() -> Result<()> {
-    // Initialize logging
-    tracing_subscriber::fmt::init();
+    // Settings store is not wired up yet; defaults plus LATCH_LOG* overrides
+    init_logging(&LogConfig::from_env()?)?;
 
     tracing::info!("Latch Engine v{}", latch_core::VERSION);
     tracing::info!("Initializing services...");
@@ -4,8 +4,9 @@
 //!
 //! ## Architecture
 //!
-//! - **Dev mode:** QuickJS for instant hot reload
-//! - **Ship mode:** WASM via AssemblyScript for performance
+//! - **Dev mode:** QuickJS for instant hot reload (`ScriptRuntime::reload`)
+//! - **Ship mode:** WASM via AssemblyScript for performance (`runtime::WasmRuntime`,
+//!   behind the `wasm` feature)
 //! - **FFI:** Zero-copy via SharedArrayBuffer (WASM) or direct array passing (QuickJS)
 //!
 //! See examples/poc4_typescript_logic.rs and examples/poc4_wasm_zero_copy.rs
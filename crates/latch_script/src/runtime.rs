@@ -2,52 +2,193 @@
 //!
 //! Provides a JavaScript runtime for game logic execution.
 //! For the PoC, we keep it simple and expose FFI via manual injection.
+//!
+//! ## Hot reload
+//!
+//! `ScriptRuntime::reload` re-evaluates edited source in the live context.
+//! Globals named by `preserve_global` (`state` by default) are taken out
+//! before the new source runs and put back afterwards, so counters, timers
+//! and other script-side state survive an edit while every function is
+//! replaced. QuickJS rejects a second top-level `let`/`const` of the same
+//! name in one context, so reloadable scripts declare their globals with
+//! `var` or `function`.
 
-use rquickjs::{Context, Runtime};
+use latch_core::ecs::World;
+use rquickjs::{CatchResultExt, CaughtError, Context, Ctx, Function, Runtime, Value};
+use std::cell::Cell;
 use std::path::Path;
+use std::ptr::NonNull;
+use thiserror::Error;
+
+mod ecs;
+
+#[cfg(feature = "wasm")]
+mod wasm;
+
+#[cfg(feature = "wasm")]
+pub use wasm::{WasmError, WasmRuntime};
+
+/// Globals carried across `ScriptRuntime::reload` unless configured otherwise.
+pub const DEFAULT_PRESERVED_GLOBALS: &[&str] = &["state"];
+
+#[derive(Debug, Error)]
+pub enum ScriptError {
+    /// The script threw; `stack` is the JS stack trace when one was recorded.
+    #[error("script error: {message}")]
+    Exception {
+        message: String,
+        stack: Option<String>,
+    },
+    #[error("quickjs error: {0}")]
+    Engine(#[from] rquickjs::Error),
+    #[error("failed to read script: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl ScriptError {
+    fn caught(err: CaughtError<'_>) -> Self {
+        match err {
+            CaughtError::Error(err) => Self::Engine(err),
+            CaughtError::Exception(exception) => Self::Exception {
+                message: exception
+                    .message()
+                    .unwrap_or_else(|| "uncaught exception".to_owned()),
+                stack: exception.stack().filter(|stack| !stack.is_empty()),
+            },
+            value @ CaughtError::Value(_) => Self::Exception {
+                message: value.to_string(),
+                stack: None,
+            },
+        }
+    }
+}
 
 /// Script execution context
 pub struct ScriptRuntime {
     #[allow(dead_code)] // Kept alive for context lifetime
     runtime: Runtime,
     pub context: Context,
+    world: ecs::WorldSlot,
+    preserved: Vec<String>,
 }
 
 impl ScriptRuntime {
-    pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
+    pub fn new() -> Result<Self, ScriptError> {
         let runtime = Runtime::new()?;
         let context = Context::full(&runtime)?;
+        let world = ecs::WorldSlot::default();
+        context.with(|ctx| ecs::install(&ctx, &world))?;
+
+        Ok(Self {
+            runtime,
+            context,
+            world,
+            preserved: DEFAULT_PRESERVED_GLOBALS
+                .iter()
+                .map(|&name| name.to_owned())
+                .collect(),
+        })
+    }
+
+    /// Lend `world` to the `ecs` script API for the duration of `f`.
+    ///
+    /// Scripts run inside `f` (via `execute`, `call_function`, ...) can call
+    /// `ecs.spawn`, `ecs.getComponent`, `ecs.setComponent`,
+    /// `ecs.hasComponent`, `ecs.componentIds` and `ecs.query`; outside it
+    /// those functions throw. The world is released when `f` returns or
+    /// unwinds, so no script can keep a reference past the call.
+    pub fn bind_ecs<R>(&self, world: &mut World, f: impl FnOnce(&Self) -> R) -> R {
+        struct Restore<'a> {
+            slot: &'a Cell<Option<NonNull<World>>>,
+            previous: Option<NonNull<World>>,
+        }
 
-        Ok(Self { runtime, context })
+        impl Drop for Restore<'_> {
+            fn drop(&mut self) {
+                self.slot.set(self.previous);
+            }
+        }
+
+        let _restore = Restore {
+            slot: &self.world,
+            previous: self.world.replace(Some(NonNull::from(world))),
+        };
+        f(self)
     }
 
-    pub fn execute_file(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    pub fn execute_file(&self, path: &Path) -> Result<(), ScriptError> {
         let source = std::fs::read_to_string(path)?;
-        self.execute(&source)?;
-        Ok(())
+        self.execute(&source)
+    }
+
+    pub fn execute(&self, source: &str) -> Result<(), ScriptError> {
+        self.context.with(|ctx| eval(&ctx, source))
+    }
+
+    /// Keep global `name` across `reload` (in addition to `state`).
+    pub fn preserve_global(&mut self, name: impl Into<String>) {
+        let name = name.into();
+        if !self.preserved.contains(&name) {
+            self.preserved.push(name);
+        }
+    }
+
+    /// Globals `reload` carries over, in the order they were added.
+    pub fn preserved_globals(&self) -> &[String] {
+        &self.preserved
     }
 
-    pub fn execute(&self, source: &str) -> Result<(), Box<dyn std::error::Error>> {
+    /// Re-evaluate `source` in the running context, keeping preserved
+    /// globals.
+    ///
+    /// Each preserved global that is defined before the reload is restored
+    /// after the new source ran, replacing whatever the source initialised
+    /// it to; on the first load (nothing defined yet) the source's own value
+    /// stays. If evaluation throws, the error is returned, preserved globals
+    /// are restored and the runtime stays usable: definitions the source
+    /// made before the throw remain replaced, everything after it keeps the
+    /// previous version.
+    pub fn reload(&self, source: &str) -> Result<(), ScriptError> {
         self.context.with(|ctx| {
-            ctx.eval::<(), _>(source)?;
-            Ok::<_, rquickjs::Error>(())
-        })?;
-        Ok(())
+            let globals = ctx.globals();
+            let mut saved = Vec::with_capacity(self.preserved.len());
+            for name in &self.preserved {
+                let value: Value = globals.get(name.as_str())?;
+                if !value.is_undefined() {
+                    saved.push((name.as_str(), value));
+                }
+            }
+
+            let result = eval(&ctx, source);
+            for (name, value) in saved {
+                globals.set(name, value)?;
+            }
+            result
+        })
+    }
+
+    pub fn reload_file(&self, path: &Path) -> Result<(), ScriptError> {
+        let source = std::fs::read_to_string(path)?;
+        self.reload(&source)
     }
 
     /// Call a JavaScript function by name with no arguments.
-    pub fn call_function(&self, name: &str) -> Result<(), Box<dyn std::error::Error>> {
-        self.context
-            .with(|ctx| -> Result<(), Box<dyn std::error::Error>> {
-                let globals = ctx.globals();
-                let func: rquickjs::Function = globals.get(name)?;
-                func.call::<_, ()>(())?;
-                Ok(())
-            })?;
-        Ok(())
+    pub fn call_function(&self, name: &str) -> Result<(), ScriptError> {
+        self.context.with(|ctx| {
+            let func: Function = ctx.globals().get(name)?;
+            func.call::<_, ()>(())
+                .catch(&ctx)
+                .map_err(ScriptError::caught)
+        })
     }
 }
 
+fn eval(ctx: &Ctx<'_>, source: &str) -> Result<(), ScriptError> {
+    ctx.eval::<(), _>(source)
+        .catch(ctx)
+        .map_err(ScriptError::caught)
+}
+
 impl Default for ScriptRuntime {
     fn default() -> Self {
         Self::new().expect("Failed to create script runtime")
@@ -0,0 +1,208 @@
+//! `ecs` global exposing the bound `World` to scripts.
+//!
+//! Entities cross into JS as numbers (`Entity::to_bits`) and component
+//! values as byte arrays in the component's native layout. Every function
+//! throws when called outside `ScriptRuntime::bind_ecs`, and when an entity
+//! handle would not fit a JS number exactly (a slot reused more than 2^21
+//! times).
+//!
+//! ```js
+//! const e = ecs.spawn([POSITION], [[0, 0, 0, 0, 0, 0, 0, 0]]);
+//! for (const entity of ecs.query([POSITION, VELOCITY])) {
+//!     const bytes = ecs.getComponent(entity, POSITION);
+//!     ecs.setComponent(entity, POSITION, bytes);
+//! }
+//! if (ecs.hasComponent(e, VELOCITY)) { /* ... */ }
+//! const ids = ecs.componentIds(e); // no value once `e` is despawned
+//! ```
+
+use latch_core::ecs::{ComponentId, Entity, EntityBuilder, World};
+use rquickjs::{Ctx, Exception, Function, Object};
+use std::cell::Cell;
+use std::ptr::NonNull;
+use std::rc::Rc;
+
+/// Pointer to the world lent to `bind_ecs`; `None` outside that call.
+pub(super) type WorldSlot = Rc<Cell<Option<NonNull<World>>>>;
+
+/// Largest integer a JS number holds exactly.
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+
+pub(super) fn install(ctx: &Ctx<'_>, slot: &WorldSlot) -> rquickjs::Result<()> {
+    let ecs = Object::new(ctx.clone())?;
+
+    let world = slot.clone();
+    ecs.set(
+        "spawn",
+        Function::new(
+            ctx.clone(),
+            move |ctx: Ctx<'_>, ids: Vec<ComponentId>, values: Vec<Vec<u8>>| {
+                with_world(&ctx, &world, |world| {
+                    if ids.len() != values.len() {
+                        return Err(format!(
+                            "spawn: {} component ids but {} values",
+                            ids.len(),
+                            values.len()
+                        ));
+                    }
+                    let mut builder = EntityBuilder::new();
+                    for (id, bytes) in ids.into_iter().zip(values) {
+                        builder = builder
+                            .with_raw_bytes(id, bytes)
+                            .map_err(|err| format!("spawn: {err}"))?;
+                    }
+                    let entity = world
+                        .spawn(builder)
+                        .map_err(|err| format!("spawn: {err}"))?;
+                    entity_to_js(entity).map_err(|err| {
+                        // Scripts never see the handle, so don't leak the entity.
+                        let _ = world.despawn(entity);
+                        format!("spawn: {err}")
+                    })
+                })
+            },
+        )?,
+    )?;
+
+    let world = slot.clone();
+    ecs.set(
+        "getComponent",
+        Function::new(
+            ctx.clone(),
+            move |ctx: Ctx<'_>, entity: f64, id: ComponentId| {
+                with_world(&ctx, &world, |world| {
+                    let entity = entity_from_js(entity)?;
+                    let loc = world
+                        .locate(entity)
+                        .map_err(|err| format!("getComponent: {err}"))?;
+                    let storage = world
+                        .storage(loc.archetype)
+                        .ok_or("getComponent: archetype missing")?;
+                    let column = storage
+                        .column(id)
+                        .map_err(|err| format!("getComponent: {err}"))?;
+                    column
+                        .slice_read(loc.index..loc.index + 1)
+                        .map(<[u8]>::to_vec)
+                        .map_err(|err| format!("getComponent: {err}"))
+                })
+            },
+        )?,
+    )?;
+
+    let world = slot.clone();
+    ecs.set(
+        "setComponent",
+        Function::new(
+            ctx.clone(),
+            move |ctx: Ctx<'_>, entity: f64, id: ComponentId, bytes: Vec<u8>| {
+                with_world(&ctx, &world, |world| {
+                    let entity = entity_from_js(entity)?;
+                    let loc = world
+                        .locate(entity)
+                        .map_err(|err| format!("setComponent: {err}"))?;
+                    let storage = world
+                        .storage_mut(loc.archetype)
+                        .ok_or("setComponent: archetype missing")?;
+                    storage
+                        .write_component(id, loc.index, &bytes, None)
+                        .map_err(|err| format!("setComponent: {err}"))
+                })
+            },
+        )?,
+    )?;
+
+    let world = slot.clone();
+    ecs.set(
+        "hasComponent",
+        Function::new(
+            ctx.clone(),
+            move |ctx: Ctx<'_>, entity: f64, id: ComponentId| {
+                with_world(&ctx, &world, |world| {
+                    Ok(world.has_component(entity_from_js(entity)?, id))
+                })
+            },
+        )?,
+    )?;
+
+    let world = slot.clone();
+    ecs.set(
+        "componentIds",
+        Function::new(ctx.clone(), move |ctx: Ctx<'_>, entity: f64| {
+            with_world(&ctx, &world, |world| {
+                let entity = entity_from_js(entity)?;
+                Ok(world.component_ids_of(entity).map(<[ComponentId]>::to_vec))
+            })
+        })?,
+    )?;
+
+    let world = slot.clone();
+    ecs.set(
+        "query",
+        Function::new(ctx.clone(), move |ctx: Ctx<'_>, ids: Vec<ComponentId>| {
+            with_world(&ctx, &world, |world| {
+                let mut archetypes: Vec<_> = world.archetypes_matching(&ids).collect();
+                archetypes.sort_unstable();
+                let mut entities = Vec::new();
+                for archetype in archetypes {
+                    let Some(storage) = world.storage(archetype) else {
+                        continue;
+                    };
+                    for row in 0..storage.entity_count() {
+                        let id = storage
+                            .entity_id_at(row)
+                            .map_err(|err| format!("query: {err}"))?;
+                        // Rows awaiting despawn no longer resolve.
+                        if let Some(entity) = world.resolve_entity(id) {
+                            let handle =
+                                entity_to_js(entity).map_err(|err| format!("query: {err}"))?;
+                            entities.push(handle);
+                        }
+                    }
+                }
+                Ok(entities)
+            })
+        })?,
+    )?;
+
+    ctx.globals().set("ecs", ecs)
+}
+
+/// Run `f` against the bound world, turning failures into JS exceptions.
+fn with_world<R>(
+    ctx: &Ctx<'_>,
+    slot: &WorldSlot,
+    f: impl FnOnce(&mut World) -> Result<R, String>,
+) -> rquickjs::Result<R> {
+    let Some(mut world) = slot.get() else {
+        return Err(Exception::throw_message(
+            ctx,
+            "ecs: no world bound (call from within ScriptRuntime::bind_ecs)",
+        ));
+    };
+    // SAFETY: the slot is only populated while `bind_ecs` holds the unique
+    // `&mut World`, and this borrow ends before returning to script code.
+    let world = unsafe { world.as_mut() };
+    f(world).map_err(|msg| Exception::throw_message(ctx, &msg))
+}
+
+/// Refuses handles above 2^53 rather than letting JS round them to a
+/// different (possibly live) entity.
+fn entity_to_js(entity: Entity) -> Result<f64, String> {
+    let bits = entity.to_bits();
+    if bits > MAX_SAFE_INTEGER as u64 {
+        return Err(format!(
+            "entity {} (generation {}) does not fit a JS number",
+            entity.index(),
+            entity.generation()
+        ));
+    }
+    Ok(bits as f64)
+}
+
+fn entity_from_js(value: f64) -> Result<Entity, String> {
+    if !(0.0..=MAX_SAFE_INTEGER).contains(&value) || value.fract() != 0.0 {
+        return Err(format!("{value} is not an entity handle"));
+    }
+    Ok(Entity::from_bits(value as u64))
+}
@@ -0,0 +1,151 @@
+//! WASM script runtime (ship mode), backed by the wasmi interpreter.
+//!
+//! Mirrors `ScriptRuntime`: create, load a module, call exported functions.
+//! The host owns the module's linear memory (imported as `env.memory`), so
+//! ECS column bytes can be copied in before a call and read back after it
+//! without any per-entity marshalling.
+
+use std::fmt::Display;
+use std::path::Path;
+use thiserror::Error;
+use wasmi::{Engine, Instance, Linker, Memory, MemoryType, Module, Store, WasmParams, WasmResults};
+
+/// Linear memory page size defined by the WASM spec.
+pub const WASM_PAGE_BYTES: usize = 64 * 1024;
+
+#[derive(Debug, Error)]
+pub enum WasmError {
+    #[error("wasm error: {reason}")]
+    Wasm { reason: Box<str> },
+    #[error("failed to read module: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("no module instantiated")]
+    NotInstantiated,
+    #[error("memory access [{offset}, {offset}+{len}) exceeds linear memory of {size} bytes")]
+    MemoryOutOfBounds {
+        offset: usize,
+        len: usize,
+        size: usize,
+    },
+}
+
+fn wasm_err(err: impl Display) -> WasmError {
+    WasmError::Wasm {
+        reason: err.to_string().into_boxed_str(),
+    }
+}
+
+/// WASM execution context with host-provided linear memory.
+pub struct WasmRuntime {
+    engine: Engine,
+    store: Store<()>,
+    linker: Linker<()>,
+    memory: Memory,
+    instance: Option<Instance>,
+}
+
+impl WasmRuntime {
+    /// Runtime with one page (64 KiB) of host memory.
+    pub fn new() -> Result<Self, WasmError> {
+        Self::with_memory_pages(1, None)
+    }
+
+    /// Runtime whose imported `env.memory` starts at `min_pages` pages.
+    pub fn with_memory_pages(min_pages: u32, max_pages: Option<u32>) -> Result<Self, WasmError> {
+        let engine = Engine::default();
+        let mut store = Store::new(&engine, ());
+        let memory =
+            Memory::new(&mut store, MemoryType::new(min_pages, max_pages)).map_err(wasm_err)?;
+        let mut linker = <Linker<()>>::new(&engine);
+        linker.define("env", "memory", memory).map_err(wasm_err)?;
+        Ok(Self {
+            engine,
+            store,
+            linker,
+            memory,
+            instance: None,
+        })
+    }
+
+    pub fn execute_file(&mut self, path: &Path) -> Result<(), WasmError> {
+        let bytes = std::fs::read(path)?;
+        self.instantiate(&bytes)
+    }
+
+    /// Compile and instantiate a module (binary `.wasm` or WAT text),
+    /// replacing any previously loaded one. The module's start function runs.
+    pub fn instantiate(&mut self, module: &[u8]) -> Result<(), WasmError> {
+        let module = Module::new(&self.engine, module).map_err(wasm_err)?;
+        let instance = self
+            .linker
+            .instantiate_and_start(&mut self.store, &module)
+            .map_err(wasm_err)?;
+        self.instance = Some(instance);
+        Ok(())
+    }
+
+    /// Alias of `instantiate`, matching `ScriptRuntime::execute`.
+    pub fn execute(&mut self, module: &[u8]) -> Result<(), WasmError> {
+        self.instantiate(module)
+    }
+
+    /// Call an exported function taking no arguments.
+    pub fn call_function(&mut self, name: &str) -> Result<(), WasmError> {
+        self.call::<(), ()>(name, ())
+    }
+
+    /// Call an exported function with typed parameters and results.
+    pub fn call<Params, Results>(
+        &mut self,
+        name: &str,
+        params: Params,
+    ) -> Result<Results, WasmError>
+    where
+        Params: WasmParams,
+        Results: WasmResults,
+    {
+        let instance = self.instance.ok_or(WasmError::NotInstantiated)?;
+        let func = instance
+            .get_typed_func::<Params, Results>(&self.store, name)
+            .map_err(wasm_err)?;
+        func.call(&mut self.store, params).map_err(wasm_err)
+    }
+
+    /// Host view of the module's linear memory.
+    pub fn memory(&self) -> &[u8] {
+        self.memory.data(&self.store)
+    }
+
+    pub fn memory_mut(&mut self) -> &mut [u8] {
+        self.memory.data_mut(&mut self.store)
+    }
+
+    /// Copy `bytes` (e.g. `ComponentColumn::slice_read`) into linear memory.
+    pub fn write_memory(&mut self, offset: usize, bytes: &[u8]) -> Result<(), WasmError> {
+        let dst = Self::range_mut(self.memory_mut(), offset, bytes.len())?;
+        dst.copy_from_slice(bytes);
+        Ok(())
+    }
+
+    /// Copy linear memory into `out` (e.g. `ComponentColumn::slice_write`).
+    pub fn read_memory(&self, offset: usize, out: &mut [u8]) -> Result<(), WasmError> {
+        let memory = self.memory();
+        let size = memory.len();
+        let src = memory.get(offset..offset.saturating_add(out.len())).ok_or(
+            WasmError::MemoryOutOfBounds {
+                offset,
+                len: out.len(),
+                size,
+            },
+        )?;
+        out.copy_from_slice(src);
+        Ok(())
+    }
+
+    fn range_mut(memory: &mut [u8], offset: usize, len: usize) -> Result<&mut [u8], WasmError> {
+        let size = memory.len();
+        memory
+            .get_mut(offset..offset.saturating_add(len))
+            .ok_or(WasmError::MemoryOutOfBounds { offset, len, size })
+    }
+}
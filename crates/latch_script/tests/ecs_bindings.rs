@@ -0,0 +1,90 @@
+use latch_core::define_component;
+use latch_core::ecs::{Component, World};
+use latch_script::runtime::ScriptRuntime;
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(C)]
+struct Pos {
+    x: i32,
+    y: i32,
+}
+define_component!(Pos, 9080, "EcsBindingsTest::Pos");
+
+#[test]
+fn scripts_spawn_query_and_mutate_components() {
+    Pos::ensure_registered();
+    let runtime = ScriptRuntime::new().unwrap();
+    runtime
+        .execute(&format!(
+            r#"
+            const POS = {id};
+            function setup() {{
+                ecs.spawn([POS], [[1, 0, 0, 0, 2, 0, 0, 0]]);
+                ecs.spawn([POS], [[3, 0, 0, 0, 4, 0, 0, 0]]);
+            }}
+            function step() {{
+                for (const entity of ecs.query([POS])) {{
+                    const bytes = ecs.getComponent(entity, POS);
+                    bytes[0] += 10;
+                    ecs.setComponent(entity, POS, bytes);
+                }}
+            }}
+            "#,
+            id = Pos::ID
+        ))
+        .unwrap();
+
+    let mut world = World::new();
+    runtime.bind_ecs(&mut world, |rt| {
+        rt.call_function("setup").unwrap();
+        rt.call_function("step").unwrap();
+    });
+    assert_eq!(world.entity_count(), 2);
+
+    let mut xs: Vec<i32> = world
+        .archetypes_with(Pos::ID)
+        .iter()
+        .flat_map(|&arch| world.column::<Pos>(arch).unwrap().iter().map(|p| p.x))
+        .collect();
+    xs.sort_unstable();
+    assert_eq!(xs, [11, 13]);
+}
+
+#[test]
+fn ecs_calls_outside_bind_ecs_throw() {
+    let runtime = ScriptRuntime::new().unwrap();
+    runtime
+        .execute("function orphan() { return ecs.query([1]); }")
+        .unwrap();
+    assert!(runtime.call_function("orphan").is_err());
+}
+
+#[test]
+fn scripts_check_component_presence() {
+    Pos::ensure_registered();
+    let mut world = World::new();
+    let alive = latch_core::spawn!(world, Pos { x: 1, y: 2 });
+    let stale = latch_core::spawn!(world, Pos { x: 3, y: 4 });
+    world.despawn(stale).unwrap();
+    world.flush_despawns().unwrap();
+
+    let runtime = ScriptRuntime::new().unwrap();
+    runtime.bind_ecs(&mut world, |rt| {
+        rt.execute(&format!(
+            r#"
+            const POS = {id};
+            if (!ecs.hasComponent({alive}, POS)) throw new Error("alive lacks POS");
+            if (ecs.hasComponent({alive}, POS + 1)) throw new Error("alive has POS + 1");
+            if (ecs.hasComponent({stale}, POS)) throw new Error("stale has POS");
+            const ids = ecs.componentIds({alive});
+            if (ids.length !== 1 || ids[0] !== POS) throw new Error("wrong ids " + ids);
+            if (ecs.componentIds({stale}) != null) throw new Error("stale has ids");
+            "#,
+            id = Pos::ID,
+            alive = alive.to_bits(),
+            stale = stale.to_bits(),
+        ))
+        .unwrap();
+    });
+}
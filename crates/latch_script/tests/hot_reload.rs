@@ -0,0 +1,51 @@
+use latch_script::runtime::{ScriptError, ScriptRuntime};
+
+const V1: &str = r#"
+    var state = { count: 0 };
+    var step = 1;
+    function tick() { state.count += step; }
+"#;
+
+const V2: &str = r#"
+    var state = { count: 100 };
+    var step = 10;
+    function tick() { state.count += step; }
+"#;
+
+fn count(runtime: &ScriptRuntime) -> i32 {
+    runtime
+        .context
+        .with(|ctx| ctx.eval::<i32, _>("state.count"))
+        .unwrap()
+}
+
+#[test]
+fn reload_replaces_functions_and_keeps_state() {
+    let runtime = ScriptRuntime::new().unwrap();
+    runtime.reload(V1).unwrap();
+    runtime.call_function("tick").unwrap();
+    runtime.call_function("tick").unwrap();
+    assert_eq!(count(&runtime), 2);
+
+    runtime.reload(V2).unwrap();
+    assert_eq!(count(&runtime), 2);
+    runtime.call_function("tick").unwrap();
+    assert_eq!(count(&runtime), 12);
+}
+
+#[test]
+fn failed_reload_reports_and_keeps_running() {
+    let mut runtime = ScriptRuntime::new().unwrap();
+    runtime.preserve_global("step");
+    runtime.execute(V1).unwrap();
+    runtime.call_function("tick").unwrap();
+
+    let err = runtime
+        .reload("var state = {}; var step = 5; throw new Error('boom');")
+        .unwrap_err();
+    assert!(matches!(err, ScriptError::Exception { ref message, .. } if message == "boom"));
+    assert!(runtime.reload("function tick( {").is_err());
+
+    runtime.call_function("tick").unwrap();
+    assert_eq!(count(&runtime), 2);
+}
@@ -0,0 +1,65 @@
+#![cfg(feature = "wasm")]
+
+use latch_script::runtime::{WasmError, WasmRuntime};
+
+/// `updatePositions` from the poc3 WASM example.
+const UPDATE_POSITIONS: &str = r#"
+(module
+  (import "env" "memory" (memory 1))
+  (func $updatePositions (export "updatePositions")
+    (param $pos_offset i32) (param $vel_offset i32) (param $count i32) (param $dt f32)
+    (local $i i32) (local $idx i32) (local $x i32) (local $y i32) (local $vx i32) (local $vy i32)
+    (loop $continue
+      (local.set $idx (i32.mul (local.get $i) (i32.const 8)))
+      (local.set $x (i32.load (i32.add (local.get $pos_offset) (local.get $idx))))
+      (local.set $y (i32.load (i32.add (local.get $pos_offset) (i32.add (local.get $idx) (i32.const 4)))))
+      (local.set $vx (i32.load (i32.add (local.get $vel_offset) (local.get $idx))))
+      (local.set $vy (i32.load (i32.add (local.get $vel_offset) (i32.add (local.get $idx) (i32.const 4)))))
+      (local.set $x (i32.add (local.get $x)
+        (i32.trunc_f32_s (f32.mul (f32.convert_i32_s (local.get $vx)) (local.get $dt)))))
+      (local.set $y (i32.add (local.get $y)
+        (i32.trunc_f32_s (f32.mul (f32.convert_i32_s (local.get $vy)) (local.get $dt)))))
+      (i32.store (i32.add (local.get $pos_offset) (local.get $idx)) (local.get $x))
+      (i32.store (i32.add (local.get $pos_offset) (i32.add (local.get $idx) (i32.const 4))) (local.get $y))
+      (local.set $i (i32.add (local.get $i) (i32.const 1)))
+      (br_if $continue (i32.lt_u (local.get $i) (local.get $count)))
+    )
+  )
+)
+"#;
+
+fn le_bytes(values: &[i32]) -> Vec<u8> {
+    values.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+#[test]
+fn update_positions_mutates_host_memory() {
+    let mut runtime = WasmRuntime::new().unwrap();
+    runtime.instantiate(UPDATE_POSITIONS.as_bytes()).unwrap();
+
+    let positions = le_bytes(&[0, 0, 10, 20]);
+    let velocities = le_bytes(&[100, 50, -100, 0]);
+    runtime.write_memory(0, &positions).unwrap();
+    runtime.write_memory(16, &velocities).unwrap();
+
+    runtime
+        .call::<(i32, i32, i32, f32), ()>("updatePositions", (0, 16, 2, 0.5))
+        .unwrap();
+
+    let mut out = [0u8; 16];
+    runtime.read_memory(0, &mut out).unwrap();
+    assert_eq!(out.to_vec(), le_bytes(&[50, 25, -40, 20]));
+}
+
+#[test]
+fn calls_before_instantiation_and_out_of_bounds_access_fail() {
+    let mut runtime = WasmRuntime::new().unwrap();
+    assert!(matches!(
+        runtime.call_function("updatePositions"),
+        Err(WasmError::NotInstantiated)
+    ));
+    assert!(matches!(
+        runtime.write_memory(64 * 1024 - 2, &[0; 4]),
+        Err(WasmError::MemoryOutOfBounds { .. })
+    ));
+}
@@ -1,5 +1,9 @@
 //! Input abstraction and recording for replays
 
+use latch_core::time::{ActionId, ActionState};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
 /// Input state (placeholder)
 #[derive(Debug, Clone, Copy)]
 pub struct InputState {
@@ -15,3 +19,67 @@ impl Default for InputState {
         }
     }
 }
+
+/// Keybindings: action name to the names of the physical inputs bound to it.
+///
+/// Stored by name (e.g. `"jump" -> ["Space", "GamepadSouth"]`) so saved
+/// settings stay valid when the game reorders its actions.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InputMap {
+    pub bindings: BTreeMap<String, Vec<String>>,
+}
+
+impl InputMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `input` to the bindings of `action` (no-op if already bound).
+    pub fn bind(&mut self, action: impl Into<String>, input: impl Into<String>) {
+        let inputs = self.bindings.entry(action.into()).or_default();
+        let input = input.into();
+        if !inputs.contains(&input) {
+            inputs.push(input);
+        }
+    }
+
+    /// Remove every binding of `action`.
+    pub fn unbind(&mut self, action: &str) {
+        self.bindings.remove(action);
+    }
+
+    /// Inputs bound to `action`, empty if none.
+    pub fn inputs_for(&self, action: &str) -> &[String] {
+        self.bindings.get(action).map_or(&[], Vec::as_slice)
+    }
+
+    /// Action bound to `input`, if any.
+    pub fn action_for(&self, input: &str) -> Option<&str> {
+        self.bindings
+            .iter()
+            .find(|(_, inputs)| inputs.iter().any(|bound| bound == input))
+            .map(|(action, _)| action.as_str())
+    }
+
+    /// Resolve the physical inputs held this tick into the `ActionState`
+    /// recorded in `TickInput::actions`.
+    ///
+    /// `actions` is the game's fixed action list: action `actions[n]` is
+    /// `ActionId(n)`, so replays follow that order rather than binding
+    /// names. Actions past `ActionId::MAX` and held inputs bound to no
+    /// listed action are ignored.
+    pub fn action_state(&self, actions: &[&str], held: &[&str], pointer: [f32; 2]) -> ActionState {
+        let mut state = ActionState {
+            pointer,
+            ..ActionState::default()
+        };
+        for (index, action) in actions.iter().take(ActionId::MAX).enumerate() {
+            let pressed = self
+                .inputs_for(action)
+                .iter()
+                .any(|input| held.contains(&input.as_str()));
+            state.set_pressed(ActionId(index as u8), pressed);
+        }
+        state
+    }
+}
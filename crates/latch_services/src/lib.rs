@@ -5,6 +5,8 @@
 pub mod input;
 pub mod save;
 pub mod settings;
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_support;
 
 /// Service initialization (placeholder)
 pub fn init_services() {
@@ -1,4 +1,282 @@
 //! Save system abstraction
+//!
+//! A save file is a short header followed by tagged chunks:
+//!
+//! - magic `SAVE_MAGIC` and container version (`u16`), then two reserved
+//!   bytes, all little-endian;
+//! - per chunk: a fourcc `ChunkId`, flags (`u32`), payload length (`u64`)
+//!   and the payload.
+//!
+//! Readers skip chunks they do not know unless the writer flagged them
+//! `CHUNK_FLAG_REQUIRED`, so new optional data (metadata fields, thumbnails,
+//! editor state) can be added without breaking older builds, and older
+//! saves missing an optional chunk still load in newer ones. Only a change
+//! to the container itself bumps `SAVE_VERSION`. The world is always a
+//! required `CHUNK_WORLD` holding a `World::snapshot`, which carries the
+//! `PhysicsConfig`, `CollisionConfig` and `Rng` resources along with the
+//! entities.
+
+use latch_core::ecs::{SnapshotCompression, SnapshotError, World};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Magic bytes at the start of a save file.
+pub const SAVE_MAGIC: [u8; 4] = *b"LSAV";
+/// Container version written by `SaveWriter`.
+pub const SAVE_VERSION: u16 = 1;
+/// Chunk flag: a reader that does not know the chunk must reject the save.
+pub const CHUNK_FLAG_REQUIRED: u32 = 1 << 0;
+
+/// World snapshot; required.
+pub const CHUNK_WORLD: ChunkId = *b"WRLD";
+/// `SaveMetadata` as JSON.
+pub const CHUNK_METADATA: ChunkId = *b"META";
+/// Encoded preview image for save slot menus.
+pub const CHUNK_THUMBNAIL: ChunkId = *b"THMB";
+
+/// Chunks this build understands.
+const KNOWN_CHUNKS: [ChunkId; 3] = [CHUNK_WORLD, CHUNK_METADATA, CHUNK_THUMBNAIL];
+
+const HEADER_LEN: usize = 8;
+const CHUNK_HEADER_LEN: usize = 16;
+
+/// Four-character chunk tag, e.g. `*b"WRLD"`.
+pub type ChunkId = [u8; 4];
+
+#[derive(Debug, Error)]
+pub enum SaveError {
+    #[error("not a save file (bad magic)")]
+    BadMagic,
+    #[error("save container version {found} is newer than supported version {supported}")]
+    NewerVersion { found: u16, supported: u16 },
+    #[error("save data truncated: needed {needed} bytes, {available} available")]
+    Truncated { needed: u64, available: u64 },
+    #[error("save has required chunk {} this build cannot read", display_id(.0))]
+    UnknownRequiredChunk(ChunkId),
+    #[error("save has no {} chunk", display_id(.0))]
+    MissingChunk(ChunkId),
+    #[error("chunk {} appears twice", display_id(.0))]
+    DuplicateChunk(ChunkId),
+    #[error("save metadata is not valid JSON: {0}")]
+    Metadata(#[from] serde_json::Error),
+    #[error(transparent)]
+    Snapshot(#[from] SnapshotError),
+}
+
+fn display_id(id: &ChunkId) -> String {
+    String::from_utf8_lossy(id).into_owned()
+}
+
+/// Descriptive data shown in load menus.
+///
+/// Every field defaults when absent and unknown fields are ignored, so
+/// metadata written by newer or older builds still loads.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SaveMetadata {
+    /// Player-facing slot name.
+    pub name: String,
+    /// Seconds since the Unix epoch when the save was written.
+    pub saved_at: u64,
+    /// Total play time in seconds.
+    pub play_time_secs: u64,
+}
+
+/// One chunk of a save file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Chunk<'a> {
+    pub id: ChunkId,
+    pub flags: u32,
+    pub payload: &'a [u8],
+}
+
+impl Chunk<'_> {
+    pub fn is_required(&self) -> bool {
+        self.flags & CHUNK_FLAG_REQUIRED != 0
+    }
+
+    /// Whether this build knows how to read the chunk.
+    pub fn is_known(&self) -> bool {
+        KNOWN_CHUNKS.contains(&self.id)
+    }
+}
+
+/// Builds a save file chunk by chunk.
+///
+/// Chunks are written in call order; each id may appear once.
+#[derive(Debug)]
+pub struct SaveWriter {
+    bytes: Vec<u8>,
+    ids: Vec<ChunkId>,
+}
+
+impl SaveWriter {
+    pub fn new() -> Self {
+        let mut bytes = Vec::with_capacity(HEADER_LEN);
+        bytes.extend_from_slice(&SAVE_MAGIC);
+        bytes.extend_from_slice(&SAVE_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&[0; 2]);
+        Self {
+            bytes,
+            ids: Vec::new(),
+        }
+    }
+
+    /// Append a chunk with `flags` (`CHUNK_FLAG_REQUIRED` or 0).
+    pub fn write_chunk(
+        &mut self,
+        id: ChunkId,
+        flags: u32,
+        payload: &[u8],
+    ) -> Result<&mut Self, SaveError> {
+        if self.ids.contains(&id) {
+            return Err(SaveError::DuplicateChunk(id));
+        }
+        self.ids.push(id);
+        self.bytes.reserve(CHUNK_HEADER_LEN + payload.len());
+        self.bytes.extend_from_slice(&id);
+        self.bytes.extend_from_slice(&flags.to_le_bytes());
+        self.bytes
+            .extend_from_slice(&(payload.len() as u64).to_le_bytes());
+        self.bytes.extend_from_slice(payload);
+        Ok(self)
+    }
+
+    /// Snapshot `world` into the required `CHUNK_WORLD`.
+    pub fn write_world(
+        &mut self,
+        world: &World,
+        compression: SnapshotCompression,
+    ) -> Result<&mut Self, SaveError> {
+        let snapshot = world.snapshot(compression)?;
+        self.write_chunk(CHUNK_WORLD, CHUNK_FLAG_REQUIRED, &snapshot)
+    }
+
+    pub fn write_metadata(&mut self, metadata: &SaveMetadata) -> Result<&mut Self, SaveError> {
+        let json = serde_json::to_vec(metadata)?;
+        self.write_chunk(CHUNK_METADATA, 0, &json)
+    }
+
+    /// Store an already encoded preview image (e.g. PNG bytes).
+    pub fn write_thumbnail(&mut self, image: &[u8]) -> Result<&mut Self, SaveError> {
+        self.write_chunk(CHUNK_THUMBNAIL, 0, image)
+    }
+
+    /// The finished file.
+    pub fn finish(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+impl Default for SaveWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Parsed chunk table of a save file, borrowing the file bytes.
+///
+/// `new` validates the header and every chunk boundary up front and
+/// rejects unknown required chunks; unknown optional ones stay listed by
+/// `chunks` but are otherwise ignored.
+#[derive(Debug)]
+pub struct SaveReader<'a> {
+    version: u16,
+    chunks: Vec<Chunk<'a>>,
+}
+
+impl<'a> SaveReader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Result<Self, SaveError> {
+        let header = take(bytes, 0, HEADER_LEN)?;
+        if header[0..4] != SAVE_MAGIC {
+            return Err(SaveError::BadMagic);
+        }
+        let version = u16::from_le_bytes([header[4], header[5]]);
+        if version > SAVE_VERSION {
+            return Err(SaveError::NewerVersion {
+                found: version,
+                supported: SAVE_VERSION,
+            });
+        }
+
+        let mut chunks: Vec<Chunk<'a>> = Vec::new();
+        let mut offset = HEADER_LEN;
+        while offset < bytes.len() {
+            let header = take(bytes, offset, CHUNK_HEADER_LEN)?;
+            offset += CHUNK_HEADER_LEN;
+            let id: ChunkId = header[0..4].try_into().unwrap();
+            let flags = u32::from_le_bytes(header[4..8].try_into().unwrap());
+            let len = u64::from_le_bytes(header[8..16].try_into().unwrap());
+            let available = (bytes.len() - offset) as u64;
+            if len > available {
+                return Err(SaveError::Truncated {
+                    needed: len,
+                    available,
+                });
+            }
+            let payload = &bytes[offset..offset + len as usize];
+            offset += len as usize;
+
+            let chunk = Chunk { id, flags, payload };
+            if chunk.is_required() && !chunk.is_known() {
+                return Err(SaveError::UnknownRequiredChunk(id));
+            }
+            if chunks.iter().any(|seen| seen.id == id) {
+                return Err(SaveError::DuplicateChunk(id));
+            }
+            chunks.push(chunk);
+        }
+        Ok(Self { version, chunks })
+    }
+
+    /// Container version the file was written with.
+    pub fn version(&self) -> u16 {
+        self.version
+    }
+
+    /// Every chunk in file order, including ones this build skips.
+    pub fn chunks(&self) -> impl Iterator<Item = &Chunk<'a>> + '_ {
+        self.chunks.iter()
+    }
+
+    /// Optional chunks this build does not understand.
+    pub fn skipped_chunks(&self) -> impl Iterator<Item = &Chunk<'a>> + '_ {
+        self.chunks.iter().filter(|chunk| !chunk.is_known())
+    }
+
+    pub fn chunk(&self, id: ChunkId) -> Option<&Chunk<'a>> {
+        self.chunks.iter().find(|chunk| chunk.id == id)
+    }
+
+    /// Restore `world` from the `CHUNK_WORLD` snapshot, including its
+    /// physics and collision configs.
+    pub fn restore_world(&self, world: &mut World) -> Result<(), SaveError> {
+        let chunk = self
+            .chunk(CHUNK_WORLD)
+            .ok_or(SaveError::MissingChunk(CHUNK_WORLD))?;
+        world.restore(chunk.payload)?;
+        Ok(())
+    }
+
+    /// `None` for saves written without metadata.
+    pub fn metadata(&self) -> Result<Option<SaveMetadata>, SaveError> {
+        self.chunk(CHUNK_METADATA)
+            .map(|chunk| serde_json::from_slice(chunk.payload))
+            .transpose()
+            .map_err(SaveError::from)
+    }
+
+    pub fn thumbnail(&self) -> Option<&'a [u8]> {
+        self.chunk(CHUNK_THUMBNAIL).map(|chunk| chunk.payload)
+    }
+}
+
+fn take(bytes: &[u8], offset: usize, len: usize) -> Result<&[u8], SaveError> {
+    bytes.get(offset..offset + len).ok_or(SaveError::Truncated {
+        needed: len as u64,
+        available: bytes.len().saturating_sub(offset) as u64,
+    })
+}
 
 /// Save slot
 pub struct SaveSlot {
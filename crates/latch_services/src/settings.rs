@@ -1,35 +1,207 @@
 //! Settings management
 
+use crate::input::InputMap;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum SettingsError {
+    #[error("settings are not valid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("settings schema version {found} is newer than supported version {supported}")]
+    NewerSchema { found: u32, supported: u32 },
+}
 
 /// Engine settings
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Saved files carry `schema_version`. Loading a file from an older schema
+/// fills every key it lacks from the defaults, so adding a setting never
+/// invalidates existing saves.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Settings {
+    pub schema_version: u32,
     pub graphics: GraphicsSettings,
     pub audio: AudioSettings,
+    /// Keybindings (added in schema version 2).
+    pub input: InputMap,
+    /// Log filtering and output (added in schema version 4).
+    pub logging: LoggingSettings,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GraphicsSettings {
     pub resolution_width: u32,
     pub resolution_height: u32,
     pub fullscreen: bool,
+    /// Quality preset shown to players (added in schema version 3).
+    pub quality: GraphicsQuality,
+    /// Values used when `quality` is `Custom` (added in schema version 3).
+    pub custom_quality: CustomQuality,
+}
+
+/// Renderer quality preset; `latch_render::QualityPreset` maps it to
+/// concrete device and pipeline settings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphicsQuality {
+    Low,
+    #[default]
+    Medium,
+    High,
+    /// Use `GraphicsSettings::custom_quality`.
+    Custom,
+}
+
+/// Individual renderer settings behind `GraphicsQuality::Custom`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomQuality {
+    /// MSAA samples per pixel (1 disables multisampling).
+    pub msaa_samples: u32,
+    /// Wait for vertical blank instead of presenting uncapped.
+    pub vsync: bool,
+    /// Added to the mip level chosen by texture samplers; positive values
+    /// blur distant textures and save bandwidth.
+    pub texture_mip_bias: f32,
+    /// Upper bound on instances drawn per frame.
+    pub max_instances: u32,
+}
+
+impl Default for CustomQuality {
+    fn default() -> Self {
+        Self {
+            msaa_samples: 4,
+            vsync: true,
+            texture_mip_bias: 0.0,
+            max_instances: 1_000_000,
+        }
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AudioSettings {
     pub master_volume: f32,
 }
 
+/// How the runtime logs; `latch_runtime::LogConfig::from_settings` turns
+/// it into a subscriber configuration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoggingSettings {
+    /// `tracing` filter directives, e.g. `"info,latch_net=debug"`.
+    pub filter: String,
+    pub format: LogFormat,
+    /// Also append log lines to this file.
+    pub file: Option<String>,
+}
+
+impl Default for LoggingSettings {
+    fn default() -> Self {
+        Self {
+            filter: "info".into(),
+            format: LogFormat::default(),
+            file: None,
+        }
+    }
+}
+
+/// Log line format.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Human-readable lines for terminals.
+    #[default]
+    Pretty,
+    /// One JSON object per line, for servers and log collectors.
+    Json,
+}
+
+impl Settings {
+    /// Schema written by `to_json`.
+    ///
+    /// 1. graphics and audio (files without a `schema_version` key)
+    /// 2. `schema_version` and `input` keybindings
+    /// 3. graphics `quality` preset and `custom_quality`
+    /// 4. `logging`
+    pub const SCHEMA_VERSION: u32 = 4;
+
+    /// Schema version of this value (`SCHEMA_VERSION` once loaded or created).
+    #[inline]
+    pub fn schema_version(&self) -> u32 {
+        self.schema_version
+    }
+
+    pub fn to_json(&self) -> Result<String, SettingsError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Load settings, migrating older schemas with `Settings::default()`.
+    pub fn from_json(json: &str) -> Result<Self, SettingsError> {
+        Self::from_json_with_defaults(json, &Self::default())
+    }
+
+    /// Load settings, filling keys missing from `json` with the game's own
+    /// `defaults`. Keys present in `json` always win; actions bound only in
+    /// `defaults` are added to the loaded keybindings.
+    pub fn from_json_with_defaults(json: &str, defaults: &Settings) -> Result<Self, SettingsError> {
+        let saved: Value = serde_json::from_str(json)?;
+        let found = saved
+            .get("schema_version")
+            .and_then(Value::as_u64)
+            .map_or(1, |version| version as u32);
+        if found > Self::SCHEMA_VERSION {
+            return Err(SettingsError::NewerSchema {
+                found,
+                supported: Self::SCHEMA_VERSION,
+            });
+        }
+
+        let mut merged = serde_json::to_value(defaults)?;
+        merge_json(&mut merged, saved);
+        let mut settings: Settings = serde_json::from_value(merged)?;
+        if found < Self::SCHEMA_VERSION {
+            tracing::info!(
+                from = found,
+                to = Self::SCHEMA_VERSION,
+                "migrated settings schema"
+            );
+        }
+        settings.schema_version = Self::SCHEMA_VERSION;
+        Ok(settings)
+    }
+}
+
+/// Overlay `saved` onto `base`, recursing into objects so nested keys missing
+/// from `saved` keep their default.
+fn merge_json(base: &mut Value, saved: Value) {
+    match (base, saved) {
+        (Value::Object(base), Value::Object(saved)) => {
+            for (key, value) in saved {
+                match base.get_mut(&key) {
+                    Some(slot) => merge_json(slot, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, saved) => *base = saved,
+    }
+}
+
 impl Default for Settings {
     fn default() -> Self {
         Self {
+            schema_version: Self::SCHEMA_VERSION,
             graphics: GraphicsSettings {
                 resolution_width: 1280,
                 resolution_height: 720,
                 fullscreen: false,
+                quality: GraphicsQuality::default(),
+                custom_quality: CustomQuality::default(),
             },
             audio: AudioSettings { master_volume: 1.0 },
+            input: InputMap::default(),
+            logging: LoggingSettings::default(),
         }
     }
 }
@@ -0,0 +1,71 @@
+//! Round-trip helpers for settings and keybinding tests
+//!
+//! Serialize through an in-memory buffer exactly as a save file would be
+//! written, read it back, and check that nothing was lost on the way.
+
+use crate::input::InputMap;
+use crate::settings::Settings;
+use serde_json::Value;
+
+/// Write `settings` to a byte buffer and load it back through migration.
+pub fn round_trip_settings(settings: &Settings) -> Settings {
+    let buffer = settings.to_json().expect("settings serialize").into_bytes();
+    let json = std::str::from_utf8(&buffer).expect("settings are UTF-8");
+    Settings::from_json(json).expect("settings deserialize")
+}
+
+/// Write `map` to a byte buffer and read it back.
+pub fn round_trip_input_map(map: &InputMap) -> InputMap {
+    let buffer = serde_json::to_vec(map).expect("input map serializes");
+    serde_json::from_slice(&buffer).expect("input map deserializes")
+}
+
+/// Dotted paths of every leaf key in the JSON form of `value`.
+pub fn key_paths<T: serde::Serialize>(value: &T) -> Vec<String> {
+    let mut paths = Vec::new();
+    collect_paths(
+        &serde_json::to_value(value).expect("value serializes"),
+        String::new(),
+        &mut paths,
+    );
+    paths.sort();
+    paths
+}
+
+/// Panic unless `settings` survives a round trip with every key and value.
+pub fn assert_settings_round_trip(settings: &Settings) {
+    let loaded = round_trip_settings(settings);
+    assert_eq!(
+        key_paths(settings),
+        key_paths(&loaded),
+        "settings keys lost in round trip"
+    );
+    assert_eq!(settings, &loaded, "settings values changed in round trip");
+}
+
+/// Panic unless `map` survives a round trip with every binding.
+pub fn assert_input_map_round_trip(map: &InputMap) {
+    let loaded = round_trip_input_map(map);
+    assert_eq!(
+        key_paths(map),
+        key_paths(&loaded),
+        "keybindings lost in round trip"
+    );
+    assert_eq!(map, &loaded, "keybindings changed in round trip");
+}
+
+fn collect_paths(value: &Value, prefix: String, out: &mut Vec<String>) {
+    match value {
+        Value::Object(fields) if !fields.is_empty() => {
+            for (key, field) in fields {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                collect_paths(field, path, out);
+            }
+        }
+        _ => out.push(prefix),
+    }
+}
@@ -0,0 +1,27 @@
+use latch_core::time::ActionId;
+use latch_services::input::InputMap;
+
+const ACTIONS: [&str; 3] = ["move", "jump", "fire"];
+
+#[test]
+fn held_inputs_resolve_to_action_ids_in_list_order() {
+    let mut map = InputMap::new();
+    map.bind("jump", "Space");
+    map.bind("jump", "GamepadSouth");
+    map.bind("fire", "MouseLeft");
+    map.bind("crouch", "Ctrl");
+
+    let state = map.action_state(&ACTIONS, &["GamepadSouth", "Ctrl"], [0.5, -0.25]);
+    assert!(!state.is_pressed(ActionId(0)));
+    assert!(state.is_pressed(ActionId(1)));
+    assert!(!state.is_pressed(ActionId(2)));
+    assert_eq!(state.pressed.count_ones(), 1);
+    assert_eq!(state.pointer, [0.5, -0.25]);
+
+    // Rebinding changes the inputs, not the recorded action ids.
+    map.unbind("jump");
+    map.bind("jump", "KeyW");
+    let rebound = map.action_state(&ACTIONS, &["KeyW", "MouseLeft"], [0.0, 0.0]);
+    assert!(rebound.is_pressed(ActionId(1)));
+    assert!(rebound.is_pressed(ActionId(2)));
+}
@@ -0,0 +1,117 @@
+use latch_core::define_component;
+use latch_core::ecs::{Component, SnapshotCompression, World};
+use latch_core::physics::CollisionConfig;
+use latch_core::spawn;
+use latch_services::save::{
+    SaveError, SaveMetadata, SaveReader, SaveWriter, CHUNK_FLAG_REQUIRED, CHUNK_THUMBNAIL,
+    CHUNK_WORLD,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Gold(u32);
+define_component!(Gold, 9300, "SaveFormatTest::Gold");
+
+fn world_with_gold() -> World {
+    let mut world = World::new();
+    spawn!(world, Gold(120));
+    spawn!(world, Gold(7));
+    world
+}
+
+#[test]
+fn reader_skips_unknown_optional_chunk() {
+    let world = world_with_gold();
+    let metadata = SaveMetadata {
+        name: "Harbor".into(),
+        saved_at: 1_700_000_000,
+        play_time_secs: 3600,
+    };
+    let mut writer = SaveWriter::new();
+    writer
+        .write_metadata(&metadata)
+        .unwrap()
+        // Written by some newer build; this one has never heard of it.
+        .write_chunk(*b"EDTR", 0, b"camera bookmarks")
+        .unwrap()
+        .write_world(&world, SnapshotCompression::None)
+        .unwrap()
+        .write_thumbnail(&[0x89, b'P', b'N', b'G'])
+        .unwrap();
+    let bytes = writer.finish();
+
+    let reader = SaveReader::new(&bytes).unwrap();
+    let ids: Vec<_> = reader.chunks().map(|chunk| chunk.id).collect();
+    assert_eq!(ids, [*b"META", *b"EDTR", CHUNK_WORLD, CHUNK_THUMBNAIL]);
+    let skipped: Vec<_> = reader.skipped_chunks().map(|chunk| chunk.id).collect();
+    assert_eq!(skipped, [*b"EDTR"]);
+    assert_eq!(reader.thumbnail(), Some(&[0x89, b'P', b'N', b'G'][..]));
+    assert_eq!(reader.metadata().unwrap(), Some(metadata));
+
+    let mut restored = World::new();
+    reader.restore_world(&mut restored).unwrap();
+    let mut gold = Vec::new();
+    restored.for_each_row(Gold::id(), |_, bytes| {
+        gold.push(u32::from_ne_bytes(bytes.try_into().unwrap()))
+    });
+    gold.sort_unstable();
+    assert_eq!(gold, [7, 120]);
+}
+
+#[test]
+fn reader_rejects_unknown_required_chunk() {
+    let mut writer = SaveWriter::new();
+    writer
+        .write_world(&world_with_gold(), SnapshotCompression::None)
+        .unwrap()
+        .write_chunk(*b"PHYS", CHUNK_FLAG_REQUIRED, &[1, 2, 3])
+        .unwrap();
+    let bytes = writer.finish();
+
+    assert!(matches!(
+        SaveReader::new(&bytes),
+        Err(SaveError::UnknownRequiredChunk(id)) if id == *b"PHYS"
+    ));
+}
+
+#[test]
+fn save_without_world_chunk_cannot_be_restored() {
+    let mut writer = SaveWriter::new();
+    writer.write_thumbnail(&[1]).unwrap();
+    let bytes = writer.finish();
+
+    let reader = SaveReader::new(&bytes).unwrap();
+    assert_eq!(reader.metadata().unwrap(), None);
+    assert!(matches!(
+        reader.restore_world(&mut World::new()),
+        Err(SaveError::MissingChunk(id)) if id == CHUNK_WORLD
+    ));
+    assert!(matches!(
+        SaveReader::new(&bytes[..bytes.len() - 1]),
+        Err(SaveError::Truncated { .. })
+    ));
+}
+
+#[test]
+fn save_round_trips_collision_config() {
+    let mut world = world_with_gold();
+    let recorded = CollisionConfig {
+        iterations: 3,
+        correction: 0.75,
+        contact_damping: 0.5,
+    };
+    world.insert_resource(recorded);
+    let mut writer = SaveWriter::new();
+    writer
+        .write_world(&world, SnapshotCompression::Best)
+        .unwrap();
+    let bytes = writer.finish();
+
+    let mut loaded = World::new();
+    loaded.insert_resource(CollisionConfig::default());
+    SaveReader::new(&bytes)
+        .unwrap()
+        .restore_world(&mut loaded)
+        .unwrap();
+    assert_eq!(loaded.resource::<CollisionConfig>(), Some(&recorded));
+    assert_eq!(loaded.entity_count(), 2);
+}
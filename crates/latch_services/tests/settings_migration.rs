@@ -0,0 +1,84 @@
+use latch_services::input::InputMap;
+use latch_services::settings::{
+    GraphicsQuality, LogFormat, LoggingSettings, Settings, SettingsError,
+};
+use latch_services::test_support::{
+    assert_input_map_round_trip, assert_settings_round_trip, key_paths,
+};
+
+#[test]
+fn settings_and_keybindings_round_trip() {
+    let mut settings = Settings::default();
+    settings.graphics.fullscreen = true;
+    settings.audio.master_volume = 0.25;
+    settings.input.bind("jump", "Space");
+    settings.input.bind("jump", "GamepadSouth");
+    settings.input.bind("fire", "MouseLeft");
+
+    assert_settings_round_trip(&settings);
+    assert_input_map_round_trip(&settings.input);
+    assert_eq!(settings.input.action_for("GamepadSouth"), Some("jump"));
+}
+
+#[test]
+fn version_one_file_migrates_with_defaults() {
+    let v1 = r#"{
+        "graphics": { "resolution_width": 1920, "resolution_height": 1080 },
+        "audio": { "master_volume": 0.5 }
+    }"#;
+
+    let mut defaults = Settings::default();
+    defaults.input.bind("jump", "Space");
+
+    let settings = Settings::from_json_with_defaults(v1, &defaults).unwrap();
+    assert_eq!(settings.schema_version(), Settings::SCHEMA_VERSION);
+    assert_eq!(settings.graphics.resolution_width, 1920);
+    assert!(!settings.graphics.fullscreen);
+    assert_eq!(settings.graphics.quality, GraphicsQuality::Medium);
+    assert_eq!(settings.audio.master_volume, 0.5);
+    assert_eq!(settings.input.inputs_for("jump"), ["Space"]);
+    assert_eq!(settings.logging, LoggingSettings::default());
+    assert_eq!(key_paths(&settings), key_paths(&defaults));
+}
+
+#[test]
+fn logging_settings_load_from_json() {
+    let v3 = r#"{
+        "schema_version": 3,
+        "logging": { "filter": "warn,latch_net=debug", "format": "json" }
+    }"#;
+
+    let settings = Settings::from_json(v3).unwrap();
+    assert_eq!(settings.logging.filter, "warn,latch_net=debug");
+    assert_eq!(settings.logging.format, LogFormat::Json);
+    assert_eq!(settings.logging.file, None);
+}
+
+#[test]
+fn saved_bindings_replace_default_bindings() {
+    let mut defaults = Settings::default();
+    defaults.input.bind("jump", "Space");
+    defaults.input.bind("fire", "MouseLeft");
+
+    let mut saved = Settings::default();
+    saved.input.bind("jump", "KeyW");
+
+    let json = saved.to_json().unwrap();
+    let loaded = Settings::from_json_with_defaults(&json, &defaults).unwrap();
+    assert_eq!(loaded.input.inputs_for("jump"), ["KeyW"]);
+    assert_eq!(loaded.input.inputs_for("fire"), ["MouseLeft"]);
+}
+
+#[test]
+fn newer_schema_is_rejected() {
+    let json = r#"{ "schema_version": 99 }"#;
+    assert!(matches!(
+        Settings::from_json(json),
+        Err(SettingsError::NewerSchema { found: 99, .. })
+    ));
+}
+
+#[test]
+fn empty_input_map_round_trips() {
+    assert_input_map_round_trip(&InputMap::new());
+}